@@ -0,0 +1,39 @@
+use fltk::{
+    app, dialog,
+    enums::{Key, Shortcut},
+    group, menu,
+    prelude::*,
+    window,
+};
+use fltk_webview::*;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut col = group::Flex::default_fill().column();
+    let mut bar = menu::MenuBar::default();
+    col.fixed(&bar, 30);
+    let mut wv_win = window::Window::default();
+    col.end();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.navigate("https://www.rust-lang.org");
+
+    // F5 triggers the menu item instead of reloading the page
+    bar.add(
+        "&View/Refresh\t",
+        Shortcut::None | Key::F5,
+        menu::MenuFlag::Normal,
+        |_| dialog::message_default("F5 was handled by fltk"),
+    );
+    wv.set_accelerator_handling(AcceleratorMode::Custom(
+        [Shortcut::None | Key::F5].into_iter().collect(),
+    ));
+
+    app.run().unwrap();
+}
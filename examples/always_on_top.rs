@@ -0,0 +1,41 @@
+use fltk::{app, button, group, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<body style="margin: 0; background: #223; color: #9f9; font: 14px monospace;">
+    <div id="clock" style="padding: 8px;"></div>
+    <script>
+        setInterval(function() {
+            document.getElementById('clock').textContent = new Date().toLocaleTimeString();
+        }, 500);
+    </script>
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    // A small HUD, staying above the other windows
+    let mut win = window::Window::default()
+        .with_size(240, 80)
+        .with_label("HUD");
+    let mut col = group::Flex::default_fill().column();
+    let mut pin = button::CheckButton::default().with_label("Always on top");
+    pin.set_checked(true);
+    col.fixed(&pin, 24);
+    let mut wv_win = window::Window::default();
+    col.end();
+    win.end();
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.set_html(HTML);
+    wv.set_always_on_top(true);
+    println!("is_always_on_top: {}", wv.is_always_on_top());
+    pin.set_callback(move |b| {
+        wv.set_always_on_top(b.is_checked());
+        println!("is_always_on_top: {}", wv.is_always_on_top());
+    });
+
+    app.run().unwrap();
+}
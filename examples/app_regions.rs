@@ -0,0 +1,49 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<body style="margin: 0; font-family: sans-serif;">
+    <header style="-webkit-app-region: drag; app-region: drag;
+        display: flex; justify-content: space-between; align-items: center;
+        height: 36px; padding: 0 8px; background: #334; color: white;">
+        <span>Drag me</span>
+        <button style="-webkit-app-region: no-drag; app-region: no-drag;"
+            onclick="window.close_window()">Close</button>
+    </header>
+    <p style="padding: 8px;">A borderless window, moved through its header bar</p>
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    win.set_border(false);
+    let mut wv_win = window::Window::default().size_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.enable_app_regions();
+    wv.bind("close_window", |_seq, _req| app::quit());
+    wv.set_html(HTML);
+
+    // Simulate a drag of the header, as the page reports it, and check that the window followed
+    let (x, y) = (win.x(), win.y());
+    wv.eval(
+        "window.__fltk_webview_app_region('start', 100, 100);
+        window.__fltk_webview_app_region('move', 150, 120);
+        window.__fltk_webview_app_region('end', 0, 0);",
+    );
+    app::add_timeout3(1.0, move |_| {
+        println!(
+            "Moved by the simulated drag: {:?}",
+            (win.x() - x, win.y() - y)
+        );
+    });
+
+    app.run().unwrap();
+}
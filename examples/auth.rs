@@ -0,0 +1,54 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+use std::time::Duration;
+
+mod common;
+
+/// `user:secret`, base64 encoded
+const AUTHORIZATION: &str = "Basic dXNlcjpzZWNyZXQ=";
+
+/// Serve a page protected by Basic authentication
+fn serve() -> String {
+    common::serve(|req| {
+        let (status, body) = if req.header("authorization") == Some(AUTHORIZATION) {
+            ("200 OK", "<h1>Protected page</h1>")
+        } else {
+            ("401 Unauthorized", "<h1>Unauthorized</h1>")
+        };
+        common::Response::new(status, "text/html", body)
+            .with_header("WWW-Authenticate", "Basic realm=\"fltk-webview\"")
+    })
+}
+
+fn main() {
+    let base = serve();
+
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    let mut attempts = 0;
+    if let Err(e) = wv.set_auth_handler(move |host, kind| {
+        println!("{:?} authentication requested by {}", kind, host);
+        attempts += 1;
+        // Give up if the credentials are rejected
+        (attempts <= 1).then(|| Credentials::new("user", "secret"))
+    }) {
+        eprintln!("{}", e);
+    }
+    wv.navigate(&format!("{}/", base));
+    match wv.wait_until_loaded(Duration::from_secs(5)) {
+        Ok(()) => println!("Status: {:?}", wv.last_response_status()),
+        Err(e) => eprintln!("{}", e),
+    }
+
+    app.run().unwrap();
+}
@@ -0,0 +1,54 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<body>
+    <h1>Sign in</h1>
+    <form>
+        <input name="email" type="email" autocomplete="email" placeholder="Email">
+        <input name="password" type="password" placeholder="Password">
+        <button type="submit">Sign in</button>
+    </form>
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.bind("report", |_seq, req| println!("autocomplete: {}", req));
+    println!(
+        "set_password_autosave(false): {:?}",
+        wv.set_password_autosave(false)
+    );
+    println!(
+        "set_password_autosave(true): {:?}",
+        wv.set_password_autosave(true)
+    );
+    println!(
+        "set_general_autofill(false): {:?}",
+        wv.set_general_autofill(false)
+    );
+    wv.set_html(HTML);
+    app::add_timeout3(1.0, {
+        let wv = wv.clone();
+        move |_| {
+            wv.eval(
+                "window.report([].map.call(document.querySelectorAll('input'), \
+                 function(i) { return i.getAttribute('autocomplete'); }))",
+            )
+        }
+    });
+
+    app.run().unwrap();
+}
@@ -0,0 +1,42 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<body>
+    <input id="name" placeholder="Name">
+    <button id="add" onclick="window.add(document.getElementById('name').value)">Add</button>
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    let mut count = 0;
+    wv.bind("add", move |_seq, req| {
+        count += 1;
+        println!("Clicked {} time(s) with {}", count, req);
+        assert!(req.contains("flt"));
+    });
+    wv.set_html(HTML);
+    app::add_timeout3(1.0, move |_| {
+        wv.type_text("#name", "fltk");
+        wv.click("#add");
+        // Focus the input again, and erase the last character
+        wv.click("#name");
+        wv.dispatch_key("Backspace");
+        wv.click("#add");
+    });
+
+    app.run().unwrap();
+}
@@ -0,0 +1,25 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+use std::time::Duration;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default_fill();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.set_html(
+        "<html><body><script>setTimeout(function() { ready('loaded', 42); }, 500);</script></body></html>",
+    );
+    match wv.await_call("ready", Duration::from_secs(5)) {
+        Ok(args) => println!("ready called with {}", args),
+        Err(e) => eprintln!("{}", e),
+    }
+
+    app.run().unwrap();
+}
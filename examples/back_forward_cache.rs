@@ -0,0 +1,42 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+use std::time::Duration;
+
+mod common;
+
+/// Serve a page at any path, linking to the next one
+fn serve() -> String {
+    common::serve(|_| {
+        common::Response::html("<html><body><a href=\"/next\">Next</a></body></html>")
+    })
+}
+
+fn main() {
+    let base = serve();
+
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.set_back_forward_cache(false);
+    wv.bind("ran", |_seq, content| {
+        println!("The init script ran at {}", content)
+    });
+    wv.init("window.ran(location.pathname)");
+    wv.navigate(&format!("{}/", base));
+    let _ = wv.wait_until_loaded(Duration::from_secs(5));
+    wv.navigate(&format!("{}/next", base));
+    let _ = wv.wait_until_loaded(Duration::from_secs(5));
+    // With the cache disabled, the script runs again for /
+    wv.eval("history.back()");
+
+    app.run().unwrap();
+}
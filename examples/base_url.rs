@@ -0,0 +1,34 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<body>
+    <p>The logo below is referenced by a relative path</p>
+    <img src="rust-logo-blk.svg" width="200"
+        onload="report(true)" onerror="report(false)">
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    let wv2 = wv.clone();
+    wv.bind("report", move |seq, content| {
+        println!("Relative image loaded: {}", content == "[true]");
+        wv2.return_(seq, 0, "null");
+    });
+    wv.set_html_with_base(HTML, "https://www.rust-lang.org/static/images/");
+
+    app.run().unwrap();
+}
@@ -0,0 +1,60 @@
+use fltk::{app, button, dialog, group, prelude::*, window};
+use fltk_webview::*;
+use std::{cell::Cell, rc::Rc};
+
+const HTML: &str = r#"<html>
+<body>
+<h1>Editor</h1>
+<textarea id="text" rows="10" cols="60" oninput="dirty = true"></textarea>
+<script>
+    var dirty = false;
+    window.addEventListener('beforeunload', function(e) {
+        if (!dirty) return;
+        e.preventDefault();
+        e.returnValue = 'You have unsaved changes';
+    });
+</script>
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut col = group::Flex::default_fill().column();
+    let mut close = button::Button::default().with_label("Close the webview");
+    col.fixed(&close, 30);
+    let mut wv_win = window::Window::default();
+    col.end();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    let asked = Rc::new(Cell::new(false));
+    wv.set_before_unload_handler({
+        let asked = asked.clone();
+        move |message| {
+            asked.set(true);
+            println!("The page asks to stay: {:?}", message);
+            dialog::choice2_default(&format!("{}\nLeave anyway?", message), "Stay", "Leave", "")
+                == Some(1)
+        }
+    });
+    wv.set_html(HTML);
+    app::add_timeout3(1.0, {
+        let wv = wv.clone();
+        move |_| wv.eval("document.getElementById('text').value = 'Draft'; dirty = true;")
+    });
+
+    close.set_callback(move |b| {
+        wv.clone().close();
+        assert!(asked.get(), "the handler wasn't consulted");
+        if !wv.is_running() {
+            b.deactivate();
+        }
+    });
+
+    app.run().unwrap();
+}
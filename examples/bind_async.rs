@@ -0,0 +1,54 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+use std::{thread, time::Duration};
+
+const HTML: &str = r#"
+<html>
+<body>
+<p id="ticks">0</p>
+<p id="out">Waiting for the slow function...</p>
+<script>
+    var ticks = 0;
+    setInterval(function() { document.getElementById('ticks').innerText = ++ticks; }, 100);
+    window.onload = async () => {
+        var out = document.getElementById('out');
+        out.innerText = await slow(3);
+        report(`slow resolved after ${ticks} ticks`);
+        try {
+            await slow(-1);
+        } catch (e) {
+            report(`slow rejected: ${e}`);
+        }
+    };
+</script>
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    // Runs on a worker thread, the page keeps ticking meanwhile
+    wv.bind_async("slow", |args| {
+        let args: tinyjson::JsonValue = args.parse().map_err(|_| "invalid arguments")?;
+        let secs = args[0].get::<f64>().copied().unwrap_or(-1.0);
+        if secs < 0.0 {
+            return Err("expected a positive duration".to_string());
+        }
+        thread::sleep(Duration::from_secs_f64(secs));
+        Ok(format!("\"slept for {} seconds\"", secs))
+    });
+    wv.bind("report", |_seq, content| println!("{}", content));
+    wv.set_html(HTML);
+
+    app.run().unwrap();
+}
@@ -0,0 +1,48 @@
+use fltk::{app, button, group, prelude::*, window};
+use fltk_webview::*;
+use std::{cell::Cell, rc::Rc};
+
+/// Counts its drops, to show that the closures owning it are freed
+struct DropCounter(Rc<Cell<u32>>);
+
+impl Drop for DropCounter {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut col = group::Flex::default_fill().column();
+    let mut btn = button::Button::default().with_label("Clear the bindings");
+    col.fixed(&btn, 30);
+    let mut wv_win = window::Window::default();
+    col.end();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    let dropped = Rc::new(Cell::new(0));
+    for name in ["one", "two", "three"] {
+        let counter = DropCounter(dropped.clone());
+        wv.bind_tracked(name, move |_seq, _req| {
+            let _ = &counter;
+        });
+    }
+    println!("Bound: {:?}", wv.bindings());
+    wv.set_html("<h1>Bound functions: one, two and three</h1>");
+    btn.set_callback(move |_| {
+        wv.clear_bindings();
+        println!(
+            "Bound after clearing: {:?}, closures dropped: {}",
+            wv.bindings(),
+            dropped.get()
+        );
+    });
+
+    app.run().unwrap();
+}
@@ -0,0 +1,22 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default_fill();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    // The init script runs on the first page already
+    let _wv = WebviewBuilder::new()
+        .background(fltk::enums::Color::Black)
+        .init("window.__started = Date.now();")
+        .html("<html><body style='background: black; color: white'><script>document.write('init ran: ' + (window.__started !== undefined));</script></body></html>")
+        .build(&mut wv_win);
+
+    app.run().unwrap();
+}
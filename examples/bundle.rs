@@ -0,0 +1,93 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+const INDEX: &str = r#"
+<html>
+<body>
+    <p id="status">Loading app.js...</p>
+    <script src="app.js"></script>
+</body>
+</html>"#;
+
+const APP_JS: &str = r#"
+document.getElementById('status').innerText = 'Served from an in-memory zip';
+loaded(location.href);
+"#;
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for b in data {
+        crc ^= *b as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+/// Build a zip of uncompressed entries, bundles would usually be made by a build script
+fn zip(files: &[(&str, &str)]) -> Vec<u8> {
+    let (mut out, mut dir) = (Vec::new(), Vec::new());
+    for (name, content) in files {
+        let (name, content) = (name.as_bytes(), content.as_bytes());
+        let offset = out.len() as u32;
+        let crc = crc32(content);
+        let len = content.len() as u32;
+        out.extend_from_slice(b"PK\x03\x04\x14\0\0\0\0\0\0\0\0\0");
+        for v in [crc, len, len] {
+            out.extend_from_slice(&v.to_le_bytes());
+        }
+        out.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        out.extend_from_slice(&[0, 0]);
+        out.extend_from_slice(name);
+        out.extend_from_slice(content);
+        dir.extend_from_slice(b"PK\x01\x02\x14\0\x14\0\0\0\0\0\0\0\0\0");
+        for v in [crc, len, len] {
+            dir.extend_from_slice(&v.to_le_bytes());
+        }
+        dir.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        dir.extend_from_slice(&[0; 12]);
+        dir.extend_from_slice(&offset.to_le_bytes());
+        dir.extend_from_slice(name);
+    }
+    let dir_offset = out.len() as u32;
+    out.extend_from_slice(&dir);
+    out.extend_from_slice(b"PK\x05\x06\0\0\0\0");
+    let count = files.len() as u16;
+    out.extend_from_slice(&count.to_le_bytes());
+    out.extend_from_slice(&count.to_le_bytes());
+    out.extend_from_slice(&(dir.len() as u32).to_le_bytes());
+    out.extend_from_slice(&dir_offset.to_le_bytes());
+    out.extend_from_slice(&[0, 0]);
+    out
+}
+
+fn main() {
+    let bundle = zip(&[("index.html", INDEX), ("app.js", APP_JS)]);
+
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.bind("loaded", |_seq, content| {
+        println!("app.js ran at {}", content)
+    });
+    match wv.serve_bundle("bundle.local", &bundle) {
+        Ok(()) => wv.navigate("https://bundle.local/"),
+        Err(e) => eprintln!("{}", e),
+    }
+
+    app.run().unwrap();
+}
@@ -0,0 +1,51 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<body>
+    <script>
+        window.multiply = function(a, b) { return a * b; };
+        window.app = { greet: function(person) { return 'Hello ' + person.name; } };
+    </script>
+</body>
+</html>"#;
+
+#[derive(serde::Serialize)]
+struct Person {
+    name: String,
+}
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.set_html(HTML);
+    let _ = wv.wait_until_loaded(std::time::Duration::from_secs(5));
+    wv.call_js("multiply", (6, 7), |res: Result<i32, WebviewError>| {
+        println!("multiply(6, 7) = {:?}", res)
+    })
+    .unwrap();
+    let person = Person {
+        name: "\"); alert(\"not injected".to_string(),
+    };
+    wv.call_js("app.greet", person, |res: Result<String, WebviewError>| {
+        println!("app.greet: {:?}", res)
+    })
+    .unwrap();
+    // Rejected before reaching the page
+    if let Err(e) = wv.call_js("alert(1);x", (), |_: Result<(), WebviewError>| ()) {
+        println!("{}", e);
+    }
+
+    app.run().unwrap();
+}
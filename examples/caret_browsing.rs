@@ -0,0 +1,34 @@
+use fltk::{app, button, group, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<body>
+    <h1>Caret browsing</h1>
+    <p>Click in this text, then move the caret with the arrow keys, Home and End.</p>
+    <p>Hold Shift to select, and Ctrl to move by words.</p>
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut col = group::Flex::default_fill().column();
+    let mut enabled = button::CheckButton::default().with_label("Caret browsing");
+    enabled.set_checked(true);
+    col.fixed(&enabled, 30);
+    let mut wv_win = window::Window::default();
+    col.end();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.set_caret_browsing(true);
+    wv.set_html(HTML);
+    enabled.set_callback(move |b| wv.set_caret_browsing(b.is_checked()));
+
+    app.run().unwrap();
+}
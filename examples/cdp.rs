@@ -0,0 +1,43 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+use tinyjson::JsonValue;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(true, &mut wv_win);
+    wv.set_html("<h1>DevTools Protocol</h1>");
+    wv.call_cdp(
+        "Runtime.evaluate",
+        r#"{"expression": "Promise.resolve(6 * 7)", "awaitPromise": true}"#,
+        |res| {
+            let res: JsonValue = res.unwrap().parse().unwrap();
+            println!("{}", res.stringify().unwrap());
+            assert_eq!(res["result"]["type"], JsonValue::from("number".to_string()));
+            assert_eq!(res["result"]["value"], JsonValue::from(42.0));
+        },
+    );
+    wv.call_cdp(
+        "Runtime.evaluate",
+        r#"{"expression": "undefinedFunction()"}"#,
+        |res| {
+            let res: JsonValue = res.unwrap().parse().unwrap();
+            println!("{}", res.stringify().unwrap());
+            assert!(res["exceptionDetails"].is_object());
+        },
+    );
+    wv.call_cdp("Network.enable", "", |res| {
+        assert!(matches!(res, Err(WebviewError::Unsupported)));
+    });
+
+    app.run().unwrap();
+}
@@ -0,0 +1,25 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default_fill();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    if let Err(e) = wv.set_default_charset("ISO-8859-1") {
+        eprintln!("{}", e);
+    }
+    wv.bind("report", |_seq, content| println!("{}", content));
+    // "Café" encoded as Latin-1, without a declared charset
+    wv.navigate(
+        "data:text/html,<p>Caf%E9</p><script>report(document.querySelector('p').textContent)</script>",
+    );
+
+    app.run().unwrap();
+}
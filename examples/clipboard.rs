@@ -0,0 +1,57 @@
+use fltk::{app, group, menu, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<body>
+    <h1>Copying to the clipboard from the page</h1>
+    <script>
+        window.copy = function() {
+            navigator.clipboard.writeText('Copied by fltk-webview').then(function() {
+                window.report('writeText succeeded');
+            }, function(e) {
+                window.report('writeText failed: ' + e.name);
+            });
+        };
+    </script>
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut col = group::Flex::default_fill().column();
+    let mut access = menu::Choice::default();
+    access.add_choice("AllowWrite|Denied|AllowRead|AllowBoth");
+    access.set_value(0);
+    col.fixed(&access, 30);
+    let mut wv_win = window::Window::default();
+    col.end();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.bind("report", |_seq, req| println!("{}", req));
+    wv.set_clipboard_access(ClipboardAccess::AllowWrite);
+    wv.set_html(HTML);
+    // Copies without a user gesture once the page is loaded
+    app::add_timeout3(1.0, {
+        let wv = wv.clone();
+        move |_| wv.eval("window.copy()")
+    });
+    access.set_callback(move |c| {
+        let access = match c.value() {
+            1 => ClipboardAccess::Denied,
+            2 => ClipboardAccess::AllowRead,
+            3 => ClipboardAccess::AllowBoth,
+            _ => ClipboardAccess::AllowWrite,
+        };
+        wv.set_clipboard_access(access);
+        wv.eval("window.copy()");
+    });
+
+    app.run().unwrap();
+}
@@ -0,0 +1,37 @@
+use fltk::{app, button, group, prelude::*, window};
+use fltk_webview::*;
+use std::{cell::RefCell, rc::Rc};
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut col = group::Flex::default_fill().column();
+    col.set_margin(5);
+    let mut btn = button::Button::default().with_label("Close and recreate");
+    col.fixed(&btn, 30);
+    let mut wv_win = window::Window::default();
+    col.end();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let wv = Webview::create(false, &mut wv_win);
+    wv.navigate("https://google.com");
+    let wv = Rc::new(RefCell::new(Some(wv)));
+    let mut count = 0;
+    btn.set_callback(move |_| {
+        if let Some(old) = wv.borrow_mut().take() {
+            let probe = old.clone();
+            old.close();
+            println!("still running: {}", probe.is_running());
+        }
+        count += 1;
+        let new = Webview::create(false, &mut wv_win);
+        new.set_html(&format!("<h1>Webview number {}</h1>", count + 1));
+        *wv.borrow_mut() = Some(new);
+    });
+
+    app.run().unwrap();
+}
@@ -0,0 +1,52 @@
+use fltk::{app, group, menu, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<head>
+<style>
+    @media (prefers-color-scheme: dark) { body { background: #222; color: #eee; } }
+</style>
+</head>
+<body>
+<p id="scheme"></p>
+<script>
+    setInterval(function() {
+        document.getElementById('scheme').textContent =
+            matchMedia('(prefers-color-scheme: dark)').matches ? 'dark' : 'light';
+    }, 200);
+</script>
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut col = group::Flex::default_fill().column();
+    col.set_margin(5);
+    let mut choice = menu::Choice::default();
+    choice.add_choice("Auto|Light|Dark");
+    choice.set_value(0);
+    col.fixed(&choice, 30);
+    let mut wv_win = window::Window::default();
+    col.end();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.set_html(HTML);
+
+    choice.set_callback(move |c| {
+        let scheme = match c.value() {
+            1 => ColorScheme::Light,
+            2 => ColorScheme::Dark,
+            _ => ColorScheme::Auto,
+        };
+        wv.set_color_scheme(scheme);
+    });
+
+    app.run().unwrap();
+}
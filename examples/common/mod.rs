@@ -0,0 +1,143 @@
+//! Local servers shared by the examples needing a real origin
+#![allow(dead_code)]
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    process::{Child, Command, Stdio},
+    sync::Arc,
+    thread,
+};
+
+/// A request received by `serve`
+pub struct Request {
+    /// The request line, e.g. `GET /path HTTP/1.1`
+    pub line: String,
+    /// The headers, with their names as sent
+    pub headers: Vec<(String, String)>,
+    /// The body, as long as its `Content-Length`
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    /// The method, e.g. `GET`
+    pub fn method(&self) -> &str {
+        self.line.split(' ').next().unwrap_or_default()
+    }
+
+    /// The path and query, e.g. `/path?q=1`
+    pub fn path(&self) -> &str {
+        self.line.split(' ').nth(1).unwrap_or("/")
+    }
+
+    /// The value of the header `name`, matched case-insensitively
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// A response answered by `serve`
+pub struct Response {
+    status: String,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl Response {
+    /// A response with `status`, e.g. `404 Not Found`, and `body` of the MIME type `content_type`
+    pub fn new(status: &str, content_type: &str, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status: status.to_string(),
+            headers: vec![("Content-Type".to_string(), content_type.to_string())],
+            body: body.into(),
+        }
+    }
+
+    /// A `200 OK` HTML page
+    pub fn html(body: impl Into<Vec<u8>>) -> Self {
+        Self::new("200 OK", "text/html", body)
+    }
+
+    /// A `302 Found` redirect to `location`
+    pub fn redirect(location: &str) -> Self {
+        Self::new("302 Found", "text/html", "").with_header("Location", location)
+    }
+
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+}
+
+/// Serve HTTP on a free loopback port, answering each request with `f` on its own thread,
+/// and return the base URL, e.g. `http://127.0.0.1:1234`
+pub fn serve<F: Fn(&Request) -> Response + Send + Sync + 'static>(f: F) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let f = Arc::new(f);
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let f = f.clone();
+            thread::spawn(move || {
+                let mut reader = BufReader::new(&stream);
+                let mut lines = (&mut reader).lines();
+                let line = lines.next().and_then(|l| l.ok()).unwrap_or_default();
+                let headers = lines
+                    .map_while(|l| l.ok())
+                    .take_while(|l| !l.is_empty())
+                    .filter_map(|l| {
+                        let (name, value) = l.split_once(':')?;
+                        Some((name.to_string(), value.trim().to_string()))
+                    })
+                    .collect();
+                let mut req = Request {
+                    line,
+                    headers,
+                    body: vec![],
+                };
+                let length = req.header("content-length").and_then(|l| l.parse().ok());
+                req.body = vec![0; length.unwrap_or(0)];
+                let _ = reader.read_exact(&mut req.body);
+                let res = f(&req);
+                let mut head = format!("HTTP/1.1 {}\r\n", res.status);
+                for (name, value) in &res.headers {
+                    head.push_str(&format!("{}: {}\r\n", name, value));
+                }
+                head.push_str(&format!(
+                    "Content-Length: {}\r\nConnection: close\r\n\r\n",
+                    res.body.len()
+                ));
+                let mut stream = &stream;
+                let _ = stream.write_all(head.as_bytes());
+                let _ = stream.write_all(&res.body);
+            });
+        }
+    });
+    format!("http://127.0.0.1:{}", port)
+}
+
+/// Serve a status page over HTTPS on `port` with a self-signed certificate for localhost,
+/// through the `openssl` command line tool
+pub fn serve_tls(port: u16) -> std::io::Result<Child> {
+    let dir = std::env::temp_dir().join("fltk-webview-tls");
+    std::fs::create_dir_all(&dir)?;
+    let status = Command::new("openssl")
+        .args(["req", "-x509", "-newkey", "rsa:2048", "-nodes"])
+        .args(["-keyout", "key.pem", "-out", "cert.pem"])
+        .args(["-days", "1", "-subj", "/CN=localhost"])
+        .current_dir(&dir)
+        .stderr(Stdio::null())
+        .status()?;
+    if !status.success() {
+        return Err(std::io::Error::other("couldn't create the certificate"));
+    }
+    Command::new("openssl")
+        .args(["s_server", "-www", "-cert", "cert.pem", "-key", "key.pem"])
+        .args(["-accept", &port.to_string()])
+        .current_dir(&dir)
+        .stdout(Stdio::null())
+        .spawn()
+}
@@ -0,0 +1,35 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<body>
+<button onclick="console.log('clicked', { at: Date.now() })">Log</button>
+<script>
+    console.info('page loaded');
+    console.error('x');
+</script>
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default_fill();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.on_console_message(|level, msg| {
+        println!("[{:?}] {}", level, msg);
+        if level == ConsoleLevel::Error {
+            assert_eq!(msg, "x");
+        }
+    });
+    wv.set_html(HTML);
+
+    app.run().unwrap();
+}
@@ -0,0 +1,35 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+const RULES: &str = r#"[
+    { "trigger": { "url-filter": ".*ads.*" }, "action": { "type": "block" } }
+]"#;
+
+const HTML: &str = r#"
+<html>
+<body>
+<img src="https://example.com/ads/banner.png"
+    onload="report('ad loaded')" onerror="report('ad blocked')">
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default_fill();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    match wv.add_content_rules(RULES) {
+        Ok(_) => println!("rules added"),
+        Err(e) => eprintln!("{}", e),
+    }
+    wv.bind("report", |_seq, content| println!("{}", content));
+    wv.set_html(HTML);
+
+    app.run().unwrap();
+}
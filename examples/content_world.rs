@@ -0,0 +1,48 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<body>
+<p id="status">Checking...</p>
+<script>
+    window.onload = () => {
+        // The isolated script shares the DOM, but not its globals
+        var marked = document.body.dataset.isolated === 'yes';
+        var visible = typeof window.__isolated_secret !== 'undefined';
+        document.getElementById('status').innerText =
+            `DOM shared: ${marked}, variable visible: ${visible}`;
+        report(marked, visible);
+    };
+</script>
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.bind("report", |_seq, content| {
+        println!("[dom shared, variable visible from the page]: {}", content);
+    });
+    let isolated = wv.init_in_world(
+        "window.__isolated_secret = 42; document.body.dataset.isolated = 'yes';",
+        InjectionTiming::DocumentEnd,
+        ContentWorld::Isolated,
+    );
+    if let Err(e) = isolated {
+        println!("Isolated worlds unavailable: {}", e);
+    }
+    wv.set_html(HTML);
+
+    app.run().unwrap();
+}
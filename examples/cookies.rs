@@ -0,0 +1,54 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let wv = Webview::create(false, &mut wv_win);
+    wv.bind("report", |_seq, req| println!("document.cookie: {}", req));
+    wv.navigate("https://example.com");
+
+    app::add_timeout3(3.0, move |_| {
+        wv.eval("document.cookie = 'session=abc123; max-age=3600; path=/'");
+        let wv = wv.clone();
+        // Leaves the script time to set the cookie
+        app::add_timeout3(0.5, move |_| {
+            let mut target = wv.clone();
+            wv.export_cookies(move |cookies| {
+                let cookies = match cookies {
+                    Ok(cookies) => cookies,
+                    Err(e) => return eprintln!("Couldn't export the cookies: {}", e),
+                };
+                let blob = serde_json::to_string(&cookies).unwrap();
+                println!("Exported: {}", blob);
+                // Storing expired copies deletes the cookies
+                let expired: Vec<Cookie> = cookies
+                    .iter()
+                    .map(|c| Cookie {
+                        expires: Some(0),
+                        ..c.clone()
+                    })
+                    .collect();
+                let restored: Vec<Cookie> = serde_json::from_str(&blob).unwrap();
+                let res = target
+                    .import_cookies(&expired)
+                    .and_then(|_| target.import_cookies(&restored));
+                match res {
+                    Ok(()) => target.eval("window.report(document.cookie)"),
+                    Err(e) => eprintln!("Couldn't import the cookies: {}", e),
+                }
+            });
+        });
+    });
+
+    app.run().unwrap();
+}
@@ -0,0 +1,42 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<head>
+    <script>window.inlineRan = true;</script>
+</head>
+<body>
+    <h1 id="status">The inline script was blocked</h1>
+    <script>document.getElementById('status').textContent = 'The inline script ran';</script>
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.bind("report", |_seq, req| {
+        let ran = req.contains("true");
+        println!("Inline scripts ran: {}", ran);
+        assert!(!ran);
+    });
+    wv.set_csp("script-src 'none'");
+    wv.set_html(HTML);
+    // Scripts evaluated by the application aren't subject to the page's policies
+    app::add_timeout3(1.0, {
+        let wv = wv.clone();
+        move |_| wv.eval("window.report(window.inlineRan === true)")
+    });
+
+    app.run().unwrap();
+}
@@ -0,0 +1,53 @@
+use fltk::{app, enums::Cursor, group, menu, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<body>
+    <p>Hover over the page, and the elements choosing their own cursor:</p>
+    <p><a href="">A link</a></p>
+    <p><input value="A text field"></p>
+    <p style="cursor: move">An element with a move cursor</p>
+</body>
+</html>"#;
+
+const CURSORS: [(&str, Cursor); 5] = [
+    ("Wait", Cursor::Wait),
+    ("Cross", Cursor::Cross),
+    ("Hand", Cursor::Hand),
+    ("Help", Cursor::Help),
+    ("None", Cursor::None),
+];
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut col = group::Flex::default_fill().column();
+    col.set_margin(5);
+    let mut choice = menu::Choice::default();
+    choice.add_choice("Page's cursors");
+    for (name, _) in CURSORS {
+        choice.add_choice(name);
+    }
+    choice.set_value(1);
+    col.fixed(&choice, 30);
+    let mut wv_win = window::Window::default();
+    col.end();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.set_html(HTML);
+    // Busy while the page loads
+    wv.set_cursor(Cursor::Wait);
+
+    choice.set_callback(move |c| match c.value() {
+        i if i > 0 => wv.set_cursor(CURSORS[i as usize - 1].1),
+        _ => wv.reset_cursor(),
+    });
+
+    app.run().unwrap();
+}
@@ -0,0 +1,55 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+use std::collections::HashMap;
+
+mod common;
+
+/// Serve pages echoing the headers of their request, with a link to another page
+fn serve() -> String {
+    common::serve(|req| {
+        let mut headers = vec![req.line.clone()];
+        headers.extend(req.headers.iter().map(|(n, v)| format!("{}: {}", n, v)));
+        let headers: Vec<String> = headers
+            .iter()
+            .map(|h| h.replace('\\', "\\\\").replace('\'', "\\'"))
+            .collect();
+        common::Response::html(format!(
+            "<html><body><a href=\"/next\">Follow a link</a><pre id=h></pre><script>
+                var headers = ['{}'];
+                document.getElementById('h').innerText = headers.join('\\n');
+                report(headers.filter(function(h) {{ return /^x-app-token:/i.test(h); }}));
+            </script></body></html>",
+            headers.join("', '")
+        ))
+    })
+}
+
+fn main() {
+    let base = serve();
+
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.bind("report", |_seq, content| {
+        println!("Token headers received by the server: {}", content);
+    });
+    let mut headers = HashMap::new();
+    headers.insert("X-App-Token".to_string(), "secret".to_string());
+    // Only the local server gets the token, not the sites its pages link to
+    match wv.set_default_headers(headers, &[&base]) {
+        Ok(()) => (),
+        Err(e) => println!("Default headers unavailable: {}", e),
+    }
+    wv.navigate(&format!("{}/", base));
+
+    app.run().unwrap();
+}
@@ -0,0 +1,22 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default_fill();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    // Created without debug, the inspector is then enabled on its own
+    let mut wv = Webview::create(false, &mut wv_win);
+    if let Err(e) = wv.set_developer_extras(true) {
+        eprintln!("{}", e);
+    }
+    wv.set_html("<html><body><p>Right-click and choose Inspect Element</p></body></html>");
+
+    app.run().unwrap();
+}
@@ -0,0 +1,43 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<body>
+    <a id="link" href="data:text/plain;base64,SGVsbG8gZnJvbSBmbHRrLXdlYnZpZXch"
+        download="hello.txt">Download hello.txt</a>
+    <script>setTimeout(function() { document.getElementById('link').click(); }, 500);</script>
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    let dir = std::env::temp_dir();
+    wv.set_download_handler(move |req| {
+        println!("Downloading {} as {}", req.url, req.suggested_filename);
+        DownloadDecision::Save(dir.join(&req.suggested_filename))
+    });
+    wv.on_download_progress(|progress| match &progress.state {
+        DownloadState::InProgress => println!("Received {} bytes", progress.received),
+        DownloadState::Finished => println!(
+            "Saved {}: {:?}",
+            progress.path.display(),
+            std::fs::read_to_string(&progress.path)
+        ),
+        state => println!("Download of {} ended: {:?}", progress.url, state),
+    });
+    wv.set_html(HTML);
+
+    app.run().unwrap();
+}
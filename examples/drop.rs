@@ -0,0 +1,27 @@
+use fltk::{app, browser, group, prelude::*, window};
+use fltk_webview::*;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut row = group::Flex::default_fill().row();
+    let mut list = browser::HoldBrowser::default();
+    row.fixed(&list, 250);
+    let mut wv_win = window::Window::default();
+    row.end();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.set_html("<html><body><h1>Drop files here</h1></body></html>");
+    wv.set_drop_handler(move |paths| {
+        for path in paths {
+            list.add(&path.display().to_string());
+        }
+    });
+
+    app.run().unwrap();
+}
@@ -0,0 +1,48 @@
+use fltk::{app, button, group, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<body>
+    <h1 id="metrics"></h1>
+    <script>
+        var show = function() {
+            var metrics = window.innerWidth + 'x' + window.innerHeight + ' @' +
+                window.devicePixelRatio + ', touch points: ' + navigator.maxTouchPoints;
+            document.getElementById('metrics').textContent = metrics;
+            if (window.report) window.report(metrics);
+        };
+        window.addEventListener('resize', show);
+        show();
+    </script>
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 700)
+        .with_label("Webview");
+    let mut col = group::Flex::default_fill().column();
+    let mut phone = button::CheckButton::default().with_label("Emulate a 375x667 phone");
+    col.fixed(&phone, 30);
+    let mut wv_win = window::Window::default();
+    col.end();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.bind("report", |_seq, req| println!("Page metrics: {}", req));
+    wv.set_html(HTML);
+    phone.set_callback(move |b| {
+        let metrics = b.is_checked().then(|| {
+            DeviceMetrics::new(375, 667)
+                .with_device_pixel_ratio(2.0)
+                .with_touch(true)
+        });
+        wv.emulate_device(metrics);
+    });
+
+    app.run().unwrap();
+}
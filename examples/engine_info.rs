@@ -0,0 +1,36 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let wv = Webview::create(true, &mut wv_win);
+    let info = wv.engine_info();
+    println!("{:?}", info);
+    let expected = if cfg!(target_os = "windows") {
+        "WebView2"
+    } else if cfg!(target_os = "macos") {
+        "WKWebView"
+    } else {
+        "WebKitGTK"
+    };
+    assert_eq!(info.name, expected);
+    assert!(info.is_debug);
+    assert!(
+        info.version.split('.').all(|n| n.parse::<u32>().is_ok()),
+        "unexpected version {:?}",
+        info.version
+    );
+    wv.set_html(&format!("<h1>{} {}</h1>", info.name, info.version));
+
+    app.run().unwrap();
+}
@@ -0,0 +1,31 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<body>
+<script>
+    report('previous visit: ' + localStorage.getItem('visited'));
+    localStorage.setItem('visited', new Date().toString());
+</script>
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default_fill();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    // Nothing is kept across runs
+    let wv = WebviewBuilder::new().ephemeral(true).build(&mut wv_win);
+    wv.bind("report", |_seq, content| println!("{}", content));
+    wv.set_html(HTML);
+
+    app.run().unwrap();
+    wv.close();
+}
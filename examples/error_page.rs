@@ -0,0 +1,46 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+const ERROR_PAGE: &str = r#"
+<html>
+<body>
+    <h1>This page couldn't be loaded</h1>
+    <p id="marker">{url}: {error}</p>
+    <button onclick="location.reload()">Try again</button>
+    <script>
+        window.report(document.getElementById('marker').textContent);
+    </script>
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.bind("report", |_seq, req| println!("Error page shown: {}", req));
+    let events = wv.events();
+    if let Err(e) = wv.set_error_page(ERROR_PAGE) {
+        eprintln!("{}", e);
+    }
+    // Nothing listens on port 1
+    wv.navigate("http://127.0.0.1:1/");
+    app::add_timeout3(0.1, move |handle| {
+        while let Ok(event) = events.try_recv() {
+            if let WebviewEvent::NavigationFailed(url, error) = event {
+                println!("{} failed: {}", url, error);
+            }
+        }
+        app::repeat_timeout3(0.1, handle);
+    });
+
+    app.run().unwrap();
+}
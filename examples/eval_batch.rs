@@ -0,0 +1,46 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+use std::time::Instant;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default().size_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.set_html("<h1>Batched evaluation</h1>");
+    wv.wait_until_loaded(std::time::Duration::from_secs(5)).ok();
+
+    // Each script declares the same variable, which stays in its own scope
+    let mut scripts: Vec<String> = (0..100).map(|i| format!("var x = {}; x * x", i)).collect();
+    scripts[50] = "throw new Error('script 50 failed')".to_string();
+    scripts[99] = "new Promise(function(resolve) { setTimeout(resolve, 10, 'done'); })".to_string();
+    let scripts: Vec<&str> = scripts.iter().map(|s| s.as_str()).collect();
+
+    let start = Instant::now();
+    wv.eval_batch_with_result(&scripts, move |results| {
+        println!("{} results in {:?}", results.len(), start.elapsed());
+        assert_eq!(results.len(), 100);
+        for (i, result) in results.iter().enumerate() {
+            match i {
+                50 => assert!(matches!(result, Err(WebviewError::Js(e)) if e.contains("failed"))),
+                99 => assert_eq!(result.as_ref().unwrap(), "\"done\""),
+                _ => assert_eq!(result.as_ref().unwrap(), &(i * i).to_string()),
+            }
+        }
+        println!("{:?}", &results[48..52]);
+    });
+    // Fire and forget, errors go to the console
+    wv.eval_batch(&[
+        "document.body.style.background = 'lightyellow'",
+        "undefinedFunction()",
+        "document.title = 'Batched'",
+    ]);
+
+    app.run().unwrap();
+}
@@ -0,0 +1,33 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    let events = wv.events();
+    wv.navigate("https://www.rust-lang.org");
+
+    while app.wait() {
+        for event in events.try_iter() {
+            match event {
+                WebviewEvent::Navigated(url) => println!("Navigated to {}", url),
+                WebviewEvent::TitleChanged(title) => win.set_label(&title),
+                WebviewEvent::Progress(p) => println!("Loaded {:.0}%", p * 100.0),
+                WebviewEvent::NewWindowRequested(url) => println!("New window for {}", url),
+                WebviewEvent::NavigationFailed(url, reason) => {
+                    println!("Loading {} failed: {}", url, reason)
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use fltk::{app, button, group, prelude::*, window};
+use fltk_webview::*;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut col = group::Flex::default_fill().column();
+    let mut row = group::Flex::default().row();
+    let mut fade_in = button::Button::default().with_label("Fade in");
+    let mut fade_out = button::Button::default().with_label("Fade out");
+    let mut close = button::Button::default().with_label("Fade out and close");
+    row.end();
+    col.fixed(&row, 30);
+    let mut wv_win = window::Window::default();
+    col.end();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.set_html("<h1 style='font-size: 4em'>Hello</h1>");
+    wv.fade_in(500);
+    app::add_timeout3(1.0, {
+        let wv = wv.clone();
+        move |_| {
+            println!("Opacity after fading in: {}", wv.opacity());
+            assert_eq!(wv.opacity(), 1.0);
+        }
+    });
+
+    fade_in.set_callback({
+        let mut wv = wv.clone();
+        move |_| wv.fade_in(300)
+    });
+    fade_out.set_callback({
+        let mut wv = wv.clone();
+        move |_| {
+            let done = wv.clone();
+            wv.fade_out(300, move || {
+                println!("Opacity after fading out: {}", done.opacity());
+                assert_eq!(done.opacity(), 0.0);
+            });
+        }
+    });
+    close.set_callback(move |b| {
+        b.deactivate();
+        let done = wv.clone();
+        wv.fade_out(300, move || done.close());
+    });
+
+    app.run().unwrap();
+}
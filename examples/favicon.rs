@@ -0,0 +1,39 @@
+use fltk::{app, button, group, prelude::*, window};
+use fltk_webview::*;
+
+// A red 1x1 PNG
+const HTML: &str = r#"
+<html>
+<head>
+<link rel="icon" href="data:image/png;base64,iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mP8z8DwHwAFBQIAX8jx0gAAAABJRU5ErkJggg==">
+</head>
+<body><h1>Favicon</h1></body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut col = group::Flex::default_fill().column();
+    col.set_margin(5);
+    let mut btn = button::Button::default().with_label("Get favicon");
+    col.fixed(&btn, 30);
+    let mut wv_win = window::Window::default();
+    col.end();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.set_html(HTML);
+
+    btn.set_callback(move |_| {
+        wv.favicon(|icon| match icon {
+            Some(png) => println!("favicon of {} bytes", png.len()),
+            None => println!("no favicon"),
+        })
+    });
+
+    app.run().unwrap();
+}
@@ -0,0 +1,43 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+const INDEX: &str = r#"
+<html>
+<body>
+    <script>
+        fetch('data.json')
+            .then(function(r) { return r.text(); })
+            .then(function(t) { report('read ' + t); })
+            .catch(function(e) { report('refused: ' + e); });
+    </script>
+</body>
+</html>"#;
+
+fn main() {
+    // A local page next to the file it reads
+    let dir = std::env::temp_dir().join("fltk-webview-file-access");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("index.html"), INDEX).unwrap();
+    std::fs::write(dir.join("data.json"), r#"{"local": true}"#).unwrap();
+
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    // Only for the application's own pages
+    if let Err(e) = wv.set_allow_file_access_from_file_urls(true) {
+        eprintln!("{}", e);
+    }
+    wv.bind("report", |_seq, content| println!("data.json: {}", content));
+    wv.navigate(&format!("file://{}", dir.join("index.html").display()));
+
+    app.run().unwrap();
+}
@@ -0,0 +1,37 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+const MARGIN: i32 = 10;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default();
+    win.end();
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.fill_parent(MARGIN);
+    wv.set_html("<h1>Resize the window, the margin stays the same</h1>");
+
+    // The webview's window tracks the parent's size
+    win.resize(win.x(), win.y(), 640, 480);
+    println!(
+        "Parent {}x{}, webview at {},{} sized {}x{}",
+        win.w(),
+        win.h(),
+        wv_win.x(),
+        wv_win.y(),
+        wv_win.w(),
+        wv_win.h()
+    );
+    assert_eq!((wv_win.x(), wv_win.y()), (MARGIN, MARGIN));
+    assert_eq!(
+        (wv_win.w(), wv_win.h()),
+        (640 - 2 * MARGIN, 480 - 2 * MARGIN)
+    );
+
+    app.run().unwrap();
+}
@@ -0,0 +1,63 @@
+use fltk::{app, button, enums, frame, group, input, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<body>
+    <p>The quick brown fox jumps over the lazy dog.</p>
+    <p>The dog barks, the fox runs.</p>
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut col = group::Flex::default_fill().column();
+    col.set_margin(5);
+    let mut row = group::Flex::default().row();
+    let mut inp = input::Input::default();
+    inp.set_trigger(enums::CallbackTrigger::EnterKeyAlways);
+    let mut prev = button::Button::default().with_label("@<");
+    let mut next = button::Button::default().with_label("@>");
+    let mut count = frame::Frame::default();
+    row.fixed(&prev, 30);
+    row.fixed(&next, 30);
+    row.fixed(&count, 100);
+    row.end();
+    col.fixed(&row, 30);
+    let mut wv_win = window::Window::default();
+    col.end();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.set_html(HTML);
+    wv.on_find_count(move |n| count.set_label(&format!("{} matches", n)));
+
+    inp.set_callback({
+        let mut wv = wv.clone();
+        move |i| {
+            if i.value().is_empty() {
+                wv.find_clear();
+            } else {
+                wv.find(
+                    &i.value(),
+                    FindOptions {
+                        case_sensitive: false,
+                        wrap_around: true,
+                    },
+                );
+            }
+        }
+    });
+    prev.set_callback({
+        let mut wv = wv.clone();
+        move |_| wv.find_previous()
+    });
+    next.set_callback(move |_| wv.find_next());
+
+    app.run().unwrap();
+}
@@ -0,0 +1,38 @@
+use fltk::{app, button, group, prelude::*, window};
+use fltk_webview::*;
+use std::{cell::Cell, rc::Rc, time::Duration};
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut col = group::Flex::default_fill().column();
+    let mut btn = button::Button::default().with_label("Queue 5 evals, flush and quit");
+    col.fixed(&btn, 30);
+    let mut wv_win = window::Window::default();
+    col.end();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    let count = Rc::new(Cell::new(0));
+    wv.bind("count", {
+        let count = count.clone();
+        move |_seq, _content| count.set(count.get() + 1)
+    });
+    wv.set_html("<h1>Flush</h1>");
+    btn.set_callback(move |_| {
+        for _ in 0..5 {
+            wv.eval("count()");
+        }
+        match wv.flush(Duration::from_secs(5)) {
+            Ok(()) => println!("Evaluations run before quitting: {}", count.get()),
+            Err(e) => println!("Flushing failed: {}", e),
+        }
+        app::quit();
+    });
+
+    app.run().unwrap();
+}
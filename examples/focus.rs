@@ -0,0 +1,30 @@
+use fltk::{app, button, input, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"<input id="inp" placeholder="web input" autofocus>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let inp = input::Input::new(5, 5, 390, 30, None);
+    let mut btn = button::Button::new(5, 40, 390, 30, "Toggle focus");
+    let mut wv_win = window::Window::new(400, 5, 395, 590, None);
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.set_html(HTML);
+
+    btn.set_callback(move |_| {
+        if wv.has_focus() {
+            inp.clone().take_focus().ok();
+        } else {
+            wv.focus();
+        }
+    });
+
+    app.run().unwrap();
+}
@@ -0,0 +1,49 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<body>
+    <p id="text">Body text in the default font.</p>
+    <pre id="code">fn main() {}</pre>
+</body>
+</html>"#;
+
+/// Reports the computed fonts of the paragraph and the code block
+const REPORT_JS: &str = r#"['text', 'code'].forEach(function(id) {
+    var style = getComputedStyle(document.getElementById(id));
+    window.report(id, style.fontFamily, style.fontSize);
+});"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default().size_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.bind("report", |_seq, req| println!("Computed font: {}", req));
+    wv.set_font_defaults(FontConfig {
+        default_family: Some("DejaVu Serif".to_string()),
+        default_size: Some(18),
+        monospace: Some("DejaVu Sans Mono".to_string()),
+        monospace_size: Some(15),
+        ..Default::default()
+    })
+    .unwrap();
+    // Sizes out of range are refused
+    assert!(wv
+        .set_font_defaults(FontConfig {
+            default_size: Some(500),
+            ..Default::default()
+        })
+        .is_err());
+    wv.set_html(HTML);
+    app::add_timeout3(1.0, move |_| wv.eval(REPORT_JS));
+
+    app.run().unwrap();
+}
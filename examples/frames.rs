@@ -0,0 +1,32 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<body>
+<p>Top-level document</p>
+<iframe name="child" srcdoc="<p id='msg'>Subframe</p>"></iframe>
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default_fill();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.set_html(HTML);
+    app::add_timeout3(1.0, move |_| {
+        wv.eval_in_frame(
+            "child",
+            "document.getElementById('msg').textContent = 'Changed from Rust';",
+        );
+    });
+
+    app.run().unwrap();
+}
@@ -0,0 +1,52 @@
+use fltk::{app, enums, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<body>
+    <div id="player" style="background: black; color: white; height: 200px">
+        <button onclick="document.getElementById('player').requestFullscreen ?
+            document.getElementById('player').requestFullscreen() :
+            document.getElementById('player').webkitRequestFullscreen()">Fullscreen</button>
+        <p>Press Escape to leave</p>
+    </div>
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default_fill();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.on_fullscreen_request({
+        let mut win = win.clone();
+        move |entering| {
+            println!("Fullscreen: {}", entering);
+            win.fullscreen(entering);
+            true
+        }
+    });
+    win.handle({
+        let mut wv = wv.clone();
+        move |w, ev| {
+            if ev == enums::Event::KeyDown
+                && app::event_key() == enums::Key::Escape
+                && w.fullscreen_active()
+            {
+                w.fullscreen(false);
+                wv.exit_fullscreen();
+                return true;
+            }
+            false
+        }
+    });
+    wv.set_html(HTML);
+
+    app.run().unwrap();
+}
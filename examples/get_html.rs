@@ -0,0 +1,25 @@
+use fltk::{app, button, group, prelude::*, window};
+use fltk_webview::*;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut col = group::Flex::default_fill().column();
+    col.set_margin(5);
+    let mut btn = button::Button::default().with_label("Print source");
+    col.fixed(&btn, 30);
+    let mut wv_win = window::Window::default();
+    col.end();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.set_html("<html><body><h1 id='title'>Hello</h1><script>title.textContent += ' world';</script></body></html>");
+
+    btn.set_callback(move |_| wv.get_html(|html| println!("{}", html)));
+
+    app.run().unwrap();
+}
@@ -0,0 +1,29 @@
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn main() {
+    use fltk_webview::*;
+    use std::os::raw;
+    extern "C" {
+        fn gtk_init(argc: *mut i32, argv: *mut *mut raw::c_char);
+        fn gtk_window_new(kind: i32) -> *mut raw::c_void;
+        fn gtk_window_set_default_size(win: *mut raw::c_void, w: i32, h: i32);
+        fn gtk_widget_show_all(widget: *mut raw::c_void);
+        fn gtk_main();
+    }
+    // A window owned by GTK rather than fltk
+    let parent = unsafe {
+        gtk_init(&mut 0, std::ptr::null_mut());
+        let win = gtk_window_new(0);
+        gtk_window_set_default_size(win, 800, 600);
+        gtk_widget_show_all(win);
+        win
+    };
+    let wv = Webview::create_in_handle(false, NativeHandle::Gtk(parent), 800, 600).unwrap();
+    wv.navigate("https://google.com");
+    // The parent's toolkit runs the event loop
+    unsafe { gtk_main() };
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn main() {
+    println!("This example embeds the webview within a GTK window, which is only used on Linux");
+}
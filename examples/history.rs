@@ -0,0 +1,50 @@
+use fltk::{app, button, group, prelude::*, window};
+use fltk_webview::*;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut col = group::Flex::default_fill().column();
+    col.set_margin(5);
+    let row = group::Flex::default().row();
+    let mut back = button::Button::default().with_label("@<-");
+    let mut forward = button::Button::default().with_label("@->");
+    row.end();
+    col.fixed(&row, 30);
+    let mut wv_win = window::Window::default();
+    col.end();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let wv = Webview::create(false, &mut wv_win);
+    wv.navigate("https://www.rust-lang.org");
+
+    back.set_callback({
+        let wv = wv.clone();
+        move |_| wv.eval("history.back()")
+    });
+    forward.set_callback({
+        let wv = wv.clone();
+        move |_| wv.eval("history.forward()")
+    });
+
+    // Keep the buttons in sync with the session history
+    app::add_timeout3(0.1, move |handle| {
+        if wv.can_go_back() {
+            back.activate();
+        } else {
+            back.deactivate();
+        }
+        if wv.can_go_forward() {
+            forward.activate();
+        } else {
+            forward.deactivate();
+        }
+        app::repeat_timeout3(0.1, handle);
+    });
+
+    app.run().unwrap();
+}
@@ -0,0 +1,38 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default().size_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    // A comment typed by a user, which must show as text rather than run
+    let comment = "<img src=x onerror=\"alert('injected')\"> & <b>bold?</b>";
+    wv.bind("greet", |_seq, req| println!("Greeted: {}", req));
+    wv.bind("report", move |_seq, req| {
+        println!("Rendered text: {}", req);
+        // The escaped markup shows literally, no element was created from it
+        assert!(req.contains("<img src=x"));
+    });
+    let html = Html::new()
+        .title("Comments & more")
+        .style("body { font-family: sans-serif; } .comment { color: gray; }")
+        .body("<h1>Latest comment</h1>")
+        .body("<p class=\"comment\" id=\"comment\" title=\"{comment}\">{comment}</p>")
+        .var("comment", comment)
+        .body("<form data-bind=\"greet\"><input name=\"name\" placeholder=\"Name\"><button>Greet</button></form>")
+        .with_bindings(&["greet"])
+        .script(
+            "var p = document.getElementById('comment');\n\
+             window.report(p.textContent + ' (' + p.children.length + ' child elements)');",
+        );
+    wv.show_html(html);
+
+    app.run().unwrap();
+}
@@ -0,0 +1,30 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<body>
+    <h1>Mixed content</h1>
+    <p>This page has a secure origin, and references an image over plain HTTP.</p>
+    <img src="http://example.com/insecure.png" alt="insecure image">
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default().size_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.on_insecure_content(|url| println!("Insecure content: {}", url));
+    wv.set_mixed_content_policy(MixedContentPolicy::Block);
+    // Gives the page the origin of the base URL, except on Windows
+    wv.set_html_with_base(HTML, "https://example.com/");
+
+    app.run().unwrap();
+}
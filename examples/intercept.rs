@@ -0,0 +1,52 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<body>
+    <h1>Data</h1>
+    <pre id="data">Loading</pre>
+    <script>
+        fetch('https://api.example/data')
+            .then(function(r) { return r.json(); })
+            .then(function(data) {
+                document.getElementById('data').textContent = JSON.stringify(data);
+                window.report(data.name);
+            });
+    </script>
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.bind("report", |_seq, req| {
+        println!("The page displays {}", req);
+        assert!(req.contains("fltk"));
+    });
+    wv.intercept("https://api.example/*", |req| {
+        println!("{} {}", req.method, req.url);
+        if req.url.ends_with("/data") {
+            Some(InterceptedResponse::new(
+                200,
+                "application/json",
+                r#"{"name": "fltk", "stars": 42}"#,
+            ))
+        } else {
+            None
+        }
+    });
+    wv.set_html(HTML);
+
+    app.run().unwrap();
+}
@@ -0,0 +1,50 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+const HELP: &str = r#"
+<html>
+<body>
+    <h1>Help</h1>
+    <p>This page is served by the application, without a web server.</p>
+    <a href="internal://settings">Settings</a>
+</body>
+</html>"#;
+
+const SETTINGS: &str = r#"
+<html>
+<body>
+    <h1>Settings</h1>
+    <a href="internal://help">Help</a>
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    let registered = wv
+        .register_internal_page("help", HELP)
+        .and_then(|_| wv.register_internal_page("settings", SETTINGS));
+    match registered {
+        Ok(()) => {
+            wv.bind("report", |_seq, req| println!("Loaded: {}", req));
+            wv.init("window.addEventListener('load', function() { window.report(document.querySelector('h1').textContent); });");
+            wv.navigate("internal://help");
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            wv.set_html(HELP);
+        }
+    }
+
+    app.run().unwrap();
+}
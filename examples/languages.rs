@@ -0,0 +1,28 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    if let Err(e) = wv.set_languages(&["fr-FR", "en"]) {
+        eprintln!("{}", e);
+    }
+    wv.bind("report", |_seq, content| {
+        println!("navigator.language and navigator.languages: {}", content)
+    });
+    wv.set_html(
+        "<html><body><script>report(navigator.language, navigator.languages);</script></body></html>",
+    );
+
+    app.run().unwrap();
+}
@@ -0,0 +1,66 @@
+use fltk::{app, button, group, prelude::*, window};
+use fltk_webview::*;
+use std::{cell::RefCell, rc::Rc, thread, time::Duration};
+
+mod common;
+
+/// Serve /1 redirecting to /2, and /2 to /3, which is a page, slowing down each hop
+fn serve() -> String {
+    common::serve(|req| {
+        thread::sleep(Duration::from_millis(300));
+        match req.path() {
+            "/1" => common::Response::redirect("/2"),
+            "/2" => common::Response::redirect("/3"),
+            _ => common::Response::html("<h1>Arrived after two redirects</h1>"),
+        }
+    })
+}
+
+fn main() {
+    let base = serve();
+
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut col = group::Flex::default_fill().column();
+    let mut stop_reload = button::Button::default().with_label("Reload");
+    col.fixed(&stop_reload, 30);
+    let mut wv_win = window::Window::default();
+    col.end();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    let changes = Rc::new(RefCell::new(vec![]));
+    wv.on_loading_changed({
+        let changes = changes.clone();
+        let mut stop_reload = stop_reload.clone();
+        move |loading| {
+            println!("Loading: {}", loading);
+            changes.borrow_mut().push(loading);
+            stop_reload.set_label(if loading { "Stop" } else { "Reload" });
+        }
+    });
+    stop_reload.set_callback({
+        let mut wv = wv.clone();
+        move |_| {
+            if wv.is_loading() {
+                wv.stop();
+            } else {
+                wv.eval("location.reload()");
+            }
+        }
+    });
+
+    wv.navigate(&format!("{}/1", base));
+    // The flag stays set through both redirects
+    while changes.borrow().last() != Some(&false) {
+        app::wait_for(0.01).unwrap();
+    }
+    println!("Changes through the redirect chain: {:?}", changes.borrow());
+    assert!(!changes.borrow()[..changes.borrow().len() - 1].contains(&false));
+
+    app.run().unwrap();
+}
@@ -0,0 +1,49 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+mod common;
+
+/// Reads its configuration from localStorage as it loads
+const HTML: &str = r#"<html>
+<body>
+<h1 id="theme"></h1>
+<script>
+    var theme = localStorage.getItem('theme');
+    document.getElementById('theme').textContent = 'Theme: ' + theme;
+    window.loaded(theme);
+</script>
+</body>
+</html>"#;
+
+/// Serve `HTML` on every path, since localStorage needs a page with an origin
+fn serve() -> String {
+    common::serve(|_| common::Response::html(HTML))
+}
+
+fn main() {
+    let base = serve();
+
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default().size_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    // Seeded before navigating, so the page's script sees it
+    wv.set_local_storage("theme", "dark \"solarized\"");
+    wv.bind("loaded", {
+        let wv = wv.clone();
+        move |_seq, req| {
+            println!("The page read: {}", req);
+            assert_eq!(req, r#"["dark \"solarized\""]"#);
+            wv.get_local_storage("theme", |value| println!("Stored value: {:?}", value));
+        }
+    });
+    wv.navigate(&format!("{}/", base));
+
+    app.run().unwrap();
+}
@@ -0,0 +1,40 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"<html>
+<body>
+<h1>Logging</h1>
+<script>
+    console.log('page loaded');
+    console.error('something went wrong');
+    setTimeout(function() { location.href = 'data:text/html,<h1>Second page</h1>'; }, 500);
+</script>
+</body>
+</html>"#;
+
+fn main() {
+    let path = std::env::temp_dir().join("fltk-webview-example.log");
+    let _ = std::fs::remove_file(&path);
+
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default().size_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.set_log_file(&path).unwrap();
+    wv.set_html(HTML);
+
+    app::add_timeout3(2.0, move |_| {
+        let log = std::fs::read_to_string(&path).unwrap();
+        println!("{}:\n{}", path.display(), log);
+        assert!(log.contains("console.error: something went wrong"));
+        assert!(log.contains("navigated: data:text/html"));
+    });
+
+    app.run().unwrap();
+}
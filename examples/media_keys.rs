@@ -0,0 +1,42 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<body>
+    <h1>Press the play/pause, previous or next key</h1>
+    <p id="action"></p>
+    <script>
+        ['play', 'pause', 'previoustrack', 'nexttrack', 'stop'].forEach(function(action) {
+            navigator.mediaSession.setActionHandler(action, function() {
+                document.getElementById('action').textContent = action;
+                window.report(action);
+            });
+        });
+    </script>
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.bind("report", |_seq, req| {
+        println!("Media Session action: {}", req)
+    });
+    wv.set_media_keys_enabled(true);
+    wv.set_html(HTML);
+    // Without a media keyboard, simulate the key
+    app::add_timeout3(1.0, move |_| wv.dispatch_key("MediaPlayPause"));
+
+    app.run().unwrap();
+}
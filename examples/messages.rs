@@ -0,0 +1,38 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<body>
+<p id="log"></p>
+<button onclick="window.external.sendMessage('ping from the page')">Send</button>
+<script>
+    window.external.receiveMessage = function(msg) {
+        document.getElementById('log').textContent = msg;
+    };
+</script>
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default_fill();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.on_message({
+        let mut wv = wv.clone();
+        move |msg| {
+            println!("{}", msg);
+            wv.post_message(&format!("pong, received \"{}\"", msg));
+        }
+    });
+    wv.set_html(HTML);
+
+    app.run().unwrap();
+}
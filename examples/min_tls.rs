@@ -0,0 +1,25 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    // Before the first page loads
+    match wv.set_minimum_tls_version(TlsVersion::Tls12) {
+        Ok(()) => println!("Connections use TLS 1.2 or later"),
+        Err(e) => println!("The minimum TLS version can't be set: {}", e),
+    }
+    wv.navigate("https://www.rust-lang.org");
+
+    app.run().unwrap();
+}
@@ -0,0 +1,46 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+fn main() {
+    // The screen to open on, e.g. `cargo run --example move_to_screen -- 1`
+    let target: usize = std::env::args()
+        .nth(1)
+        .and_then(|a| a.parse().ok())
+        .unwrap_or(1);
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default().size_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.set_html("<h1>Which screen am I on?</h1>");
+
+    let count = app::screen_count() as usize;
+    println!("{} screen(s), moving to screen {}", count, target);
+    wv.move_to_screen(target);
+    // Window managers apply the move asynchronously
+    app::add_timeout3(1.0, move |_| {
+        let screen = target.min(count - 1);
+        let (x, y, w, h) = app::screen_xywh(screen as i32);
+        let inside = win.x() >= x && win.y() >= y && win.x() < x + w && win.y() < y + h;
+        println!(
+            "Window at {},{}, on screen {}, within screen {}: {}",
+            win.x(),
+            win.y(),
+            wv.screen(),
+            screen,
+            inside
+        );
+        if count < 2 {
+            println!("Single screen, skipping the check");
+        } else {
+            assert!(inside && wv.screen() == screen);
+        }
+    });
+
+    app.run().unwrap();
+}
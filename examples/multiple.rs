@@ -0,0 +1,23 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webviews");
+    let mut left = window::Window::new(5, 5, 392, 590, None);
+    left.end();
+    let mut right = window::Window::new(403, 5, 392, 590, None);
+    right.end();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let left = Webview::create(false, &mut left);
+    left.navigate("https://www.rust-lang.org");
+    let right = Webview::create(false, &mut right);
+    right.navigate("https://www.fltk.org");
+
+    app.run().unwrap();
+}
@@ -0,0 +1,35 @@
+use fltk::{app, button, group, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<body>
+    <video controls autoplay loop width="640"
+        src="https://interactive-examples.mdn.mozilla.net/media/cc0-videos/flower.webm"></video>
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut col = group::Flex::default_fill().column();
+    let mut mute = button::CheckButton::default().with_label("Muted");
+    col.fixed(&mute, 30);
+    let mut wv_win = window::Window::default();
+    col.end();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    let _ = wv.set_autoplay_policy(AutoplayPolicy::Allow);
+    wv.set_html(HTML);
+    mute.set_callback(move |b| {
+        wv.set_muted(b.is_checked());
+        println!("is_muted: {}", wv.is_muted());
+    });
+
+    app.run().unwrap();
+}
@@ -0,0 +1,30 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let wv = Webview::create(false, &mut wv_win);
+    wv.navigate("https://www.rust-lang.org");
+
+    match wv.native_window_handle() {
+        #[cfg(target_os = "windows")]
+        NativeHandle::Win32(hwnd) => println!("HWND: {:?}", hwnd),
+        #[cfg(target_os = "macos")]
+        NativeHandle::Cocoa(nswindow) => println!("NSWindow: {:?}", nswindow),
+        #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+        NativeHandle::Gtk(gtkwindow) => println!("GtkWindow: {:?}", gtkwindow),
+        _ => unreachable!(),
+    }
+
+    app.run().unwrap();
+}
@@ -0,0 +1,61 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+use std::time::{Duration, Instant};
+
+mod common;
+
+/// Serve a page naming its path, printing the cache headers of the requests
+fn serve() -> String {
+    common::serve(|req| {
+        if let Some(cache) = req.header("cache-control") {
+            println!("{} with Cache-Control: {}", req.line, cache);
+        }
+        common::Response::html(format!("<h1>{}</h1>", req.path()))
+            .with_header("Cache-Control", "max-age=3600")
+    })
+}
+
+fn main() {
+    let base = serve();
+
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    let url = |path: &str| format!("{}{}", base, path);
+    wv.navigate(&url("/first"));
+    wv.wait_until_loaded(Duration::from_secs(5)).unwrap();
+
+    let replace = NavigateOptions {
+        replace_current_entry: true,
+        ..Default::default()
+    };
+    wv.navigate_with_options(&url("/second"), replace).unwrap();
+    // Let the page run location.replace
+    let end = Instant::now() + Duration::from_millis(300);
+    while Instant::now() < end {
+        app::wait_for(0.01).unwrap();
+    }
+    wv.wait_until_loaded(Duration::from_secs(5)).unwrap();
+    println!("can_go_back after replacing: {}", wv.can_go_back());
+    assert!(!wv.can_go_back());
+
+    let fresh = NavigateOptions {
+        bypass_cache: true,
+        ..Default::default()
+    };
+    match wv.navigate_with_options(&url("/second"), fresh) {
+        Ok(()) => println!("Revalidating /second"),
+        Err(e) => eprintln!("bypass_cache: {}", e),
+    }
+
+    app.run().unwrap();
+}
@@ -0,0 +1,39 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+use std::time::{Duration, Instant};
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    let events = wv.events();
+    if let Err(e) = wv.set_navigation_timeout(Some(Duration::from_secs(3))) {
+        println!("Navigation timeouts unavailable: {}", e);
+    }
+    // A non-routable address, the connection hangs
+    let start = Instant::now();
+    wv.navigate("http://10.255.255.1/");
+
+    while app.wait() {
+        for event in events.try_iter() {
+            if let WebviewEvent::NavigationFailed(url, reason) = event {
+                println!(
+                    "Loading {} failed after {:.1}s: {}",
+                    url,
+                    start.elapsed().as_secs_f64(),
+                    reason
+                );
+                wv.set_html("<h1>The page took too long to load</h1>");
+            }
+        }
+    }
+}
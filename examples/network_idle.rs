@@ -0,0 +1,62 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+    time::Duration,
+};
+
+mod common;
+
+/// Set once the delayed request was answered
+static ANSWERED: AtomicBool = AtomicBool::new(false);
+
+const HTML: &str = r#"<h1>Network idle</h1>
+<p id="data">Loading...</p>
+<script>
+    setTimeout(function() {
+        var xhr = new XMLHttpRequest();
+        xhr.open('GET', '/slow');
+        xhr.onload = function() { document.getElementById('data').textContent = xhr.responseText; };
+        xhr.send();
+    }, 200);
+</script>"#;
+
+/// Serve the page at `/`, and answer `/slow` after a second
+fn serve() -> String {
+    common::serve(|req| {
+        if req.path() != "/slow" {
+            return common::Response::html(HTML);
+        }
+        thread::sleep(Duration::from_secs(1));
+        ANSWERED.store(true, Ordering::SeqCst);
+        common::Response::html("Loaded after a second")
+    })
+}
+
+fn main() {
+    let base = serve();
+
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.navigate(&format!("{}/", base));
+    match wv.wait_for_network_idle(500, Duration::from_secs(10)) {
+        Ok(()) => {
+            println!("Network idle");
+            assert!(ANSWERED.load(Ordering::SeqCst));
+        }
+        Err(e) => eprintln!("{}", e),
+    }
+
+    app.run().unwrap();
+}
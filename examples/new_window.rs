@@ -0,0 +1,38 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<body>
+    <p><a href="https://www.rust-lang.org" target="_blank">Opens in this webview</a></p>
+    <p><a href="https://crates.io" target="_blank">Opens in the default browser</a></p>
+    <p><button onclick="window.open('https://example.com')">Denied</button></p>
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.set_new_window_handler(|url| {
+        if url.starts_with("https://crates.io") {
+            NewWindowDecision::OpenExternally
+        } else if url.starts_with("https://example.com") {
+            NewWindowDecision::Deny
+        } else {
+            NewWindowDecision::OpenInSame
+        }
+    });
+    wv.set_html(HTML);
+
+    app.run().unwrap();
+}
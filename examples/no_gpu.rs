@@ -0,0 +1,20 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default_fill();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let wv = WebviewBuilder::new()
+        .hardware_acceleration(HardwareAcceleration::Never)
+        .build(&mut wv_win);
+    wv.navigate("https://google.com");
+
+    app.run().unwrap();
+}
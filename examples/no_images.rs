@@ -0,0 +1,60 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+mod common;
+
+const HTML: &str = r#"
+<html>
+<body>
+    <img id="img" src="/pixel.png">
+    <script>
+        window.addEventListener('load', function() {
+            window.report(document.getElementById('img').naturalWidth);
+        });
+    </script>
+</body>
+</html>"#;
+
+/// A 1x1 PNG
+const PIXEL: &[u8] = &[
+    0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44, 0x52,
+    0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x04, 0x00, 0x00, 0x00, 0xb5, 0x1c, 0x0c,
+    0x02, 0x00, 0x00, 0x00, 0x0b, 0x49, 0x44, 0x41, 0x54, 0x78, 0xda, 0x63, 0x64, 0x60, 0x00, 0x00,
+    0x00, 0x06, 0x00, 0x02, 0x30, 0x81, 0xd0, 0x2f, 0x00, 0x00, 0x00, 0x00, 0x49, 0x45, 0x4e, 0x44,
+    0xae, 0x42, 0x60, 0x82,
+];
+
+/// Serve the page, and the image it shows
+fn serve() -> String {
+    common::serve(|req| {
+        if req.path() == "/pixel.png" {
+            println!("The image was requested");
+            common::Response::new("200 OK", "image/png", PIXEL)
+        } else {
+            common::Response::html(HTML)
+        }
+    })
+}
+
+fn main() {
+    let base = serve();
+
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default_fill();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.bind("report", |_seq, req| println!("naturalWidth: {}", req));
+    match wv.set_auto_load_images(false) {
+        Ok(()) => println!("Images disabled, naturalWidth should be 0"),
+        Err(e) => eprintln!("set_auto_load_images: {}", e),
+    }
+    wv.navigate(&format!("{}/", base));
+
+    app.run().unwrap();
+}
@@ -0,0 +1,36 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<head><title>Untouched</title></head>
+<body>
+    <p>Scripts are disabled, so the title stays "Untouched"</p>
+    <script>document.title = "Changed by a script";</script>
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    if let Err(e) = wv.set_javascript_enabled(false) {
+        eprintln!("{}", e);
+    }
+    wv.on_title_changed(move |title| {
+        println!("Title: {}", title);
+        win.set_label(title);
+    });
+    wv.set_html(HTML);
+
+    app.run().unwrap();
+}
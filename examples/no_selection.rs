@@ -0,0 +1,45 @@
+use fltk::{app, button, group, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<body>
+    <h1>Kiosk</h1>
+    <p>This text can't be selected or copied while selection is disabled.</p>
+    <input value="Form fields stay editable">
+</body>
+</html>"#;
+
+/// Selects the whole document, and reports the selected text after the page handled it
+const SELECT_JS: &str = r#"window.getSelection().selectAllChildren(document.body);
+setTimeout(function() { window.report(window.getSelection().toString()); }, 100);"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut col = group::Flex::default_fill().column();
+    let mut enabled = button::CheckButton::default().with_label("Selection enabled");
+    col.fixed(&enabled, 30);
+    let mut wv_win = window::Window::default();
+    col.end();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.bind("report", |_seq, req| println!("Selected text: {}", req));
+    wv.set_selection_enabled(false);
+    wv.set_html(HTML);
+    app::add_timeout3(1.0, {
+        let wv = wv.clone();
+        move |_| wv.eval(SELECT_JS)
+    });
+    enabled.set_callback(move |b| {
+        wv.set_selection_enabled(b.is_checked());
+        wv.eval(SELECT_JS);
+    });
+
+    app.run().unwrap();
+}
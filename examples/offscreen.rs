@@ -0,0 +1,15 @@
+use fltk::app;
+use fltk_webview::*;
+
+fn main() {
+    let app = app::App::default();
+
+    let mut wv = Webview::create_offscreen(false, 800, 600).expect("Couldn't create the webview");
+    wv.on_title_changed(|title| {
+        println!("Rendered offscreen: {}", title);
+        app::quit();
+    });
+    wv.navigate("data:text/html,<title>Hello</title><p>Hello offscreen</p>");
+
+    app.run().unwrap();
+}
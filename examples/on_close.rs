@@ -0,0 +1,28 @@
+use fltk::{app, dialog, group, prelude::*, window};
+use fltk_webview::*;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    // The webview is nested two levels deep, in a group within a flex
+    let outer = group::Group::default_fill();
+    let mut col = group::Flex::default_fill().column();
+    col.set_margin(10);
+    let mut wv_win = window::Window::default();
+    col.end();
+    outer.end();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.set_html("<h1>Close the window</h1>");
+    wv.on_close(|| {
+        println!("close requested");
+        dialog::choice2_default("Really close?", "No", "Yes", "") == Some(1)
+    });
+
+    app.run().unwrap();
+}
@@ -0,0 +1,27 @@
+use fltk::{app, prelude::*, valuator, window};
+use fltk_webview::*;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut slider = valuator::HorNiceSlider::new(10, 10, 780, 30, None);
+    slider.set_bounds(0.0, 1.0);
+    slider.set_value(1.0);
+    let mut wv_win = window::Window::new(10, 50, 780, 540, None);
+    wv_win.end();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.set_html("<h1>Drag the slider to fade the webview</h1>");
+    wv.set_opacity(0.5);
+    println!("Opacity: {}", wv.opacity());
+    assert_eq!(wv.opacity(), 0.5);
+    slider.set_value(wv.opacity());
+    slider.set_callback(move |s| wv.set_opacity(s.value()));
+
+    app.run().unwrap();
+}
@@ -0,0 +1,36 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<body>
+<p id="result"></p>
+<script>
+    window.onload = function() {
+        explode().then(function(v) {
+            document.getElementById('result').textContent = 'resolved: ' + v;
+        }).catch(function(e) {
+            document.getElementById('result').textContent = 'rejected: ' + e;
+        });
+    };
+</script>
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default_fill();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    // The panic rejects the promise instead of aborting the application
+    wv.bind_result("explode", |_req| panic!("something went wrong"));
+    wv.set_html(HTML);
+
+    app.run().unwrap();
+}
@@ -0,0 +1,41 @@
+use fltk::{app, button, group, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<body>
+    <video id="video" controls autoplay muted loop width="640"
+        src="https://interactive-examples.mdn.mozilla.net/media/cc0-videos/flower.webm"></video>
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut col = group::Flex::default_fill().column();
+    let mut pause = button::CheckButton::default().with_label("Paused");
+    col.fixed(&pause, 30);
+    let mut wv_win = window::Window::default();
+    col.end();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.set_html(HTML);
+    wv.bind("report", |_seq, content| {
+        println!("video.paused: {}", content)
+    });
+    pause.set_callback(move |b| {
+        if b.is_checked() {
+            wv.pause_media();
+        } else {
+            wv.resume_media();
+        }
+        wv.eval("report(document.getElementById('video').paused)");
+    });
+
+    app.run().unwrap();
+}
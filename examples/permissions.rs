@@ -0,0 +1,39 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<body>
+    <p id="status">Requesting the location...</p>
+    <script>
+        navigator.geolocation.getCurrentPosition(
+            p => document.getElementById('status').innerText =
+                `Located at ${p.coords.latitude}, ${p.coords.longitude}`,
+            e => document.getElementById('status').innerText = `Denied: ${e.message}`);
+    </script>
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    if let Err(e) = wv.set_permission_handler(|kind, origin| {
+        println!("{:?} requested by {}", kind, origin);
+        kind == PermissionKind::Geolocation
+    }) {
+        eprintln!("{}", e);
+    }
+    wv.set_html_with_base(HTML, "https://localhost/");
+
+    app.run().unwrap();
+}
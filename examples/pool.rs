@@ -0,0 +1,47 @@
+use fltk::{app, button, group, prelude::*, window};
+use fltk_webview::*;
+use std::{cell::RefCell, rc::Rc};
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut col = group::Flex::default_fill().column();
+    let mut btn = button::Button::default().with_label("Show the next preview");
+    col.fixed(&btn, 30);
+    let mut wv_win = window::Window::default();
+    col.end();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut pool = WebviewPool::default();
+    let first = pool.acquire(&mut wv_win);
+    let native = first.get_window();
+    first.set_html("<h1>Preview 1</h1>");
+    // Returned to the pool, and reset
+    drop(first);
+    println!("Idle after release: {}", pool.idle_count());
+    let second = pool.acquire(&mut wv_win);
+    println!(
+        "Reused: {}, same instance: {}, bindings: {:?}",
+        second.is_reused(),
+        second.get_window() == native,
+        second.bindings()
+    );
+
+    let pool = Rc::new(RefCell::new(pool));
+    let current = Rc::new(RefCell::new(Some(second)));
+    let mut count = 1;
+    btn.set_callback(move |_| {
+        count += 1;
+        // Release the current preview before acquiring the next one
+        current.borrow_mut().take();
+        let preview = pool.borrow_mut().acquire(&mut wv_win);
+        preview.set_html(&format!("<h1>Preview {}</h1>", count));
+        *current.borrow_mut() = Some(preview);
+    });
+
+    app.run().unwrap();
+}
@@ -0,0 +1,35 @@
+use fltk::{app, button, group, prelude::*, window};
+use fltk_webview::*;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut col = group::Flex::default_fill().column();
+    let mut btn = button::Button::default().with_label("Move the webview");
+    col.fixed(&btn, 30);
+    let mut wv_win = window::Window::default();
+    col.end();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let wv = Webview::create(false, &mut wv_win);
+    wv.set_html("<h1>Moved within its fltk window</h1>");
+    let mut offset = 0;
+    btn.set_callback({
+        let mut wv = wv.clone();
+        move |_| {
+            offset = (offset + 20) % 100;
+            wv.set_position(offset, offset * 2);
+            println!(
+                "Moved to {:?}, read back {:?}",
+                (offset, offset * 2),
+                wv.position()
+            );
+        }
+    });
+
+    app.run().unwrap();
+}
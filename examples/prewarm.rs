@@ -0,0 +1,35 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+use std::time::Instant;
+
+// Compare `cargo run --example prewarm` with `cargo run --example prewarm -- cold`,
+// each process starting the engine once
+fn main() {
+    let cold = std::env::args().any(|a| a == "cold");
+    let app = app::App::default();
+    if !cold {
+        let start = Instant::now();
+        prewarm().unwrap();
+        println!("Prewarmed in {:?}", start.elapsed());
+        // Stands for the work an application does before showing its window
+        app::sleep(1.0);
+    }
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default().size_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let start = Instant::now();
+    let wv = Webview::create(false, &mut wv_win);
+    println!(
+        "{} create took {:?}",
+        if cold { "Cold" } else { "Prewarmed" },
+        start.elapsed()
+    );
+    wv.set_html("<html><body><h1>Hello</h1></body></html>");
+
+    app.run().unwrap();
+}
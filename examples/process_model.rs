@@ -0,0 +1,28 @@
+use fltk::{app, group, prelude::*, window};
+use fltk_webview::*;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let row = group::Flex::default_fill().row();
+    let mut left = window::Window::default();
+    left.end();
+    let mut right = window::Window::default();
+    right.end();
+    row.end();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    // The model must be set for the first webview, older WebKitGTK versions
+    // then serve both webviews from a single web process
+    let builder = WebviewBuilder::new().process_model(ProcessModel::SharedSecondaryProcess);
+    let first = builder.clone().build(&mut left);
+    first.navigate("https://www.rust-lang.org");
+    let second = builder.build(&mut right);
+    second.navigate("https://www.fltk.org");
+
+    app.run().unwrap();
+}
@@ -0,0 +1,29 @@
+use fltk::{app, group, misc, prelude::*, window};
+use fltk_webview::*;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut col = group::Flex::default_fill().column();
+    col.set_margin(5);
+    let mut bar = misc::Progress::default();
+    bar.set_minimum(0.0);
+    bar.set_maximum(1.0);
+    col.fixed(&bar, 20);
+    let mut wv_win = window::Window::default();
+    col.end();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.on_progress(move |p| {
+        bar.set_value(p);
+        bar.set_label(&format!("{:.0}%", p * 100.0));
+    });
+    wv.navigate("https://www.rust-lang.org");
+
+    app.run().unwrap();
+}
@@ -0,0 +1,33 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+use std::ffi::{CStr, CString};
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default_fill();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let wv = Webview::create(false, &mut wv_win);
+    // webview_version isn't wrapped by fltk-webview
+    let version = unsafe {
+        let info = &*fltk_webview_sys::webview_version();
+        CStr::from_ptr(info.version_number.as_ptr())
+            .to_string_lossy()
+            .into_owned()
+    };
+    let html = CString::new(format!(
+        "<html><body><h1>webview {}</h1></body></html>",
+        version
+    ))
+    .unwrap();
+    unsafe {
+        fltk_webview_sys::webview_set_html(wv.as_raw(), html.as_ptr());
+    }
+
+    app.run().unwrap();
+}
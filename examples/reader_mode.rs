@@ -0,0 +1,47 @@
+use fltk::{app, button, group, prelude::*, window};
+use fltk_webview::*;
+
+const ARTICLE: &str = r##"
+<html>
+<head><title>An article</title></head>
+<body style="display: flex">
+    <nav style="width: 200px; background: #ddd">
+        <a href="#">Home</a> <a href="#">News</a> <a href="#">About</a>
+    </nav>
+    <div class="post">
+        <p>Reader mode keeps the main content of a page, such as this article, and drops the rest.</p>
+        <p>The paragraphs, their length and their commas, make this container the most likely content,
+        while the navigation, mostly made of links, is left out.</p>
+        <p>Scripts, forms and the page's styling are removed, and the text is laid out for reading.</p>
+        <form><input placeholder="Subscribe"><button>Send</button></form>
+    </div>
+    <aside class="sidebar" style="width: 200px; background: #eee">Related links and ads</aside>
+</body>
+</html>"##;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut col = group::Flex::default_fill().column();
+    let mut reader = button::CheckButton::default().with_label("Reader mode");
+    col.fixed(&reader, 30);
+    let mut wv_win = window::Window::default();
+    col.end();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.set_html(ARTICLE);
+    wv.bind("report", |_seq, content| {
+        println!("Simplified: {}", content)
+    });
+    reader.set_callback(move |b| {
+        wv.set_reader_mode(b.is_checked());
+        wv.eval("report(!!document.querySelector('.reader-content'))");
+    });
+
+    app.run().unwrap();
+}
@@ -0,0 +1,39 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+mod common;
+
+/// Serve /login redirecting to /auth, and /auth to /home, which is a page
+fn serve() -> String {
+    common::serve(|req| match req.path() {
+        "/login" => common::Response::redirect("/auth?state=xyz"),
+        "/auth?state=xyz" => common::Response::redirect("/home"),
+        _ => common::Response::html("<h1>Logged in</h1>"),
+    })
+}
+
+fn main() {
+    let base = serve();
+
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    if let Err(e) = wv.on_redirect(|from, to| println!("Redirected from {} to {}", from, to)) {
+        eprintln!("{}", e);
+    }
+    wv.navigate(&format!("{}/login", base));
+    app::add_timeout3(2.0, move |_| {
+        println!("Redirect chain: {:?}", wv.last_redirect_chain());
+    });
+
+    app.run().unwrap();
+}
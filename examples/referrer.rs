@@ -0,0 +1,49 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+use std::time::Duration;
+
+mod common;
+
+/// Serve a page reporting the `Referer` header of its request through the `report` binding
+fn serve() -> String {
+    common::serve(|req| {
+        let referer = req.header("referer").unwrap_or_default();
+        common::Response::html(format!(
+            "<h1>Echo</h1><script>window.report({:?});</script>",
+            referer
+        ))
+    })
+}
+
+fn main() {
+    let base = serve();
+
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default().size_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    let referrer = "https://partner.example/landing";
+    wv.bind("report", move |_seq, req| {
+        println!("Referer received by the server: {}", req);
+        assert!(req.contains(referrer));
+    });
+    wv.set_referrer_policy(ReferrerPolicy::StrictOrigin);
+    let opts = NavigateOptions {
+        referrer: Some(referrer.to_string()),
+        ..Default::default()
+    };
+    match wv.navigate_with_options(&format!("{}/echo", base), opts) {
+        Ok(()) => {
+            let _ = wv.wait_until_loaded(Duration::from_secs(5));
+        }
+        Err(e) => eprintln!("referrer: {}", e),
+    }
+
+    app.run().unwrap();
+}
@@ -0,0 +1,39 @@
+use fltk::{app, button, group, prelude::*, window};
+use fltk_webview::*;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut col = group::Flex::default_fill().column();
+    let mut toggle = button::Button::default().with_label("Close the webview");
+    col.fixed(&toggle, 30);
+    let mut host = window::Window::default();
+    host.end();
+    col.end();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    // On Linux, GTK is only pumped while a webview is open, so the application
+    // stays idle between closing the webview and opening the next one
+    let mut wv = Some(Webview::create(false, &mut host));
+    let mut opened = 1;
+    wv.as_ref().unwrap().set_html("<h1>Webview 1</h1>");
+    toggle.set_callback(move |b| match wv.take() {
+        Some(w) => {
+            w.close();
+            b.set_label("Open a webview");
+        }
+        None => {
+            opened += 1;
+            let w = Webview::create(false, &mut host);
+            w.set_html(&format!("<h1>Webview {}</h1>", opened));
+            wv = Some(w);
+            b.set_label("Close the webview");
+        }
+    });
+
+    app.run().unwrap();
+}
@@ -0,0 +1,71 @@
+use fltk::{app, button, group, prelude::*, window};
+use fltk_webview::*;
+use std::{cell::Cell, rc::Rc};
+
+const HTML: &str = r#"
+<html>
+<body>
+    <h1>Count: <span id="count">0</span></h1>
+    <button onclick="increment()">Increment</button>
+    <script>
+        function increment() {
+            window.count().then(function(c) { document.getElementById('count').textContent = c; });
+        }
+    </script>
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut first = window::Window::default()
+        .with_size(600, 400)
+        .with_label("First");
+    let mut col = group::Flex::default_fill().column();
+    let mut move_btn = button::Button::default().with_label("Move to the other window");
+    col.fixed(&move_btn, 30);
+    let mut first_host = window::Window::default();
+    first_host.end();
+    col.end();
+    first.end();
+    first.make_resizable(true);
+    first.show();
+
+    let mut second = window::Window::new(650, 100, 600, 400, "Second");
+    let mut second_host = window::Window::default_fill();
+    second_host.end();
+    second.end();
+    second.make_resizable(true);
+    second.show();
+
+    let mut wv = Webview::create(false, &mut first_host);
+    let count = Rc::new(Cell::new(0));
+    wv.bind("count", {
+        let wv = wv.clone();
+        let count = count.clone();
+        move |seq, _req| {
+            count.set(count.get() + 1);
+            wv.return_(seq, 0, &count.get().to_string());
+        }
+    });
+    wv.set_html(HTML);
+
+    let mut in_first = true;
+    move_btn.set_callback(move |_| {
+        let target = if in_first {
+            &mut second_host
+        } else {
+            &mut first_host
+        };
+        match wv.reparent(target) {
+            Ok(()) => {
+                in_first = !in_first;
+                // The page and its bindings survived the move
+                wv.eval("increment()");
+                println!("Moved, count before: {}", count.get());
+            }
+            Err(e) => eprintln!("{}", e),
+        }
+    });
+
+    app.run().unwrap();
+}
@@ -0,0 +1,39 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+use std::time::Duration;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.navigate("https://example.com");
+    if let Err(e) = wv.wait_until_loaded(Duration::from_secs(10)) {
+        eprintln!("{}", e);
+    }
+    // Report the engine's use every 2 seconds
+    app::add_timeout3(0.1, move |handle| {
+        wv.resource_usage(|usage| {
+            println!(
+                "{} processes, {:.1} MB, {:.1}% CPU",
+                usage.processes,
+                usage.memory as f64 / 1e6,
+                usage.cpu
+            );
+            if usage.processes > 0 {
+                assert!(usage.memory > 0);
+            }
+        });
+        app::repeat_timeout3(2.0, handle);
+    });
+
+    app.run().unwrap();
+}
@@ -0,0 +1,49 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<body>
+<p id="out"></p>
+<script>
+    window.onload = async () => {
+        var out = document.getElementById('out');
+        out.innerText = `double(21) = ${await double(21)}`;
+        try {
+            await forget();
+            report('forget resolved');
+        } catch (e) {
+            report(`forget rejected: ${e}`);
+        }
+    };
+</script>
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.bind_with_responder("double", |responder, args| {
+        let args: tinyjson::JsonValue = args.parse().unwrap();
+        match args[0].get::<f64>() {
+            Some(n) => responder.resolve(&(n * 2.0).to_string()),
+            None => responder.reject("expected a number"),
+        }
+    });
+    // The responder is dropped without responding, which rejects the promise
+    wv.bind_with_responder("forget", |_responder, _args| ());
+    wv.bind("report", |_seq, content| println!("{}", content));
+    wv.set_html(HTML);
+
+    app.run().unwrap();
+}
@@ -0,0 +1,42 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+use std::time::Duration;
+
+mod common;
+
+/// Serve `/` and answer 404 for any other path
+fn serve() -> String {
+    common::serve(|req| {
+        if req.path() == "/" {
+            common::Response::html("<h1>Home</h1><a href=\"/missing\">A broken link</a>")
+        } else {
+            common::Response::new("404 Not Found", "text/html", "<h1>Not found</h1>")
+        }
+    })
+}
+
+fn main() {
+    let base = serve();
+
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    for path in ["/", "/missing"] {
+        wv.navigate(&format!("{}{}", base, path));
+        match wv.wait_until_loaded(Duration::from_secs(5)) {
+            Ok(()) => println!("{}: {:?}", path, wv.last_response_status()),
+            Err(e) => eprintln!("{}: {}", path, e),
+        }
+    }
+
+    app.run().unwrap();
+}
@@ -0,0 +1,42 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+};
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let wv = Webview::create(false, &mut wv_win);
+    wv.set_html("<h1>Running a closure on the main thread</h1>");
+    let main_thread = thread::current().id();
+    let ran = Arc::new(AtomicBool::new(false));
+    thread::spawn({
+        let wv = wv.clone();
+        let ran = ran.clone();
+        move || {
+            wv.run_on_main(move || {
+                let on_main = thread::current().id() == main_thread;
+                ran.store(on_main, Ordering::SeqCst);
+            });
+        }
+    });
+    app::add_timeout3(1.0, move |_| {
+        println!("Ran on the main thread: {}", ran.load(Ordering::SeqCst));
+    });
+
+    app.run().unwrap();
+}
@@ -0,0 +1,27 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    // An intranet application, whose hosts aren't checked against phishing and malware lists
+    let wv = WebviewBuilder::new()
+        .safe_browsing(false)
+        .url("https://example.com")
+        .try_build(&mut wv_win);
+    match wv {
+        Ok(_) => println!("Created without safe browsing"),
+        Err(e) => eprintln!("{}", e),
+    }
+
+    app.run().unwrap();
+}
@@ -0,0 +1,60 @@
+use fltk::{app, button, group, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<head>
+    <style>
+        body { font-family: sans-serif; }
+        h1 { color: steelblue; }
+    </style>
+</head>
+<body>
+    <h1>Snapshot</h1>
+    <canvas id="c" width="200" height="100"></canvas>
+    <p><input value="Edit me before saving"></p>
+    <script>
+        var ctx = document.getElementById('c').getContext('2d');
+        ctx.fillStyle = 'orange';
+        ctx.fillRect(20, 20, 160, 60);
+    </script>
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut col = group::Flex::default_fill().column();
+    let mut save = button::Button::default().with_label("Save complete page");
+    col.fixed(&save, 30);
+    let mut wv_win = window::Window::default();
+    col.end();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.set_html(HTML);
+    save.set_callback(move |_| {
+        let path = std::env::temp_dir().join("fltk-webview-snapshot.html");
+        wv.save_complete(&path, {
+            let path = path.clone();
+            move |res| match res {
+                Ok(()) => {
+                    let html = std::fs::read_to_string(&path).unwrap_or_default();
+                    println!(
+                        "Saved {} ({} bytes, canvas inlined: {})",
+                        path.display(),
+                        html.len(),
+                        html.contains("data:image")
+                    );
+                }
+                Err(e) => eprintln!("Saving failed: {}", e),
+            }
+        });
+    });
+
+    app.run().unwrap();
+}
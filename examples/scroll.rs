@@ -0,0 +1,50 @@
+use fltk::{app, button, group, prelude::*, window};
+use fltk_webview::*;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut col = group::Flex::default_fill().column();
+    col.set_margin(5);
+    let row = group::Flex::default().row();
+    let mut down = button::Button::default().with_label("Scroll down");
+    let mut top = button::Button::default().with_label("Back to top");
+    let mut pos = button::Button::default().with_label("Position");
+    let mut bars = button::CheckButton::default().with_label("Scrollbars");
+    bars.set_checked(true);
+    row.end();
+    col.fixed(&row, 30);
+    let mut wv_win = window::Window::default();
+    col.end();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let wv = Webview::create(false, &mut wv_win);
+    let paragraphs: String = (1..=200).map(|i| format!("<p>Line {}</p>", i)).collect();
+    wv.set_html(&format!("<html><body>{}</body></html>", paragraphs));
+
+    down.set_callback({
+        let mut wv = wv.clone();
+        move |_| wv.scroll_to(0.0, 1000.0)
+    });
+    top.set_callback({
+        let mut wv = wv.clone();
+        move |_| wv.scroll_to(0.0, 0.0)
+    });
+    pos.set_callback({
+        let mut wv = wv.clone();
+        move |b| {
+            let mut b = b.clone();
+            wv.scroll_position(move |x, y| b.set_label(&format!("{}, {}", x, y)));
+        }
+    });
+    bars.set_callback({
+        let mut wv = wv.clone();
+        move |b| wv.set_scrollbars_visible(b.is_checked())
+    });
+
+    app.run().unwrap();
+}
@@ -0,0 +1,46 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<body>
+    <h1>Selection</h1>
+    <p id="quote">The quick brown fox jumps over the lazy dog</p>
+</body>
+</html>"#;
+
+/// Selects the words "brown fox" of the paragraph
+const SELECT_JS: &str = r#"(function() {
+    var text = document.getElementById('quote').firstChild;
+    var range = document.createRange();
+    range.setStart(text, 10);
+    range.setEnd(text, 19);
+    var sel = window.getSelection();
+    sel.removeAllRanges();
+    sel.addRange(range);
+})()"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.set_html(HTML);
+    app::add_timeout3(1.0, move |_| {
+        wv.eval(SELECT_JS);
+        wv.selected_text(|text| {
+            println!("Selected: {:?}", text);
+            assert_eq!(text, "brown fox");
+        });
+    });
+
+    app.run().unwrap();
+}
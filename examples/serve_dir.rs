@@ -0,0 +1,46 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+use std::time::Duration;
+
+const INDEX: &str = r#"
+<html>
+<body>
+    <h1 id="title">Loading</h1>
+    <script type="module" src="main.js"></script>
+</body>
+</html>"#;
+
+const MAIN_JS: &str = r#"
+document.getElementById('title').textContent = 'Served from ' + location.origin;
+window.report(location.href);
+"#;
+
+fn main() {
+    let dir = std::env::temp_dir().join(format!("fltk-webview-serve-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("index.html"), INDEX).unwrap();
+    std::fs::write(dir.join("main.js"), MAIN_JS).unwrap();
+
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.bind("report", |_seq, req| println!("Module loaded at {}", req));
+    let base = wv.serve_dir(&dir).unwrap();
+    println!("Serving {} at {}", dir.display(), base);
+    wv.navigate(&format!("{}index.html", base));
+    if let Err(e) = wv.wait_until_loaded(Duration::from_secs(5)) {
+        eprintln!("{}", e);
+    }
+
+    app.run().unwrap();
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,99 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+mod common;
+
+/// Serve a page with a form at any path
+fn serve() -> String {
+    common::serve(|req| {
+        common::Response::html(format!(
+            "<h1>Page {}</h1><input placeholder=\"Type here\"><div style=\"height: 3000px\"></div>",
+            req.path()
+        ))
+    })
+}
+
+fn window(title: &str) -> window::Window {
+    let mut win = window::Window::default()
+        .with_size(600, 400)
+        .with_label(title);
+    let wv_win = window::Window::default()
+        .with_size(590, 390)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+    wv_win
+}
+
+/// Pump the event loop until `f` returns true, or 5 seconds passed
+fn wait_until(mut f: impl FnMut() -> bool) {
+    for _ in 0..500 {
+        if f() {
+            return;
+        }
+        app::wait_for(0.01).unwrap();
+    }
+}
+
+fn main() {
+    let base = serve();
+
+    let _app = app::App::default();
+    let mut first = Webview::create(false, &mut window("Saved"));
+    for page in ["/first", "/second"] {
+        first.navigate(&format!("{}{}", base, page));
+        // Lets the navigation start
+        app::wait_for(0.1).unwrap();
+        first.wait_until_loaded(Duration::from_secs(5)).unwrap();
+    }
+    first.eval("document.querySelector('input').value = 'Restored text'; window.scrollTo(0, 500);");
+
+    let blob = Rc::new(RefCell::new(None));
+    first.save_session_state({
+        let blob = blob.clone();
+        move |state| *blob.borrow_mut() = Some(state)
+    });
+    wait_until(|| blob.borrow().is_some());
+    let blob = blob.borrow_mut().take().unwrap().unwrap();
+    println!("Saved {} bytes of session state", blob.len());
+
+    // A fresh webview, as after restarting the application
+    let mut second = Webview::create(false, &mut window("Restored"));
+    second.restore_session_state(&blob).unwrap();
+    app::wait_for(0.1).unwrap();
+    second.wait_until_loaded(Duration::from_secs(5)).unwrap();
+
+    let url = Rc::new(RefCell::new(None));
+    second.eval_batch_with_result(&["location.href"], {
+        let url = url.clone();
+        move |results| *url.borrow_mut() = results.into_iter().next()
+    });
+    wait_until(|| url.borrow().is_some());
+    let url = url.borrow_mut().take().unwrap().unwrap();
+    println!("Restored {}, can go back: {}", url, second.can_go_back());
+    assert_eq!(url, format!("\"{}/second\"", base));
+    // The history isn't recovered from the page's state, on Windows and before macOS 12
+    if cfg!(not(target_os = "windows")) {
+        assert!(second.can_go_back());
+    }
+
+    // The text typed on the page is set again once it loaded
+    let mut text = None;
+    wait_until(|| {
+        let result = Rc::new(RefCell::new(None));
+        second.eval_batch_with_result(&["document.querySelector('input').value"], {
+            let result = result.clone();
+            move |results| *result.borrow_mut() = results.into_iter().next()
+        });
+        wait_until(|| result.borrow().is_some());
+        text = result.borrow_mut().take().and_then(|r| r.ok());
+        text.as_deref() == Some("\"Restored text\"")
+    });
+    println!("Restored text field: {:?}", text);
+    // The engines restoring their own state may not keep the fields of the current page
+    if cfg!(target_os = "windows") {
+        assert_eq!(text.as_deref(), Some("\"Restored text\""));
+    }
+}
@@ -0,0 +1,34 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<body>
+<img src="https://www.fltk.org/images/logo.png"
+    onload="report('image loaded')" onerror="report('image blocked')">
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default_fill();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    let res = wv.with_settings(|s| {
+        s.images = Some(false);
+        s.developer_extras = Some(true);
+    });
+    if let Err(e) = res {
+        eprintln!("{}", e);
+    }
+    wv.bind("report", |_seq, content| println!("{}", content));
+    wv.set_html(HTML);
+
+    app.run().unwrap();
+}
@@ -0,0 +1,31 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+fn main() {
+    let mut instance = match SingleInstance::acquire("fltk-webview-example").unwrap() {
+        Some(instance) => instance,
+        None => {
+            println!("Already running, the existing window was asked to come forward");
+            return;
+        }
+    };
+    // A second claim, as made by another launch, finds this instance
+    assert!(SingleInstance::acquire("fltk-webview-example")
+        .unwrap()
+        .is_none());
+
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default_fill();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.set_html("<h1>Launch this example again, this window comes forward</h1>");
+    instance.focus_on_activate(&wv);
+
+    app.run().unwrap();
+}
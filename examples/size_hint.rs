@@ -0,0 +1,46 @@
+use fltk::{app, button, group, prelude::*, window};
+use fltk_webview::*;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut col = group::Flex::default_fill().column();
+    let mut row = group::Flex::default().row();
+    let mut fix = button::Button::default().with_label("Fix to 600x400");
+    let mut free = button::Button::default().with_label("Free with SizeHint::None");
+    row.end();
+    col.fixed(&row, 30);
+    let mut wv_win = window::Window::default();
+    col.end();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let wv = Webview::create(false, &mut wv_win);
+    wv.set_html("<h1>Try resizing the window after each button</h1>");
+    fix.set_callback({
+        let mut wv = wv.clone();
+        move |_| {
+            wv.set_size_and_hint(600, 400, SizeHint::Fixed);
+            assert_eq!(wv.size_hint(), SizeHint::Fixed);
+            // The backend applies the size asynchronously
+            let wv = wv.clone();
+            app::add_timeout3(0.2, move |_| println!("Fixed to {:?}", wv.size()));
+        }
+    });
+    free.set_callback({
+        let mut wv = wv.clone();
+        let wv_win = wv_win.clone();
+        move |_| {
+            wv.set_size_and_hint(wv_win.w(), wv_win.h(), SizeHint::None);
+            // The top window accepts other sizes again
+            let mut top = wv_win.top_window().unwrap();
+            top.resize(top.x(), top.y(), top.w() + 100, top.h() + 50);
+            println!("Resizable again: {}", wv_win.w() != 600);
+        }
+    });
+
+    app.run().unwrap();
+}
@@ -0,0 +1,23 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    // The window can't shrink below 400x300 or grow above 1200x900 webview pixels
+    wv.set_min_size(400, 300);
+    wv.set_max_size(1200, 900);
+    wv.navigate("https://www.rust-lang.org");
+
+    app.run().unwrap();
+}
@@ -0,0 +1,38 @@
+use fltk::{app, button, group, prelude::*, window};
+use fltk_webview::*;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut col = group::Flex::default_fill().column();
+    let mut row = group::Flex::default().row();
+    let mut smooth = button::CheckButton::default().with_label("Smooth scrolling");
+    let mut down = button::Button::default().with_label("Scroll down");
+    let mut up = button::Button::default().with_label("Scroll up");
+    row.end();
+    col.fixed(&row, 30);
+    let mut wv_win = window::Window::default();
+    col.end();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    let paragraphs: String = (1..=200)
+        .map(|i| format!("<p>Paragraph {}</p>", i))
+        .collect();
+    wv.set_html(&format!("<html><body>{}</body></html>", paragraphs));
+    smooth.set_callback({
+        let mut wv = wv.clone();
+        move |b| wv.set_smooth_scrolling(b.is_checked())
+    });
+    down.set_callback({
+        let mut wv = wv.clone();
+        move |_| wv.scroll_to(0.0, 5000.0)
+    });
+    up.set_callback(move |_| wv.scroll_to(0.0, 0.0));
+
+    app.run().unwrap();
+}
@@ -0,0 +1,21 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default_fill();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    if let Err(e) = wv.set_spellcheck(true, &["en-US"]) {
+        eprintln!("{}", e);
+    }
+    wv.set_html(r#"<html><body><textarea rows="10" cols="60">Thiss sentense has typos</textarea></body></html>"#);
+
+    app.run().unwrap();
+}
@@ -0,0 +1,35 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<body>
+    <p id="runs"></p>
+    <script>
+        var runs = Number(localStorage.getItem('runs') || 0) + 1;
+        localStorage.setItem('runs', runs);
+        document.getElementById('runs').innerText = `This page was opened ${runs} time(s)`;
+    </script>
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    // Run the example several times, the count is kept across runs
+    let mut wv = WebviewBuilder::new()
+        .persistent_storage(std::env::temp_dir().join("fltk-webview-storage"))
+        .build(&mut wv_win);
+    wv.set_html_with_base(HTML, "https://localhost/");
+
+    app.run().unwrap();
+}
@@ -0,0 +1,36 @@
+use fltk::{app, button, group, prelude::*, window};
+use fltk_webview::*;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut col = group::Flex::default_fill().column();
+    col.set_margin(5);
+    let mut btn = button::ToggleButton::default().with_label("Hide panel");
+    col.fixed(&btn, 30);
+    let mut wv_win = window::Window::default();
+    col.end();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.navigate("https://www.youtube.com");
+
+    // Suspend the webview while its panel is hidden
+    btn.set_callback(move |b| {
+        if b.is_toggled() {
+            wv.suspend();
+            wv_win.hide();
+            b.set_label("Show panel");
+        } else {
+            wv_win.show();
+            wv.resume();
+            b.set_label("Hide panel");
+        }
+    });
+
+    app.run().unwrap();
+}
@@ -0,0 +1,55 @@
+use fltk::{app, group, menu, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<body>
+    <p id="text">Only the text is scaled, the image keeps its size</p>
+    <img id="image" width="160" height="120"
+        src="https://interactive-examples.mdn.mozilla.net/media/cc0-images/grapefruit-slice-332-332.jpg">
+</body>
+</html>"#;
+
+/// Reports the font size of the paragraph and the width of the image
+const REPORT_JS: &str =
+    "window.report(getComputedStyle(document.getElementById('text')).fontSize, \
+    document.getElementById('image').getBoundingClientRect().width)";
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut col = group::Flex::default_fill().column();
+    let mut zoom = menu::Choice::default();
+    zoom.add_choice("100%|150%|200%|50%");
+    zoom.set_value(0);
+    col.fixed(&zoom, 30);
+    let mut wv_win = window::Window::default();
+    col.end();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.bind("report", |_seq, req| {
+        println!("font-size and image width: {}", req)
+    });
+    wv.set_html(HTML);
+    zoom.set_callback(move |c| {
+        let factor = match c.value() {
+            1 => 1.5,
+            2 => 2.0,
+            3 => 0.5,
+            _ => 1.0,
+        };
+        wv.set_text_zoom(factor);
+        // Layout is updated before the next frame
+        wv.eval(&format!(
+            "requestAnimationFrame(function() {{ {} }})",
+            REPORT_JS
+        ));
+    });
+
+    app.run().unwrap();
+}
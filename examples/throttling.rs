@@ -0,0 +1,35 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<body>
+<h1 id="frames">0</h1>
+<p>Focus another window, the counter slows down to about one frame a second</p>
+<script>
+    var frames = 0;
+    var tick = function() {
+        document.getElementById('frames').textContent = ++frames;
+        requestAnimationFrame(tick);
+    };
+    requestAnimationFrame(tick);
+</script>
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default_fill();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.set_background_throttling(true);
+    wv.set_html(HTML);
+
+    app.run().unwrap();
+}
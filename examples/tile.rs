@@ -0,0 +1,35 @@
+use fltk::{app, browser, group, prelude::*, window};
+use fltk_webview::*;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let tile = group::Tile::default_fill();
+    let mut links = browser::HoldBrowser::new(0, 0, 400, 600, None);
+    for link in [
+        "https://www.rust-lang.org",
+        "https://www.fltk.org",
+        "https://crates.io",
+    ] {
+        links.add(link);
+    }
+    // The webview takes the right half, and follows the tile's divider
+    let mut pane = group::Group::new(400, 0, 400, 600, None);
+    pane.end();
+    tile.end();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let wv = Webview::create_in_group(false, &mut pane).unwrap();
+    wv.navigate("https://www.rust-lang.org");
+    links.set_callback(move |b| {
+        if let Some(url) = b.selected_text() {
+            wv.navigate(&url);
+        }
+    });
+
+    app.run().unwrap();
+}
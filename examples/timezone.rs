@@ -0,0 +1,38 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<body>
+    <p id="date"></p>
+    <script>
+        var now = new Date();
+        document.getElementById('date').textContent = now.toString();
+        window.report(Intl.DateTimeFormat().resolvedOptions().timeZone,
+            now.toLocaleString(), (1234567.5).toLocaleString());
+    </script>
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default_fill();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.bind("report", |_seq, req| {
+        println!("Time zone, date and number: {}", req);
+        assert!(req.starts_with(r#"["America/New_York""#));
+    });
+    wv.override_timezone(Some("America/New_York")).unwrap();
+    wv.override_locale(Some("de-DE")).unwrap();
+    assert!(wv.override_timezone(Some("not a zone")).is_err());
+    wv.set_html(HTML);
+
+    app.run().unwrap();
+}
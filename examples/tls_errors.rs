@@ -0,0 +1,39 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+mod common;
+
+fn main() {
+    let port = 8443;
+    let mut server = match common::serve_tls(port) {
+        Ok(server) => server,
+        Err(e) => {
+            eprintln!("Needs openssl: {}", e);
+            return;
+        }
+    };
+
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    // For development only: accept the certificates of the local server, and no other
+    let mut wv = Webview::create(false, &mut wv_win);
+    if let Err(e) = wv.set_tls_error_handler(|host, cert| {
+        println!("Rejected certificate of {}: {}", host, cert);
+        host == "localhost"
+    }) {
+        eprintln!("{}", e);
+    }
+    wv.navigate(&format!("https://localhost:{}/", port));
+
+    app.run().unwrap();
+    let _ = server.kill();
+}
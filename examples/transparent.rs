@@ -0,0 +1,44 @@
+use fltk::{app, draw, enums::Color, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<body style="background: transparent; color: white; font-size: 32px;">
+    <p>The gradient behind this text is drawn by fltk</p>
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    if let Err(e) = wv.set_transparent(true) {
+        eprintln!("{}", e);
+    }
+    wv.set_html(HTML);
+
+    // Replaces the default draw callback, so the webview also needs resizing here
+    wv_win.draw(move |w| {
+        for y in 0..w.h() {
+            let t = y as f64 / w.h() as f64;
+            draw::set_draw_color(Color::from_rgb(
+                (40.0 + 150.0 * t) as u8,
+                40,
+                (200.0 - 150.0 * t) as u8,
+            ));
+            draw::draw_xyline(0, y, w.w());
+        }
+        wv.set_size(w.w(), w.h(), SizeHint::None);
+    });
+
+    app.run().unwrap();
+}
@@ -0,0 +1,35 @@
+use fltk::{app, button, group, prelude::*, window};
+use fltk_webview::*;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut col = group::Flex::default_fill().column();
+    col.set_margin(5);
+    let mut btn = button::Button::default().with_label("Toggle red background");
+    col.fixed(&btn, 30);
+    let mut wv_win = window::Window::default();
+    col.end();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.bind("report", |_seq, content| {
+        println!("background: {}", content)
+    });
+    wv.set_html("<html><body><h1>Hello</h1></body></html>");
+
+    let mut style = None;
+    btn.set_callback(move |_| {
+        match style.take() {
+            Some(handle) => wv.remove_user_style(handle),
+            None => style = Some(wv.add_user_style("body { background: red; }")),
+        }
+        wv.eval("report(getComputedStyle(document.body).backgroundColor)");
+    });
+
+    app.run().unwrap();
+}
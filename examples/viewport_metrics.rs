@@ -0,0 +1,34 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<body style="margin: 0">
+    <div style="width: 2000px; height: 3000px; background: linear-gradient(teal, navy)"></div>
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.set_html(HTML);
+    app::add_timeout3(1.0, move |_| {
+        wv.viewport_metrics(|m| {
+            println!("{:?}", m);
+            assert_eq!((m.scroll_width, m.scroll_height), (2000.0, 3000.0));
+            assert!(m.inner_width > 0.0 && m.device_pixel_ratio > 0.0);
+        });
+    });
+
+    app.run().unwrap();
+}
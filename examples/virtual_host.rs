@@ -0,0 +1,38 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+const INDEX: &str = r#"
+<html>
+<head><link rel="stylesheet" href="style.css"></head>
+<body>
+    <p>Served from a local folder</p>
+</body>
+</html>"#;
+
+const STYLE: &str = "body { background: #223; color: white; font-size: 32px; }";
+
+fn main() {
+    let dir = std::env::temp_dir().join("fltk-webview-virtual-host");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("index.html"), INDEX).unwrap();
+    std::fs::write(dir.join("style.css"), STYLE).unwrap();
+
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    match wv.map_virtual_host("app.local", &dir, HostAccess::Deny) {
+        Ok(()) => wv.navigate("https://app.local/index.html"),
+        Err(e) => eprintln!("{}", e),
+    }
+
+    app.run().unwrap();
+}
@@ -0,0 +1,45 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+use std::time::Duration;
+
+const HTML: &str = r#"
+<html>
+<body>
+    <p>The results appear in a second...</p>
+    <script>
+        setTimeout(function() {
+            var div = document.createElement('div');
+            div.id = 'results';
+            div.innerText = 'Results';
+            document.body.appendChild(div);
+        }, 1000);
+    </script>
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default()
+        .with_size(790, 590)
+        .center_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.set_html(HTML);
+    match wv.wait_for_selector("#results", Duration::from_secs(5)) {
+        Ok(found) => println!("#results appeared: {}", found),
+        Err(e) => eprintln!("{}", e),
+    }
+    // Quotes in the selector are passed as a string, not as code
+    match wv.wait_for_selector("'); alert('injected", Duration::from_millis(500)) {
+        Ok(found) => println!("Unexpected match: {}", found),
+        Err(e) => println!("Invalid selector: {}", e),
+    }
+
+    app.run().unwrap();
+}
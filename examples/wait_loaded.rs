@@ -0,0 +1,23 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+use std::time::Duration;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default_fill();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.navigate("data:text/html,<html><body><h1>Loaded</h1></body></html>");
+    match wv.wait_until_loaded(Duration::from_secs(5)) {
+        Ok(()) => wv.eval("document.body.innerHTML += '<p>' + document.readyState + '</p>';"),
+        Err(e) => eprintln!("{}", e),
+    }
+
+    app.run().unwrap();
+}
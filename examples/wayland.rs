@@ -0,0 +1,33 @@
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+
+fn main() {
+    if std::env::var_os("WAYLAND_DISPLAY").is_none() {
+        println!("Not a Wayland session, skipping");
+        return;
+    }
+    // Pass `xwayland` to embed through XWayland instead
+    if std::env::args().nth(1).as_deref() == Some("xwayland") {
+        app::disable_wayland();
+    }
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut wv_win = window::Window::default().size_of_parent();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    match WebviewBuilder::new().try_build(&mut wv_win) {
+        Ok(wv) => {
+            println!("Embedded through XWayland");
+            wv.set_html("<h1>Running on XWayland</h1>");
+            app.run().unwrap();
+        }
+        Err(e) => {
+            println!("Couldn't embed: {}", e);
+            assert!(matches!(e, WebviewError::UnsupportedDisplay));
+        }
+    }
+}
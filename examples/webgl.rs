@@ -0,0 +1,40 @@
+use fltk::{app, button, group, prelude::*, window};
+use fltk_webview::*;
+
+const HTML: &str = r#"
+<html>
+<body>
+    <canvas id="canvas" width="300" height="150"></canvas>
+    <script>
+        window.report(document.createElement('canvas').getContext('webgl') !== null);
+    </script>
+</body>
+</html>"#;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut col = group::Flex::default_fill().column();
+    let mut enabled = button::CheckButton::default().with_label("WebGL enabled");
+    col.fixed(&enabled, 30);
+    let mut wv_win = window::Window::default();
+    col.end();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.bind("report", |_seq, req| {
+        println!("WebGL context created: {}", req)
+    });
+    wv.set_webgl_enabled(false);
+    wv.set_html(HTML);
+    enabled.set_callback(move |b| {
+        wv.set_webgl_enabled(b.is_checked());
+        wv.set_html(HTML);
+    });
+
+    app.run().unwrap();
+}
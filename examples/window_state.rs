@@ -0,0 +1,50 @@
+use fltk::{app, button, group, prelude::*, window};
+use fltk_webview::*;
+
+fn main() {
+    let app = app::App::default();
+    let mut win = window::Window::default()
+        .with_size(800, 600)
+        .with_label("Webview");
+    let mut col = group::Flex::default_fill().column();
+    let mut row = group::Flex::default().row();
+    let mut minimize = button::Button::default().with_label("Minimize");
+    let mut maximize = button::Button::default().with_label("Maximize");
+    let mut restore = button::Button::default().with_label("Restore");
+    row.end();
+    col.fixed(&row, 30);
+    let mut wv_win = window::Window::default();
+    col.end();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.set_html(
+        r#"<html><body>
+        <p id="size"></p>
+        <script>
+            var show = function() {
+                document.getElementById('size').textContent = innerWidth + 'x' + innerHeight;
+            };
+            window.addEventListener('resize', show);
+            show();
+        </script>
+        </body></html>"#,
+    );
+    wv.on_window_state_changed(|state| println!("Window state: {:?}", state));
+    // Maximizes on start, the handler then reports `Maximized`
+    wv.set_window_state(WindowState::Maximized);
+
+    minimize.set_callback({
+        let mut wv = wv.clone();
+        move |_| wv.set_window_state(WindowState::Minimized)
+    });
+    maximize.set_callback({
+        let mut wv = wv.clone();
+        move |_| wv.set_window_state(WindowState::Maximized)
+    });
+    restore.set_callback(move |_| wv.set_window_state(WindowState::Normal));
+
+    app.run().unwrap();
+}
@@ -1,6 +1,7 @@
 fn main() {
     println!("cargo:rerun-if-changed=src/gtk_helper.c");
     println!("cargo:rerun-if-changed=src/cocoa_helper.m");
+    println!("cargo:rerun-if-env-changed=PKG_CONFIG_PATH");
 
     #[cfg(target_os = "macos")]
     compile_cocoa_helper();
@@ -11,23 +12,93 @@ fn main() {
 
 #[cfg(not(any(target_os = "macos", target_os = "windows")))]
 fn compile_gtk_helper() {
-    let cflags = std::process::Command::new("pkg-config")
-        .args(&["--cflags", "gtk+-3.0"])
-        .output()
-        .expect("Needs pkg-config and gtk installed");
-    let cflags = String::from_utf8_lossy(&cflags.stdout).to_string();
-    let cflags: Vec<&str> = cflags.split_ascii_whitespace().collect();
+    // Newer distros only ship webkit2gtk-4.1 (libsoup3), older ones only 4.0 (libsoup2).
+    // Linking is left to wv-sys, only the include paths and defines are needed here
+    let (version, webkit) = ["webkit2gtk-4.1", "webkit2gtk-4.0"]
+        .iter()
+        .find_map(|name| probe(name).map(|lib| (*name, lib)))
+        .expect("Needs pkg-config and webkit2gtk-4.1 or webkit2gtk-4.0 installed");
+    let gtk = probe("gtk+-3.0").expect("Needs pkg-config and gtk+-3.0 installed");
+    if std::env::var_os("CARGO_FEATURE_STATIC_WEBKIT").is_some() {
+        link_static(version);
+    }
     let mut build = cc::Build::new();
     build.file("src/gtk_helper.c");
-    for flag in cflags {
-        build.flag(flag);
+    for lib in [webkit, gtk] {
+        for path in lib.include_paths {
+            build.include(path);
+        }
+        for (name, value) in lib.defines {
+            build.define(&name, value.as_deref());
+        }
     }
     build.compile("gtkwid");
 }
 
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn probe(name: &str) -> Option<pkg_config::Library> {
+    pkg_config::Config::new()
+        .cargo_metadata(false)
+        .probe(name)
+        .ok()
+}
+
+/// Libraries of the C runtime, which are always linked dynamically
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+const SYSTEM_LIBS: &[&str] = &["c", "m", "dl", "pthread", "rt", "resolv", "stdc++", "gcc_s"];
+
+/// Link webkit2gtk and its dependencies, as listed by `pkg-config --static`, to their static archives.
+/// They come before the dynamic libraries wv-sys links, so the linker resolves the symbols from the archives,
+/// and drops the dynamic libraries which aren't needed anymore.
+/// Libraries without an archive stay dynamic, and are reported as warnings
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn link_static(webkit: &str) {
+    let lib = pkg_config::Config::new()
+        .statik(true)
+        .cargo_metadata(false)
+        .probe(webkit)
+        .unwrap_or_else(|e| panic!("static-webkit: {}", e));
+    // pkg-config leaves out the default directories
+    let mut dirs = lib.link_paths.clone();
+    if let Some(paths) = std::env::var_os("LIBRARY_PATH") {
+        dirs.extend(std::env::split_paths(&paths));
+    }
+    let arch = std::env::var("CARGO_CFG_TARGET_ARCH").unwrap_or_default();
+    for dir in [
+        format!("/usr/lib/{}-linux-gnu", arch),
+        "/usr/lib64".to_string(),
+        "/usr/lib".to_string(),
+        "/usr/local/lib".to_string(),
+    ] {
+        dirs.push(dir.into());
+    }
+    let mut dynamic = vec![];
+    for name in &lib.libs {
+        if SYSTEM_LIBS.contains(&name.as_str()) {
+            continue;
+        }
+        let archive = format!("lib{}.a", name);
+        match dirs.iter().find(|dir| dir.join(&archive).exists()) {
+            Some(dir) => {
+                println!("cargo:rustc-link-search=native={}", dir.display());
+                println!("cargo:rustc-link-lib=static={}", name);
+            }
+            None => dynamic.push(name.as_str()),
+        }
+    }
+    if !dynamic.is_empty() {
+        println!(
+            "cargo:warning=static-webkit: no static archive of {}, which stay dynamically linked",
+            dynamic.join(", ")
+        );
+    }
+}
+
 #[cfg(target_os = "macos")]
 fn compile_cocoa_helper() {
     let mut build = cc::Build::new();
     build.file("src/cocoa_helper.m");
     build.compile("cocoa");
+    // Evaluates the server certificates rejected by WebKit
+    println!("cargo:rustc-link-lib=framework=Security");
 }
@@ -1,5 +1,10 @@
 #import <Cocoa/Cocoa.h>
+#import <WebKit/WebKit.h>
 #include <assert.h>
+#include <ctype.h>
+#include <libproc.h>
+#include <mach/mach_time.h>
+#include <objc/message.h>
 #include <objc/runtime.h>
 
 @implementation NSWindow (KeyWindow)
@@ -70,8 +75,1352 @@ void make_delegate(NSWindow *child, NSWindow *parent, int add_menu) {
   add_nsmenu(add_menu);
 }
 
+// Stops win from forwarding its events to a webview moved to another window
+void my_clear_delegate(NSWindow *win) { [win setDelegate:nil]; }
+
 void my_close_win(NSWindow *win) {
   NSView *view = [win contentView];
   [view removeFromSuperview];
   [win close];
-}
\ No newline at end of file
+}
+
+static WKWebView *my_get_webview(void *win) {
+  return (WKWebView *)[(NSWindow *)win contentView];
+}
+
+typedef void (*my_str_cb)(const char *s, void *data);
+typedef void (*my_double_cb)(double d, void *data);
+typedef void (*my_flag_cb)(int flag, void *data);
+
+// Forwards key-value changes of a WKWebView property to a Rust callback
+@interface MyObserver : NSObject
+@property void (*cb)(void);
+@property void *data;
+@end
+
+@implementation MyObserver
+- (void)observeValueForKeyPath:(NSString *)keyPath
+                      ofObject:(id)object
+                        change:(NSDictionary *)change
+                       context:(void *)context {
+  if ([keyPath isEqualToString:@"title"]) {
+    NSString *title = [(WKWebView *)object title];
+    ((my_str_cb)self.cb)(title ? [title UTF8String] : "", self.data);
+  } else if ([keyPath isEqualToString:@"URL"]) {
+    NSURL *url = [(WKWebView *)object URL];
+    ((my_str_cb)self.cb)(url ? [[url absoluteString] UTF8String] : "",
+                         self.data);
+  } else if ([keyPath isEqualToString:@"estimatedProgress"]) {
+    ((my_double_cb)self.cb)([(WKWebView *)object estimatedProgress],
+                            self.data);
+  } else if ([keyPath isEqualToString:@"loading"]) {
+    ((my_flag_cb)self.cb)([(WKWebView *)object isLoading], self.data);
+  }
+}
+@end
+
+static void my_observe(void *win, NSString *key, void (*cb)(void),
+                       void *data) {
+  WKWebView *wv = my_get_webview(win);
+  MyObserver *observer = [[MyObserver alloc] init];
+  observer.cb = cb;
+  observer.data = data;
+  [wv addObserver:observer
+       forKeyPath:key
+          options:NSKeyValueObservingOptionNew
+          context:nil];
+  // Keep the observer alive as long as the webview
+  objc_setAssociatedObject(wv, (__bridge const void *)key, observer,
+                           OBJC_ASSOCIATION_RETAIN);
+  [observer release];
+}
+
+// The returned script is kept alive until passed to my_remove_user_script
+// Returns NULL if isolated worlds aren't available, before macOS 11
+void *my_add_user_script(void *win, const char *js, int at_end, int isolated) {
+  NSString *source = [NSString stringWithUTF8String:js];
+  WKUserScriptInjectionTime time = at_end
+                                       ? WKUserScriptInjectionTimeAtDocumentEnd
+                                       : WKUserScriptInjectionTimeAtDocumentStart;
+  WKUserScript *script;
+  if (!isolated) {
+    script = [[WKUserScript alloc] initWithSource:source
+                                    injectionTime:time
+                                 forMainFrameOnly:YES];
+  } else if (@available(macOS 11.0, *)) {
+    script = [[WKUserScript alloc]
+          initWithSource:source
+           injectionTime:time
+        forMainFrameOnly:YES
+          inContentWorld:[WKContentWorld worldWithName:@"fltk-webview"]];
+  } else {
+    return NULL;
+  }
+  [[[my_get_webview(win) configuration] userContentController]
+      addUserScript:script];
+  return script;
+}
+
+void my_remove_user_script(void *win, void *script) {
+  // WKUserContentController can only remove all scripts, so re-add the rest
+  WKUserContentController *manager =
+      [[my_get_webview(win) configuration] userContentController];
+  NSArray *scripts = [[manager userScripts] copy];
+  [manager removeAllUserScripts];
+  for (WKUserScript *s in scripts) {
+    if (s != script)
+      [manager addUserScript:s];
+  }
+  [scripts release];
+  [(WKUserScript *)script release];
+}
+
+int my_load_request(void *win, const char *method, const char *url,
+                    const char *const *header_names,
+                    const char *const *header_values, int header_count,
+                    const unsigned char *body, size_t body_len) {
+  NSURL *nsurl = [NSURL URLWithString:[NSString stringWithUTF8String:url]];
+  if (!nsurl)
+    return 0;
+  NSMutableURLRequest *req = [NSMutableURLRequest requestWithURL:nsurl];
+  [req setHTTPMethod:[NSString stringWithUTF8String:method]];
+  for (int i = 0; i < header_count; i++)
+    [req setValue:[NSString stringWithUTF8String:header_values[i]]
+        forHTTPHeaderField:[NSString stringWithUTF8String:header_names[i]]];
+  if (body)
+    [req setHTTPBody:[NSData dataWithBytes:body length:body_len]];
+  [my_get_webview(win) loadRequest:req];
+  return 1;
+}
+
+static char my_headers_key;
+static char my_header_origins_key;
+static char my_navigation_delegate_key;
+static char my_status_key;
+static char my_download_key;
+static char my_download_path_key;
+
+// The origin of url, e.g. https://example.com:8443, lowercased and without
+// the default port, or nil if it isn't an http(s) URL
+static NSString *my_origin(NSURL *url) {
+  NSString *scheme = [[url scheme] lowercaseString];
+  NSString *host = [[url host] lowercaseString];
+  BOOL http = [scheme isEqualToString:@"http"];
+  if ([host length] == 0 || !(http || [scheme isEqualToString:@"https"]))
+    return nil;
+  // IPv6 addresses are given without their brackets
+  if ([host containsString:@":"] && ![host hasPrefix:@"["])
+    host = [NSString stringWithFormat:@"[%@]", host];
+  NSNumber *port = [url port];
+  if (!port || [port integerValue] == (http ? 80 : 443))
+    return [NSString stringWithFormat:@"%@://%@", scheme, host];
+  return [NSString stringWithFormat:@"%@://%@:%@", scheme, host, port];
+}
+
+// Reissues the GET navigations of the main frame to the origins of the
+// default headers lacking them, with them added to a copy of the request.
+// Subresource requests can't be intercepted
+static BOOL my_reissue_with_headers(WKWebView *w, WKNavigationAction *action) {
+  NSDictionary *defaults = objc_getAssociatedObject(w, &my_headers_key);
+  NSArray *origins = objc_getAssociatedObject(w, &my_header_origins_key);
+  NSURLRequest *req = [action request];
+  NSString *origin = my_origin([req URL]);
+  if ([defaults count] == 0 || ![[action targetFrame] isMainFrame] ||
+      ![[req HTTPMethod] isEqualToString:@"GET"] || !origin ||
+      ![origins containsObject:origin])
+    return NO;
+  BOOL missing = NO;
+  for (NSString *name in defaults)
+    if (![req valueForHTTPHeaderField:name])
+      missing = YES;
+  if (!missing)
+    return NO;
+  NSMutableURLRequest *with = [req mutableCopy];
+  for (NSString *name in defaults)
+    [with setValue:defaults[name] forHTTPHeaderField:name];
+  [w loadRequest:with];
+  [with release];
+  return YES;
+}
+
+typedef int (*my_tls_cb)(const char *host, const char *cert, void *data);
+
+@interface MyTlsHandler : NSObject
+@property my_tls_cb cb;
+@property void *data;
+@end
+
+@implementation MyTlsHandler
+@end
+
+static char my_tls_key;
+
+// kind is 0 for Basic, 1 for Digest, 2 for NTLM and 3 for Negotiate.
+// Returns 1 after pointing user and password to the credentials, 0 to cancel
+typedef int (*my_auth_cb)(const char *host, int kind, const char **user,
+                          const char **password, void *data);
+
+@interface MyAuthHandler : NSObject
+@property my_auth_cb cb;
+@property void *data;
+@end
+
+@implementation MyAuthHandler
+@end
+
+static char my_auth_key;
+
+// The kind passed to my_auth_cb, -1 for other challenges
+static int my_auth_kind(NSString *method) {
+  NSString *methods[] = {
+      NSURLAuthenticationMethodHTTPBasic,
+      NSURLAuthenticationMethodHTTPDigest,
+      NSURLAuthenticationMethodNTLM,
+      NSURLAuthenticationMethodNegotiate,
+  };
+  for (int i = 0; i < 4; i++)
+    if ([method isEqualToString:methods[i]])
+      return i;
+  return -1;
+}
+
+// Asks the handlers of w for credentials, and whether to trust a server whose
+// certificate the system rejected. Other challenges get the default handling
+static void my_handle_challenge(
+    WKWebView *w, NSURLAuthenticationChallenge *challenge,
+    void (^complete)(NSURLSessionAuthChallengeDisposition, NSURLCredential *)) {
+  NSURLProtectionSpace *space = [challenge protectionSpace];
+  MyAuthHandler *a = objc_getAssociatedObject(w, &my_auth_key);
+  int kind = my_auth_kind([space authenticationMethod]);
+  if (a && kind >= 0) {
+    const char *user = NULL, *password = NULL;
+    if (a.cb([[space host] UTF8String], kind, &user, &password, a.data))
+      complete(NSURLSessionAuthChallengeUseCredential,
+               [NSURLCredential
+                   credentialWithUser:[NSString stringWithUTF8String:user]
+                             password:[NSString stringWithUTF8String:password]
+                          persistence:NSURLCredentialPersistenceForSession]);
+    else
+      complete(NSURLSessionAuthChallengeCancelAuthenticationChallenge, nil);
+    return;
+  }
+  MyTlsHandler *h = objc_getAssociatedObject(w, &my_tls_key);
+  SecTrustRef trust = [space serverTrust];
+  NSString *method = [space authenticationMethod];
+  if (!h || !trust ||
+      ![method isEqualToString:NSURLAuthenticationMethodServerTrust]) {
+    complete(NSURLSessionAuthChallengePerformDefaultHandling, nil);
+    return;
+  }
+  CFErrorRef error = NULL;
+  if (SecTrustEvaluateWithError(trust, &error)) {
+    complete(NSURLSessionAuthChallengePerformDefaultHandling, nil);
+    return;
+  }
+  NSMutableString *summary = [NSMutableString string];
+  if (SecTrustGetCertificateCount(trust) > 0) {
+    CFStringRef subject = SecCertificateCopySubjectSummary(
+        SecTrustGetCertificateAtIndex(trust, 0));
+    if (subject) {
+      [summary appendFormat:@"%@ ", (__bridge NSString *)subject];
+      CFRelease(subject);
+    }
+  }
+  if (error) {
+    [summary appendFormat:@"(%@)",
+                          [(__bridge NSError *)error localizedDescription]];
+    CFRelease(error);
+  }
+  if (h.cb([[space host] UTF8String], [summary UTF8String], h.data))
+    complete(NSURLSessionAuthChallengeUseCredential,
+             [NSURLCredential credentialForTrust:trust]);
+  else
+    complete(NSURLSessionAuthChallengePerformDefaultHandling, nil);
+}
+
+// Returns 0 to cancel the download, 1 to save it at *path, 2 for the default
+typedef int (*my_download_cb)(const char *url, const char *filename,
+                              const char **path, void *data);
+// state is 0 while in progress, 1 when finished, 2 on failure, 3 on cancel.
+// total is -1 when unknown
+typedef void (*my_download_progress_cb)(const char *url, const char *path,
+                                        unsigned long long received,
+                                        long long total, int state,
+                                        const char *error, void *data);
+
+API_AVAILABLE(macos(11.3))
+@interface MyDownloadHandler : NSObject <WKDownloadDelegate>
+@property my_download_cb decide;
+@property my_download_progress_cb progress;
+@property void *data;
+@end
+
+API_AVAILABLE(macos(11.3))
+static void my_report_download(MyDownloadHandler *h, WKDownload *d, int state,
+                               NSString *error) {
+  NSURL *dest = objc_getAssociatedObject(d, &my_download_path_key);
+  NSProgress *p = [d progress];
+  h.progress([[[[d originalRequest] URL] absoluteString] UTF8String],
+             dest ? [[dest path] UTF8String] : "", [p completedUnitCount],
+             [p totalUnitCount], state, error ? [error UTF8String] : NULL,
+             h.data);
+}
+
+// The destination is associated with the download, which is observed until
+// it finishes or fails
+@implementation MyDownloadHandler
+- (void)download:(WKDownload *)d
+    decideDestinationUsingResponse:(NSURLResponse *)response
+                 suggestedFilename:(NSString *)filename
+                 completionHandler:(void (^)(NSURL *))complete {
+  const char *path = NULL;
+  NSString *url = [[[d originalRequest] URL] absoluteString];
+  NSURL *dest = nil;
+  switch (self.decide([url UTF8String], [filename UTF8String], &path,
+                      self.data)) {
+  case 1:
+    dest = [NSURL fileURLWithPath:[NSString stringWithUTF8String:path]];
+    break;
+  case 2:
+    dest = [[[[NSFileManager defaultManager]
+        URLsForDirectory:NSDownloadsDirectory
+               inDomains:NSUserDomainMask] firstObject]
+        URLByAppendingPathComponent:filename];
+    break;
+  }
+  if (dest) {
+    // WKDownload fails instead of overwriting
+    [[NSFileManager defaultManager] removeItemAtURL:dest error:nil];
+    objc_setAssociatedObject(d, &my_download_path_key, dest,
+                             OBJC_ASSOCIATION_RETAIN);
+    [[d progress] addObserver:self
+                   forKeyPath:@"completedUnitCount"
+                      options:0
+                      context:d];
+  } else {
+    my_report_download(self, d, 3, nil);
+  }
+  complete(dest);
+}
+
+// Progress can be updated off the main thread
+- (void)observeValueForKeyPath:(NSString *)path
+                      ofObject:(id)object
+                        change:(NSDictionary *)change
+                       context:(void *)context {
+  WKDownload *d = [(WKDownload *)context retain];
+  dispatch_async(dispatch_get_main_queue(), ^{
+    my_report_download(self, d, 0, nil);
+    [d release];
+  });
+}
+
+- (void)downloadDidFinish:(WKDownload *)d {
+  [[d progress] removeObserver:self forKeyPath:@"completedUnitCount"];
+  my_report_download(self, d, 1, nil);
+}
+
+- (void)download:(WKDownload *)d
+    didFailWithError:(NSError *)error
+          resumeData:(NSData *)data {
+  if (objc_getAssociatedObject(d, &my_download_path_key))
+    [[d progress] removeObserver:self forKeyPath:@"completedUnitCount"];
+  if ([error code] == NSURLErrorCancelled)
+    my_report_download(self, d, 3, nil);
+  else
+    my_report_download(self, d, 2, [error localizedDescription]);
+}
+@end
+
+// Whether a response should be downloaded rather than shown
+static BOOL my_is_download(WKNavigationResponse *response) {
+  if (![response canShowMIMEType])
+    return YES;
+  NSURLResponse *r = [response response];
+  if (![r isKindOfClass:[NSHTTPURLResponse class]])
+    return NO;
+  NSString *disposition = [(NSHTTPURLResponse *)r
+      valueForHTTPHeaderField:@"Content-Disposition"];
+  return [[disposition lowercaseString] hasPrefix:@"attachment"];
+}
+
+// Returns 1 when it loaded a page replacing the error page
+typedef int (*my_load_failed_cb)(const char *uri, const char *error,
+                                 void *data);
+
+@interface MyLoadFailedHandler : NSObject
+@property my_load_failed_cb cb;
+@property void *data;
+@end
+
+@implementation MyLoadFailedHandler
+@end
+
+static char my_load_failed_key;
+
+// from is NULL when a navigation starts at to
+typedef void (*my_redirect_cb)(const char *from, const char *to, void *data);
+
+@interface MyRedirectHandler : NSObject
+@property my_redirect_cb cb;
+@property void *data;
+// The URL before a redirect, as the webview's has already changed when it's
+// reported
+@property(copy) NSString *from;
+@end
+
+@implementation MyRedirectHandler
+- (void)dealloc {
+  [_from release];
+  [super dealloc];
+}
+@end
+
+static char my_redirect_key;
+
+static void my_redirected(WKWebView *w, BOOL started) {
+  MyRedirectHandler *h = objc_getAssociatedObject(w, &my_redirect_key);
+  NSString *to = [[w URL] absoluteString];
+  if (!h || !to)
+    return;
+  h.cb(started || !h.from ? NULL : [h.from UTF8String], [to UTF8String],
+       h.data);
+  h.from = to;
+}
+
+static void my_load_failed(WKWebView *w, NSError *error) {
+  MyLoadFailedHandler *h = objc_getAssociatedObject(w, &my_load_failed_key);
+  if (!h)
+    return;
+  // Stopped loads, and responses turned into downloads, aren't failures
+  BOOL cancelled = [[error domain] isEqualToString:NSURLErrorDomain] &&
+                   [error code] == NSURLErrorCancelled;
+  BOOL interrupted = [[error domain] isEqualToString:@"WebKitErrorDomain"] &&
+                     [error code] == 102;
+  if (cancelled || interrupted)
+    return;
+  NSURL *url = [error userInfo][NSURLErrorFailingURLErrorKey];
+  NSString *uri = [(url ? url : [w URL]) absoluteString];
+  h.cb(uri ? [uri UTF8String] : "",
+       [[error localizedDescription] UTF8String], h.data);
+}
+
+// Used when webview didn't set a navigation delegate
+@interface MyNavigationDelegate : NSObject <WKNavigationDelegate>
+@end
+
+@implementation MyNavigationDelegate
+- (void)webView:(WKWebView *)w
+    decidePolicyForNavigationAction:(WKNavigationAction *)action
+                    decisionHandler:
+                        (void (^)(WKNavigationActionPolicy))decide {
+  if (@available(macOS 11.3, *)) {
+    if (objc_getAssociatedObject(w, &my_download_key) &&
+        [action shouldPerformDownload]) {
+      decide(WKNavigationActionPolicyDownload);
+      return;
+    }
+  }
+  decide(my_reissue_with_headers(w, action) ? WKNavigationActionPolicyCancel
+                                            : WKNavigationActionPolicyAllow);
+}
+
+// Keeps the HTTP status of the main frame's responses
+- (void)webView:(WKWebView *)w
+    decidePolicyForNavigationResponse:(WKNavigationResponse *)response
+                      decisionHandler:
+                          (void (^)(WKNavigationResponsePolicy))decide {
+  if ([response isForMainFrame]) {
+    NSURLResponse *r = [response response];
+    NSNumber *status =
+        [r isKindOfClass:[NSHTTPURLResponse class]]
+            ? [NSNumber numberWithInteger:[(NSHTTPURLResponse *)r statusCode]]
+            : nil;
+    objc_setAssociatedObject(w, &my_status_key, status,
+                             OBJC_ASSOCIATION_RETAIN);
+  }
+  if (@available(macOS 11.3, *)) {
+    if (objc_getAssociatedObject(w, &my_download_key) &&
+        my_is_download(response)) {
+      decide(WKNavigationResponsePolicyDownload);
+      return;
+    }
+  }
+  decide(WKNavigationResponsePolicyAllow);
+}
+
+- (void)webView:(WKWebView *)w
+    navigationAction:(WKNavigationAction *)action
+   didBecomeDownload:(WKDownload *)d API_AVAILABLE(macos(11.3)) {
+  [d setDelegate:objc_getAssociatedObject(w, &my_download_key)];
+}
+
+- (void)webView:(WKWebView *)w
+    navigationResponse:(WKNavigationResponse *)response
+     didBecomeDownload:(WKDownload *)d API_AVAILABLE(macos(11.3)) {
+  [d setDelegate:objc_getAssociatedObject(w, &my_download_key)];
+}
+
+- (void)webView:(WKWebView *)w
+    didReceiveAuthenticationChallenge:(NSURLAuthenticationChallenge *)challenge
+                    completionHandler:
+                        (void (^)(NSURLSessionAuthChallengeDisposition,
+                                  NSURLCredential *))complete {
+  my_handle_challenge(w, challenge, complete);
+}
+
+- (void)webView:(WKWebView *)w
+    didStartProvisionalNavigation:(WKNavigation *)navigation {
+  my_redirected(w, YES);
+}
+
+- (void)webView:(WKWebView *)w
+    didReceiveServerRedirectForProvisionalNavigation:
+        (WKNavigation *)navigation {
+  my_redirected(w, NO);
+}
+
+- (void)webView:(WKWebView *)w
+    didFailProvisionalNavigation:(WKNavigation *)navigation
+                       withError:(NSError *)error {
+  my_load_failed(w, error);
+}
+
+- (void)webView:(WKWebView *)w
+    didFailNavigation:(WKNavigation *)navigation
+            withError:(NSError *)error {
+  my_load_failed(w, error);
+}
+@end
+
+// The navigation delegate of wv, set to a MyNavigationDelegate if webview
+// didn't set one
+static id my_navigation_delegate(WKWebView *wv) {
+  id delegate = [wv navigationDelegate];
+  if (!delegate) {
+    delegate = [[MyNavigationDelegate alloc] init];
+    [wv setNavigationDelegate:delegate];
+    // The navigation delegate is a weak reference
+    objc_setAssociatedObject(wv, &my_navigation_delegate_key, delegate,
+                             OBJC_ASSOCIATION_RETAIN);
+    [delegate release];
+  }
+  return delegate;
+}
+
+void my_set_default_headers(void *win, const char *const *header_names,
+                            const char *const *header_values, int header_count,
+                            const char *const *origins, int origin_count) {
+  WKWebView *wv = my_get_webview(win);
+  NSMutableDictionary *defaults = [NSMutableDictionary dictionary];
+  for (int i = 0; i < header_count; i++)
+    defaults[[NSString stringWithUTF8String:header_names[i]]] =
+        [NSString stringWithUTF8String:header_values[i]];
+  objc_setAssociatedObject(wv, &my_headers_key, defaults,
+                           OBJC_ASSOCIATION_RETAIN);
+  NSMutableArray *allowed = [NSMutableArray array];
+  for (int i = 0; i < origin_count; i++)
+    [allowed addObject:[NSString stringWithUTF8String:origins[i]]];
+  objc_setAssociatedObject(wv, &my_header_origins_key, allowed,
+                           OBJC_ASSOCIATION_RETAIN);
+  id delegate = my_navigation_delegate(wv);
+  if (![delegate isKindOfClass:[MyNavigationDelegate class]]) {
+    SEL sel = @selector(webView:decidePolicyForNavigationAction:decisionHandler:);
+    id block = ^(id self, WKWebView *w, WKNavigationAction *action,
+                 void (^decide)(WKNavigationActionPolicy)) {
+      decide(my_reissue_with_headers(w, action)
+                 ? WKNavigationActionPolicyCancel
+                 : WKNavigationActionPolicyAllow);
+    };
+    // webview's navigation delegate class is shared by all webviews.
+    // Fails harmlessly if it was already added
+    class_addMethod([delegate class], sel, imp_implementationWithBlock(block),
+                    "v@:@@@?");
+  }
+}
+
+// webview doesn't set a navigation delegate, so the one receiving the responses
+// is set at creation
+void my_track_response_status(void *win) {
+  my_navigation_delegate(my_get_webview(win));
+}
+
+// The bundle version of the loaded WebKit framework, which follows the
+// system's updates rather than the SDK's
+void my_get_engine_version(my_str_cb cb, void *data) {
+  NSString *version = [[NSBundle bundleForClass:[WKWebView class]]
+      objectForInfoDictionaryKey:@"CFBundleVersion"];
+  cb(version ? [version UTF8String] : "", data);
+}
+
+// Downloads need macOS 11.3, this is a no-op on older versions
+void my_on_download(void *win, my_download_cb decide,
+                    my_download_progress_cb progress, void *data) {
+  if (@available(macOS 11.3, *)) {
+    WKWebView *wv = my_get_webview(win);
+    MyDownloadHandler *handler = [[MyDownloadHandler alloc] init];
+    handler.decide = decide;
+    handler.progress = progress;
+    handler.data = data;
+    objc_setAssociatedObject(wv, &my_download_key, handler,
+                             OBJC_ASSOCIATION_RETAIN);
+    [handler release];
+    my_navigation_delegate(wv);
+  }
+}
+
+// Has the navigation delegate of wv pass challenges to my_handle_challenge
+static void my_receive_challenges(WKWebView *wv) {
+  id delegate = my_navigation_delegate(wv);
+  if (![delegate isKindOfClass:[MyNavigationDelegate class]]) {
+    SEL sel = @selector(webView:
+        didReceiveAuthenticationChallenge:completionHandler:);
+    id block = ^(id self, WKWebView *w, NSURLAuthenticationChallenge *challenge,
+                 void (^complete)(NSURLSessionAuthChallengeDisposition,
+                                  NSURLCredential *)) {
+      my_handle_challenge(w, challenge, complete);
+    };
+    // Fails harmlessly if it was already added
+    class_addMethod([delegate class], sel, imp_implementationWithBlock(block),
+                    "v@:@@@?");
+  }
+}
+
+void my_on_tls_error(void *win, my_tls_cb cb, void *data) {
+  WKWebView *wv = my_get_webview(win);
+  MyTlsHandler *handler = [[MyTlsHandler alloc] init];
+  handler.cb = cb;
+  handler.data = data;
+  objc_setAssociatedObject(wv, &my_tls_key, handler, OBJC_ASSOCIATION_RETAIN);
+  [handler release];
+  my_receive_challenges(wv);
+}
+
+void my_on_auth(void *win, my_auth_cb cb, void *data) {
+  WKWebView *wv = my_get_webview(win);
+  MyAuthHandler *handler = [[MyAuthHandler alloc] init];
+  handler.cb = cb;
+  handler.data = data;
+  objc_setAssociatedObject(wv, &my_auth_key, handler, OBJC_ASSOCIATION_RETAIN);
+  [handler release];
+  my_receive_challenges(wv);
+}
+
+void my_on_load_failed(void *win, my_load_failed_cb cb, void *data) {
+  WKWebView *wv = my_get_webview(win);
+  MyLoadFailedHandler *handler = [[MyLoadFailedHandler alloc] init];
+  handler.cb = cb;
+  handler.data = data;
+  objc_setAssociatedObject(wv, &my_load_failed_key, handler,
+                           OBJC_ASSOCIATION_RETAIN);
+  [handler release];
+  id delegate = my_navigation_delegate(wv);
+  if (![delegate isKindOfClass:[MyNavigationDelegate class]]) {
+    id block = ^(id self, WKWebView *w, WKNavigation *n, NSError *error) {
+      my_load_failed(w, error);
+    };
+    // Fail harmlessly if they were already added
+    class_addMethod([delegate class],
+                    @selector(webView:didFailProvisionalNavigation:withError:),
+                    imp_implementationWithBlock(block), "v@:@@@");
+    class_addMethod([delegate class],
+                    @selector(webView:didFailNavigation:withError:),
+                    imp_implementationWithBlock(block), "v@:@@@");
+  }
+}
+
+// Only reports the redirects of the main frame's navigations
+void my_on_redirect(void *win, my_redirect_cb cb, void *data) {
+  WKWebView *wv = my_get_webview(win);
+  MyRedirectHandler *handler = [[MyRedirectHandler alloc] init];
+  handler.cb = cb;
+  handler.data = data;
+  objc_setAssociatedObject(wv, &my_redirect_key, handler,
+                           OBJC_ASSOCIATION_RETAIN);
+  [handler release];
+  id delegate = my_navigation_delegate(wv);
+  if (![delegate isKindOfClass:[MyNavigationDelegate class]]) {
+    id started = ^(id self, WKWebView *w, WKNavigation *n) {
+      my_redirected(w, YES);
+    };
+    id redirected = ^(id self, WKWebView *w, WKNavigation *n) {
+      my_redirected(w, NO);
+    };
+    // Fail harmlessly if they were already added
+    class_addMethod([delegate class],
+                    @selector(webView:didStartProvisionalNavigation:),
+                    imp_implementationWithBlock(started), "v@:@@");
+    class_addMethod(
+        [delegate class],
+        @selector(webView:didReceiveServerRedirectForProvisionalNavigation:),
+        imp_implementationWithBlock(redirected), "v@:@@");
+  }
+}
+
+typedef void (*my_bytes_cb)(const unsigned char *data, size_t len, void *user);
+
+// interactionState needs macOS 12, cb isn't called on older versions
+void my_get_session_state(void *win, my_bytes_cb cb, void *user) {
+  if (@available(macOS 12.0, *)) {
+    id state = [my_get_webview(win) interactionState];
+    if ([state isKindOfClass:[NSData class]])
+      cb([(NSData *)state bytes], [(NSData *)state length], user);
+  }
+}
+
+// Returns 0 before macOS 12. Setting interactionState loads its current item
+int my_restore_session_state(void *win, const unsigned char *data,
+                             size_t len) {
+  if (@available(macOS 12.0, *)) {
+    [my_get_webview(win)
+        setInteractionState:[NSData dataWithBytes:data length:len]];
+    return 1;
+  }
+  return 0;
+}
+
+// Shows html in place of the page at uri, which stays the URL of the webview
+// when WKWebView's private method is available
+void my_load_alternate_html(void *win, const char *html, const char *uri) {
+  WKWebView *wv = my_get_webview(win);
+  NSString *content = [NSString stringWithUTF8String:html];
+  NSURL *url = [NSURL URLWithString:[NSString stringWithUTF8String:uri]];
+  SEL sel = NSSelectorFromString(@"_loadAlternateHTMLString:baseURL:"
+                                 @"forUnreachableURL:");
+  if (url && [wv respondsToSelector:sel])
+    ((void (*)(id, SEL, NSString *, NSURL *, NSURL *))objc_msgSend)(
+        wv, sel, content, nil, url);
+  else
+    [wv loadHTMLString:content baseURL:nil];
+}
+
+void my_focus(void *win) {
+  [(NSWindow *)win makeKeyAndOrderFront:nil];
+  [(NSWindow *)win makeFirstResponder:my_get_webview(win)];
+}
+
+int my_has_focus(void *win) {
+  NSResponder *responder = [(NSWindow *)win firstResponder];
+  return [(NSWindow *)win isKeyWindow] &&
+         [responder isKindOfClass:[NSView class]] &&
+         [(NSView *)responder isDescendantOf:my_get_webview(win)];
+}
+
+void my_set_transparent(void *win, int transparent) {
+  [my_get_webview(win) setValue:[NSNumber numberWithBool:!transparent]
+                         forKey:@"drawsBackground"];
+  [(NSWindow *)win setOpaque:!transparent];
+  [(NSWindow *)win setBackgroundColor:transparent ? [NSColor clearColor]
+                                                  : [NSColor whiteColor]];
+}
+
+void my_set_background(void *win, unsigned char r, unsigned char g,
+                       unsigned char b) {
+  // The window shows through until the page paints its own background
+  [my_get_webview(win) setValue:[NSNumber numberWithBool:NO]
+                         forKey:@"drawsBackground"];
+  [(NSWindow *)win setOpaque:YES];
+  [(NSWindow *)win setBackgroundColor:[NSColor colorWithSRGBRed:r / 255.0
+                                                          green:g / 255.0
+                                                           blue:b / 255.0
+                                                          alpha:1.0]];
+}
+
+void my_set_opacity(void *win, double opacity) {
+  [(NSWindow *)win setAlphaValue:opacity];
+}
+
+void my_reload_bypass_cache(void *win) {
+  [my_get_webview(win) reloadFromOrigin];
+}
+
+void my_stop_loading(void *win) { [my_get_webview(win) stopLoading]; }
+
+// Includes the URL of a load which hasn't committed yet
+const char *my_get_uri(void *win) {
+  NSURL *url = [my_get_webview(win) URL];
+  return url ? [[url absoluteString] UTF8String] : "";
+}
+
+void my_load_html(void *win, const char *html, const char *base_url) {
+  [my_get_webview(win)
+      loadHTMLString:[NSString stringWithUTF8String:html]
+             baseURL:[NSURL
+                         URLWithString:[NSString stringWithUTF8String:base_url]]];
+}
+
+int my_can_go_back(void *win) { return [my_get_webview(win) canGoBack]; }
+
+int my_can_go_forward(void *win) {
+  return [my_get_webview(win) canGoForward];
+}
+
+int my_is_loading(void *win) { return [my_get_webview(win) isLoading]; }
+
+double my_get_progress(void *win) {
+  return [my_get_webview(win) estimatedProgress];
+}
+
+// 0 until a response is received, and for non-HTTP pages
+int my_get_response_status(void *win) {
+  NSNumber *status =
+      objc_getAssociatedObject(my_get_webview(win), &my_status_key);
+  return [status intValue];
+}
+
+void my_on_title_changed(void *win, my_str_cb cb, void *data) {
+  my_observe(win, @"title", (void (*)(void))cb, data);
+}
+
+void my_on_uri_changed(void *win, my_str_cb cb, void *data) {
+  my_observe(win, @"URL", (void (*)(void))cb, data);
+}
+
+void my_on_progress(void *win, my_double_cb cb, void *data) {
+  my_observe(win, @"estimatedProgress", (void (*)(void))cb, data);
+}
+
+// loading stays set through the redirects of a load, until it finishes
+void my_on_loading_changed(void *win, my_flag_cb cb, void *data) {
+  my_observe(win, @"loading", (void (*)(void))cb, data);
+}
+
+void my_set_javascript_enabled(void *win, int enabled) {
+  my_get_webview(win).configuration.preferences.javaScriptEnabled = enabled;
+}
+
+// scheme is 0 for the system's, 1 for light and 2 for dark. Only the web view
+// takes the appearance, not the window it's embedded in
+void my_set_color_scheme(void *win, int scheme) {
+  NSAppearance *appearance = nil;
+  if (scheme == 1)
+    appearance = [NSAppearance appearanceNamed:NSAppearanceNameAqua];
+  else if (scheme == 2)
+    appearance = [NSAppearance appearanceNamed:NSAppearanceNameDarkAqua];
+  [my_get_webview(win) setAppearance:appearance];
+}
+
+// values are in the order of the fields of Settings, -1 keeps a setting.
+// Images, WebGL and universal access can't be toggled
+void my_apply_settings(void *win, const int *values) {
+  WKPreferences *prefs = my_get_webview(win).configuration.preferences;
+  if (values[0] >= 0)
+    prefs.javaScriptEnabled = values[0];
+  if (values[2] >= 0)
+    [prefs setValue:@(values[2] != 0) forKey:@"plugInsEnabled"];
+  if (values[4] >= 0)
+    [prefs setValue:@(values[4] != 0) forKey:@"developerExtrasEnabled"];
+  if (values[5] >= 0)
+    [prefs setValue:@(values[5] != 0) forKey:@"allowFileAccessFromFileURLs"];
+}
+
+// languages is a NULL terminated array of locales, like en_US.
+// The spell checker is shared by the application, so only the first is used
+void my_set_spellcheck(void *win, int enabled, const char *const *languages) {
+  WKWebView *wv = my_get_webview(win);
+  SEL get = NSSelectorFromString(@"isContinuousSpellCheckingEnabled");
+  SEL toggle = NSSelectorFromString(@"toggleContinuousSpellChecking:");
+  if ([wv respondsToSelector:get] && [wv respondsToSelector:toggle] &&
+      ((BOOL(*)(id, SEL))objc_msgSend)(wv, get) != (enabled != 0))
+    ((void (*)(id, SEL, id))objc_msgSend)(wv, toggle, nil);
+  if (languages[0]) {
+    NSSpellChecker *checker = [NSSpellChecker sharedSpellChecker];
+    [checker setAutomaticallyIdentifiesLanguages:NO];
+    [checker setLanguage:[NSString stringWithUTF8String:languages[0]]];
+  }
+}
+
+typedef int (*my_key_cb)(int key, int state, void *data);
+
+// Converts to fltk's key codes, which are X keysyms
+static int my_fl_key(NSEvent *e) {
+  NSString *chars = [e charactersIgnoringModifiers];
+  if ([chars length] == 0)
+    return 0;
+  unichar c = [chars characterAtIndex:0];
+  if (c >= NSF1FunctionKey && c <= NSF35FunctionKey)
+    return 0xffbe + (c - NSF1FunctionKey);
+  switch (c) {
+  case NSUpArrowFunctionKey:
+    return 0xff52;
+  case NSDownArrowFunctionKey:
+    return 0xff54;
+  case NSLeftArrowFunctionKey:
+    return 0xff51;
+  case NSRightArrowFunctionKey:
+    return 0xff53;
+  case NSHomeFunctionKey:
+    return 0xff50;
+  case NSEndFunctionKey:
+    return 0xff57;
+  case NSPageUpFunctionKey:
+    return 0xff55;
+  case NSPageDownFunctionKey:
+    return 0xff56;
+  case NSDeleteFunctionKey:
+    return 0xffff;
+  case 0x1b:
+    return 0xff1b;
+  case 0x7f:
+    return 0xff08;
+  case '\r':
+    return 0xff0d;
+  case '\t':
+    return 0xff09;
+  default:
+    return c < 0x80 ? tolower(c) : c;
+  }
+}
+
+// Matches FL_SHIFT, FL_CTRL, FL_ALT and FL_META
+static int my_fl_state(NSEventModifierFlags flags) {
+  int ret = 0;
+  if (flags & NSEventModifierFlagShift)
+    ret |= 0x10000;
+  if (flags & NSEventModifierFlagControl)
+    ret |= 0x40000;
+  if (flags & NSEventModifierFlagOption)
+    ret |= 0x80000;
+  if (flags & NSEventModifierFlagCommand)
+    ret |= 0x400000;
+  return ret;
+}
+
+void my_on_key(void *win, my_key_cb cb, void *data, void *host) {
+  [NSEvent
+      addLocalMonitorForEventsMatchingMask:NSEventMaskKeyDown
+                                   handler:^NSEvent *(NSEvent *e) {
+                                     if ([e window] != (NSWindow *)win ||
+                                         !cb(my_fl_key(e),
+                                             my_fl_state([e modifierFlags]),
+                                             data))
+                                       return e;
+                                     // Let the fltk window handle the key
+                                     [(NSWindow *)host sendEvent:e];
+                                     return nil;
+                                   }];
+}
+
+typedef int (*my_permission_cb)(int kind, const char *origin, void *data);
+
+@interface MyPermissionHandler : NSObject
+@property my_permission_cb cb;
+@property void *data;
+@end
+
+@implementation MyPermissionHandler
+@end
+
+static char my_permission_key;
+
+// webview's UI delegate class is shared by all webviews,
+// so the handler is looked up on the webview making the request
+void my_on_permission(void *win, my_permission_cb cb, void *data) {
+  WKWebView *wv = my_get_webview(win);
+  MyPermissionHandler *handler = [[MyPermissionHandler alloc] init];
+  handler.cb = cb;
+  handler.data = data;
+  objc_setAssociatedObject(wv, &my_permission_key, handler,
+                           OBJC_ASSOCIATION_RETAIN);
+  [handler release];
+  SEL sel = NSSelectorFromString(@"webView:requestMediaCapturePermissionForOrigin:"
+                                 @"initiatedByFrame:type:decisionHandler:");
+  id block = ^(id self, WKWebView *w, WKSecurityOrigin *origin,
+               WKFrameInfo *frame, NSInteger type,
+               void (^decide)(NSInteger)) {
+    MyPermissionHandler *h = objc_getAssociatedObject(w, &my_permission_key);
+    NSString *o = [origin port]
+                      ? [NSString stringWithFormat:@"%@://%@:%ld",
+                                                   [origin protocol],
+                                                   [origin host],
+                                                   (long)[origin port]]
+                      : [NSString stringWithFormat:@"%@://%@",
+                                                   [origin protocol],
+                                                   [origin host]];
+    // WKMediaCaptureTypeMicrophone is 1, camera requests may include audio
+    int kind = type == 1 ? 2 : 1;
+    // WKPermissionDecisionGrant is 1, WKPermissionDecisionDeny is 2
+    decide(h && h.cb(kind, [o UTF8String], h.data) ? 1 : 2);
+  };
+  // Fails harmlessly if another webview already added it
+  class_addMethod([[wv UIDelegate] class], sel,
+                  imp_implementationWithBlock(block), "v@:@@@q@?");
+}
+
+static char my_drop_key;
+static BOOL (*my_perform_drag)(id, SEL, id<NSDraggingInfo>);
+
+// Passes the dropped files as newline-separated paths instead of letting the
+// page handle the drop
+static BOOL my_perform_drag_operation(id self, SEL sel,
+                                      id<NSDraggingInfo> info) {
+  MyObserver *handler = objc_getAssociatedObject(self, &my_drop_key);
+  NSArray *urls = [[info draggingPasteboard]
+      readObjectsForClasses:@[ [NSURL class] ]
+                    options:@{NSPasteboardURLReadingFileURLsOnlyKey : @YES}];
+  if (!handler || [urls count] == 0)
+    return my_perform_drag(self, sel, info);
+  NSMutableArray *paths = [NSMutableArray array];
+  for (NSURL *url in urls)
+    [paths addObject:[url path]];
+  ((my_str_cb)handler.cb)([[paths componentsJoinedByString:@"\n"] UTF8String],
+                          handler.data);
+  return YES;
+}
+
+void my_on_drop(void *win, my_str_cb cb, void *data) {
+  WKWebView *wv = my_get_webview(win);
+  MyObserver *handler = [[MyObserver alloc] init];
+  handler.cb = (void (*)(void))cb;
+  handler.data = data;
+  objc_setAssociatedObject(wv, &my_drop_key, handler, OBJC_ASSOCIATION_RETAIN);
+  [handler release];
+  if (!my_perform_drag) {
+    Method m = class_getInstanceMethod([WKWebView class],
+                                       @selector(performDragOperation:));
+    my_perform_drag = (BOOL(*)(id, SEL, id<NSDraggingInfo>))
+        method_setImplementation(m, (IMP)my_perform_drag_operation);
+  }
+}
+
+typedef int (*my_uri_cb)(const char *uri, void *data);
+
+static char my_new_window_key;
+
+// Returns no new view, the page is loaded in the same view if cb returns 1.
+// webview's UI delegate class is shared by all webviews,
+// so the handler is looked up on the webview making the request
+void my_on_new_window(void *win, my_uri_cb cb, void *data) {
+  WKWebView *wv = my_get_webview(win);
+  MyObserver *handler = [[MyObserver alloc] init];
+  handler.cb = (void (*)(void))cb;
+  handler.data = data;
+  objc_setAssociatedObject(wv, &my_new_window_key, handler,
+                           OBJC_ASSOCIATION_RETAIN);
+  [handler release];
+  SEL sel = NSSelectorFromString(@"webView:createWebViewWithConfiguration:"
+                                 @"forNavigationAction:windowFeatures:");
+  id block = ^WKWebView *(id self, WKWebView *w, WKWebViewConfiguration *config,
+                          WKNavigationAction *action,
+                          WKWindowFeatures *features) {
+    MyObserver *h = objc_getAssociatedObject(w, &my_new_window_key);
+    NSURLRequest *req = [action request];
+    NSString *uri = [[req URL] absoluteString];
+    if (h && uri && ((my_uri_cb)h.cb)([uri UTF8String], h.data))
+      [w loadRequest:req];
+    return nil;
+  };
+  // Fails harmlessly if another webview already added it
+  class_addMethod([[wv UIDelegate] class], sel,
+                  imp_implementationWithBlock(block), "@@:@@@@");
+}
+
+typedef void (*my_rules_cb)(void *rules, const char *error, void *data);
+
+// Compiles the rules asynchronously, cb receives the added rule list, which is
+// kept alive until passed to my_remove_content_rules, or an error
+void my_add_content_rules(void *win, const char *ident, const char *json,
+                          size_t len, my_rules_cb cb, void *data) {
+  WKUserContentController *manager =
+      [[my_get_webview(win) configuration] userContentController];
+  NSString *rules = [[[NSString alloc] initWithBytes:json
+                                              length:len
+                                            encoding:NSUTF8StringEncoding]
+      autorelease];
+  [[WKContentRuleListStore defaultStore]
+      compileContentRuleListForIdentifier:[NSString stringWithUTF8String:ident]
+                   encodedContentRuleList:rules
+                        completionHandler:^(WKContentRuleList *list,
+                                            NSError *err) {
+                          if (list) {
+                            [manager addContentRuleList:list];
+                            cb([list retain], NULL, data);
+                          } else {
+                            cb(NULL, [[err localizedDescription] UTF8String],
+                               data);
+                          }
+                        }];
+}
+
+void my_remove_content_rules(void *win, void *rules) {
+  [[[my_get_webview(win) configuration] userContentController]
+      removeContentRuleList:(WKContentRuleList *)rules];
+  [(WKContentRuleList *)rules release];
+}
+
+// Ordering the window out makes WebKit throttle the page, which sees
+// document.hidden
+void my_set_suspended(void *win, int suspended) {
+  if (suspended)
+    [(NSWindow *)win orderOut:nil];
+  else
+    [(NSWindow *)win orderFront:nil];
+}
+
+// The webview's window floats above the embedding window, so both are moved
+// to the floating level
+void my_set_always_on_top(void *win, void *top, int on) {
+  NSWindowLevel level = on ? NSFloatingWindowLevel : NSNormalWindowLevel;
+  [(NSWindow *)top setLevel:level];
+  [(NSWindow *)win setLevel:level];
+}
+
+// Zooming toggles between the maximized and the user's frame
+void my_set_maximized(void *win, void *top, int on) {
+  NSWindow *w = (NSWindow *)top;
+  if (!![w isZoomed] != !!on)
+    [w zoom:nil];
+}
+
+// Returns 1 if top is minimized, 2 if it's zoomed, and 0 otherwise
+int my_get_window_state(void *win, void *top) {
+  NSWindow *w = (NSWindow *)top;
+  if ([w isMiniaturized])
+    return 1;
+  return [w isZoomed] ? 2 : 0;
+}
+
+// The webview's window floats above the embedding window, so positions are
+// converted from the top-left corner of the embedding window into screen
+// coordinates, which start at the bottom-left
+void my_set_position(void *win, void *parent, int x, int y) {
+  NSRect host = [(NSWindow *)parent frame];
+  NSRect frame = [(NSWindow *)win frame];
+  [(NSWindow *)win
+      setFrameOrigin:NSMakePoint(host.origin.x + x,
+                                 host.origin.y + host.size.height - y -
+                                     frame.size.height)];
+}
+
+void my_get_position(void *win, void *parent, int *x, int *y) {
+  NSRect host = [(NSWindow *)parent frame];
+  NSRect frame = [(NSWindow *)win frame];
+  *x = (int)(frame.origin.x - host.origin.x);
+  *y = (int)(host.origin.y + host.size.height - frame.origin.y -
+             frame.size.height);
+}
+
+void my_get_size(void *win, int *w, int *h) {
+  NSRect frame = [(NSWindow *)win frame];
+  *w = (int)frame.size.width;
+  *h = (int)frame.size.height;
+}
+
+// WKWebView only mutes through its private _setPageMuted:, returns 0 if it's
+// missing
+int my_set_muted(void *win, int muted) {
+  WKWebView *wv = my_get_webview(win);
+  SEL sel = NSSelectorFromString(@"_setPageMuted:");
+  if (![wv respondsToSelector:sel])
+    return 0;
+  // _WKMediaAudioMuted is 1
+  ((void (*)(id, SEL, NSUInteger))objc_msgSend)(wv, sel, muted ? 1 : 0);
+  return 1;
+}
+
+// The page cache is only toggled through the private _setUsesPageCache: of
+// the preferences, which the webview reads, returns 0 if it's missing
+int my_set_page_cache(void *win, int enabled) {
+  WKPreferences *prefs = [[my_get_webview(win) configuration] preferences];
+  SEL sel = NSSelectorFromString(@"_setUsesPageCache:");
+  if (![prefs respondsToSelector:sel])
+    return 0;
+  ((void (*)(id, SEL, BOOL))objc_msgSend)(prefs, sel, enabled ? YES : NO);
+  return 1;
+}
+
+// Caret browsing is only toggled through the private _setCaretBrowsingEnabled:
+// of the preferences, returns 0 if it's missing
+int my_set_caret_browsing(void *win, int enabled) {
+  WKPreferences *prefs = [[my_get_webview(win) configuration] preferences];
+  SEL sel = NSSelectorFromString(@"_setCaretBrowsingEnabled:");
+  if (![prefs respondsToSelector:sel])
+    return 0;
+  ((void (*)(id, SEL, BOOL))objc_msgSend)(prefs, sel, enabled ? YES : NO);
+  return 1;
+}
+
+// The default fonts are only set through private setters of the preferences,
+// returns 0 if they're missing. families holds the default, serif, sans-serif
+// and monospace families, NULL keeping one, and sizes of 0 keep theirs
+int my_set_font_defaults(void *win, const char **families, int size,
+                         int monospace_size) {
+  WKPreferences *prefs = [[my_get_webview(win) configuration] preferences];
+  SEL family_sels[4] = {NSSelectorFromString(@"_setStandardFontFamily:"),
+                        NSSelectorFromString(@"_setSerifFontFamily:"),
+                        NSSelectorFromString(@"_setSansSerifFontFamily:"),
+                        NSSelectorFromString(@"_setFixedFontFamily:")};
+  SEL size_sel = NSSelectorFromString(@"_setDefaultFontSize:");
+  SEL mono_sel = NSSelectorFromString(@"_setDefaultFixedPitchFontSize:");
+  for (int i = 0; i < 4; i++)
+    if (![prefs respondsToSelector:family_sels[i]])
+      return 0;
+  if (![prefs respondsToSelector:size_sel] ||
+      ![prefs respondsToSelector:mono_sel])
+    return 0;
+  for (int i = 0; i < 4; i++)
+    if (families[i])
+      ((void (*)(id, SEL, NSString *))objc_msgSend)(
+          prefs, family_sels[i], [NSString stringWithUTF8String:families[i]]);
+  if (size)
+    ((void (*)(id, SEL, NSUInteger))objc_msgSend)(prefs, size_sel, size);
+  if (monospace_size)
+    ((void (*)(id, SEL, NSUInteger))objc_msgSend)(prefs, mono_sel,
+                                                  monospace_size);
+  return 1;
+}
+
+// A NULL user_agent restores WKWebView's
+void my_set_user_agent(void *win, const char *user_agent) {
+  [my_get_webview(win)
+      setCustomUserAgent:user_agent ? [NSString stringWithUTF8String:user_agent]
+                                    : nil];
+}
+
+// Clipboard access without a user gesture is only set through the private
+// _setJavaScriptCanAccessClipboard: and _setDOMPasteAllowed: of the
+// preferences, returns 0 if they're missing
+int my_set_clipboard_access(void *win, int allowed) {
+  WKPreferences *prefs = [[my_get_webview(win) configuration] preferences];
+  SEL access = NSSelectorFromString(@"_setJavaScriptCanAccessClipboard:");
+  SEL paste = NSSelectorFromString(@"_setDOMPasteAllowed:");
+  if (![prefs respondsToSelector:access] || ![prefs respondsToSelector:paste])
+    return 0;
+  ((void (*)(id, SEL, BOOL))objc_msgSend)(prefs, access, allowed ? YES : NO);
+  ((void (*)(id, SEL, BOOL))objc_msgSend)(prefs, paste, allowed ? YES : NO);
+  return 1;
+}
+
+// Text zoom is only set through the private _setTextZoomFactor: of
+// WKWebView, returns 0 if it's missing
+int my_set_text_zoom(void *win, double factor) {
+  WKWebView *wv = my_get_webview(win);
+  SEL sel = NSSelectorFromString(@"_setTextZoomFactor:");
+  if (![wv respondsToSelector:sel])
+    return 0;
+  ((void (*)(id, SEL, double))objc_msgSend)(wv, sel, factor);
+  return 1;
+}
+
+typedef void (*my_cookie_cb)(const char *name, const char *value,
+                             const char *domain, const char *path,
+                             long long expires, int secure, int http_only,
+                             void *data);
+
+static WKHTTPCookieStore *my_cookie_store(void *win) {
+  return [[[my_get_webview(win) configuration] websiteDataStore]
+      httpCookieStore];
+}
+
+// Passes each cookie to cb, expires being in seconds since the epoch or -1,
+// then calls it with a NULL name
+void my_get_cookies(void *win, my_cookie_cb cb, void *data) {
+  [my_cookie_store(win) getAllCookies:^(NSArray<NSHTTPCookie *> *cookies) {
+    for (NSHTTPCookie *c in cookies) {
+      long long expires =
+          c.expiresDate ? (long long)[c.expiresDate timeIntervalSince1970] : -1;
+      cb([c.name UTF8String], [c.value UTF8String], [c.domain UTF8String],
+         [c.path UTF8String], expires, c.isSecure, c.isHTTPOnly, data);
+    }
+    cb(NULL, NULL, NULL, NULL, -1, 0, 0, data);
+  }];
+}
+
+typedef void (*my_done_cb)(int ok, void *data);
+
+// cb receives whether the cookie was stored, NSHTTPCookie refuses invalid ones
+void my_add_cookie(void *win, const char *name, const char *value,
+                   const char *domain, const char *path, long long expires,
+                   int secure, int http_only, my_done_cb cb, void *data) {
+  NSMutableDictionary *props = [NSMutableDictionary dictionary];
+  props[NSHTTPCookieName] = [NSString stringWithUTF8String:name];
+  props[NSHTTPCookieValue] = [NSString stringWithUTF8String:value];
+  props[NSHTTPCookieDomain] = [NSString stringWithUTF8String:domain];
+  props[NSHTTPCookiePath] = [NSString stringWithUTF8String:path];
+  if (expires >= 0)
+    props[NSHTTPCookieExpires] = [NSDate dateWithTimeIntervalSince1970:expires];
+  if (secure)
+    props[NSHTTPCookieSecure] = @"TRUE";
+  if (http_only)
+    props[@"HttpOnly"] = @"TRUE";
+  NSHTTPCookie *cookie = [NSHTTPCookie cookieWithProperties:props];
+  if (!cookie) {
+    cb(0, data);
+    return;
+  }
+  [my_cookie_store(win) setCookie:cookie
+                completionHandler:^{
+                  cb(1, data);
+                }];
+}
+
+// The element fullscreen API is disabled by default. Its preference is public
+// from macOS 12.3, and private before
+void my_enable_fullscreen(void *win) {
+  WKPreferences *prefs = my_get_webview(win).configuration.preferences;
+  SEL sel = NSSelectorFromString(@"setElementFullscreenEnabled:");
+  if (![prefs respondsToSelector:sel])
+    sel = NSSelectorFromString(@"_setFullScreenEnabled:");
+  if ([prefs respondsToSelector:sel])
+    ((void (*)(id, SEL, BOOL))objc_msgSend)(prefs, sel, YES);
+}
+
+// Fraudulent website warnings are toggled through the preferences on macOS
+// 10.15 and later, and always shown before
+void my_set_safe_browsing(void *win, int enabled) {
+  WKPreferences *prefs = [[my_get_webview(win) configuration] preferences];
+  SEL sel = NSSelectorFromString(@"setFraudulentWebsiteWarningEnabled:");
+  if ([prefs respondsToSelector:sel])
+    ((void (*)(id, SEL, BOOL))objc_msgSend)(prefs, sel, enabled ? YES : NO);
+}
+
+// The web process is only identified through the private
+// _webProcessIdentifier, returns 0 if it's missing or the process can't be
+// queried. The CPU time is in Mach ticks on Apple silicon, and converted to
+// nanoseconds
+int my_web_process_usage(void *win, uint64_t *memory, uint64_t *cpu) {
+  WKWebView *wv = my_get_webview(win);
+  SEL sel = NSSelectorFromString(@"_webProcessIdentifier");
+  if (![wv respondsToSelector:sel])
+    return 0;
+  pid_t pid = ((pid_t(*)(id, SEL))objc_msgSend)(wv, sel);
+  struct proc_taskinfo info;
+  if (pid <= 0 || proc_pidinfo(pid, PROC_PIDTASKINFO, 0, &info,
+                               sizeof(info)) != sizeof(info))
+    return 0;
+  mach_timebase_info_data_t base;
+  mach_timebase_info(&base);
+  *memory = info.pti_resident_size;
+  *cpu = (info.pti_total_user + info.pti_total_system) * base.numer /
+         base.denom;
+  return 1;
+}
+
+typedef int (*my_confirm_cb)(const char *message, void *data);
+
+static char my_before_unload_key;
+
+// WebKit leaves pages asking to stay without the private UI delegate method,
+// which webview's delegate class shares between webviews, so the handler is
+// looked up on the webview of the page
+void my_on_before_unload(void *win, my_confirm_cb cb, void *data) {
+  WKWebView *wv = my_get_webview(win);
+  MyObserver *handler = [[MyObserver alloc] init];
+  handler.cb = (void (*)(void))cb;
+  handler.data = data;
+  objc_setAssociatedObject(wv, &my_before_unload_key, handler,
+                           OBJC_ASSOCIATION_RETAIN);
+  [handler release];
+  SEL sel = NSSelectorFromString(@"_webView:"
+                                 @"runBeforeUnloadConfirmPanelWithMessage:"
+                                 @"initiatedByFrame:completionHandler:");
+  id block = ^(id self, WKWebView *w, NSString *message, WKFrameInfo *frame,
+               void (^done)(BOOL)) {
+    MyObserver *h = objc_getAssociatedObject(w, &my_before_unload_key);
+    const char *m = message ? [message UTF8String] : "";
+    done(!h || ((my_confirm_cb)h.cb)(m, h.data) ? YES : NO);
+  };
+  // Fails harmlessly if another webview already added it
+  class_addMethod([[wv UIDelegate] class], sel,
+                  imp_implementationWithBlock(block), "v@:@@@@?");
+}
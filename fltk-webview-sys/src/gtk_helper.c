@@ -1,11 +1,19 @@
+#include <X11/Xatom.h>
 #include <X11/Xlib.h>
 #include <X11/extensions/Xfixes.h>
 #include <gdk/gdk.h>
 #include <gdk/gdkx.h>
 #include <gtk/gtk.h>
+#include <webkit2/webkit2.h>
 
 long my_get_xid(GdkWindow *win) { return GDK_WINDOW_XID(win); }
 
+// Whether GTK runs on X11, which embedding the webview relies on
+int my_is_x11_display(void) {
+  GdkDisplay *display = gdk_display_get_default();
+  return display && GDK_IS_X11_DISPLAY(display);
+}
+
 GdkWindow *my_get_win(GtkWindow *win) {
   GdkWindow *w = gtk_widget_get_window(GTK_WIDGET(win));
   return w;
@@ -37,3 +45,1495 @@ void x_init(Display *disp, Window child, Window parent) {
 
   XSendEvent(disp, child, False, StructureNotifyMask, &client_event);
 }
+
+static WebKitWebView *my_get_webview(void *win) {
+  return WEBKIT_WEB_VIEW(gtk_bin_get_child(GTK_BIN(win)));
+}
+
+typedef void (*my_str_cb)(const char *s, void *data);
+typedef void (*my_double_cb)(double d, void *data);
+
+// A Rust callback with its data, connected to a signal
+typedef struct {
+  void (*cb)(void);
+  void *data;
+} my_closure;
+
+static my_closure *my_closure_new(void (*cb)(void), void *data) {
+  my_closure *c = g_new(my_closure, 1);
+  c->cb = cb;
+  c->data = data;
+  return c;
+}
+
+static void my_closure_free(gpointer c, GClosure *unused) { g_free(c); }
+
+// The returned script is kept alive until passed to my_remove_user_script
+void *my_add_user_script(void *win, const char *js, int at_end,
+                         int isolated) {
+  WebKitUserContentManager *manager =
+      webkit_web_view_get_user_content_manager(my_get_webview(win));
+  WebKitUserScript *script = webkit_user_script_new_for_world(
+      js, WEBKIT_USER_CONTENT_INJECT_TOP_FRAME,
+      at_end ? WEBKIT_USER_SCRIPT_INJECT_AT_DOCUMENT_END
+             : WEBKIT_USER_SCRIPT_INJECT_AT_DOCUMENT_START,
+      isolated ? "fltk-webview" : NULL, NULL, NULL);
+  webkit_user_content_manager_add_script(manager, script);
+  return script;
+}
+
+void my_remove_user_script(void *win, void *script) {
+  WebKitUserContentManager *manager =
+      webkit_web_view_get_user_content_manager(my_get_webview(win));
+  webkit_user_content_manager_remove_script(manager, script);
+  webkit_user_script_unref(script);
+}
+
+// The returned style sheet is kept alive until passed to my_remove_user_style
+void *my_add_user_style(void *win, const char *css) {
+  WebKitUserContentManager *manager =
+      webkit_web_view_get_user_content_manager(my_get_webview(win));
+  WebKitUserStyleSheet *sheet = webkit_user_style_sheet_new(
+      css, WEBKIT_USER_CONTENT_INJECT_ALL_FRAMES,
+      WEBKIT_USER_STYLE_LEVEL_USER, NULL, NULL);
+  webkit_user_content_manager_add_style_sheet(manager, sheet);
+  return sheet;
+}
+
+void my_remove_user_style(void *win, void *sheet) {
+  WebKitUserContentManager *manager =
+      webkit_web_view_get_user_content_manager(my_get_webview(win));
+#if WEBKIT_CHECK_VERSION(2, 32, 0)
+  webkit_user_content_manager_remove_style_sheet(manager, sheet);
+#else
+  // Older versions can only remove all style sheets
+  webkit_user_content_manager_remove_all_style_sheets(manager);
+#endif
+  webkit_user_style_sheet_unref(sheet);
+}
+
+// Returns 0 if the request can't be expressed with WebKitURIRequest
+int my_load_request(void *win, const char *method, const char *url,
+                    const char *const *header_names,
+                    const char *const *header_values, int header_count,
+                    const unsigned char *body, size_t body_len) {
+  if (g_ascii_strcasecmp(method, "GET") != 0 || body)
+    return 0;
+  WebKitURIRequest *req = webkit_uri_request_new(url);
+  SoupMessageHeaders *headers = webkit_uri_request_get_http_headers(req);
+  if (headers) {
+    for (int i = 0; i < header_count; i++)
+      soup_message_headers_append(headers, header_names[i], header_values[i]);
+  }
+  webkit_web_view_load_request(my_get_webview(win), req);
+  g_object_unref(req);
+  return 1;
+}
+
+// The default headers of a webview, the origins they're sent to, and the
+// requests of the GET navigations to those origins lacking them, by URI
+typedef struct {
+  GHashTable *headers;
+  char **origins;
+  GHashTable *pending;
+  char *reissued;
+} my_headers;
+
+static void my_headers_free(my_headers *h) {
+  g_hash_table_unref(h->headers);
+  g_hash_table_unref(h->pending);
+  g_strfreev(h->origins);
+  g_free(h->reissued);
+  g_free(h);
+}
+
+// The origin of uri, e.g. https://example.com:8443, lowercased and without
+// the default port, or NULL if it isn't an http(s) URI
+static char *my_origin(const char *uri) {
+  GUri *parsed = g_uri_parse(uri, G_URI_FLAGS_ENCODED, NULL);
+  if (!parsed)
+    return NULL;
+  const char *scheme = g_uri_get_scheme(parsed);
+  const char *host = g_uri_get_host(parsed);
+  gboolean http = g_ascii_strcasecmp(scheme, "http") == 0;
+  char *origin = NULL;
+  if (host && *host && (http || g_ascii_strcasecmp(scheme, "https") == 0)) {
+    int port = g_uri_get_port(parsed);
+    GString *s = g_string_new(scheme);
+    // IPv6 addresses are given without their brackets
+    g_string_append_printf(s, g_strstr_len(host, -1, ":") ? "://[%s]" : "://%s",
+                           host);
+    if (port != -1 && port != (http ? 80 : 443))
+      g_string_append_printf(s, ":%d", port);
+    origin = g_ascii_strdown(s->str, -1);
+    g_string_free(s, TRUE);
+  }
+  g_uri_unref(parsed);
+  return origin;
+}
+
+// Keeps the requests of the GET navigations to the origins of the default
+// headers lacking them. decide-policy is also emitted for iframes, without
+// telling them apart, so they're only reissued once the main frame loads them
+static gboolean my_headers_decide(WebKitWebView *wv, WebKitPolicyDecision *d,
+                                  WebKitPolicyDecisionType type,
+                                  my_headers *h) {
+  if (type != WEBKIT_POLICY_DECISION_TYPE_NAVIGATION_ACTION ||
+      g_hash_table_size(h->headers) == 0)
+    return FALSE;
+  WebKitURIRequest *req = webkit_navigation_action_get_request(
+      webkit_navigation_policy_decision_get_navigation_action(
+          WEBKIT_NAVIGATION_POLICY_DECISION(d)));
+  const char *uri = webkit_uri_request_get_uri(req);
+  const char *method = webkit_uri_request_get_http_method(req);
+  SoupMessageHeaders *headers = webkit_uri_request_get_http_headers(req);
+  if (!headers || (method && g_ascii_strcasecmp(method, "GET") != 0))
+    return FALSE;
+  char *origin = my_origin(uri);
+  gboolean allowed =
+      origin && g_strv_contains((const char *const *)h->origins, origin);
+  g_free(origin);
+  if (!allowed)
+    return FALSE;
+  GHashTableIter iter;
+  gpointer name;
+  g_hash_table_iter_init(&iter, h->headers);
+  while (g_hash_table_iter_next(&iter, &name, NULL)) {
+    if (!soup_message_headers_get_one(headers, name)) {
+      g_hash_table_replace(h->pending, g_strdup(uri), g_object_ref(req));
+      break;
+    }
+  }
+  return FALSE;
+}
+
+static void my_copy_header(const char *name, const char *value,
+                           gpointer headers) {
+  soup_message_headers_append(headers, name, value);
+}
+
+// Stops the main frame's load of a pending request, when it starts or is
+// redirected to it, and reissues it with its headers and the default ones
+static void my_headers_load_changed(WebKitWebView *wv, WebKitLoadEvent event,
+                                    my_headers *h) {
+  if (event == WEBKIT_LOAD_COMMITTED) {
+    g_hash_table_remove_all(h->pending);
+    return;
+  }
+  const char *uri = webkit_web_view_get_uri(wv);
+  if (!uri || (event != WEBKIT_LOAD_STARTED && event != WEBKIT_LOAD_REDIRECTED))
+    return;
+  // The reissued load may not show its headers to decide-policy
+  if (g_strcmp0(h->reissued, uri) == 0) {
+    g_clear_pointer(&h->reissued, g_free);
+    return;
+  }
+  WebKitURIRequest *req = g_hash_table_lookup(h->pending, uri);
+  if (!req)
+    return;
+  WebKitURIRequest *with = webkit_uri_request_new(uri);
+  SoupMessageHeaders *with_headers = webkit_uri_request_get_http_headers(with);
+  soup_message_headers_foreach(webkit_uri_request_get_http_headers(req),
+                               my_copy_header, with_headers);
+  GHashTableIter iter;
+  gpointer name, value;
+  g_hash_table_iter_init(&iter, h->headers);
+  while (g_hash_table_iter_next(&iter, &name, &value))
+    soup_message_headers_replace(with_headers, name, value);
+  g_free(h->reissued);
+  h->reissued = g_strdup(uri);
+  g_hash_table_remove_all(h->pending);
+  webkit_web_view_stop_loading(wv);
+  webkit_web_view_load_request(wv, with);
+  g_object_unref(with);
+}
+
+void my_set_default_headers(void *win, const char *const *header_names,
+                            const char *const *header_values, int header_count,
+                            const char *const *origins, int origin_count) {
+  WebKitWebView *wv = my_get_webview(win);
+  my_headers *h = g_object_get_data(G_OBJECT(wv), "my_headers");
+  if (!h) {
+    h = g_new0(my_headers, 1);
+    h->headers = g_hash_table_new_full(g_str_hash, g_str_equal, g_free, g_free);
+    h->pending =
+        g_hash_table_new_full(g_str_hash, g_str_equal, g_free, g_object_unref);
+    g_object_set_data_full(G_OBJECT(wv), "my_headers", h,
+                           (GDestroyNotify)my_headers_free);
+    g_signal_connect(wv, "decide-policy", G_CALLBACK(my_headers_decide), h);
+    g_signal_connect(wv, "load-changed", G_CALLBACK(my_headers_load_changed),
+                     h);
+  }
+  g_hash_table_remove_all(h->headers);
+  g_hash_table_remove_all(h->pending);
+  for (int i = 0; i < header_count; i++)
+    g_hash_table_insert(h->headers, g_strdup(header_names[i]),
+                        g_strdup(header_values[i]));
+  g_strfreev(h->origins);
+  h->origins = g_new0(char *, origin_count + 1);
+  for (int i = 0; i < origin_count; i++)
+    h->origins[i] = g_strdup(origins[i]);
+}
+
+void my_focus(void *win) {
+  GdkWindow *gdkwin = gtk_widget_get_window(GTK_WIDGET(win));
+  // The GTK window is reparented, so X focus has to be requested explicitly
+  if (gdkwin)
+    XSetInputFocus(GDK_WINDOW_XDISPLAY(gdkwin), GDK_WINDOW_XID(gdkwin),
+                   RevertToParent, CurrentTime);
+  gtk_widget_grab_focus(GTK_WIDGET(my_get_webview(win)));
+}
+
+int my_has_focus(void *win) {
+  return gtk_widget_has_focus(GTK_WIDGET(my_get_webview(win)));
+}
+
+void my_set_transparent(void *win, int transparent) {
+  GdkRGBA color = {1.0, 1.0, 1.0, transparent ? 0.0 : 1.0};
+  gtk_widget_set_app_paintable(GTK_WIDGET(win), transparent);
+  webkit_web_view_set_background_color(my_get_webview(win), &color);
+}
+
+void my_set_background(void *win, unsigned char r, unsigned char g,
+                       unsigned char b) {
+  GdkRGBA color = {r / 255.0, g / 255.0, b / 255.0, 1.0};
+  gtk_widget_set_app_paintable(GTK_WIDGET(win), 0);
+  webkit_web_view_set_background_color(my_get_webview(win), &color);
+}
+
+// The GtkWindow is reparented into fltk's, where the window manager's
+// opacity doesn't apply, so the web view itself is faded
+void my_set_opacity(void *win, double opacity) {
+  gtk_widget_set_opacity(GTK_WIDGET(my_get_webview(win)), opacity);
+}
+
+// values are in the order of the fields of Settings, -1 keeps a setting
+void my_apply_settings(void *win, const int *values) {
+  WebKitSettings *s = webkit_web_view_get_settings(my_get_webview(win));
+  if (values[0] >= 0)
+    webkit_settings_set_enable_javascript(s, values[0]);
+  if (values[1] >= 0)
+    webkit_settings_set_auto_load_images(s, values[1]);
+#if !WEBKIT_CHECK_VERSION(2, 32, 0)
+  if (values[2] >= 0)
+    webkit_settings_set_enable_plugins(s, values[2]);
+#endif
+  if (values[3] >= 0)
+    webkit_settings_set_enable_webgl(s, values[3]);
+  if (values[4] >= 0)
+    webkit_settings_set_enable_developer_extras(s, values[4]);
+  if (values[5] >= 0)
+    webkit_settings_set_allow_file_access_from_file_urls(s, values[5]);
+  if (values[6] >= 0)
+    webkit_settings_set_allow_universal_access_from_file_urls(s, values[6]);
+}
+
+void my_set_default_charset(void *win, const char *charset) {
+  webkit_settings_set_default_charset(
+      webkit_web_view_get_settings(my_get_webview(win)), charset);
+}
+
+// Only takes effect before the first web process is launched
+void my_enable_sandbox(void) {
+  WebKitWebContext *ctx = webkit_web_context_get_default();
+  if (!webkit_web_context_get_sandbox_enabled(ctx))
+    webkit_web_context_set_sandbox_enabled(ctx, TRUE);
+}
+
+// Stores favicons in the default location, under the XDG cache directory.
+// The database must be enabled before anything is loaded in the context
+void my_enable_favicons(void) {
+  WebKitWebContext *ctx = webkit_web_context_get_default();
+  if (!webkit_web_context_get_favicon_database_directory(ctx))
+    webkit_web_context_set_favicon_database_directory(ctx, NULL);
+}
+
+// The policy belongs to the data manager shared by all webviews
+void my_ignore_tls_errors(void *win) {
+  WebKitWebContext *ctx = webkit_web_view_get_context(my_get_webview(win));
+#if WEBKIT_CHECK_VERSION(2, 32, 0)
+  webkit_website_data_manager_set_tls_errors_policy(
+      webkit_web_context_get_website_data_manager(ctx),
+      WEBKIT_TLS_ERRORS_POLICY_IGNORE);
+#else
+  webkit_web_context_set_tls_errors_policy(ctx,
+                                           WEBKIT_TLS_ERRORS_POLICY_IGNORE);
+#endif
+}
+
+// Only takes effect before the first web process is launched
+void my_set_process_model(int shared, unsigned int max) {
+  WebKitWebContext *ctx = webkit_web_context_get_default();
+  G_GNUC_BEGIN_IGNORE_DEPRECATIONS
+  webkit_web_context_set_process_model(
+      ctx, shared ? WEBKIT_PROCESS_MODEL_SHARED_SECONDARY_PROCESS
+                  : WEBKIT_PROCESS_MODEL_MULTIPLE_SECONDARY_PROCESSES);
+  if (!shared)
+    webkit_web_context_set_web_process_count_limit(ctx, max);
+  G_GNUC_END_IGNORE_DEPRECATIONS
+}
+
+void my_set_hardware_acceleration(void *win, int policy) {
+  webkit_settings_set_hardware_acceleration_policy(
+      webkit_web_view_get_settings(my_get_webview(win)), policy);
+}
+
+typedef void (*my_count_cb)(unsigned int count, void *data);
+
+static void my_counted_matches(WebKitFindController *fc, guint count,
+                               gpointer unused) {
+  my_count_cb cb = (my_count_cb)g_object_get_data(G_OBJECT(fc), "my_count_cb");
+  if (cb)
+    cb(count, g_object_get_data(G_OBJECT(fc), "my_count_data"));
+}
+
+static void my_failed_to_find(WebKitFindController *fc, gpointer unused) {
+  my_counted_matches(fc, 0, NULL);
+}
+
+void my_find(void *win, const char *text, int case_sensitive, int wrap_around,
+             my_count_cb cb, void *data) {
+  WebKitFindController *fc =
+      webkit_web_view_get_find_controller(my_get_webview(win));
+  if (!g_object_get_data(G_OBJECT(fc), "my_count_cb")) {
+    g_signal_connect(fc, "counted-matches", G_CALLBACK(my_counted_matches),
+                     NULL);
+    g_signal_connect(fc, "failed-to-find-text", G_CALLBACK(my_failed_to_find),
+                     NULL);
+  }
+  g_object_set_data(G_OBJECT(fc), "my_count_cb", cb);
+  g_object_set_data(G_OBJECT(fc), "my_count_data", data);
+  guint32 options = WEBKIT_FIND_OPTIONS_NONE;
+  if (!case_sensitive)
+    options |= WEBKIT_FIND_OPTIONS_CASE_INSENSITIVE;
+  if (wrap_around)
+    options |= WEBKIT_FIND_OPTIONS_WRAP_AROUND;
+  webkit_find_controller_count_matches(fc, text, options, G_MAXUINT);
+  webkit_find_controller_search(fc, text, options, G_MAXUINT);
+}
+
+void my_find_next(void *win) {
+  webkit_find_controller_search_next(
+      webkit_web_view_get_find_controller(my_get_webview(win)));
+}
+
+void my_find_previous(void *win) {
+  webkit_find_controller_search_previous(
+      webkit_web_view_get_find_controller(my_get_webview(win)));
+}
+
+void my_find_clear(void *win) {
+  webkit_find_controller_search_finish(
+      webkit_web_view_get_find_controller(my_get_webview(win)));
+}
+
+int my_get_scale_factor(void *win) {
+  return gtk_widget_get_scale_factor(GTK_WIDGET(win));
+}
+
+void my_reload_bypass_cache(void *win) {
+  webkit_web_view_reload_bypass_cache(my_get_webview(win));
+}
+
+void my_stop_loading(void *win) {
+  webkit_web_view_stop_loading(my_get_webview(win));
+}
+
+// Includes the URI of a load which hasn't committed yet
+const char *my_get_uri(void *win) {
+  const char *uri = webkit_web_view_get_uri(my_get_webview(win));
+  return uri ? uri : "";
+}
+
+void my_load_html(void *win, const char *html, const char *base_url) {
+  webkit_web_view_load_html(my_get_webview(win), html, base_url);
+}
+
+// Shows html in place of the page at uri, which stays the URL of the webview
+void my_load_alternate_html(void *win, const char *html, const char *uri) {
+  webkit_web_view_load_alternate_html(my_get_webview(win), html, uri, NULL);
+}
+
+// Returns 1 when it loaded a page replacing the error page
+typedef int (*my_load_failed_cb)(const char *uri, const char *error,
+                                 void *data);
+
+static gboolean my_load_failed(WebKitWebView *wv, WebKitLoadEvent event,
+                               gchar *uri, GError *error, my_closure *c) {
+  // Stopped loads, and responses turned into downloads, aren't failures
+  if (g_error_matches(error, WEBKIT_NETWORK_ERROR,
+                      WEBKIT_NETWORK_ERROR_CANCELLED) ||
+      g_error_matches(
+          error, WEBKIT_POLICY_ERROR,
+          WEBKIT_POLICY_ERROR_FRAME_LOAD_INTERRUPTED_BY_POLICY_CHANGE))
+    return FALSE;
+  return ((my_load_failed_cb)c->cb)(uri, error->message, c->data);
+}
+
+void my_on_load_failed(void *win, my_load_failed_cb cb, void *data) {
+  g_signal_connect_data(my_get_webview(win), "load-failed",
+                        G_CALLBACK(my_load_failed),
+                        my_closure_new((void (*)(void))cb, data),
+                        my_closure_free, 0);
+}
+
+int my_can_go_back(void *win) {
+  return webkit_web_view_can_go_back(my_get_webview(win));
+}
+
+int my_can_go_forward(void *win) {
+  return webkit_web_view_can_go_forward(my_get_webview(win));
+}
+
+int my_is_loading(void *win) {
+  return webkit_web_view_is_loading(my_get_webview(win));
+}
+
+double my_get_progress(void *win) {
+  return webkit_web_view_get_estimated_load_progress(my_get_webview(win));
+}
+
+// 0 until the main resource's response is received, and for non-HTTP pages
+int my_get_response_status(void *win) {
+  WebKitWebResource *res =
+      webkit_web_view_get_main_resource(my_get_webview(win));
+  WebKitURIResponse *response =
+      res ? webkit_web_resource_get_response(res) : NULL;
+  return response ? webkit_uri_response_get_status_code(response) : 0;
+}
+
+static void my_title_changed(WebKitWebView *wv, GParamSpec *unused,
+                             my_closure *c) {
+  const char *title = webkit_web_view_get_title(wv);
+  ((my_str_cb)c->cb)(title ? title : "", c->data);
+}
+
+void my_on_title_changed(void *win, my_str_cb cb, void *data) {
+  g_signal_connect_data(my_get_webview(win), "notify::title",
+                        G_CALLBACK(my_title_changed),
+                        my_closure_new((void (*)(void))cb, data),
+                        my_closure_free, 0);
+}
+
+static void my_uri_changed(WebKitWebView *wv, GParamSpec *unused,
+                           my_closure *c) {
+  const char *uri = webkit_web_view_get_uri(wv);
+  ((my_str_cb)c->cb)(uri ? uri : "", c->data);
+}
+
+void my_on_uri_changed(void *win, my_str_cb cb, void *data) {
+  g_signal_connect_data(my_get_webview(win), "notify::uri",
+                        G_CALLBACK(my_uri_changed),
+                        my_closure_new((void (*)(void))cb, data),
+                        my_closure_free, 0);
+}
+
+static void my_progress_changed(WebKitWebView *wv, GParamSpec *unused,
+                                my_closure *c) {
+  ((my_double_cb)c->cb)(webkit_web_view_get_estimated_load_progress(wv),
+                        c->data);
+}
+
+void my_on_progress(void *win, my_double_cb cb, void *data) {
+  g_signal_connect_data(my_get_webview(win), "notify::estimated-load-progress",
+                        G_CALLBACK(my_progress_changed),
+                        my_closure_new((void (*)(void))cb, data),
+                        my_closure_free, 0);
+}
+
+typedef void (*my_flag_cb)(int flag, void *data);
+
+static void my_loading_changed(WebKitWebView *wv, GParamSpec *unused,
+                               my_closure *c) {
+  ((my_flag_cb)c->cb)(webkit_web_view_is_loading(wv), c->data);
+}
+
+// is-loading stays set through the redirects of a load, until it finishes
+void my_on_loading_changed(void *win, my_flag_cb cb, void *data) {
+  g_signal_connect_data(my_get_webview(win), "notify::is-loading",
+                        G_CALLBACK(my_loading_changed),
+                        my_closure_new((void (*)(void))cb, data),
+                        my_closure_free, 0);
+}
+
+// from is NULL when a navigation starts at to
+typedef void (*my_redirect_cb)(const char *from, const char *to, void *data);
+
+// The URL before a redirect is kept on the webview, as the URI has already
+// changed when load-changed reports it
+static void my_redirect_load_changed(WebKitWebView *wv, WebKitLoadEvent event,
+                                     my_closure *c) {
+  const char *uri = webkit_web_view_get_uri(wv);
+  if (!uri || (event != WEBKIT_LOAD_STARTED && event != WEBKIT_LOAD_REDIRECTED))
+    return;
+  const char *from = g_object_get_data(G_OBJECT(wv), "my-redirect-from");
+  ((my_redirect_cb)c->cb)(event == WEBKIT_LOAD_REDIRECTED ? from : NULL, uri,
+                          c->data);
+  g_object_set_data_full(G_OBJECT(wv), "my-redirect-from", g_strdup(uri),
+                         g_free);
+}
+
+// Only reports the redirects of the main frame's navigations
+void my_on_redirect(void *win, my_redirect_cb cb, void *data) {
+  g_signal_connect_data(my_get_webview(win), "load-changed",
+                        G_CALLBACK(my_redirect_load_changed),
+                        my_closure_new((void (*)(void))cb, data),
+                        my_closure_free, 0);
+}
+
+void my_set_javascript_enabled(void *win, int enabled) {
+  webkit_settings_set_enable_javascript(
+      webkit_web_view_get_settings(my_get_webview(win)), enabled);
+}
+
+typedef void (*my_scheme_cb)(void *request, const char *uri, void *win);
+
+static void my_scheme_request(WebKitURISchemeRequest *request,
+                              gpointer cb) {
+  GtkWidget *wv = GTK_WIDGET(webkit_uri_scheme_request_get_web_view(request));
+  ((my_scheme_cb)cb)(request, webkit_uri_scheme_request_get_uri(request),
+                     gtk_widget_get_toplevel(wv));
+}
+
+// The scheme is registered on the web context shared by all webviews,
+// so a scheme can only be registered once per process
+void my_register_uri_scheme(void *win, const char *scheme, my_scheme_cb cb) {
+  WebKitWebContext *ctx = webkit_web_view_get_context(my_get_webview(win));
+  webkit_web_context_register_uri_scheme(ctx, scheme, my_scheme_request, cb,
+                                         NULL);
+  webkit_security_manager_register_uri_scheme_as_secure(
+      webkit_web_context_get_security_manager(ctx), scheme);
+}
+
+void my_enable_scheme_cors(void *win, const char *scheme) {
+  WebKitWebContext *ctx = webkit_web_view_get_context(my_get_webview(win));
+  webkit_security_manager_register_uri_scheme_as_cors_enabled(
+      webkit_web_context_get_security_manager(ctx), scheme);
+}
+
+void my_uri_request_finish(void *request, const unsigned char *data,
+                           size_t len, const char *mime) {
+  GBytes *bytes = g_bytes_new(data, len);
+  GInputStream *stream = g_memory_input_stream_new_from_bytes(bytes);
+  webkit_uri_scheme_request_finish(request, stream, len, mime);
+  g_object_unref(stream);
+  g_bytes_unref(bytes);
+}
+
+void my_uri_request_fail(void *request, const char *message) {
+  GError *err = g_error_new_literal(G_IO_ERROR, G_IO_ERROR_NOT_FOUND, message);
+  webkit_uri_scheme_request_finish_error(request, err);
+  g_error_free(err);
+}
+
+// Redirects navigations to https://<host>/ onto <scheme>://<host>/
+static gboolean my_redirect_host(WebKitWebView *wv, WebKitPolicyDecision *d,
+                                 WebKitPolicyDecisionType type,
+                                 GHashTable *hosts) {
+  if (type != WEBKIT_POLICY_DECISION_TYPE_NAVIGATION_ACTION)
+    return FALSE;
+  WebKitNavigationAction *action =
+      webkit_navigation_policy_decision_get_navigation_action(
+          WEBKIT_NAVIGATION_POLICY_DECISION(d));
+  const char *uri =
+      webkit_uri_request_get_uri(webkit_navigation_action_get_request(action));
+  GUri *parsed = g_uri_parse(uri, G_URI_FLAGS_ENCODED, NULL);
+  if (!parsed)
+    return FALSE;
+  const char *scheme = NULL;
+  if (g_strcmp0(g_uri_get_scheme(parsed), "https") == 0 &&
+      g_uri_get_host(parsed))
+    scheme = g_hash_table_lookup(hosts, g_uri_get_host(parsed));
+  if (scheme) {
+    GUri *target = g_uri_build(G_URI_FLAGS_ENCODED, scheme, NULL,
+                               g_uri_get_host(parsed), -1,
+                               g_uri_get_path(parsed), g_uri_get_query(parsed),
+                               g_uri_get_fragment(parsed));
+    char *target_uri = g_uri_to_string(target);
+    webkit_policy_decision_ignore(d);
+    webkit_web_view_load_uri(wv, target_uri);
+    g_free(target_uri);
+    g_uri_unref(target);
+  }
+  g_uri_unref(parsed);
+  return scheme != NULL;
+}
+
+void my_redirect_https_host(void *win, const char *host, const char *scheme) {
+  WebKitWebView *wv = my_get_webview(win);
+  GHashTable *hosts = g_object_get_data(G_OBJECT(wv), "my_hosts");
+  if (!hosts) {
+    hosts = g_hash_table_new_full(g_str_hash, g_str_equal, g_free, g_free);
+    g_object_set_data_full(G_OBJECT(wv), "my_hosts", hosts,
+                           (GDestroyNotify)g_hash_table_unref);
+    g_signal_connect(wv, "decide-policy", G_CALLBACK(my_redirect_host), hosts);
+  }
+  g_hash_table_insert(hosts, g_strdup(host), g_strdup(scheme));
+}
+
+typedef int (*my_key_cb)(int key, int state, void *data);
+
+// Matches FL_SHIFT, FL_CTRL, FL_ALT and FL_META
+static int my_fl_state(guint state) {
+  int ret = 0;
+  if (state & GDK_SHIFT_MASK)
+    ret |= 0x10000;
+  if (state & GDK_CONTROL_MASK)
+    ret |= 0x40000;
+  if (state & GDK_MOD1_MASK)
+    ret |= 0x80000;
+  if (state & (GDK_SUPER_MASK | GDK_META_MASK))
+    ret |= 0x400000;
+  return ret;
+}
+
+// fltk's key codes are X keysyms, so GDK keyvals can be passed as is
+static gboolean my_key_pressed(GtkWidget *w, GdkEventKey *ev, my_closure *c) {
+  guint key = gdk_keyval_to_lower(ev->keyval);
+  if (!((my_key_cb)c->cb)(key, my_fl_state(ev->state), c->data))
+    return FALSE;
+  // Resend the key to the fltk window, so fltk handles it as its own
+  XEvent xev = {0};
+  xev.xkey.type = KeyPress;
+  xev.xkey.send_event = True;
+  xev.xkey.display = GDK_WINDOW_XDISPLAY(ev->window);
+  xev.xkey.window = (Window)g_object_get_data(G_OBJECT(w), "my_key_host");
+  xev.xkey.root = GDK_ROOT_WINDOW();
+  xev.xkey.time = ev->time;
+  xev.xkey.state = ev->state & GDK_MODIFIER_MASK;
+  xev.xkey.keycode = ev->hardware_keycode;
+  xev.xkey.same_screen = True;
+  XSendEvent(xev.xkey.display, xev.xkey.window, True, KeyPressMask, &xev);
+  return TRUE;
+}
+
+// host is the XID of the fltk window
+void my_on_key(void *win, my_key_cb cb, void *data, void *host) {
+  WebKitWebView *wv = my_get_webview(win);
+  g_object_set_data(G_OBJECT(wv), "my_key_host", host);
+  g_signal_connect_data(wv, "key-press-event", G_CALLBACK(my_key_pressed),
+                        my_closure_new((void (*)(void))cb, data),
+                        my_closure_free, 0);
+}
+
+void my_set_autoplay(void *win, int allow) {
+  webkit_settings_set_media_playback_requires_user_gesture(
+      webkit_web_view_get_settings(my_get_webview(win)), !allow);
+}
+
+// A NULL user_agent restores WebKitGTK's
+void my_set_user_agent(void *win, const char *user_agent) {
+  webkit_settings_set_user_agent(
+      webkit_web_view_get_settings(my_get_webview(win)), user_agent);
+}
+
+// Lets scripts copy and paste without a user gesture
+int my_set_clipboard_access(void *win, int allowed) {
+  webkit_settings_set_javascript_can_access_clipboard(
+      webkit_web_view_get_settings(my_get_webview(win)), allowed);
+  return 1;
+}
+
+void my_set_smooth_scrolling(void *win, int enabled) {
+  webkit_settings_set_enable_smooth_scrolling(
+      webkit_web_view_get_settings(my_get_webview(win)), enabled);
+}
+
+// model is a WebKitCacheModel
+void my_set_cache_model(void *win, int model) {
+  webkit_web_context_set_cache_model(
+      webkit_web_view_get_context(my_get_webview(win)), model);
+}
+
+// languages is a NULL terminated array of locales, like en_US
+void my_set_spellcheck(void *win, int enabled, const char *const *languages) {
+  WebKitWebContext *ctx = webkit_web_view_get_context(my_get_webview(win));
+  webkit_web_context_set_spell_checking_enabled(ctx, enabled);
+  if (languages[0])
+    webkit_web_context_set_spell_checking_languages(ctx, languages);
+}
+
+void my_set_preferred_languages(void *win, const char *const *languages) {
+  webkit_web_context_set_preferred_languages(
+      webkit_web_view_get_context(my_get_webview(win)), languages);
+}
+
+typedef int (*my_permission_cb)(int kind, const char *origin, void *data);
+
+// kind is 0 for geolocation, 1 for camera, 2 for microphone, 3 for
+// notifications, 4 for anything else and 5 for clipboard reads
+static gboolean my_permission_request(WebKitWebView *wv,
+                                      WebKitPermissionRequest *req,
+                                      my_closure *c) {
+  int kind = 4;
+  if (WEBKIT_IS_GEOLOCATION_PERMISSION_REQUEST(req))
+    kind = 0;
+  else if (WEBKIT_IS_USER_MEDIA_PERMISSION_REQUEST(req))
+    kind = webkit_user_media_permission_is_for_video_device(
+               WEBKIT_USER_MEDIA_PERMISSION_REQUEST(req))
+               ? 1
+               : 2;
+  else if (WEBKIT_IS_NOTIFICATION_PERMISSION_REQUEST(req))
+    kind = 3;
+#if WEBKIT_CHECK_VERSION(2, 42, 0)
+  else if (WEBKIT_IS_CLIPBOARD_PERMISSION_REQUEST(req))
+    kind = 5;
+#endif
+  const char *uri = webkit_web_view_get_uri(wv);
+  if (((my_permission_cb)c->cb)(kind, uri ? uri : "", c->data))
+    webkit_permission_request_allow(req);
+  else
+    webkit_permission_request_deny(req);
+  return TRUE;
+}
+
+void my_on_permission(void *win, my_permission_cb cb, void *data) {
+  g_signal_connect_data(my_get_webview(win), "permission-request",
+                        G_CALLBACK(my_permission_request),
+                        my_closure_new((void (*)(void))cb, data),
+                        my_closure_free, 0);
+}
+
+typedef int (*my_tls_cb)(const char *host, const char *cert, void *data);
+
+// Describes the certificate's subject, when GLib can read it, and its errors
+static char *my_cert_summary(GTlsCertificate *cert,
+                             GTlsCertificateFlags errors) {
+  static const struct {
+    GTlsCertificateFlags flag;
+    const char *name;
+  } names[] = {
+      {G_TLS_CERTIFICATE_UNKNOWN_CA, "unknown-ca"},
+      {G_TLS_CERTIFICATE_BAD_IDENTITY, "bad-identity"},
+      {G_TLS_CERTIFICATE_NOT_ACTIVATED, "not-activated"},
+      {G_TLS_CERTIFICATE_EXPIRED, "expired"},
+      {G_TLS_CERTIFICATE_REVOKED, "revoked"},
+      {G_TLS_CERTIFICATE_INSECURE, "insecure"},
+      {G_TLS_CERTIFICATE_GENERIC_ERROR, "generic-error"},
+  };
+  GString *summary = g_string_new(NULL);
+#if GLIB_CHECK_VERSION(2, 70, 0)
+  gchar *subject = g_tls_certificate_get_subject_name(cert);
+  if (subject) {
+    g_string_append_printf(summary, "%s ", subject);
+    g_free(subject);
+  }
+#endif
+  g_string_append_c(summary, '(');
+  gboolean first = TRUE;
+  for (size_t i = 0; i < G_N_ELEMENTS(names); i++) {
+    if (errors & names[i].flag) {
+      g_string_append_printf(summary, first ? "%s" : ", %s", names[i].name);
+      first = FALSE;
+    }
+  }
+  g_string_append_c(summary, ')');
+  return g_string_free(summary, FALSE);
+}
+
+// Only emitted for the main resource of a navigation. An accepted certificate
+// is allowed for its host in the web context, and the page is loaded again
+static gboolean my_load_failed_with_tls_errors(WebKitWebView *wv, gchar *uri,
+                                               GTlsCertificate *cert,
+                                               GTlsCertificateFlags errors,
+                                               my_closure *c) {
+  GUri *parsed = g_uri_parse(uri, G_URI_FLAGS_ENCODED, NULL);
+  if (!parsed)
+    return FALSE;
+  gboolean accepted = FALSE;
+  const char *host = g_uri_get_host(parsed);
+  if (host) {
+    char *summary = my_cert_summary(cert, errors);
+    accepted = ((my_tls_cb)c->cb)(host, summary, c->data);
+    g_free(summary);
+    if (accepted) {
+      webkit_web_context_allow_tls_certificate_for_host(
+          webkit_web_view_get_context(wv), cert, host);
+      webkit_web_view_load_uri(wv, uri);
+    }
+  }
+  g_uri_unref(parsed);
+  return accepted;
+}
+
+void my_on_tls_error(void *win, my_tls_cb cb, void *data) {
+  g_signal_connect_data(my_get_webview(win), "load-failed-with-tls-errors",
+                        G_CALLBACK(my_load_failed_with_tls_errors),
+                        my_closure_new((void (*)(void))cb, data),
+                        my_closure_free, 0);
+}
+
+// kind is 0 for Basic, 1 for Digest, 2 for NTLM and 3 for Negotiate.
+// Returns 1 after pointing user and password to the credentials, 0 to cancel
+typedef int (*my_auth_cb)(const char *host, int kind, const char **user,
+                          const char **password, void *data);
+
+static gboolean my_authenticate(WebKitWebView *wv,
+                                WebKitAuthenticationRequest *request,
+                                my_closure *c) {
+  int kind;
+  switch (webkit_authentication_request_get_scheme(request)) {
+  case WEBKIT_AUTHENTICATION_SCHEME_HTTP_BASIC:
+    kind = 0;
+    break;
+  case WEBKIT_AUTHENTICATION_SCHEME_HTTP_DIGEST:
+    kind = 1;
+    break;
+  case WEBKIT_AUTHENTICATION_SCHEME_NTLM:
+    kind = 2;
+    break;
+  case WEBKIT_AUTHENTICATION_SCHEME_NEGOTIATE:
+    kind = 3;
+    break;
+  default:
+    return FALSE;
+  }
+  const char *user = NULL, *password = NULL;
+  if (((my_auth_cb)c->cb)(webkit_authentication_request_get_host(request),
+                          kind, &user, &password, c->data)) {
+    WebKitCredential *credential = webkit_credential_new(
+        user, password, WEBKIT_CREDENTIAL_PERSISTENCE_FOR_SESSION);
+    webkit_authentication_request_authenticate(request, credential);
+    webkit_credential_free(credential);
+  } else {
+    webkit_authentication_request_cancel(request);
+  }
+  return TRUE;
+}
+
+void my_on_auth(void *win, my_auth_cb cb, void *data) {
+  g_signal_connect_data(my_get_webview(win), "authenticate",
+                        G_CALLBACK(my_authenticate),
+                        my_closure_new((void (*)(void))cb, data),
+                        my_closure_free, 0);
+}
+
+// Passes the dropped files as newline-separated paths instead of letting the
+// page handle the drop
+static void my_drag_data_received(GtkWidget *w, GdkDragContext *ctx, gint x,
+                                  gint y, GtkSelectionData *data, guint info,
+                                  guint time, my_closure *c) {
+  gchar **uris = gtk_selection_data_get_uris(data);
+  if (!uris)
+    return;
+  GString *paths = g_string_new(NULL);
+  for (gchar **uri = uris; *uri; uri++) {
+    gchar *path = g_filename_from_uri(*uri, NULL, NULL);
+    if (path) {
+      if (paths->len)
+        g_string_append_c(paths, '\n');
+      g_string_append(paths, path);
+      g_free(path);
+    }
+  }
+  g_strfreev(uris);
+  if (paths->len) {
+    g_signal_stop_emission_by_name(w, "drag-data-received");
+    gtk_drag_finish(ctx, TRUE, FALSE, time);
+    ((my_str_cb)c->cb)(paths->str, c->data);
+  }
+  g_string_free(paths, TRUE);
+}
+
+void my_on_drop(void *win, my_str_cb cb, void *data) {
+  g_signal_connect_data(my_get_webview(win), "drag-data-received",
+                        G_CALLBACK(my_drag_data_received),
+                        my_closure_new((void (*)(void))cb, data),
+                        my_closure_free, 0);
+}
+
+typedef int (*my_uri_cb)(const char *uri, void *data);
+
+// Returns no new view, the page is loaded in the same view if cb returns 1
+static GtkWidget *my_create(WebKitWebView *wv, WebKitNavigationAction *action,
+                            my_closure *c) {
+  const char *uri =
+      webkit_uri_request_get_uri(webkit_navigation_action_get_request(action));
+  if (uri && ((my_uri_cb)c->cb)(uri, c->data))
+    webkit_web_view_load_uri(wv, uri);
+  return NULL;
+}
+
+void my_on_new_window(void *win, my_uri_cb cb, void *data) {
+  g_signal_connect_data(my_get_webview(win), "create", G_CALLBACK(my_create),
+                        my_closure_new((void (*)(void))cb, data),
+                        my_closure_free, 0);
+}
+
+typedef void (*my_rules_cb)(void *rules, const char *error, void *data);
+
+typedef struct {
+  my_rules_cb cb;
+  void *data;
+  WebKitUserContentManager *manager;
+} my_rules_request;
+
+static void my_rules_saved(GObject *store, GAsyncResult *res, gpointer user) {
+  my_rules_request *r = user;
+  GError *err = NULL;
+  WebKitUserContentFilter *filter = webkit_user_content_filter_store_save_finish(
+      WEBKIT_USER_CONTENT_FILTER_STORE(store), res, &err);
+  if (filter) {
+    webkit_user_content_manager_add_filter(r->manager, filter);
+    r->cb(filter, NULL, r->data);
+  } else {
+    r->cb(NULL, err->message, r->data);
+    g_error_free(err);
+  }
+  g_object_unref(r->manager);
+  g_free(r);
+}
+
+// Compiles the rules asynchronously, cb receives the added filter, which is
+// kept alive until passed to my_remove_content_rules, or an error
+void my_add_content_rules(void *win, const char *id, const char *json,
+                          size_t len, my_rules_cb cb, void *data) {
+  static WebKitUserContentFilterStore *store = NULL;
+  if (!store) {
+    gchar *path = g_build_filename(g_get_user_cache_dir(), "fltk-webview",
+                                   "content-rules", NULL);
+    store = webkit_user_content_filter_store_new(path);
+    g_free(path);
+  }
+  my_rules_request *r = g_new(my_rules_request, 1);
+  r->cb = cb;
+  r->data = data;
+  r->manager = g_object_ref(
+      webkit_web_view_get_user_content_manager(my_get_webview(win)));
+  GBytes *bytes = g_bytes_new(json, len);
+  webkit_user_content_filter_store_save(store, id, bytes, NULL, my_rules_saved,
+                                        r);
+  g_bytes_unref(bytes);
+}
+
+void my_remove_content_rules(void *win, void *rules) {
+  webkit_user_content_manager_remove_filter(
+      webkit_web_view_get_user_content_manager(my_get_webview(win)), rules);
+  webkit_user_content_filter_unref(rules);
+}
+
+static cairo_status_t my_png_write(void *bytes, const unsigned char *data,
+                                   unsigned int len) {
+  g_byte_array_append(bytes, data, len);
+  return CAIRO_STATUS_SUCCESS;
+}
+
+typedef void (*my_bytes_cb)(const unsigned char *data, size_t len, void *user);
+
+// Passes the PNG encoded favicon to cb, which isn't called without a favicon,
+// see my_enable_favicons
+void my_get_favicon(void *win, my_bytes_cb cb, void *user) {
+  WebKitWebView *wv = my_get_webview(win);
+  cairo_surface_t *icon = webkit_web_view_get_favicon(wv);
+  if (!icon)
+    return;
+  GByteArray *bytes = g_byte_array_new();
+  if (cairo_surface_write_to_png_stream(icon, my_png_write, bytes) ==
+      CAIRO_STATUS_SUCCESS)
+    cb(bytes->data, bytes->len, user);
+  g_byte_array_unref(bytes);
+}
+
+// Passes the serialized back/forward list to cb, with the scroll position and
+// form state of its pages
+void my_get_session_state(void *win, my_bytes_cb cb, void *user) {
+  WebKitWebViewSessionState *state =
+      webkit_web_view_get_session_state(my_get_webview(win));
+  GBytes *bytes = webkit_web_view_session_state_serialize(state);
+  gsize len = 0;
+  const unsigned char *data = g_bytes_get_data(bytes, &len);
+  cb(data, len, user);
+  g_bytes_unref(bytes);
+  webkit_web_view_session_state_unref(state);
+}
+
+// Returns 0 if data isn't a serialized session state. Restoring only replaces
+// the back/forward list, so its current item is loaded
+int my_restore_session_state(void *win, const unsigned char *data,
+                             size_t len) {
+  WebKitWebView *wv = my_get_webview(win);
+  GBytes *bytes = g_bytes_new(data, len);
+  WebKitWebViewSessionState *state = webkit_web_view_session_state_new(bytes);
+  g_bytes_unref(bytes);
+  if (!state)
+    return 0;
+  webkit_web_view_restore_session_state(wv, state);
+  webkit_web_view_session_state_unref(state);
+  WebKitBackForwardListItem *item = webkit_back_forward_list_get_current_item(
+      webkit_web_view_get_back_forward_list(wv));
+  if (item)
+    webkit_web_view_go_to_back_forward_list_item(wv, item);
+  return 1;
+}
+
+// Hiding the window makes WebKit throttle the page, which sees
+// document.hidden
+void my_set_suspended(void *win, int suspended) {
+  if (suspended)
+    gtk_widget_hide(GTK_WIDGET(win));
+  else
+    gtk_widget_show_all(GTK_WIDGET(win));
+}
+
+// Asks the window manager to keep the top-level fltk window, top, above
+// others, through _NET_WM_STATE. The webview's X window follows its parent
+void my_set_always_on_top(void *win, void *top, int on) {
+  GdkWindow *w = gtk_widget_get_window(GTK_WIDGET(win));
+  if (!w)
+    return;
+  Display *disp = GDK_WINDOW_XDISPLAY(w);
+  XEvent e = {0};
+  e.xclient.type = ClientMessage;
+  e.xclient.window = (Window)top;
+  e.xclient.message_type = XInternAtom(disp, "_NET_WM_STATE", False);
+  e.xclient.format = 32;
+  // _NET_WM_STATE_ADD or _NET_WM_STATE_REMOVE
+  e.xclient.data.l[0] = on ? 1 : 0;
+  e.xclient.data.l[1] = XInternAtom(disp, "_NET_WM_STATE_ABOVE", False);
+  // Sent by a normal application
+  e.xclient.data.l[3] = 1;
+  XSendEvent(disp, DefaultRootWindow(disp), False,
+             SubstructureRedirectMask | SubstructureNotifyMask, &e);
+  XFlush(disp);
+}
+
+// Asks the window manager to maximize the top-level fltk window, top, or to
+// restore it, through _NET_WM_STATE
+void my_set_maximized(void *win, void *top, int on) {
+  GdkWindow *w = gtk_widget_get_window(GTK_WIDGET(win));
+  if (!w)
+    return;
+  Display *disp = GDK_WINDOW_XDISPLAY(w);
+  XEvent e = {0};
+  e.xclient.type = ClientMessage;
+  e.xclient.window = (Window)top;
+  e.xclient.message_type = XInternAtom(disp, "_NET_WM_STATE", False);
+  e.xclient.format = 32;
+  e.xclient.data.l[0] = on ? 1 : 0;
+  e.xclient.data.l[1] =
+      XInternAtom(disp, "_NET_WM_STATE_MAXIMIZED_VERT", False);
+  e.xclient.data.l[2] =
+      XInternAtom(disp, "_NET_WM_STATE_MAXIMIZED_HORZ", False);
+  e.xclient.data.l[3] = 1;
+  XSendEvent(disp, DefaultRootWindow(disp), False,
+             SubstructureRedirectMask | SubstructureNotifyMask, &e);
+  XFlush(disp);
+}
+
+// Returns 1 if top is minimized, 2 if it's maximized in both directions, and
+// 0 otherwise, as last set by the window manager in _NET_WM_STATE
+int my_get_window_state(void *win, void *top) {
+  GdkWindow *w = gtk_widget_get_window(GTK_WIDGET(win));
+  if (!w)
+    return 0;
+  Display *disp = GDK_WINDOW_XDISPLAY(w);
+  Atom type;
+  int format;
+  unsigned long count, remaining;
+  unsigned char *data = NULL;
+  if (XGetWindowProperty(disp, (Window)top,
+                         XInternAtom(disp, "_NET_WM_STATE", False), 0, 64,
+                         False, XA_ATOM, &type, &format, &count, &remaining,
+                         &data) != Success ||
+      !data)
+    return 0;
+  Atom hidden = XInternAtom(disp, "_NET_WM_STATE_HIDDEN", False);
+  Atom vert = XInternAtom(disp, "_NET_WM_STATE_MAXIMIZED_VERT", False);
+  Atom horz = XInternAtom(disp, "_NET_WM_STATE_MAXIMIZED_HORZ", False);
+  int is_hidden = 0, maximized = 0;
+  for (unsigned long i = 0; i < count; i++) {
+    Atom a = ((Atom *)data)[i];
+    if (a == hidden)
+      is_hidden = 1;
+    else if (a == vert || a == horz)
+      maximized++;
+  }
+  XFree(data);
+  return is_hidden ? 1 : maximized == 2 ? 2 : 0;
+}
+
+// The webview's X window is a child of the embedding window after x_init,
+// so positions are relative to it
+void my_set_position(void *win, void *parent, int x, int y) {
+  GdkWindow *w = gtk_widget_get_window(GTK_WIDGET(win));
+  if (!w)
+    return;
+  XMoveWindow(GDK_WINDOW_XDISPLAY(w), GDK_WINDOW_XID(w), x, y);
+  XFlush(GDK_WINDOW_XDISPLAY(w));
+}
+
+void my_get_position(void *win, void *parent, int *x, int *y) {
+  GdkWindow *w = gtk_widget_get_window(GTK_WIDGET(win));
+  XWindowAttributes attrs;
+  if (!w || !XGetWindowAttributes(GDK_WINDOW_XDISPLAY(w), GDK_WINDOW_XID(w),
+                                  &attrs)) {
+    *x = *y = 0;
+    return;
+  }
+  *x = attrs.x;
+  *y = attrs.y;
+}
+
+void my_get_size(void *win, int *w, int *h) {
+  GdkWindow *gw = gtk_widget_get_window(GTK_WIDGET(win));
+  XWindowAttributes attrs;
+  if (!gw || !XGetWindowAttributes(GDK_WINDOW_XDISPLAY(gw),
+                                   GDK_WINDOW_XID(gw), &attrs)) {
+    *w = *h = 0;
+    return;
+  }
+  *w = attrs.width;
+  *h = attrs.height;
+}
+
+// Returns 0 if muting isn't available, before WebKitGTK 2.30
+int my_set_muted(void *win, int muted) {
+#if WEBKIT_CHECK_VERSION(2, 30, 0)
+  webkit_web_view_set_is_muted(my_get_webview(win), muted);
+  return 1;
+#else
+  return 0;
+#endif
+}
+
+// The page cache is WebKit's back-forward cache
+int my_set_page_cache(void *win, int enabled) {
+  webkit_settings_set_enable_page_cache(
+      webkit_web_view_get_settings(my_get_webview(win)), enabled);
+  return 1;
+}
+
+int my_set_caret_browsing(void *win, int enabled) {
+  webkit_settings_set_enable_caret_browsing(
+      webkit_web_view_get_settings(my_get_webview(win)), enabled);
+  return 1;
+}
+
+// families holds the default, serif, sans-serif and monospace families, NULL
+// keeping one, and sizes of 0 keep theirs
+int my_set_font_defaults(void *win, const char **families, int size,
+                         int monospace_size) {
+  WebKitSettings *settings =
+      webkit_web_view_get_settings(my_get_webview(win));
+  if (families[0])
+    webkit_settings_set_default_font_family(settings, families[0]);
+  if (families[1])
+    webkit_settings_set_serif_font_family(settings, families[1]);
+  if (families[2])
+    webkit_settings_set_sans_serif_font_family(settings, families[2]);
+  if (families[3])
+    webkit_settings_set_monospace_font_family(settings, families[3]);
+  if (size)
+    webkit_settings_set_default_font_size(settings, size);
+  if (monospace_size)
+    webkit_settings_set_default_monospace_font_size(settings, monospace_size);
+  return 1;
+}
+
+typedef void (*my_cookie_cb)(const char *name, const char *value,
+                             const char *domain, const char *path,
+                             long long expires, int secure, int http_only,
+                             void *data);
+
+typedef struct {
+  my_cookie_cb cb;
+  void *data;
+} my_cookies_request;
+
+static WebKitCookieManager *my_cookie_manager(void *win) {
+  return webkit_website_data_manager_get_cookie_manager(
+      webkit_web_context_get_website_data_manager(
+          webkit_web_view_get_context(my_get_webview(win))));
+}
+
+static void my_cookies_got(GObject *manager, GAsyncResult *res,
+                           gpointer user) {
+  my_cookies_request *r = user;
+#if WEBKIT_CHECK_VERSION(2, 42, 0)
+  GList *cookies = webkit_cookie_manager_get_all_cookies_finish(
+      WEBKIT_COOKIE_MANAGER(manager), res, NULL);
+#else
+  GList *cookies = webkit_cookie_manager_get_cookies_finish(
+      WEBKIT_COOKIE_MANAGER(manager), res, NULL);
+#endif
+  for (GList *l = cookies; l; l = l->next) {
+    SoupCookie *c = l->data;
+    long long expires = -1;
+#if SOUP_CHECK_VERSION(3, 0, 0)
+    GDateTime *date = soup_cookie_get_expires(c);
+    if (date)
+      expires = g_date_time_to_unix(date);
+#else
+    SoupDate *date = soup_cookie_get_expires(c);
+    if (date)
+      expires = soup_date_to_time_t(date);
+#endif
+    r->cb(soup_cookie_get_name(c), soup_cookie_get_value(c),
+          soup_cookie_get_domain(c), soup_cookie_get_path(c), expires,
+          soup_cookie_get_secure(c), soup_cookie_get_http_only(c), r->data);
+  }
+  g_list_free_full(cookies, (GDestroyNotify)soup_cookie_free);
+  r->cb(NULL, NULL, NULL, NULL, -1, 0, 0, r->data);
+  g_free(r);
+}
+
+// Passes each cookie to cb, expires being in seconds since the epoch or -1,
+// then calls it with a NULL name. Before WebKitGTK 2.42, only the cookies of
+// the current URI can be listed
+void my_get_cookies(void *win, my_cookie_cb cb, void *data) {
+  my_cookies_request *r = g_new(my_cookies_request, 1);
+  r->cb = cb;
+  r->data = data;
+#if WEBKIT_CHECK_VERSION(2, 42, 0)
+  webkit_cookie_manager_get_all_cookies(my_cookie_manager(win), NULL,
+                                        my_cookies_got, r);
+#else
+  const char *uri = webkit_web_view_get_uri(my_get_webview(win));
+  if (!uri) {
+    cb(NULL, NULL, NULL, NULL, -1, 0, 0, data);
+    g_free(r);
+    return;
+  }
+  webkit_cookie_manager_get_cookies(my_cookie_manager(win), uri, NULL,
+                                    my_cookies_got, r);
+#endif
+}
+
+typedef void (*my_done_cb)(int ok, void *data);
+
+typedef struct {
+  my_done_cb cb;
+  void *data;
+} my_add_cookie_request;
+
+static void my_cookie_added(GObject *manager, GAsyncResult *res,
+                            gpointer user) {
+  my_add_cookie_request *r = user;
+  int ok = webkit_cookie_manager_add_cookie_finish(
+      WEBKIT_COOKIE_MANAGER(manager), res, NULL);
+  r->cb(ok, r->data);
+  g_free(r);
+}
+
+// cb receives whether the cookie was stored
+void my_add_cookie(void *win, const char *name, const char *value,
+                   const char *domain, const char *path, long long expires,
+                   int secure, int http_only, my_done_cb cb, void *data) {
+  SoupCookie *cookie = soup_cookie_new(name, value, domain, path, -1);
+  if (expires >= 0) {
+#if SOUP_CHECK_VERSION(3, 0, 0)
+    GDateTime *date = g_date_time_new_from_unix_utc(expires);
+    soup_cookie_set_expires(cookie, date);
+    g_date_time_unref(date);
+#else
+    SoupDate *date = soup_date_new_from_time_t(expires);
+    soup_cookie_set_expires(cookie, date);
+    soup_date_free(date);
+#endif
+  }
+  soup_cookie_set_secure(cookie, secure);
+  soup_cookie_set_http_only(cookie, http_only);
+  my_add_cookie_request *r = g_new(my_add_cookie_request, 1);
+  r->cb = cb;
+  r->data = data;
+  webkit_cookie_manager_add_cookie(my_cookie_manager(win), cookie, NULL,
+                                   my_cookie_added, r);
+  soup_cookie_free(cookie);
+}
+
+// The version of the WebKitGTK library loaded at runtime
+void my_get_engine_version(my_str_cb cb, void *data) {
+  char version[32];
+  g_snprintf(version, sizeof(version), "%u.%u.%u", webkit_get_major_version(),
+             webkit_get_minor_version(), webkit_get_micro_version());
+  cb(version, data);
+}
+
+// Returns 0 to cancel the download, 1 to save it at *path, 2 for the default
+typedef int (*my_download_cb)(const char *url, const char *filename,
+                              const char **path, void *data);
+// state is 0 while in progress, 1 when finished, 2 on failure, 3 on cancel.
+// total is -1 when unknown
+typedef void (*my_download_progress_cb)(const char *url, const char *path,
+                                        unsigned long long received,
+                                        long long total, int state,
+                                        const char *error, void *data);
+
+typedef struct {
+  my_download_cb decide;
+  my_download_progress_cb progress;
+  void *data;
+  WebKitWebView *wv;
+} my_download_hooks;
+
+static void my_report_download(WebKitDownload *d, my_download_hooks *h,
+                               int state, const char *error) {
+  WebKitURIResponse *response = webkit_download_get_response(d);
+  guint64 total =
+      response ? webkit_uri_response_get_content_length(response) : 0;
+  const char *dest = webkit_download_get_destination(d);
+  gchar *path = dest ? g_filename_from_uri(dest, NULL, NULL) : NULL;
+  h->progress(webkit_uri_request_get_uri(webkit_download_get_request(d)),
+              path ? path : "", webkit_download_get_received_data_length(d),
+              total ? (long long)total : -1, state, error, h->data);
+  g_free(path);
+}
+
+static gboolean my_decide_destination(WebKitDownload *d, gchar *filename,
+                                      my_download_hooks *h) {
+  const char *path = NULL;
+  const char *url = webkit_uri_request_get_uri(webkit_download_get_request(d));
+  switch (h->decide(url, filename, &path, h->data)) {
+  case 1: {
+    gchar *uri = g_filename_to_uri(path, NULL, NULL);
+    if (!uri)
+      break;
+    webkit_download_set_allow_overwrite(d, TRUE);
+    webkit_download_set_destination(d, uri);
+    g_free(uri);
+    return TRUE;
+  }
+  case 2:
+    return FALSE;
+  }
+  webkit_download_cancel(d);
+  return TRUE;
+}
+
+static void my_download_received(WebKitDownload *d, guint64 len,
+                                 my_download_hooks *h) {
+  my_report_download(d, h, 0, NULL);
+}
+
+// finished is also emitted after failed
+static void my_download_failed(WebKitDownload *d, GError *error,
+                               my_download_hooks *h) {
+  g_object_set_data(G_OBJECT(d), "my-download-failed", GINT_TO_POINTER(1));
+  if (g_error_matches(error, WEBKIT_DOWNLOAD_ERROR,
+                      WEBKIT_DOWNLOAD_ERROR_CANCELLED_BY_USER))
+    my_report_download(d, h, 3, NULL);
+  else
+    my_report_download(d, h, 2, error->message);
+}
+
+static void my_download_finished(WebKitDownload *d, my_download_hooks *h) {
+  if (!g_object_get_data(G_OBJECT(d), "my-download-failed"))
+    my_report_download(d, h, 1, NULL);
+}
+
+// Downloads are started in the web context, which webviews can share
+static void my_download_started(WebKitWebContext *ctx, WebKitDownload *d,
+                                my_download_hooks *h) {
+  if (webkit_download_get_web_view(d) != h->wv)
+    return;
+  g_signal_connect(d, "decide-destination", G_CALLBACK(my_decide_destination),
+                   h);
+  g_signal_connect(d, "received-data", G_CALLBACK(my_download_received), h);
+  g_signal_connect(d, "failed", G_CALLBACK(my_download_failed), h);
+  g_signal_connect(d, "finished", G_CALLBACK(my_download_finished), h);
+}
+
+// Downloads the responses which can't be shown, or are sent as attachments
+static gboolean my_download_responses(WebKitWebView *wv,
+                                      WebKitPolicyDecision *decision,
+                                      WebKitPolicyDecisionType type,
+                                      gpointer unused) {
+  if (type != WEBKIT_POLICY_DECISION_TYPE_RESPONSE)
+    return FALSE;
+  WebKitResponsePolicyDecision *r = WEBKIT_RESPONSE_POLICY_DECISION(decision);
+  SoupMessageHeaders *headers = webkit_uri_response_get_http_headers(
+      webkit_response_policy_decision_get_response(r));
+  const char *disposition =
+      headers ? soup_message_headers_get_one(headers, "Content-Disposition")
+              : NULL;
+  if (webkit_response_policy_decision_is_mime_type_supported(r) &&
+      !(disposition && !g_ascii_strncasecmp(disposition, "attachment", 10)))
+    return FALSE;
+  webkit_policy_decision_download(decision);
+  return TRUE;
+}
+
+void my_on_download(void *win, my_download_cb decide,
+                    my_download_progress_cb progress, void *data) {
+  my_download_hooks *h = g_new(my_download_hooks, 1);
+  h->decide = decide;
+  h->progress = progress;
+  h->data = data;
+  h->wv = my_get_webview(win);
+  g_signal_connect(h->wv, "decide-policy", G_CALLBACK(my_download_responses),
+                   NULL);
+  // Outlives the webview, its downloads can still be running
+  g_signal_connect(webkit_web_view_get_context(h->wv), "download-started",
+                   G_CALLBACK(my_download_started), h);
+}
+
+typedef void (*my_load_cb)(int delta, void *data);
+
+static void my_resource_finished(WebKitWebResource *resource, my_closure *c) {
+  ((my_load_cb)c->cb)(-1, c->data);
+}
+
+static void my_resource_started(WebKitWebView *wv, WebKitWebResource *resource,
+                                WebKitURIRequest *request, my_closure *c) {
+  ((my_load_cb)c->cb)(1, c->data);
+  // Also emitted after "failed"
+  g_signal_connect(resource, "finished", G_CALLBACK(my_resource_finished), c);
+}
+
+// cb receives 1 when a resource starts loading, and -1 when it's done
+void my_on_resource_load(void *win, my_load_cb cb, void *data) {
+  // Never freed, resources can finish after the webview is destroyed
+  g_signal_connect(my_get_webview(win), "resource-load-started",
+                   G_CALLBACK(my_resource_started),
+                   my_closure_new((void (*)(void))cb, data));
+}
+
+typedef int (*my_fullscreen_cb)(int entering, void *data);
+
+// Returning FALSE lets WebKit fullscreen its GtkWindow, which the window
+// manager ignores since it's reparented, so the element fills the view
+static gboolean my_enter_fullscreen(WebKitWebView *wv, my_closure *c) {
+  return !((my_fullscreen_cb)c->cb)(1, c->data);
+}
+
+static gboolean my_leave_fullscreen(WebKitWebView *wv, my_closure *c) {
+  ((my_fullscreen_cb)c->cb)(0, c->data);
+  return FALSE;
+}
+
+// cb receives 1 when an element requests fullscreen, and returns whether to
+// allow it, and 0 when it leaves fullscreen
+void my_on_fullscreen(void *win, my_fullscreen_cb cb, void *data) {
+  WebKitWebView *wv = my_get_webview(win);
+  g_signal_connect_data(wv, "enter-fullscreen",
+                        G_CALLBACK(my_enter_fullscreen),
+                        my_closure_new((void (*)(void))cb, data),
+                        my_closure_free, 0);
+  g_signal_connect_data(wv, "leave-fullscreen",
+                        G_CALLBACK(my_leave_fullscreen),
+                        my_closure_new((void (*)(void))cb, data),
+                        my_closure_free, 0);
+}
+
+typedef int (*my_confirm_cb)(const char *message, void *data);
+
+// Lets other script dialogs take their default handling
+static gboolean my_script_dialog(WebKitWebView *wv, WebKitScriptDialog *dialog,
+                                 my_closure *c) {
+  if (webkit_script_dialog_get_dialog_type(dialog) !=
+      WEBKIT_SCRIPT_DIALOG_BEFORE_UNLOAD_CONFIRM)
+    return FALSE;
+  const char *message = webkit_script_dialog_get_message(dialog);
+  webkit_script_dialog_confirm_set_confirmed(
+      dialog, ((my_confirm_cb)c->cb)(message ? message : "", c->data));
+  return TRUE;
+}
+
+// cb receives the message of the page asking to stay, and returns whether to
+// leave it anyway
+void my_on_before_unload(void *win, my_confirm_cb cb, void *data) {
+  g_signal_connect_data(my_get_webview(win), "script-dialog",
+                        G_CALLBACK(my_script_dialog),
+                        my_closure_new((void (*)(void))cb, data),
+                        my_closure_free, 0);
+}
@@ -1,2 +1,327 @@
 pub use wv_sys::*;
-pub use wv::*;
\ No newline at end of file
+pub use wv::*;
+
+#[cfg(not(target_os = "windows"))]
+use std::os::raw;
+
+// Implemented in gtk_helper.c and cocoa_helper.m, win is the window returned by webview_get_window
+#[cfg(not(target_os = "windows"))]
+extern "C" {
+    pub fn my_add_user_script(
+        win: *mut raw::c_void,
+        js: *const raw::c_char,
+        at_end: raw::c_int,
+        isolated: raw::c_int,
+    ) -> *mut raw::c_void;
+    pub fn my_remove_user_script(win: *mut raw::c_void, script: *mut raw::c_void);
+    pub fn my_load_request(
+        win: *mut raw::c_void,
+        method: *const raw::c_char,
+        url: *const raw::c_char,
+        header_names: *const *const raw::c_char,
+        header_values: *const *const raw::c_char,
+        header_count: raw::c_int,
+        body: *const u8,
+        body_len: usize,
+    ) -> raw::c_int;
+    pub fn my_set_default_headers(
+        win: *mut raw::c_void,
+        header_names: *const *const raw::c_char,
+        header_values: *const *const raw::c_char,
+        header_count: raw::c_int,
+        origins: *const *const raw::c_char,
+        origin_count: raw::c_int,
+    );
+    pub fn my_focus(win: *mut raw::c_void);
+    pub fn my_has_focus(win: *mut raw::c_void) -> raw::c_int;
+    pub fn my_set_transparent(win: *mut raw::c_void, transparent: raw::c_int);
+    pub fn my_set_background(win: *mut raw::c_void, r: u8, g: u8, b: u8);
+    pub fn my_set_opacity(win: *mut raw::c_void, opacity: f64);
+    pub fn my_reload_bypass_cache(win: *mut raw::c_void);
+    pub fn my_stop_loading(win: *mut raw::c_void);
+    pub fn my_get_uri(win: *mut raw::c_void) -> *const raw::c_char;
+    pub fn my_set_javascript_enabled(win: *mut raw::c_void, enabled: raw::c_int);
+    pub fn my_apply_settings(win: *mut raw::c_void, values: *const raw::c_int);
+    pub fn my_set_spellcheck(
+        win: *mut raw::c_void,
+        enabled: raw::c_int,
+        languages: *const *const raw::c_char,
+    );
+    pub fn my_can_go_back(win: *mut raw::c_void) -> raw::c_int;
+    pub fn my_can_go_forward(win: *mut raw::c_void) -> raw::c_int;
+    pub fn my_is_loading(win: *mut raw::c_void) -> raw::c_int;
+    pub fn my_get_progress(win: *mut raw::c_void) -> f64;
+    pub fn my_get_response_status(win: *mut raw::c_void) -> raw::c_int;
+    pub fn my_load_html(
+        win: *mut raw::c_void,
+        html: *const raw::c_char,
+        base_url: *const raw::c_char,
+    );
+    pub fn my_load_alternate_html(
+        win: *mut raw::c_void,
+        html: *const raw::c_char,
+        uri: *const raw::c_char,
+    );
+    pub fn my_on_load_failed(
+        win: *mut raw::c_void,
+        cb: extern "C" fn(
+            uri: *const raw::c_char,
+            error: *const raw::c_char,
+            data: *mut raw::c_void,
+        ) -> raw::c_int,
+        data: *mut raw::c_void,
+    );
+    pub fn my_on_title_changed(
+        win: *mut raw::c_void,
+        cb: extern "C" fn(title: *const raw::c_char, data: *mut raw::c_void),
+        data: *mut raw::c_void,
+    );
+    pub fn my_on_uri_changed(
+        win: *mut raw::c_void,
+        cb: extern "C" fn(uri: *const raw::c_char, data: *mut raw::c_void),
+        data: *mut raw::c_void,
+    );
+    pub fn my_on_progress(
+        win: *mut raw::c_void,
+        cb: extern "C" fn(progress: f64, data: *mut raw::c_void),
+        data: *mut raw::c_void,
+    );
+    pub fn my_on_loading_changed(
+        win: *mut raw::c_void,
+        cb: extern "C" fn(loading: raw::c_int, data: *mut raw::c_void),
+        data: *mut raw::c_void,
+    );
+    pub fn my_get_session_state(
+        win: *mut raw::c_void,
+        cb: extern "C" fn(data: *const u8, len: usize, user: *mut raw::c_void),
+        user: *mut raw::c_void,
+    );
+    pub fn my_restore_session_state(
+        win: *mut raw::c_void,
+        data: *const u8,
+        len: usize,
+    ) -> raw::c_int;
+    pub fn my_on_redirect(
+        win: *mut raw::c_void,
+        cb: extern "C" fn(from: *const raw::c_char, to: *const raw::c_char, data: *mut raw::c_void),
+        data: *mut raw::c_void,
+    );
+    pub fn my_on_new_window(
+        win: *mut raw::c_void,
+        cb: extern "C" fn(uri: *const raw::c_char, data: *mut raw::c_void) -> raw::c_int,
+        data: *mut raw::c_void,
+    );
+    pub fn my_on_before_unload(
+        win: *mut raw::c_void,
+        cb: extern "C" fn(message: *const raw::c_char, data: *mut raw::c_void) -> raw::c_int,
+        data: *mut raw::c_void,
+    );
+    pub fn my_on_drop(
+        win: *mut raw::c_void,
+        cb: extern "C" fn(paths: *const raw::c_char, data: *mut raw::c_void),
+        data: *mut raw::c_void,
+    );
+    pub fn my_on_permission(
+        win: *mut raw::c_void,
+        cb: extern "C" fn(
+            kind: raw::c_int,
+            origin: *const raw::c_char,
+            data: *mut raw::c_void,
+        ) -> raw::c_int,
+        data: *mut raw::c_void,
+    );
+    pub fn my_on_key(
+        win: *mut raw::c_void,
+        cb: extern "C" fn(key: raw::c_int, state: raw::c_int, data: *mut raw::c_void) -> raw::c_int,
+        data: *mut raw::c_void,
+        host: *mut raw::c_void,
+    );
+    pub fn my_set_suspended(win: *mut raw::c_void, suspended: raw::c_int);
+    pub fn my_set_always_on_top(win: *mut raw::c_void, top: *mut raw::c_void, on: raw::c_int);
+    pub fn my_set_maximized(win: *mut raw::c_void, top: *mut raw::c_void, on: raw::c_int);
+    pub fn my_get_window_state(win: *mut raw::c_void, top: *mut raw::c_void) -> raw::c_int;
+    pub fn my_set_muted(win: *mut raw::c_void, muted: raw::c_int) -> raw::c_int;
+    pub fn my_set_page_cache(win: *mut raw::c_void, enabled: raw::c_int) -> raw::c_int;
+    pub fn my_set_caret_browsing(win: *mut raw::c_void, enabled: raw::c_int) -> raw::c_int;
+    pub fn my_set_font_defaults(
+        win: *mut raw::c_void,
+        families: *const *const raw::c_char,
+        size: raw::c_int,
+        monospace_size: raw::c_int,
+    ) -> raw::c_int;
+    pub fn my_set_user_agent(win: *mut raw::c_void, user_agent: *const raw::c_char);
+    pub fn my_set_clipboard_access(win: *mut raw::c_void, allowed: raw::c_int) -> raw::c_int;
+    pub fn my_get_cookies(
+        win: *mut raw::c_void,
+        cb: extern "C" fn(
+            name: *const raw::c_char,
+            value: *const raw::c_char,
+            domain: *const raw::c_char,
+            path: *const raw::c_char,
+            expires: raw::c_longlong,
+            secure: raw::c_int,
+            http_only: raw::c_int,
+            data: *mut raw::c_void,
+        ),
+        data: *mut raw::c_void,
+    );
+    pub fn my_add_cookie(
+        win: *mut raw::c_void,
+        name: *const raw::c_char,
+        value: *const raw::c_char,
+        domain: *const raw::c_char,
+        path: *const raw::c_char,
+        expires: raw::c_longlong,
+        secure: raw::c_int,
+        http_only: raw::c_int,
+        cb: extern "C" fn(ok: raw::c_int, data: *mut raw::c_void),
+        data: *mut raw::c_void,
+    );
+    pub fn my_get_engine_version(
+        cb: extern "C" fn(version: *const raw::c_char, data: *mut raw::c_void),
+        data: *mut raw::c_void,
+    );
+    pub fn my_on_download(
+        win: *mut raw::c_void,
+        decide: extern "C" fn(
+            url: *const raw::c_char,
+            filename: *const raw::c_char,
+            path: *mut *const raw::c_char,
+            data: *mut raw::c_void,
+        ) -> raw::c_int,
+        progress: extern "C" fn(
+            url: *const raw::c_char,
+            path: *const raw::c_char,
+            received: raw::c_ulonglong,
+            total: raw::c_longlong,
+            state: raw::c_int,
+            error: *const raw::c_char,
+            data: *mut raw::c_void,
+        ),
+        data: *mut raw::c_void,
+    );
+    pub fn my_set_position(
+        win: *mut raw::c_void,
+        parent: *mut raw::c_void,
+        x: raw::c_int,
+        y: raw::c_int,
+    );
+    pub fn my_get_position(
+        win: *mut raw::c_void,
+        parent: *mut raw::c_void,
+        x: *mut raw::c_int,
+        y: *mut raw::c_int,
+    );
+    pub fn my_get_size(win: *mut raw::c_void, w: *mut raw::c_int, h: *mut raw::c_int);
+    pub fn my_add_content_rules(
+        win: *mut raw::c_void,
+        id: *const raw::c_char,
+        json: *const raw::c_char,
+        len: usize,
+        cb: extern "C" fn(
+            rules: *mut raw::c_void,
+            error: *const raw::c_char,
+            data: *mut raw::c_void,
+        ),
+        data: *mut raw::c_void,
+    );
+    pub fn my_remove_content_rules(win: *mut raw::c_void, rules: *mut raw::c_void);
+    pub fn my_on_tls_error(
+        win: *mut raw::c_void,
+        cb: extern "C" fn(
+            host: *const raw::c_char,
+            cert: *const raw::c_char,
+            data: *mut raw::c_void,
+        ) -> raw::c_int,
+        data: *mut raw::c_void,
+    );
+    pub fn my_on_auth(
+        win: *mut raw::c_void,
+        cb: extern "C" fn(
+            host: *const raw::c_char,
+            kind: raw::c_int,
+            user: *mut *const raw::c_char,
+            password: *mut *const raw::c_char,
+            data: *mut raw::c_void,
+        ) -> raw::c_int,
+        data: *mut raw::c_void,
+    );
+}
+
+// Only implemented in gtk_helper.c
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+extern "C" {
+    pub fn my_find(
+        win: *mut raw::c_void,
+        text: *const raw::c_char,
+        case_sensitive: raw::c_int,
+        wrap_around: raw::c_int,
+        cb: extern "C" fn(count: raw::c_uint, data: *mut raw::c_void),
+        data: *mut raw::c_void,
+    );
+    pub fn my_find_next(win: *mut raw::c_void);
+    pub fn my_find_previous(win: *mut raw::c_void);
+    pub fn my_find_clear(win: *mut raw::c_void);
+    pub fn my_get_scale_factor(win: *mut raw::c_void) -> raw::c_int;
+    pub fn my_add_user_style(win: *mut raw::c_void, css: *const raw::c_char) -> *mut raw::c_void;
+    pub fn my_remove_user_style(win: *mut raw::c_void, sheet: *mut raw::c_void);
+    pub fn my_get_favicon(
+        win: *mut raw::c_void,
+        cb: extern "C" fn(data: *const u8, len: usize, user: *mut raw::c_void),
+        user: *mut raw::c_void,
+    );
+    pub fn my_set_autoplay(win: *mut raw::c_void, allow: raw::c_int);
+    pub fn my_set_smooth_scrolling(win: *mut raw::c_void, enabled: raw::c_int);
+    pub fn my_set_preferred_languages(win: *mut raw::c_void, languages: *const *const raw::c_char);
+    pub fn my_set_default_charset(win: *mut raw::c_void, charset: *const raw::c_char);
+    pub fn my_enable_sandbox();
+    pub fn my_enable_favicons();
+    pub fn my_set_process_model(shared: raw::c_int, max: raw::c_uint);
+    pub fn my_set_hardware_acceleration(win: *mut raw::c_void, policy: raw::c_int);
+    pub fn my_ignore_tls_errors(win: *mut raw::c_void);
+    pub fn my_set_cache_model(win: *mut raw::c_void, model: raw::c_int);
+    pub fn my_register_uri_scheme(
+        win: *mut raw::c_void,
+        scheme: *const raw::c_char,
+        cb: extern "C" fn(
+            request: *mut raw::c_void,
+            uri: *const raw::c_char,
+            win: *mut raw::c_void,
+        ),
+    );
+    pub fn my_enable_scheme_cors(win: *mut raw::c_void, scheme: *const raw::c_char);
+    pub fn my_uri_request_finish(
+        request: *mut raw::c_void,
+        data: *const u8,
+        len: usize,
+        mime: *const raw::c_char,
+    );
+    pub fn my_uri_request_fail(request: *mut raw::c_void, message: *const raw::c_char);
+    pub fn my_redirect_https_host(
+        win: *mut raw::c_void,
+        host: *const raw::c_char,
+        scheme: *const raw::c_char,
+    );
+    pub fn my_on_resource_load(
+        win: *mut raw::c_void,
+        cb: extern "C" fn(delta: raw::c_int, data: *mut raw::c_void),
+        data: *mut raw::c_void,
+    );
+    pub fn my_on_fullscreen(
+        win: *mut raw::c_void,
+        cb: extern "C" fn(entering: raw::c_int, data: *mut raw::c_void) -> raw::c_int,
+        data: *mut raw::c_void,
+    );
+    pub fn my_is_x11_display() -> raw::c_int;
+}
+
+// Only implemented in cocoa_helper.m
+#[cfg(target_os = "macos")]
+extern "C" {
+    pub fn my_track_response_status(win: *mut raw::c_void);
+    pub fn my_enable_fullscreen(win: *mut raw::c_void);
+    pub fn my_set_safe_browsing(win: *mut raw::c_void, enabled: i32);
+    pub fn my_web_process_usage(win: *mut raw::c_void, memory: *mut u64, cpu: *mut u64) -> i32;
+    pub fn my_set_text_zoom(win: *mut raw::c_void, factor: f64) -> raw::c_int;
+    pub fn my_set_color_scheme(win: *mut raw::c_void, scheme: raw::c_int);
+}
@@ -0,0 +1,183 @@
+use crate::{state, Webview};
+#[cfg(not(target_os = "windows"))]
+use fltk::enums::Key;
+use fltk::enums::Shortcut;
+use std::{collections::HashSet, os::raw};
+
+/// Determines which keyboard shortcuts are handled by the fltk application instead of the webview
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum AcceleratorMode {
+    /// The webview handles all keys
+    #[default]
+    Default,
+    /// Keys combined with Ctrl, Alt or Meta, and the function keys, are passed to fltk
+    PassToHost,
+    /// Only the listed shortcuts are passed to fltk.
+    /// Letters should be lowercase, e.g. `Shortcut::Ctrl | 'r'`
+    Custom(HashSet<Shortcut>),
+}
+
+#[cfg(not(target_os = "windows"))]
+impl AcceleratorMode {
+    fn passes(&self, key: Key, state: Shortcut) -> bool {
+        match self {
+            AcceleratorMode::Default => false,
+            AcceleratorMode::PassToHost => {
+                state.intersects(Shortcut::Ctrl | Shortcut::Alt | Shortcut::Meta)
+                    || Key::is_fn_key(key)
+            }
+            AcceleratorMode::Custom(keys) => keys.contains(&(state | key)),
+        }
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+extern "C" fn key_cb(key: raw::c_int, state: raw::c_int, data: *mut raw::c_void) -> raw::c_int {
+    let mode = state::with_key(data as usize, |s| s.accel_mode.clone());
+    mode.passes(Key::from_i32(key), Shortcut::from_i32(state)) as _
+}
+
+/// Keyboard shortcut handling
+pub trait AcceleratorExt {
+    /// Set which shortcuts are passed to the fltk application instead of the webview,
+    /// as key events of the embedding window
+    fn set_accelerator_handling(&mut self, mode: AcceleratorMode);
+}
+
+impl AcceleratorExt for Webview {
+    fn set_accelerator_handling(&mut self, mode: AcceleratorMode) {
+        let (first, host) = state::with(self, |s| {
+            let first = !s.accel_hooked;
+            s.accel_hooked = true;
+            s.accel_mode = mode.clone();
+            (first, s.win.clone())
+        });
+        #[cfg(not(target_os = "windows"))]
+        if first {
+            use fltk::prelude::WindowExt;
+            let win = self.get_window();
+            let host = host.map(|h| h.raw_handle()).unwrap_or_default();
+            unsafe {
+                fltk_webview_sys::my_on_key(win, key_cb, win, host as _);
+            }
+        }
+        #[cfg(target_os = "windows")]
+        {
+            use fltk::prelude::WindowExt;
+            extern "system" {
+                fn PostMessageW(
+                    hwnd: *mut raw::c_void,
+                    msg: u32,
+                    wparam: usize,
+                    lparam: isize,
+                ) -> i32;
+            }
+            const WM_KEYDOWN: u32 = 0x0100;
+            const WM_KEYUP: u32 = 0x0101;
+            if first {
+                let host = host.map(|h| h.raw_handle() as usize).unwrap_or_default();
+                self.bind("__fltk_webview_key", move |_seq, req| {
+                    let args = crate::utils::parse_args(req);
+                    if let Some(vk) = args.first().and_then(|v| v.get::<f64>()) {
+                        unsafe {
+                            PostMessageW(host as _, WM_KEYDOWN, *vk as usize, 1);
+                            PostMessageW(
+                                host as _,
+                                WM_KEYUP,
+                                *vk as usize,
+                                0xC000_0001u32 as isize,
+                            );
+                        }
+                    }
+                });
+                self.init(KEY_JS);
+                self.eval(KEY_JS);
+            }
+            let config = js_config(&mode);
+            crate::scripts::set_script(self, "accelerators", &config);
+        }
+    }
+}
+
+/// Reports the keys matching `window.__fltk_webview_accel`, using fltk's key codes
+#[cfg(target_os = "windows")]
+const KEY_JS: &str = r#"(function() {
+    var keys = { 8: 0xff08, 9: 0xff09, 13: 0xff0d, 27: 0xff1b, 33: 0xff55, 34: 0xff56, 35: 0xff57,
+        36: 0xff50, 37: 0xff51, 38: 0xff52, 39: 0xff53, 40: 0xff54, 46: 0xffff };
+    document.addEventListener('keydown', function(e) {
+        var accel = window.__fltk_webview_accel;
+        if (!accel) return;
+        var key = keys[e.keyCode];
+        if (e.keyCode >= 112 && e.keyCode <= 135) key = 0xffbe + e.keyCode - 112;
+        else if (e.keyCode >= 65 && e.keyCode <= 90) key = e.keyCode + 32;
+        else if (e.keyCode >= 48 && e.keyCode <= 57) key = e.keyCode;
+        else if (key === undefined && e.key.length === 1) key = e.key.toLowerCase().charCodeAt(0);
+        if (key === undefined) return;
+        var state = (e.shiftKey ? 0x10000 : 0) | (e.ctrlKey ? 0x40000 : 0)
+            | (e.altKey ? 0x80000 : 0) | (e.metaKey ? 0x400000 : 0);
+        var pass = accel.host
+            ? (state & 0x4c0000) !== 0 || (key >= 0xffbe && key < 0xffe0)
+            : accel.keys.indexOf(key + ':' + state) !== -1;
+        if (pass) {
+            e.preventDefault();
+            e.stopPropagation();
+            window.__fltk_webview_key(e.keyCode);
+        }
+    }, true);
+})()"#;
+
+#[cfg(target_os = "windows")]
+fn js_config(mode: &AcceleratorMode) -> String {
+    match mode {
+        AcceleratorMode::Default => "window.__fltk_webview_accel = null;".to_string(),
+        AcceleratorMode::PassToHost => {
+            "window.__fltk_webview_accel = { host: true, keys: [] };".to_string()
+        }
+        AcceleratorMode::Custom(keys) => {
+            let mods = Shortcut::Shift | Shortcut::Ctrl | Shortcut::Alt | Shortcut::Meta;
+            let keys: Vec<String> = keys
+                .iter()
+                .map(|k| format!("'{}:{}'", k.key().bits(), k.bits() & mods.bits()))
+                .collect();
+            format!(
+                "window.__fltk_webview_accel = {{ host: false, keys: [{}] }};",
+                keys.join(", ")
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn passes_matching_shortcuts() {
+        let none = Shortcut::None;
+        assert!(!AcceleratorMode::Default.passes(Key::F5, none));
+        let host = AcceleratorMode::PassToHost;
+        assert!(host.passes(Key::F5, none));
+        assert!(host.passes(Key::from_char('r'), Shortcut::Ctrl));
+        assert!(!host.passes(Key::from_char('r'), none));
+        assert!(!host.passes(Key::from_char('r'), Shortcut::Shift));
+        let custom = AcceleratorMode::Custom([Shortcut::Ctrl | 'p'].into_iter().collect());
+        assert!(custom.passes(Key::from_char('p'), Shortcut::Ctrl));
+        assert!(!custom.passes(Key::from_char('r'), Shortcut::Ctrl));
+        assert!(!custom.passes(Key::F5, none));
+    }
+
+    #[test]
+    #[cfg(target_os = "windows")]
+    fn configures_the_page() {
+        assert_eq!(
+            js_config(&AcceleratorMode::Default),
+            "window.__fltk_webview_accel = null;"
+        );
+        let custom = AcceleratorMode::Custom([Shortcut::Ctrl | 'p'].into_iter().collect());
+        assert_eq!(
+            js_config(&custom),
+            "window.__fltk_webview_accel = { host: false, keys: ['112:262144'] };"
+        );
+    }
+}
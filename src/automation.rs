@@ -0,0 +1,141 @@
+use crate::{utils, Webview};
+
+/// Defines `window.__fltk_webview_input`, which dispatches the events of user input
+const INPUT_JS: &str = r#"(function() {
+    if (window.__fltk_webview_input) return;
+    var codes = {
+        Backspace: 8, Tab: 9, Enter: 13, Escape: 27, ' ': 32, PageUp: 33, PageDown: 34,
+        End: 35, Home: 36, ArrowLeft: 37, ArrowUp: 38, ArrowRight: 39, ArrowDown: 40, Delete: 46
+    };
+    var keyCode = function(key) {
+        return codes[key] || (key.length === 1 ? key.toUpperCase().charCodeAt(0) : 0);
+    };
+    var key = function(el, type, k) {
+        var e = new KeyboardEvent(type, { key: k, bubbles: true, cancelable: true, view: window });
+        var code = type === 'keypress' ? k.charCodeAt(0) : keyCode(k);
+        Object.defineProperty(e, 'keyCode', { get: function() { return code; } });
+        Object.defineProperty(e, 'which', { get: function() { return code; } });
+        return el.dispatchEvent(e);
+    };
+    var isField = function(el) {
+        return (el.tagName === 'INPUT' || el.tagName === 'TEXTAREA') && !el.readOnly && !el.disabled;
+    };
+    // Edits the focused field like the user would, unless its keydown was canceled
+    var edit = function(el, k) {
+        var type, data = null;
+        if (k.length === 1) {
+            type = 'insertText';
+            data = k;
+        } else if (k === 'Backspace' || k === 'Delete') {
+            type = k === 'Backspace' ? 'deleteContentBackward' : 'deleteContentForward';
+        } else if (k === 'Enter' && el.tagName === 'TEXTAREA') {
+            type = 'insertLineBreak';
+            data = '\n';
+        } else {
+            return;
+        }
+        if (isField(el)) {
+            var start = el.selectionStart, end = el.selectionEnd;
+            if (start === null || start === undefined) {
+                // Fields like number inputs don't expose their selection
+                start = end = el.value.length;
+            }
+            if (data === null && start === end) {
+                if (type === 'deleteContentBackward' && start > 0) start--;
+                else if (type === 'deleteContentForward') end++;
+            }
+            el.value = el.value.slice(0, start) + (data || '') + el.value.slice(end);
+            var caret = start + (data || '').length;
+            try { el.setSelectionRange(caret, caret); } catch (e) {}
+        } else if (el.isContentEditable) {
+            if (data !== null) document.execCommand('insertText', false, data);
+            else document.execCommand(type === 'deleteContentBackward' ? 'delete' : 'forwardDelete');
+            return;
+        } else {
+            return;
+        }
+        el.dispatchEvent(new InputEvent('input', { inputType: type, data: data, bubbles: true }));
+    };
+    var press = function(el, k) {
+        if (!key(el, 'keydown', k)) return;
+        if (k.length === 1 || k === 'Enter') key(el, 'keypress', k === 'Enter' ? '\r' : k);
+        edit(el, k);
+        key(el, 'keyup', k);
+    };
+    var find = function(selector) {
+        var el = document.querySelector(selector);
+        if (!el) throw new Error('no element matches ' + selector);
+        return el;
+    };
+    window.__fltk_webview_input = {
+        click: function(selector) {
+            var el = find(selector);
+            el.scrollIntoView({ block: 'center', inline: 'center' });
+            var r = el.getBoundingClientRect();
+            var init = {
+                bubbles: true, cancelable: true, view: window, button: 0, detail: 1,
+                clientX: r.left + r.width / 2, clientY: r.top + r.height / 2
+            };
+            var Pointer = window.PointerEvent || MouseEvent;
+            el.dispatchEvent(new Pointer('pointerdown', init));
+            el.dispatchEvent(new MouseEvent('mousedown', init));
+            if (el.focus) el.focus();
+            el.dispatchEvent(new Pointer('pointerup', init));
+            el.dispatchEvent(new MouseEvent('mouseup', init));
+            el.dispatchEvent(new MouseEvent('click', init));
+        },
+        type: function(selector, text) {
+            var el = find(selector);
+            el.focus();
+            if (isField(el)) {
+                try { el.setSelectionRange(el.value.length, el.value.length); } catch (e) {}
+            }
+            Array.from(text).forEach(function(c) { press(el, c === '\n' ? 'Enter' : c); });
+            if (isField(el)) el.dispatchEvent(new Event('change', { bubbles: true }));
+        },
+        key: function(k) {
+            press(document.activeElement || document.body, k);
+        }
+    };
+})()"#;
+
+/// Simulated user input, e.g. for end-to-end tests driving the page, through untrusted events
+pub trait AutomationExt {
+    /// Click the first element matching the CSS `selector`, after scrolling it into view,
+    /// dispatching the pointer and mouse events of a left click at its center, and focusing it
+    fn click(&mut self, selector: &str);
+    /// Focus the first element matching the CSS `selector`, and type `text` at its end, one character at a time,
+    /// dispatching the keyboard and `input` events of each one, then a `change` event.
+    /// Newlines are typed as the Enter key
+    fn type_text(&mut self, selector: &str, text: &str);
+    /// Press and release `key`, a `KeyboardEvent.key` value such as `Enter`, `Escape`, `ArrowDown` or `a`,
+    /// on the focused element
+    fn dispatch_key(&mut self, key: &str);
+}
+
+impl AutomationExt for Webview {
+    fn click(&mut self, selector: &str) {
+        self.eval(&format!(
+            "{};\nwindow.__fltk_webview_input.click({});",
+            INPUT_JS,
+            utils::quote(selector)
+        ));
+    }
+
+    fn type_text(&mut self, selector: &str, text: &str) {
+        self.eval(&format!(
+            "{};\nwindow.__fltk_webview_input.type({}, {});",
+            INPUT_JS,
+            utils::quote(selector),
+            utils::quote(text)
+        ));
+    }
+
+    fn dispatch_key(&mut self, key: &str) {
+        self.eval(&format!(
+            "{};\nwindow.__fltk_webview_input.key({});",
+            INPUT_JS,
+            utils::quote(key)
+        ));
+    }
+}
@@ -0,0 +1,85 @@
+use crate::{eval, Webview, WebviewError};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+};
+
+struct Shared<T> {
+    value: Option<T>,
+    waker: Option<Waker>,
+}
+
+/// Completes once a value is sent from the main thread
+struct Receiver<T>(Arc<Mutex<Shared<T>>>);
+
+struct Sender<T>(Arc<Mutex<Shared<T>>>);
+
+fn oneshot<T>() -> (Sender<T>, Receiver<T>) {
+    let shared = Arc::new(Mutex::new(Shared {
+        value: None,
+        waker: None,
+    }));
+    (Sender(shared.clone()), Receiver(shared))
+}
+
+impl<T> Sender<T> {
+    fn send(self, value: T) {
+        let mut shared = self.0.lock().unwrap();
+        shared.value = Some(value);
+        if let Some(waker) = shared.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl<T> Future for Receiver<T> {
+    type Output = T;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let mut shared = self.0.lock().unwrap();
+        match shared.value.take() {
+            Some(value) => Poll::Ready(value),
+            None => {
+                shared.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Future based methods, requires the `async` feature.
+/// The futures are completed from the fltk event loop, which has to keep running
+/// for them to make progress
+pub trait AsyncExt {
+    /// Evaluate JavaScript code, resolving to its JSON-encoded result.
+    /// Promises are awaited, and thrown errors resolve to `WebviewError::Js`
+    fn eval_future(&mut self, js: &str) -> impl Future<Output = Result<String, WebviewError>>;
+    /// Bind `name`, resolving to the `(seq, req)` of its next invocation, after which it's unbound.
+    /// `name` must not already be bound, and the call should be answered using `return_`
+    fn next_call(&mut self, name: &str) -> impl Future<Output = (String, String)>;
+}
+
+impl AsyncExt for Webview {
+    fn eval_future(&mut self, js: &str) -> impl Future<Output = Result<String, WebviewError>> {
+        let (tx, rx) = oneshot();
+        eval::eval_with(self, js, move |res| tx.send(res.map_err(WebviewError::Js)));
+        rx
+    }
+
+    fn next_call(&mut self, name: &str) -> impl Future<Output = (String, String)> {
+        let (tx, rx) = oneshot();
+        let mut tx = Some(tx);
+        let mut wv = self.clone();
+        let name = name.to_string();
+        self.bind(&name.clone(), move |seq, req| {
+            if let Some(tx) = tx.take() {
+                tx.send((seq.to_string(), req.to_string()));
+                // Can't unbind while the binding is being called
+                let name = name.clone();
+                wv.dispatch(move |w| w.unbind(&name));
+            }
+        });
+        rx
+    }
+}
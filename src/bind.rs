@@ -0,0 +1,310 @@
+use crate::{eval, state, utils, Webview, WebviewError};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc, Mutex, OnceLock,
+    },
+    time::{Duration, Instant},
+};
+
+/// Responds to a single call of a function bound through `bind_with_responder`.
+/// It can be kept to respond later, from the main thread.
+/// Dropping it without responding rejects the call, so the page's promise never stays pending
+pub struct Responder {
+    wv: Webview,
+    seq: Option<String>,
+}
+
+impl Responder {
+    /// Resolve the call's promise with `json`, a JSON value
+    pub fn resolve(mut self, json: &str) {
+        if let Some(seq) = self.seq.take() {
+            self.wv.return_(&seq, 0, json);
+        }
+    }
+
+    /// Reject the call's promise, `.catch` receives `msg` as a string
+    pub fn reject(mut self, msg: &str) {
+        if let Some(seq) = self.seq.take() {
+            self.wv.return_(&seq, 1, &utils::quote(msg));
+        }
+    }
+}
+
+impl Drop for Responder {
+    fn drop(&mut self) {
+        if let Some(seq) = self.seq.take() {
+            self.wv
+                .return_(&seq, 1, &utils::quote("the bound function didn't respond"));
+        }
+    }
+}
+
+impl std::fmt::Debug for Responder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Responder").field("seq", &self.seq).finish()
+    }
+}
+
+/// Bindings with error reporting
+pub trait BindExt {
+    /// Bind `f` under `name` as a global JavaScript function returning a promise,
+    /// resolved with the JSON value of `Ok`, rejected with the message of `Err` or when `f` panics
+    fn bind_result<F: FnMut(&str) -> Result<String, String> + 'static>(&mut self, name: &str, f: F);
+    /// Bind `f` under `name` as a global JavaScript function returning a promise,
+    /// passing `f` a `Responder` settling the call and the JSON array of its arguments.
+    /// Unlike the sequence id passed by `bind`, the responder can only respond once,
+    /// and rejects the call when dropped without responding, including when `f` panics
+    fn bind_with_responder<F: FnMut(Responder, &str) + 'static>(&mut self, name: &str, f: F);
+    /// Like `bind_result`, calling `f` on a worker thread shared by the webviews,
+    /// so that blocking work, such as file or network I/O, doesn't stall the event loop
+    fn bind_async<F: Fn(String) -> Result<String, String> + Send + Sync + 'static>(
+        &mut self,
+        name: &str,
+        f: F,
+    );
+    /// Like `Webview::bind`, also tracking the function for `bindings` and `clear_bindings`.
+    /// Panics in `f`, and calls made while it runs, reject the call
+    fn bind_tracked<F: FnMut(&str, &str) + 'static>(&mut self, name: &str, f: F);
+    /// Like `Webview::dispatch`, catching a panic in `f`, which is reported by the panic hook
+    fn dispatch_guarded<F: FnOnce(Webview) + Send + 'static>(&mut self, f: F);
+    /// The names of the tracked bound functions, sorted.
+    /// Functions bound through `Webview::bind` aren't tracked, nor the ones fltk-webview binds for itself
+    fn bindings(&self) -> Vec<String>;
+    /// Unbind all the tracked functions, dropping their closures,
+    /// or after their current call if they're running
+    fn clear_bindings(&mut self);
+    /// Bind `name` until the page calls it once, running the fltk event loop meanwhile,
+    /// and return the JSON array of its arguments. The call's promise resolves to `null`.
+    /// Returns `WebviewError::Timeout` if the page doesn't call it within `timeout`.
+    /// `name` is unbound in both cases, replacing an existing binding of the same name
+    fn await_call(&mut self, name: &str, timeout: Duration) -> Result<String, WebviewError>;
+    /// Run the fltk event loop until the dispatched closures, evaluations and binding responses queued before the call have run,
+    /// or return `WebviewError::Timeout` after `timeout`. Call it before closing the webview, so the last evaluations aren't lost
+    fn flush(&mut self, timeout: Duration) -> Result<(), WebviewError>;
+    /// Run `f` on the main thread, from any thread, like `dispatch` without needing a `&mut Webview`
+    /// or a running webview. On Linux, `f` is scheduled on GTK's main context through `g_idle_add`,
+    /// so it can call GTK safely, elsewhere through fltk's `app::awake_callback`.
+    /// A panic in `f` is caught
+    fn run_on_main<F: FnOnce() + Send + 'static>(&self, f: F);
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+extern "C" {
+    fn g_idle_add(
+        f: extern "C" fn(*mut std::os::raw::c_void) -> i32,
+        data: *mut std::os::raw::c_void,
+    ) -> u32;
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+extern "C" fn idle_cb<F: FnOnce()>(data: *mut std::os::raw::c_void) -> i32 {
+    let f = unsafe { Box::from_raw(data as *mut F) };
+    utils::guard(f);
+    // G_SOURCE_REMOVE
+    0
+}
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// Run `job` on the worker threads of `bind_async`, which are started on first use
+fn run_on_worker(job: Job) {
+    static JOBS: OnceLock<Mutex<mpsc::Sender<Job>>> = OnceLock::new();
+    let jobs = JOBS.get_or_init(|| {
+        let (tx, rx) = mpsc::channel::<Job>();
+        let rx = Arc::new(Mutex::new(rx));
+        let workers = std::thread::available_parallelism().map_or(4, |n| n.get().clamp(2, 8));
+        for i in 0..workers {
+            let rx = rx.clone();
+            std::thread::Builder::new()
+                .name(format!("fltk-webview-worker-{}", i))
+                .spawn(move || loop {
+                    let job = match rx.lock().unwrap().recv() {
+                        Ok(job) => job,
+                        Err(_) => return,
+                    };
+                    job();
+                })
+                .expect("couldn't start a worker thread");
+        }
+        Mutex::new(tx)
+    });
+    let _ = jobs.lock().unwrap().send(job);
+}
+
+impl BindExt for Webview {
+    fn bind_result<F: FnMut(&str) -> Result<String, String> + 'static>(
+        &mut self,
+        name: &str,
+        mut f: F,
+    ) {
+        let wv = self.clone();
+        self.bind_tracked(name, move |seq, req| match utils::guard(|| f(req)) {
+            Some(Ok(v)) => wv.return_(seq, 0, &v),
+            Some(Err(e)) => wv.return_(seq, 1, &utils::quote(&e)),
+            None => wv.return_(seq, 1, &utils::quote("the bound function panicked")),
+        });
+    }
+
+    fn bind_with_responder<F: FnMut(Responder, &str) + 'static>(&mut self, name: &str, mut f: F) {
+        let wv = self.clone();
+        self.bind_tracked(name, move |seq, req| {
+            let responder = Responder {
+                wv: wv.clone(),
+                seq: Some(seq.to_string()),
+            };
+            utils::guard(|| f(responder, req));
+        });
+    }
+
+    fn bind_async<F: Fn(String) -> Result<String, String> + Send + Sync + 'static>(
+        &mut self,
+        name: &str,
+        f: F,
+    ) {
+        let f = Arc::new(f);
+        let key = self.get_window() as usize;
+        let wv = self.clone();
+        self.bind_tracked(name, move |seq, req| {
+            let f = f.clone();
+            let wv = wv.clone();
+            let seq = seq.to_string();
+            let req = req.to_string();
+            run_on_worker(Box::new(move || {
+                let res = utils::guard(|| f(req))
+                    .unwrap_or_else(|| Err("the bound function panicked".to_string()));
+                wv.run_on_main({
+                    let wv = wv.clone();
+                    move || {
+                        if !state::is_running(key) {
+                            return;
+                        }
+                        match res {
+                            Ok(v) => wv.return_(&seq, 0, &v),
+                            Err(e) => wv.return_(&seq, 1, &utils::quote(&e)),
+                        }
+                    }
+                });
+            }));
+        });
+    }
+
+    fn bind_tracked<F: FnMut(&str, &str) + 'static>(&mut self, name: &str, f: F) {
+        let first = state::with(self, |s| {
+            s.bindings
+                .insert(name.to_string(), Rc::new(RefCell::new(f)))
+                .is_none()
+        });
+        if !first {
+            return;
+        }
+        // The closure passed to webview is never freed, so it only looks the function up
+        let key = self.get_window() as usize;
+        let owned = name.to_string();
+        let wv = self.clone();
+        self.bind(name, move |seq, req| {
+            let f = match state::with_key(key, |s| s.bindings.get(&owned).cloned()) {
+                Some(f) => f,
+                None => return,
+            };
+            let error = match f.try_borrow_mut() {
+                // Called again by the page while it runs, e.g. while it runs the event loop
+                Err(_) => Some("the bound function is already running"),
+                Ok(mut f) => utils::guard(|| (*f)(seq, req))
+                    .is_none()
+                    .then_some("the bound function panicked"),
+            };
+            if let Some(error) = error {
+                wv.return_(seq, 1, &utils::quote(error));
+            }
+        });
+    }
+
+    fn dispatch_guarded<F: FnOnce(Webview) + Send + 'static>(&mut self, f: F) {
+        self.dispatch(move |wv| {
+            // The panic hook has reported it, the other dispatched closures still run
+            let _ = utils::guard(|| f(wv));
+        });
+    }
+
+    fn bindings(&self) -> Vec<String> {
+        let mut names: Vec<String> = state::with(self, |s| s.bindings.keys().cloned().collect());
+        names.sort();
+        names
+    }
+
+    fn clear_bindings(&mut self) {
+        let bindings = state::with(self, |s| std::mem::take(&mut s.bindings));
+        for name in bindings.keys() {
+            self.unbind(name);
+        }
+    }
+
+    fn await_call(&mut self, name: &str, timeout: Duration) -> Result<String, WebviewError> {
+        let call: Rc<RefCell<Option<String>>> = Rc::default();
+        self.bind(name, {
+            let call = call.clone();
+            let wv = self.clone();
+            move |seq, req| {
+                wv.return_(seq, 0, "null");
+                call.borrow_mut().get_or_insert_with(|| req.to_string());
+            }
+        });
+        let end = Instant::now() + timeout;
+        let res = loop {
+            if let Some(req) = call.borrow_mut().take() {
+                break Ok(req);
+            }
+            if Instant::now() >= end {
+                break Err(WebviewError::Timeout);
+            }
+            let _ = fltk::app::wait_for(0.01);
+        };
+        self.unbind(name);
+        res
+    }
+
+    fn flush(&mut self, timeout: Duration) -> Result<(), WebviewError> {
+        // Markers queued after the pending work run once it did
+        let dispatched = Arc::new(AtomicBool::new(false));
+        self.dispatch({
+            let dispatched = dispatched.clone();
+            move |_| dispatched.store(true, Ordering::SeqCst)
+        });
+        let evaluated: Rc<Cell<bool>> = Rc::default();
+        eval::eval_with(self, "null", {
+            let evaluated = evaluated.clone();
+            move |_| evaluated.set(true)
+        });
+        let end = Instant::now() + timeout;
+        while !(dispatched.load(Ordering::SeqCst) && evaluated.get()) {
+            if Instant::now() >= end {
+                return Err(WebviewError::Timeout);
+            }
+            let _ = fltk::app::wait_for(0.01);
+        }
+        Ok(())
+    }
+
+    fn run_on_main<F: FnOnce() + Send + 'static>(&self, f: F) {
+        #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+        {
+            unsafe {
+                g_idle_add(idle_cb::<F>, Box::into_raw(Box::new(f)) as _);
+            }
+            // GTK's main loop may not be pumped since the last webview closed
+            fltk::app::awake_callback(crate::start_pump);
+        }
+        #[cfg(any(target_os = "macos", target_os = "windows"))]
+        {
+            let mut f = Some(f);
+            fltk::app::awake_callback(move || {
+                if let Some(f) = f.take() {
+                    f();
+                }
+            });
+        }
+    }
+}
@@ -0,0 +1,260 @@
+use crate::{NativeHandle, Webview, WebviewError};
+use fltk::{enums, group, prelude::*, window};
+use std::{
+    path::PathBuf,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// The temporary data folder of ephemeral webviews, deleted once the last webview storing in it is gone
+pub(crate) struct EphemeralDir(pub(crate) PathBuf);
+
+impl Drop for EphemeralDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// When the web engine renders with the GPU
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum HardwareAcceleration {
+    /// Let the backend decide
+    #[default]
+    OnDemand,
+    /// Always render with the GPU
+    Always,
+    /// Never render with the GPU. Helps on virtual machines, remote desktops and headless
+    /// environments, where GPU compositing can leave the webview blank or corrupted
+    Never,
+}
+
+/// How WebKitGTK distributes the webviews between web processes
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ProcessModel {
+    /// A single web process serves all the webviews
+    SharedSecondaryProcess,
+    /// Webviews get their own web process, up to `max` processes, 0 meaning no limit
+    MultipleSecondaryProcesses { max: u32 },
+}
+
+/// The first page loaded by a webview
+#[derive(Debug, Clone)]
+pub(crate) enum InitialContent {
+    Url(String),
+    Html(String),
+}
+
+/// Configures a webview before its creation
+#[derive(Debug, Clone)]
+pub struct WebviewBuilder {
+    pub(crate) debug: bool,
+    pub(crate) background: enums::Color,
+    pub(crate) storage: Option<PathBuf>,
+    /// Additional command line arguments of WebView2's browser process
+    pub(crate) browser_args: Vec<String>,
+    pub(crate) hardware_acceleration: HardwareAcceleration,
+    pub(crate) sandbox: bool,
+    pub(crate) process_model: Option<ProcessModel>,
+    pub(crate) ephemeral: bool,
+    pub(crate) ignore_tls_errors: bool,
+    pub(crate) webgl: bool,
+    pub(crate) safe_browsing: bool,
+    /// Scripts installed before the first page loads
+    pub(crate) scripts: Vec<String>,
+    pub(crate) content: Option<InitialContent>,
+}
+
+impl Default for WebviewBuilder {
+    fn default() -> Self {
+        Self {
+            debug: false,
+            background: enums::Color::White,
+            storage: None,
+            browser_args: vec![],
+            hardware_acceleration: HardwareAcceleration::OnDemand,
+            sandbox: false,
+            process_model: None,
+            ephemeral: false,
+            ignore_tls_errors: false,
+            webgl: true,
+            safe_browsing: true,
+            scripts: vec![],
+            content: None,
+        }
+    }
+}
+
+impl WebviewBuilder {
+    /// Create a builder with the default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable the developer tools
+    pub fn debug(mut self, debug: bool) -> Self {
+        self.debug = debug;
+        self
+    }
+
+    /// Set the color of the embedding window, and of the page background before the first paint.
+    /// Defaults to white
+    pub fn background(mut self, color: enums::Color) -> Self {
+        self.background = color;
+        self
+    }
+
+    /// Store cookies, localStorage and other website data under `dir`, so they persist across runs.
+    /// Without it, website data persists in a per-application location chosen by the backend
+    pub fn persistent_storage(mut self, dir: PathBuf) -> Self {
+        self.storage = Some(dir);
+        self
+    }
+
+    /// Bound the disk cache to `bytes`
+    pub fn disk_cache_limit(mut self, bytes: u64) -> Self {
+        self.browser_args
+            .push(format!("--disk-cache-size={}", bytes));
+        self
+    }
+
+    /// Set when the web engine renders with the GPU, before anything is rendered
+    pub fn hardware_acceleration(mut self, policy: HardwareAcceleration) -> Self {
+        self.hardware_acceleration = policy;
+        self
+    }
+
+    /// Confine WebKitGTK's web processes to a bubblewrap sandbox, which needs `bwrap` to be installed.
+    /// The sandbox is shared by the process's webviews, so it should be requested for the first webview
+    pub fn sandbox(mut self, enabled: bool) -> Self {
+        self.sandbox = enabled;
+        self
+    }
+
+    /// Choose how many web processes WebKitGTK spawns, to limit its memory use on constrained devices.
+    /// Like `sandbox`, the model is shared by the process's webviews, so it should be set for the first webview
+    pub fn process_model(mut self, model: ProcessModel) -> Self {
+        self.process_model = Some(model);
+        self
+    }
+
+    /// Keep cookies, localStorage, caches and other website data only for the life of the webview,
+    /// in a temporary folder deleted when the webview is closed through `EmbedExt::close`
+    pub fn ephemeral(mut self, ephemeral: bool) -> Self {
+        self.ephemeral = ephemeral;
+        self
+    }
+
+    /// Accept invalid server certificates, such as self-signed ones, without asking.
+    /// **This disables TLS's protection for every site**, prefer `NavigationExt::set_tls_error_handler`
+    pub fn ignore_tls_errors(mut self, ignore: bool) -> Self {
+        self.ignore_tls_errors = ignore;
+        self
+    }
+
+    /// Enable or disable WebGL for the whole life of the webview, enabled by default where the GPU supports it
+    pub fn webgl(mut self, enabled: bool) -> Self {
+        self.webgl = enabled;
+        self
+    }
+
+    /// Enable or disable the warnings about phishing and malware sites, enabled by default,
+    /// e.g. for applications only loading intranet hosts which the warnings flag
+    pub fn safe_browsing(mut self, enabled: bool) -> Self {
+        self.safe_browsing = enabled;
+        self
+    }
+
+    /// Inject JavaScript code at the initialization of every page, like `Webview::init`,
+    /// including the first one set through `url` or `html`
+    pub fn init(mut self, js: &str) -> Self {
+        self.scripts.push(js.to_string());
+        self
+    }
+
+    /// Start loading `url` at creation. Replaces the content set through `html`
+    pub fn url(mut self, url: &str) -> Self {
+        self.content = Some(InitialContent::Url(url.to_string()));
+        self
+    }
+
+    /// Start loading `html` at creation. Replaces the URL set through `url`
+    pub fn html(mut self, html: &str) -> Self {
+        self.content = Some(InitialContent::Html(html.to_string()));
+        self
+    }
+
+    /// The options with the temporary data folder of ephemeral webviews, see `EphemeralDir`
+    pub(crate) fn resolve(&self) -> WebviewBuilder {
+        let mut opts = self.clone();
+        if opts.ephemeral {
+            static COUNT: AtomicUsize = AtomicUsize::new(0);
+            let n = COUNT.fetch_add(1, Ordering::Relaxed);
+            opts.storage = Some(std::env::temp_dir().join(format!(
+                "fltk-webview-{}-{}",
+                std::process::id(),
+                n
+            )));
+        }
+        opts
+    }
+
+    /// Create the Webview within an embedded fltk window.
+    /// Requires that the window is shown, and waits for it to be mapped.
+    /// Panics if it isn't shown within 2 seconds, or on Wayland, see `try_build`
+    pub fn build(self, win: &mut window::Window) -> Webview {
+        self.try_build(win)
+            .unwrap_or_else(|e| panic!("couldn't create the webview: {} ({:?})", e, e))
+    }
+
+    /// Create the Webview within an embedded fltk window, running the event loop until the window is mapped.
+    /// Returns `WebviewError::WindowNotShown` if it isn't shown within 2 seconds.
+    /// On Linux, webviews are embedded through X11, so `WebviewError::UnsupportedDisplay` is returned
+    /// when fltk runs on Wayland; call `app::disable_wayland` before creating the `App` to use XWayland
+    pub fn try_build(self, win: &mut window::Window) -> Result<Webview, WebviewError> {
+        crate::create(&self, win)
+    }
+
+    /// Create the Webview within a borderless window placed outside of the screens,
+    /// for tests and rendering without showing anything.
+    /// A display is still needed, on Linux CI this can be provided by `Xvfb` (e.g. `xvfb-run cargo test`).
+    /// Returns `WebviewError::Unsupported` if the window can't be shown
+    pub fn build_offscreen(self, width: i32, height: i32) -> Result<Webview, WebviewError> {
+        let mut win = window::Window::new(-width - 10000, -height - 10000, width, height, None);
+        win.end();
+        win.set_border(false);
+        // Keeps window managers from moving it back onto a screen
+        win.set_override();
+        win.show();
+        if !win.shown() {
+            return Err(WebviewError::Unsupported);
+        }
+        crate::create(&self, &mut win)
+    }
+
+    /// Create the Webview over the area of `group`, within a shown window, following the group as it's resized or moved.
+    /// Replaces the group's resize callback. Returns `WebviewError::WindowNotShown` if the group isn't shown within 2 seconds
+    pub fn build_in_group(self, group: &mut group::Group) -> Result<Webview, WebviewError> {
+        let mut win = window::Window::new(group.x(), group.y(), group.w(), group.h(), None);
+        win.end();
+        group.add(&win);
+        group.resizable(&win);
+        group.resize_callback({
+            let mut win = win.clone();
+            move |_, x, y, w, h| win.resize(x, y, w, h)
+        });
+        win.show();
+        crate::create(&self, &mut win)
+    }
+
+    /// Create the Webview within a native window which isn't managed by fltk, e.g. one owned by another toolkit,
+    /// with a size of `w`x`h` backend pixels. Handles of other platforms, or null ones, return `WebviewError::Unsupported`.
+    /// The parent must outlive the webview, and its owner resizes the webview with `Webview::set_size`.
+    /// On Linux, the GTK main loop must keep running
+    pub fn build_in_handle(
+        self,
+        parent: NativeHandle,
+        w: i32,
+        h: i32,
+    ) -> Result<Webview, WebviewError> {
+        crate::create_in_handle(&self, parent, w, h)
+    }
+}
@@ -0,0 +1,328 @@
+use crate::WebviewError;
+use std::{borrow::Cow, collections::HashMap};
+
+/// A file within a bundle
+struct Entry {
+    offset: usize,
+    len: usize,
+    /// Whether the file is deflated, as zip entries may be
+    deflated: bool,
+}
+
+/// The files of an in-memory zip or tar archive, keyed by path.
+/// Zip entries are inflated when requested, compressed tar archives are decompressed once
+pub(crate) struct Bundle {
+    data: Vec<u8>,
+    entries: HashMap<String, Entry>,
+}
+
+fn invalid(msg: &str) -> WebviewError {
+    WebviewError::InvalidArgument(format!("invalid bundle: {}", msg))
+}
+
+fn u16_at(data: &[u8], pos: usize) -> Option<usize> {
+    let b = data.get(pos..pos + 2)?;
+    Some(u16::from_le_bytes([b[0], b[1]]) as usize)
+}
+
+fn u32_at(data: &[u8], pos: usize) -> Option<usize> {
+    let b = data.get(pos..pos + 4)?;
+    Some(u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as usize)
+}
+
+/// Strip the leading `./` and `/` of archive paths
+fn normalize(name: &str) -> String {
+    name.trim_start_matches("./")
+        .trim_start_matches('/')
+        .to_string()
+}
+
+impl Bundle {
+    /// Read a zip archive, or a tar archive, optionally compressed with gzip or brotli
+    pub(crate) fn parse(data: &[u8]) -> Result<Bundle, WebviewError> {
+        if data.starts_with(b"PK\x03\x04") {
+            Self::parse_zip(data.to_vec())
+        } else if data.starts_with(&[0x1f, 0x8b]) {
+            Self::parse_tar(gunzip(data)?)
+        } else if is_tar(data) {
+            Self::parse_tar(data.to_vec())
+        } else {
+            // Brotli streams have no magic number
+            let mut out = Vec::new();
+            brotli_decompressor::BrotliDecompress(&mut &data[..], &mut out)
+                .map_err(|_| invalid("expected a zip or tar archive"))?;
+            if !is_tar(&out) {
+                return Err(invalid("expected a tar archive in the brotli stream"));
+            }
+            Self::parse_tar(out)
+        }
+    }
+
+    fn parse_zip(data: Vec<u8>) -> Result<Bundle, WebviewError> {
+        // The end of central directory record is followed by a comment of up to 64KiB
+        let min = data.len().saturating_sub(22 + 0xffff);
+        let eocd = (min..data.len().saturating_sub(21))
+            .rev()
+            .find(|&i| data[i..].starts_with(b"PK\x05\x06"))
+            .ok_or_else(|| invalid("missing zip directory"))?;
+        let count = u16_at(&data, eocd + 10).unwrap_or_default();
+        let mut pos = u32_at(&data, eocd + 16).unwrap_or_default();
+        let mut entries = HashMap::new();
+        for _ in 0..count {
+            if !data
+                .get(pos..)
+                .map_or(false, |d| d.starts_with(b"PK\x01\x02"))
+            {
+                return Err(invalid("corrupt zip directory"));
+            }
+            let field = |off| u16_at(&data, pos + off).ok_or_else(|| invalid("truncated zip"));
+            let method = field(10)?;
+            let (name_len, extra_len, comment_len) = (field(28)?, field(30)?, field(32)?);
+            let len = u32_at(&data, pos + 20).ok_or_else(|| invalid("truncated zip"))?;
+            let local = u32_at(&data, pos + 42).ok_or_else(|| invalid("truncated zip"))?;
+            let name = data
+                .get(pos + 46..pos + 46 + name_len)
+                .ok_or_else(|| invalid("truncated zip"))?;
+            let name = String::from_utf8_lossy(name).into_owned();
+            pos += 46 + name_len + extra_len + comment_len;
+            if name.ends_with('/') {
+                continue;
+            }
+            if method != 0 && method != 8 {
+                return Err(invalid(&format!("unsupported compression of {}", name)));
+            }
+            let offset = match (u16_at(&data, local + 26), u16_at(&data, local + 28)) {
+                (Some(n), Some(e)) => local + 30 + n + e,
+                _ => return Err(invalid("truncated zip")),
+            };
+            if offset + len > data.len() {
+                return Err(invalid("truncated zip"));
+            }
+            entries.insert(
+                normalize(&name),
+                Entry {
+                    offset,
+                    len,
+                    deflated: method == 8,
+                },
+            );
+        }
+        Ok(Bundle { data, entries })
+    }
+
+    fn parse_tar(data: Vec<u8>) -> Result<Bundle, WebviewError> {
+        let field = |header: &[u8], range: std::ops::Range<usize>| {
+            let bytes = &header[range];
+            let end = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+            String::from_utf8_lossy(&bytes[..end]).into_owned()
+        };
+        let mut entries = HashMap::new();
+        let mut pos = 0;
+        while pos + 512 <= data.len() {
+            let header = &data[pos..pos + 512];
+            if header.iter().all(|b| *b == 0) {
+                break;
+            }
+            let size = usize::from_str_radix(field(header, 124..136).trim(), 8)
+                .map_err(|_| invalid("corrupt tar header"))?;
+            let mut name = field(header, 0..100);
+            if &header[257..262] == b"ustar" {
+                let prefix = field(header, 345..500);
+                if !prefix.is_empty() {
+                    name = format!("{}/{}", prefix, name);
+                }
+            }
+            let offset = pos + 512;
+            if offset + size > data.len() {
+                return Err(invalid("truncated tar"));
+            }
+            // Regular files only
+            if matches!(header[156], b'0' | 0) {
+                entries.insert(
+                    normalize(&name),
+                    Entry {
+                        offset,
+                        len: size,
+                        deflated: false,
+                    },
+                );
+            }
+            pos = offset + (size + 511) / 512 * 512;
+        }
+        Ok(Bundle { data, entries })
+    }
+
+    /// The content of the file at `path`
+    pub(crate) fn get(&self, path: &str) -> Option<Cow<'_, [u8]>> {
+        let entry = self.entries.get(path)?;
+        let raw = &self.data[entry.offset..entry.offset + entry.len];
+        if entry.deflated {
+            miniz_oxide::inflate::decompress_to_vec(raw)
+                .ok()
+                .map(Cow::Owned)
+        } else {
+            Some(Cow::Borrowed(raw))
+        }
+    }
+
+    /// Whether `path` is a folder of the bundle
+    pub(crate) fn is_dir(&self, path: &str) -> bool {
+        let prefix = format!("{}/", path.trim_end_matches('/'));
+        path.is_empty() || self.entries.keys().any(|k| k.starts_with(&prefix))
+    }
+}
+
+fn is_tar(data: &[u8]) -> bool {
+    data.get(257..262) == Some(&b"ustar"[..])
+}
+
+/// Decompress a gzip stream
+fn gunzip(data: &[u8]) -> Result<Vec<u8>, WebviewError> {
+    const FHCRC: u8 = 2;
+    const FEXTRA: u8 = 4;
+    const FNAME: u8 = 8;
+    const FCOMMENT: u8 = 16;
+    let flags = *data.get(3).ok_or_else(|| invalid("truncated gzip"))?;
+    if data.get(2) != Some(&8) {
+        return Err(invalid("unsupported gzip compression"));
+    }
+    let mut pos = 10;
+    if flags & FEXTRA != 0 {
+        pos += 2 + u16_at(data, pos).ok_or_else(|| invalid("truncated gzip"))?;
+    }
+    for flag in [FNAME, FCOMMENT] {
+        if flags & flag != 0 {
+            let end = data
+                .get(pos..)
+                .and_then(|d| d.iter().position(|b| *b == 0))
+                .ok_or_else(|| invalid("truncated gzip"))?;
+            pos += end + 1;
+        }
+    }
+    if flags & FHCRC != 0 {
+        pos += 2;
+    }
+    let stream = data.get(pos..).ok_or_else(|| invalid("truncated gzip"))?;
+    miniz_oxide::inflate::decompress_to_vec(stream).map_err(|_| invalid("corrupt gzip"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A ustar archive of `files`
+    fn tar(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut out = vec![];
+        for (name, content) in files {
+            let mut header = [0u8; 512];
+            header[..name.len()].copy_from_slice(name.as_bytes());
+            header[100..108].copy_from_slice(b"0000644\0");
+            header[124..136].copy_from_slice(format!("{:011o}\0", content.len()).as_bytes());
+            header[156] = b'0';
+            header[257..263].copy_from_slice(b"ustar\0");
+            out.extend_from_slice(&header);
+            out.extend_from_slice(content);
+            out.resize(out.len().div_ceil(512) * 512, 0);
+        }
+        out.resize(out.len() + 1024, 0);
+        out
+    }
+
+    /// A gzip stream of `data`, with a file name, and without a valid trailer
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut out = vec![0x1f, 0x8b, 8, 8, 0, 0, 0, 0, 0, 255];
+        out.extend_from_slice(b"site.tar\0");
+        out.extend(miniz_oxide::deflate::compress_to_vec(data, 6));
+        out.extend_from_slice(&[0; 8]);
+        out
+    }
+
+    /// A zip archive of `files`, deflating the ones marked so
+    fn zip(files: &[(&str, &[u8], bool)]) -> Vec<u8> {
+        let (mut out, mut dir) = (vec![], vec![]);
+        for (name, content, deflate) in files {
+            let data = if *deflate {
+                miniz_oxide::deflate::compress_to_vec(content, 6)
+            } else {
+                content.to_vec()
+            };
+            let method: u16 = if *deflate { 8 } else { 0 };
+            let offset = out.len() as u32;
+            out.extend_from_slice(b"PK\x03\x04");
+            out.extend_from_slice(&[20, 0, 0, 0]);
+            out.extend_from_slice(&method.to_le_bytes());
+            out.extend_from_slice(&[0; 8]);
+            out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            out.extend_from_slice(&(content.len() as u32).to_le_bytes());
+            out.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            out.extend_from_slice(&[0, 0]);
+            out.extend_from_slice(name.as_bytes());
+            out.extend_from_slice(&data);
+            dir.extend_from_slice(b"PK\x01\x02");
+            dir.extend_from_slice(&[20, 0, 20, 0, 0, 0]);
+            dir.extend_from_slice(&method.to_le_bytes());
+            dir.extend_from_slice(&[0; 8]);
+            dir.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            dir.extend_from_slice(&(content.len() as u32).to_le_bytes());
+            dir.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            dir.extend_from_slice(&[0; 12]);
+            dir.extend_from_slice(&offset.to_le_bytes());
+            dir.extend_from_slice(name.as_bytes());
+        }
+        let dir_offset = out.len() as u32;
+        out.extend_from_slice(&dir);
+        out.extend_from_slice(b"PK\x05\x06");
+        out.extend_from_slice(&[0; 4]);
+        out.extend_from_slice(&(files.len() as u16).to_le_bytes());
+        out.extend_from_slice(&(files.len() as u16).to_le_bytes());
+        out.extend_from_slice(&(dir.len() as u32).to_le_bytes());
+        out.extend_from_slice(&dir_offset.to_le_bytes());
+        out.extend_from_slice(&[0, 0]);
+        out
+    }
+
+    fn check(bundle: &Bundle) {
+        assert_eq!(
+            bundle.get("index.html").as_deref(),
+            Some(&b"<p>Index</p>"[..])
+        );
+        assert_eq!(bundle.get("assets/app.js").as_deref(), Some(&b"run();"[..]));
+        assert!(bundle.get("assets").is_none());
+        assert!(bundle.is_dir("") && bundle.is_dir("assets") && bundle.is_dir("assets/"));
+        assert!(!bundle.is_dir("index.html") && !bundle.is_dir("missing"));
+    }
+
+    #[test]
+    fn reads_tar_archives() {
+        let files: &[(&str, &[u8])] = &[
+            ("./index.html", b"<p>Index</p>"),
+            ("assets/app.js", b"run();"),
+        ];
+        check(&Bundle::parse(&tar(files)).unwrap());
+        check(&Bundle::parse(&gzip(&tar(files))).unwrap());
+    }
+
+    #[test]
+    fn reads_zip_archives() {
+        let files: &[(&str, &[u8], bool)] = &[
+            ("assets/", b"", false),
+            ("index.html", b"<p>Index</p>", false),
+            ("/assets/app.js", b"run();", true),
+        ];
+        check(&Bundle::parse(&zip(files)).unwrap());
+    }
+
+    #[test]
+    fn rejects_other_data() {
+        for data in [
+            &b"plain text"[..],
+            b"PK\x03\x04truncated",
+            &[0x1f, 0x8b, 8, 0],
+        ] {
+            assert!(matches!(
+                Bundle::parse(data),
+                Err(WebviewError::InvalidArgument(_))
+            ));
+        }
+    }
+}
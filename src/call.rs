@@ -0,0 +1,57 @@
+use crate::{eval, Webview, WebviewError};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+/// Calling page functions with typed arguments and results, requires the `serde` feature
+pub trait CallExt {
+    /// Call the page function at `fn_name`, a dotted path under `window` such as `app.api.load`, spreading `args`
+    /// if it's a tuple or sequence, and pass its awaited result, or `WebviewError::Js`, to `cb` on the main thread.
+    /// Returns `WebviewError::InvalidArgument` if `fn_name` isn't a valid path, or if `args` can't be encoded
+    fn call_js<A, R, F>(&mut self, fn_name: &str, args: A, cb: F) -> Result<(), WebviewError>
+    where
+        A: Serialize,
+        R: DeserializeOwned + 'static,
+        F: FnOnce(Result<R, WebviewError>) + 'static;
+}
+
+/// Whether `path` is a dotted path of JavaScript identifiers
+fn is_identifier_path(path: &str) -> bool {
+    path.split('.').all(|part| {
+        let mut chars = part.chars();
+        matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_' || c == '$')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+    })
+}
+
+impl CallExt for Webview {
+    fn call_js<A, R, F>(&mut self, fn_name: &str, args: A, cb: F) -> Result<(), WebviewError>
+    where
+        A: Serialize,
+        R: DeserializeOwned + 'static,
+        F: FnOnce(Result<R, WebviewError>) + 'static,
+    {
+        if !is_identifier_path(fn_name) {
+            return Err(WebviewError::InvalidArgument(format!(
+                "{:?} isn't a function path",
+                fn_name
+            )));
+        }
+        let args = match serde_json::to_value(args) {
+            Ok(Value::Array(args)) => args,
+            Ok(Value::Null) => vec![],
+            Ok(arg) => vec![arg],
+            Err(e) => return Err(WebviewError::InvalidArgument(e.to_string())),
+        };
+        // JSON values are JavaScript literals, so the arguments can't inject code
+        let args = Value::Array(args).to_string();
+        let js = format!("window.{}(...{})", fn_name, args);
+        eval::eval_with(self, &js, move |res| {
+            cb(res.map_err(WebviewError::Js).and_then(|json| {
+                serde_json::from_str(&json).map_err(|e| {
+                    WebviewError::InvalidArgument(format!("unexpected result {}: {}", json, e))
+                })
+            }))
+        });
+        Ok(())
+    }
+}
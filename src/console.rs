@@ -0,0 +1,116 @@
+use crate::{events, navigation, state, utils, Webview, WebviewError};
+use std::{cell::RefCell, io::Write, path::Path, rc::Rc};
+
+/// The reserved binding receiving the console output of pages
+const CONSOLE_BINDING: &str = "__fltk_webview_console";
+
+/// Forwards the `console` calls of the page to the binding, as a level and a message,
+/// still passing them to the original methods
+const CONSOLE_JS: &str = r#"(function() {
+    if (window.__fltk_webview_console_hooked) return;
+    window.__fltk_webview_console_hooked = true;
+    var format = function(args) {
+        return Array.prototype.map.call(args, function(a) {
+            if (typeof a === 'string') return a;
+            if (a instanceof Error) return a.stack || String(a);
+            try {
+                var json = JSON.stringify(a);
+                return json === undefined ? String(a) : json;
+            } catch (e) {
+                return String(a);
+            }
+        }).join(' ');
+    };
+    ['log', 'debug', 'info', 'warn', 'error'].forEach(function(level) {
+        var original = console[level];
+        console[level] = function() {
+            window.__fltk_webview_console(level, format(arguments));
+            return original.apply(console, arguments);
+        };
+    });
+})()"#;
+
+/// The severity of a console message
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ConsoleLevel {
+    /// `console.log`
+    Log,
+    /// `console.debug`
+    Debug,
+    /// `console.info`
+    Info,
+    /// `console.warn`
+    Warn,
+    /// `console.error`
+    Error,
+}
+
+/// Access to the console output of pages
+pub trait ConsoleExt {
+    /// Set the handler receiving what pages print through `console.log`, `debug`, `info`, `warn` and `error`,
+    /// called on the main thread. Messages logged by workers, or before init scripts run, aren't received
+    fn on_console_message<F: FnMut(ConsoleLevel, &str) + 'static>(&mut self, f: F);
+    /// Append the console messages and navigations of the webview to the file at `path`, one line per entry
+    /// starting with its UTC time, until the webview is closed. Failing to open the file returns `WebviewError::Io`
+    fn set_log_file(&mut self, path: &Path) -> Result<(), WebviewError>;
+}
+
+/// Append `line` to the log file of the webview, if one was set
+pub(crate) fn log(key: usize, line: &str) {
+    state::with_key(key, |s| {
+        if let Some(file) = &mut s.log_file {
+            let _ = writeln!(file, "{} {}", utils::timestamp(), line);
+        }
+    });
+}
+
+/// Forward the console output of pages to the console handler and the log file, once per webview
+fn hook_console(wv: &Webview) {
+    if !state::with(wv, |s| s.hooks.insert("console")) {
+        return;
+    }
+    let key = wv.get_window() as usize;
+    wv.bind(CONSOLE_BINDING, move |_seq, req| {
+        let args = utils::parse_args(req);
+        let (level, msg) = match (
+            args.first().and_then(|l| l.get::<String>()),
+            args.get(1).and_then(|m| m.get::<String>()),
+        ) {
+            (Some(level), Some(msg)) => (level, msg),
+            _ => return,
+        };
+        log(key, &format!("console.{}: {}", level, msg));
+        let level = match level.as_str() {
+            "debug" => ConsoleLevel::Debug,
+            "info" => ConsoleLevel::Info,
+            "warn" => ConsoleLevel::Warn,
+            "error" => ConsoleLevel::Error,
+            _ => ConsoleLevel::Log,
+        };
+        if let Some(handler) = state::with_key(key, |s| s.console_handler.clone()) {
+            utils::guard(|| (handler.borrow_mut())(level, msg));
+        }
+    });
+    wv.init(CONSOLE_JS);
+    wv.eval(CONSOLE_JS);
+}
+
+impl ConsoleExt for Webview {
+    fn on_console_message<F: FnMut(ConsoleLevel, &str) + 'static>(&mut self, f: F) {
+        state::with(self, |s| s.console_handler = Some(Rc::new(RefCell::new(f))));
+        hook_console(self);
+    }
+
+    fn set_log_file(&mut self, path: &Path) -> Result<(), WebviewError> {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(WebviewError::Io)?;
+        state::with(self, |s| s.log_file = Some(file));
+        hook_console(self);
+        events::hook_uri(self);
+        navigation::hook_load_failed(self);
+        Ok(())
+    }
+}
@@ -0,0 +1,107 @@
+use crate::{eval, utils, Webview, WebviewError};
+use tinyjson::JsonValue;
+
+/// Chrome DevTools Protocol methods
+pub trait DevToolsExt {
+    /// Call the DevTools Protocol `method` with its JSON-encoded `params`, and pass its JSON-encoded result
+    /// to `f` on the main thread. Only `Runtime.evaluate` is supported, other methods pass `WebviewError::Unsupported`
+    fn call_cdp<F: FnOnce(Result<String, WebviewError>) + 'static>(
+        &mut self,
+        method: &str,
+        params: &str,
+        f: F,
+    );
+}
+
+/// Called with the expression, `returnByValue` and `awaitPromise`,
+/// returns the result of `Runtime.evaluate`, or a promise of it
+const EVALUATE_JS: &str = r#"(function(expression, byValue, awaitPromise) {
+    var remote = function(v) {
+        var t = typeof v, o = { type: t };
+        if (v === null) {
+            o.subtype = 'null';
+            o.value = null;
+        } else if (t === 'object' || t === 'function') {
+            o.className = (v.constructor && v.constructor.name) || 'Object';
+            if (Array.isArray(v)) o.subtype = 'array';
+            else if (v instanceof Error) o.subtype = 'error';
+            o.description = o.subtype === 'error' ? String(v.stack || v)
+                : t === 'function' ? String(v) : o.className;
+            if (byValue) {
+                try { o.value = JSON.parse(JSON.stringify(v)); } catch (e) {}
+            }
+        } else if (t === 'bigint') {
+            o.unserializableValue = v + 'n';
+            o.description = v + 'n';
+        } else if (t === 'number' && (!isFinite(v) || Object.is(v, -0))) {
+            o.unserializableValue = Object.is(v, -0) ? '-0' : String(v);
+            o.description = o.unserializableValue;
+        } else if (t !== 'undefined') {
+            if (t !== 'symbol') o.value = v;
+            o.description = String(v);
+        }
+        return o;
+    };
+    var ok = function(v) { return { result: remote(v) }; };
+    var thrown = function(e) {
+        var exception = remote(e);
+        return {
+            result: exception,
+            exceptionDetails: {
+                exceptionId: 1,
+                text: 'Uncaught',
+                lineNumber: 0,
+                columnNumber: 0,
+                exception: exception
+            }
+        };
+    };
+    var r;
+    try {
+        r = (0, eval)(expression);
+    } catch (e) {
+        return thrown(e);
+    }
+    if (awaitPromise && r && typeof r.then === 'function') return Promise.resolve(r).then(ok, thrown);
+    return ok(r);
+})"#;
+
+impl DevToolsExt for Webview {
+    fn call_cdp<F: FnOnce(Result<String, WebviewError>) + 'static>(
+        &mut self,
+        method: &str,
+        params: &str,
+        f: F,
+    ) {
+        if method != "Runtime.evaluate" {
+            f(Err(WebviewError::Unsupported));
+            return;
+        }
+        let params = match params.trim() {
+            "" => Default::default(),
+            params => match params.parse::<JsonValue>() {
+                Ok(JsonValue::Object(params)) => params,
+                _ => {
+                    f(Err(WebviewError::InvalidArgument(params.to_string())));
+                    return;
+                }
+            },
+        };
+        let expression = match params.get("expression") {
+            Some(JsonValue::String(expression)) => expression,
+            _ => {
+                f(Err(WebviewError::InvalidArgument("expression".to_string())));
+                return;
+            }
+        };
+        let flag = |name: &str| matches!(params.get(name), Some(JsonValue::Boolean(true)));
+        let js = format!(
+            "{}({}, {}, {})",
+            EVALUATE_JS,
+            utils::quote(expression),
+            flag("returnByValue"),
+            flag("awaitPromise")
+        );
+        eval::eval_with(self, &js, move |res| f(res.map_err(WebviewError::Js)));
+    }
+}
@@ -0,0 +1,317 @@
+use crate::{state, Webview};
+use std::{cell::RefCell, path::PathBuf, rc::Rc};
+#[cfg(not(target_os = "windows"))]
+use std::{ffi::CStr, os::raw};
+
+/// A download started by a page, through a link with a `download` attribute,
+/// or a response which can't be shown or is sent as an attachment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DownloadRequest {
+    /// The URL of the downloaded resource
+    pub url: String,
+    /// The file name proposed by the server or the link, without a directory
+    pub suggested_filename: String,
+}
+
+/// What to do with a download
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DownloadDecision {
+    /// Save the file at this path, replacing an existing file
+    Save(PathBuf),
+    Cancel,
+}
+
+/// The state of a download
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DownloadState {
+    InProgress,
+    Finished,
+    /// The download failed, with the backend's description of the error
+    Failed(String),
+    Cancelled,
+}
+
+/// The progress of a download, passed to the handler of `DownloadExt::on_download_progress`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DownloadProgress {
+    /// The URL of the downloaded resource
+    pub url: String,
+    /// Where the file is saved, empty for cancelled downloads
+    pub path: PathBuf,
+    /// The number of bytes received
+    pub received: u64,
+    /// The size of the resource, if the server sent it
+    pub total: Option<u64>,
+    pub state: DownloadState,
+}
+
+/// Download handling methods
+pub trait DownloadExt {
+    /// Set the handler deciding where downloads are saved, or cancelling them.
+    /// Until it's set, the backend's default applies
+    fn set_download_handler<F: FnMut(DownloadRequest) -> DownloadDecision + 'static>(
+        &mut self,
+        f: F,
+    );
+    /// Set the handler receiving the progress of downloads, on the main thread,
+    /// until they're finished, failed or cancelled
+    fn on_download_progress<F: FnMut(&DownloadProgress) + 'static>(&mut self, f: F);
+}
+
+/// Ask the download handler of a webview, `None` if it has none.
+/// A panicking handler cancels the download
+fn decide(key: usize, req: DownloadRequest) -> Option<DownloadDecision> {
+    let handler = state::with_key(key, |s| s.download_handler.clone())?;
+    Some(crate::utils::guard(|| (handler.borrow_mut())(req)).unwrap_or(DownloadDecision::Cancel))
+}
+
+fn report(key: usize, progress: DownloadProgress) {
+    if let Some(handler) = state::with_key(key, |s| s.download_progress_handler.clone()) {
+        crate::utils::guard(|| (handler.borrow_mut())(&progress));
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+extern "C" fn decide_cb(
+    url: *const raw::c_char,
+    filename: *const raw::c_char,
+    path: *mut *const raw::c_char,
+    data: *mut raw::c_void,
+) -> raw::c_int {
+    use std::os::unix::ffi::OsStringExt;
+    let req = DownloadRequest {
+        url: unsafe { CStr::from_ptr(url) }
+            .to_string_lossy()
+            .into_owned(),
+        suggested_filename: unsafe { CStr::from_ptr(filename) }
+            .to_string_lossy()
+            .into_owned(),
+    };
+    let dest = match decide(data as usize, req) {
+        Some(DownloadDecision::Save(dest)) => dest,
+        Some(DownloadDecision::Cancel) => return 0,
+        None => return 2,
+    };
+    let dest = match std::ffi::CString::new(dest.into_os_string().into_vec()) {
+        Ok(dest) => dest,
+        Err(_) => return 0,
+    };
+    // Kept in the state until the next download, the backend copies it
+    unsafe {
+        *path = dest.as_ptr();
+    }
+    state::with_key(data as usize, |s| s.download_path = Some(dest));
+    1
+}
+
+#[cfg(not(target_os = "windows"))]
+extern "C" fn progress_cb(
+    url: *const raw::c_char,
+    path: *const raw::c_char,
+    received: raw::c_ulonglong,
+    total: raw::c_longlong,
+    state: raw::c_int,
+    error: *const raw::c_char,
+    data: *mut raw::c_void,
+) {
+    use std::os::unix::ffi::OsStrExt;
+    let state = match state {
+        0 => DownloadState::InProgress,
+        1 => DownloadState::Finished,
+        3 => DownloadState::Cancelled,
+        _ if error.is_null() => DownloadState::Failed(String::new()),
+        _ => DownloadState::Failed(
+            unsafe { CStr::from_ptr(error) }
+                .to_string_lossy()
+                .into_owned(),
+        ),
+    };
+    let path = std::ffi::OsStr::from_bytes(unsafe { CStr::from_ptr(path) }.to_bytes());
+    report(
+        data as usize,
+        DownloadProgress {
+            url: unsafe { CStr::from_ptr(url) }
+                .to_string_lossy()
+                .into_owned(),
+            path: PathBuf::from(path),
+            received: received as u64,
+            total: u64::try_from(total).ok(),
+            state,
+        },
+    );
+}
+
+#[cfg(target_os = "windows")]
+const DOWNLOAD_BINDING: &str = "__fltk_webview_download";
+
+/// Intercepts clicks on links to downloads, which `window.__fltk_webview_download_fetch(id)` passes
+/// to the application, and `window.__fltk_webview_download_default(id)` lets WebView2 handle
+#[cfg(target_os = "windows")]
+const DOWNLOAD_JS: &str = r#"(function() {
+    if (window.__fltk_webview_downloads) return;
+    var pending = window.__fltk_webview_downloads = {};
+    var next = 0, bypass = false;
+    document.addEventListener('click', function(e) {
+        if (bypass || e.defaultPrevented) return;
+        var a = e.target.closest && e.target.closest('a[href]');
+        if (!a || !(a.hasAttribute('download') || /^(data|blob):/i.test(a.href))) return;
+        e.preventDefault();
+        var id = ++next;
+        var name = a.getAttribute('download') ||
+            (/^(data|blob):/i.test(a.href) ? 'download' : decodeURIComponent(a.pathname.split('/').pop()));
+        pending[id] = { href: a.href, name: a.getAttribute('download') };
+        window.__fltk_webview_download('start', id, a.href, name);
+    });
+    var take = function(id) {
+        var d = pending[id];
+        delete pending[id];
+        return d;
+    };
+    window.__fltk_webview_download_fetch = function(id) {
+        var d = take(id);
+        fetch(d.href).then(function(r) {
+            if (!r.ok) throw new Error('HTTP ' + r.status);
+            return r.blob();
+        }).then(function(blob) {
+            return new Promise(function(resolve, reject) {
+                var reader = new FileReader();
+                reader.onload = function() { resolve(reader.result.split(',')[1] || ''); };
+                reader.onerror = function() { reject(reader.error); };
+                reader.readAsDataURL(blob);
+            });
+        }).then(function(data) {
+            window.__fltk_webview_download('data', id, data);
+        }, function(e) {
+            window.__fltk_webview_download('error', id, String(e));
+        });
+    };
+    window.__fltk_webview_download_default = function(id) {
+        var d = take(id);
+        var a = document.createElement('a');
+        a.href = d.href;
+        if (d.name !== null) a.download = d.name;
+        document.body.appendChild(a);
+        bypass = true;
+        a.click();
+        bypass = false;
+        a.remove();
+    };
+    window.__fltk_webview_download_cancel = take;
+})()"#;
+
+/// Intercept downloads in the page, see `DOWNLOAD_JS`
+#[cfg(target_os = "windows")]
+fn hook_downloads_js(wv: &Webview) {
+    use std::collections::HashMap;
+    let key = wv.get_window() as usize;
+    let page = wv.clone();
+    // The URL and destination of the downloads being fetched, by id
+    let fetching: RefCell<HashMap<u64, (String, PathBuf)>> = RefCell::default();
+    wv.bind(DOWNLOAD_BINDING, move |_seq, req| {
+        let args = crate::utils::parse_args(req);
+        let arg = |i: usize| {
+            args.get(i)
+                .and_then(|a| a.get::<String>())
+                .cloned()
+                .unwrap_or_default()
+        };
+        let id = match args.get(1).and_then(|i| i.get::<f64>()) {
+            Some(id) => *id as u64,
+            None => return,
+        };
+        let done = |url: String, path: PathBuf, received: u64, state: DownloadState| {
+            let total = matches!(state, DownloadState::Finished).then_some(received);
+            report(
+                key,
+                DownloadProgress {
+                    url,
+                    path,
+                    received,
+                    total,
+                    state,
+                },
+            )
+        };
+        match arg(0).as_str() {
+            "start" => {
+                let url = arg(2);
+                let req = DownloadRequest {
+                    url: url.clone(),
+                    suggested_filename: arg(3),
+                };
+                match decide(key, req) {
+                    Some(DownloadDecision::Save(path)) => {
+                        fetching
+                            .borrow_mut()
+                            .insert(id, (url.clone(), path.clone()));
+                        done(url, path, 0, DownloadState::InProgress);
+                        page.eval(&format!("window.__fltk_webview_download_fetch({})", id));
+                    }
+                    Some(DownloadDecision::Cancel) => {
+                        done(url, PathBuf::new(), 0, DownloadState::Cancelled);
+                        page.eval(&format!("window.__fltk_webview_download_cancel({})", id));
+                    }
+                    None => page.eval(&format!("window.__fltk_webview_download_default({})", id)),
+                }
+            }
+            "data" => {
+                let (url, path) = match fetching.borrow_mut().remove(&id) {
+                    Some(download) => download,
+                    None => return,
+                };
+                let content = match crate::utils::base64_decode(&arg(2)) {
+                    Some(content) => content,
+                    None => {
+                        let failed = DownloadState::Failed("invalid content".to_string());
+                        return done(url, path, 0, failed);
+                    }
+                };
+                match std::fs::write(&path, &content) {
+                    Ok(()) => done(url, path, content.len() as u64, DownloadState::Finished),
+                    Err(e) => done(url, path, 0, DownloadState::Failed(e.to_string())),
+                }
+            }
+            "error" => {
+                if let Some((url, path)) = fetching.borrow_mut().remove(&id) {
+                    done(url, path, 0, DownloadState::Failed(arg(2)));
+                }
+            }
+            _ => (),
+        }
+    });
+    wv.init(DOWNLOAD_JS);
+    wv.eval(DOWNLOAD_JS);
+}
+
+/// Connect the native download signals, once per webview
+fn hook_downloads(wv: &Webview) {
+    if !state::with(wv, |s| s.hooks.insert("download")) {
+        return;
+    }
+    #[cfg(not(target_os = "windows"))]
+    unsafe {
+        let win = wv.get_window();
+        fltk_webview_sys::my_on_download(win, decide_cb, progress_cb, win);
+    }
+    #[cfg(target_os = "windows")]
+    hook_downloads_js(wv);
+}
+
+impl DownloadExt for Webview {
+    fn set_download_handler<F: FnMut(DownloadRequest) -> DownloadDecision + 'static>(
+        &mut self,
+        f: F,
+    ) {
+        state::with(self, |s| {
+            s.download_handler = Some(Rc::new(RefCell::new(f)))
+        });
+        hook_downloads(self);
+    }
+
+    fn on_download_progress<F: FnMut(&DownloadProgress) + 'static>(&mut self, f: F) {
+        state::with(self, |s| {
+            s.download_progress_handler = Some(Rc::new(RefCell::new(f)))
+        });
+        hook_downloads(self);
+    }
+}
@@ -0,0 +1,1003 @@
+use crate::{state, SizeHint, Webview, WebviewError};
+use fltk::{
+    app,
+    prelude::{GroupExt, WidgetBase, WidgetExt, WindowExt},
+    window,
+};
+use std::{
+    cell::Cell,
+    os::raw,
+    time::{Duration, Instant},
+};
+
+#[cfg(target_os = "windows")]
+extern "system" {
+    fn SetFocus(child: *mut ()) -> *mut ();
+    fn GetFocus() -> *mut raw::c_void;
+    fn GetWindow(hwnd: *mut raw::c_void, cmd: u32) -> *mut raw::c_void;
+    fn ShowWindow(hwnd: *mut raw::c_void, cmd: i32) -> i32;
+    fn IsChild(parent: *mut raw::c_void, hwnd: *mut raw::c_void) -> i32;
+    fn SetWindowPos(
+        hwnd: *mut raw::c_void,
+        after: *mut raw::c_void,
+        x: i32,
+        y: i32,
+        cx: i32,
+        cy: i32,
+        flags: u32,
+    ) -> i32;
+    fn GetWindowRect(hwnd: *mut raw::c_void, rect: *mut [i32; 4]) -> i32;
+    fn GetWindowLongW(hwnd: *mut raw::c_void, index: i32) -> i32;
+    fn SetWindowLongW(hwnd: *mut raw::c_void, index: i32, value: i32) -> i32;
+    fn SetLayeredWindowAttributes(hwnd: *mut raw::c_void, key: u32, alpha: u8, flags: u32) -> i32;
+    fn ScreenToClient(hwnd: *mut raw::c_void, point: *mut [i32; 2]) -> i32;
+    fn IsZoomed(hwnd: *mut raw::c_void) -> i32;
+    fn IsIconic(hwnd: *mut raw::c_void) -> i32;
+}
+
+/// Give keyboard focus to the native web content, without touching fltk's focus
+pub(crate) fn native_focus(win: *mut raw::c_void) {
+    #[cfg(target_os = "windows")]
+    unsafe {
+        // The WebView2 controller lives in a child of the embedding window
+        const GW_CHILD: u32 = 5;
+        let child = GetWindow(win, GW_CHILD);
+        SetFocus(if child.is_null() { win } else { child } as _);
+    }
+    #[cfg(not(target_os = "windows"))]
+    unsafe {
+        fltk_webview_sys::my_focus(win);
+    }
+}
+
+/// Convert the size of the embedding window into the size expected by the backend
+pub(crate) fn backend_size(native: *mut raw::c_void, win: &window::Window) -> (i32, i32) {
+    let scale = match state::with_key(native as usize, |s| s.device_scale) {
+        Some(scale) => scale,
+        // fltk sizes are scaled by its screen scale, GTK's by its own scale factor
+        #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+        None => {
+            app::screen_scale(win.screen_num()) as f64
+                / unsafe { fltk_webview_sys::my_get_scale_factor(native) } as f64
+        }
+        // WebView2 applies the window's dpi, and Cocoa works in points like fltk
+        #[cfg(any(target_os = "macos", target_os = "windows"))]
+        None => 1.0,
+    };
+    (
+        (win.w() as f64 * scale).round() as i32,
+        (win.h() as f64 * scale).round() as i32,
+    )
+}
+
+/// The native window of a webview, tagged by platform.
+/// The handle is only valid while the webview lives,
+/// and calling into the native toolkit through it must happen on the main thread
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum NativeHandle {
+    /// The `GtkWindow` containing the `WebKitWebView`
+    Gtk(*mut raw::c_void),
+    /// The `HWND` hosting the WebView2 controller
+    Win32(*mut raw::c_void),
+    /// The `NSWindow` whose content view is the `WKWebView`
+    Cocoa(*mut raw::c_void),
+}
+
+/// The engine running a webview, as reported by `EmbedExt::engine_info`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EngineInfo {
+    /// `WebKitGTK`, `WebView2` or `WKWebView`
+    pub name: &'static str,
+    /// The version of the engine loaded at runtime, empty if it couldn't be read:
+    /// the WebKitGTK library version, the WebView2 runtime version,
+    /// or the bundle version of the WebKit framework on macOS
+    pub version: String,
+    /// Whether the webview was created with developer tools enabled
+    pub is_debug: bool,
+}
+
+/// The state of the top-level window of a webview, see `EmbedExt::set_window_state`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WindowState {
+    /// Neither minimized nor maximized
+    Normal,
+    /// Minimized, or iconized
+    Minimized,
+    /// Maximized, or zoomed on macOS
+    Maximized,
+}
+
+/// How often the state of the top-level window is polled, since fltk reports no event for maximizing
+const WINDOW_STATE_INTERVAL: f64 = 0.1;
+
+/// Methods concerning the embedding of the webview within its fltk window
+pub trait EmbedExt {
+    /// Move keyboard focus into the web content.
+    /// The embedding fltk window becomes fltk's focus widget, so fltk widgets lose focus.
+    /// Tabbing into the embedding window from fltk widgets also focuses the web content
+    fn focus(&mut self);
+    /// Whether the web content has keyboard focus
+    fn has_focus(&self) -> bool;
+    /// Make the page background transparent, so the embedding fltk window shows through
+    /// transparent regions of the page. On X11, this requires a compositing window manager
+    fn set_transparent(&mut self, transparent: bool) -> Result<(), WebviewError>;
+    /// Override the factor converting the size of the embedding window into backend pixels.
+    /// By default, it's computed from `app::screen_scale` and the backend's own scaling.
+    /// `Webview::set_size` passes its dimensions to the backend unconverted
+    fn set_device_scale(&mut self, scale: f64);
+    /// Get the native window of the webview.
+    /// Destroying or reparenting the window through the handle breaks the embedding
+    fn native_window_handle(&self) -> NativeHandle;
+    /// Get the `webview_t` of the webview, to call the functions of `fltk_webview_sys` this crate doesn't wrap.
+    /// The pointer is only valid until the webview is closed, and its functions must be called on the main thread.
+    /// fltk-webview keeps owning the webview, so it mustn't be destroyed, terminated or run through the pointer,
+    /// and calls which change what fltk-webview tracks, like the size or the bindings, can leave it out of sync
+    fn as_raw(&self) -> fltk_webview_sys::webview_t;
+    /// Keep the webview from being resized below `w`x`h`, without resizing it.
+    /// The limit is applied to the top-level fltk window, accounting for the space around the webview,
+    /// which the backend window follows
+    fn set_min_size(&mut self, w: i32, h: i32);
+    /// Keep the webview from being resized above `w`x`h`, without resizing it.
+    /// Has the same behavior as `set_min_size`
+    fn set_max_size(&mut self, w: i32, h: i32);
+    /// Like `Webview::set_size`, also applying the hint to the top-level fltk window.
+    /// `SizeHint::None` clears the limits set through the other hints
+    fn set_size_and_hint(&mut self, w: i32, h: i32, hint: SizeHint);
+    /// The hint last passed to `set_size_and_hint`, `SizeHint::None` before.
+    /// Hints passed to `Webview::set_size` don't go through fltk-webview, so they aren't tracked
+    fn size_hint(&self) -> SizeHint;
+    /// The size of the native window of the webview, in the units of `Webview::set_size`
+    fn size(&self) -> (i32, i32);
+    /// Move the native window of the webview to `x`, `y`, relative to the top-left corner
+    /// of the embedding fltk window, in backend pixels, as `Webview::set_size`
+    fn set_position(&mut self, x: i32, y: i32);
+    /// The position of the native window of the webview, relative to the embedding fltk window,
+    /// in the units of `set_position`
+    fn position(&self) -> (i32, i32);
+    /// Destroy the webview, keeping the application running, and the embedding fltk window for another webview.
+    /// Other clones of the webview must not be used afterwards. Dropping a webview doesn't destroy it.
+    /// A page kept by the handler of `EventsExt::set_before_unload_handler` stays open, which `is_running` tells
+    fn close(self);
+    /// Whether the webview wasn't closed, through `close`, or on macOS, by closing its top-level window.
+    /// Can be called from any thread
+    fn is_running(&self) -> bool;
+    /// Reduce the resources used by the webview while it isn't visible, e.g. when its panel is hidden,
+    /// pausing media and hiding the native window, so the page is throttled like a background tab
+    fn suspend(&mut self);
+    /// Undo `suspend`, showing the native window and playing the paused media again
+    fn resume(&mut self);
+    /// Let pages move the top-level window, e.g. a borderless one, by dragging elements styled
+    /// with `app-region: drag` or marked with `data-app-region="drag"`, as in Electron
+    fn enable_app_regions(&mut self);
+    /// Describe the engine running the webview, e.g. for bug reports
+    fn engine_info(&self) -> EngineInfo;
+    /// Keep the top-level fltk window of the webview above other windows, e.g. for overlays, or stop.
+    /// On X11, the request goes to the window manager, which can ignore it,
+    /// and it's lost when the window is hidden, so call this again after showing it
+    fn set_always_on_top(&mut self, on: bool);
+    /// Whether the top-level window was kept above others through `set_always_on_top`
+    fn is_always_on_top(&self) -> bool;
+    /// Minimize, maximize or restore the top-level fltk window of the webview.
+    /// Restoring or maximizing a minimized window also shows it.
+    /// The webview follows the new size of the window once the window manager applied it.
+    /// On X11, maximizing is a request to the window manager, which can ignore it
+    fn set_window_state(&mut self, state: WindowState);
+    /// The state of the top-level fltk window, `WindowState::Normal` if it isn't shown
+    fn window_state(&self) -> WindowState;
+    /// Set the handler called on the main thread when the top-level window is minimized,
+    /// maximized or restored, by the application or the user.
+    /// fltk reports no event for maximizing, so the state is polled every 100ms while the webview runs
+    fn on_window_state_changed<F: FnMut(WindowState) + 'static>(&mut self, f: F);
+    /// Center the top-level fltk window of the webview in the work area of the screen `screen`, numbered by fltk from 0,
+    /// e.g. to place kiosks on a given monitor. Indices past the last screen are clamped to it
+    fn move_to_screen(&mut self, screen: usize);
+    /// The screen containing the center of the top-level fltk window, 0 if it isn't shown
+    fn screen(&self) -> usize;
+    /// Move the webview into another fltk window, e.g. when dragging a tab to another window,
+    /// keeping its page, history and bindings. Returns `WebviewError::WindowNotShown` if `new_win` isn't shown within 2 seconds
+    fn reparent(&mut self, new_win: &mut window::Window) -> Result<(), WebviewError>;
+    /// Resize the embedding fltk window to fill its parent, leaving `margin` pixels on every side,
+    /// and make it the parent's resizable widget. Does nothing if the embedding window has no parent
+    fn fill_parent(&mut self, margin: i32);
+    /// Set the opacity of the webview, clamped from 0.0, invisible, to 1.0, opaque, e.g. for fade transitions
+    fn set_opacity(&mut self, opacity: f64);
+    /// The opacity set through `set_opacity`, 1.0 by default
+    fn opacity(&self) -> f64;
+    /// Fade the webview in, from invisible to opaque, over `duration_ms` milliseconds,
+    /// e.g. when showing a panel. The opacity is stepped through `set_opacity`
+    /// about 60 times per second from fltk's event loop, so it has the same backend differences.
+    /// Starting another fade stops this one
+    fn fade_in(&mut self, duration_ms: u32);
+    /// Fade the webview out, from its current opacity to invisible, over `duration_ms` milliseconds,
+    /// then call `then` on the main thread, e.g. to `close` it.
+    /// `then` isn't called if another fade starts in the meantime, or the webview is closed
+    fn fade_out<F: FnOnce() + 'static>(&mut self, duration_ms: u32, then: F);
+}
+
+/// How often fades step the opacity
+const FADE_INTERVAL: f64 = 1.0 / 60.0;
+
+/// Step the opacity of the webview from `from` to `to` over `duration`, then call `then`,
+/// unless another fade started
+fn fade(wv: &Webview, from: f64, to: f64, duration: Duration, then: Option<Box<dyn FnOnce()>>) {
+    let fade = state::with(wv, |s| {
+        s.fades += 1;
+        s.fades
+    });
+    let key = wv.get_window() as usize;
+    let mut wv = wv.clone();
+    let mut then = then;
+    let start = Instant::now();
+    wv.set_opacity(from);
+    app::add_timeout3(FADE_INTERVAL, move |handle| {
+        if !state::is_running(key) || state::with_key(key, |s| s.fades) != fade {
+            return;
+        }
+        let progress = if duration.is_zero() {
+            1.0
+        } else {
+            (start.elapsed().as_secs_f64() / duration.as_secs_f64()).min(1.0)
+        };
+        wv.set_opacity(from + (to - from) * progress);
+        if progress < 1.0 {
+            app::repeat_timeout3(FADE_INTERVAL, handle);
+        } else if let Some(then) = then.take() {
+            crate::utils::guard(then);
+        }
+    });
+}
+
+const SUSPEND_JS: &str = r#"(function() {
+    var playing = Array.prototype.filter.call(document.querySelectorAll('video, audio'),
+        function(m) { return !m.paused; });
+    playing.forEach(function(m) { m.pause(); });
+    window.__fltk_webview_paused = playing;
+})()"#;
+
+const RESUME_JS: &str = r#"(function() {
+    (window.__fltk_webview_paused || []).forEach(function(m) { m.play(); });
+    window.__fltk_webview_paused = [];
+})()"#;
+
+const APP_REGION_BINDING: &str = "__fltk_webview_app_region";
+
+const APP_REGIONS_JS: &str = r#"(function() {
+    if (window.__fltk_webview_app_regions) return;
+    window.__fltk_webview_app_regions = true;
+    var region = function(el) {
+        for (; el && el.nodeType === 1; el = el.parentElement) {
+            var style = getComputedStyle(el);
+            var value = style.getPropertyValue('app-region') ||
+                style.getPropertyValue('-webkit-app-region') || el.getAttribute('data-app-region');
+            if (!value) {
+                var m = /(?:^|;)\s*(?:-webkit-)?app-region\s*:\s*(no-drag|drag)/.exec(el.getAttribute('style') || '');
+                value = m && m[1];
+            }
+            value = (value || '').trim();
+            if (value === 'drag' || value === 'no-drag') return value;
+        }
+        return null;
+    };
+    var dragging = false, pending = null;
+    var report = function() {
+        if (pending) window.__fltk_webview_app_region('move', pending[0], pending[1]);
+        pending = null;
+    };
+    document.addEventListener('pointerdown', function(e) {
+        if (e.button !== 0 || region(e.target) !== 'drag') return;
+        dragging = true;
+        e.preventDefault();
+        if (e.target.setPointerCapture) e.target.setPointerCapture(e.pointerId);
+        window.__fltk_webview_app_region('start', e.screenX, e.screenY);
+    }, true);
+    document.addEventListener('pointermove', function(e) {
+        if (!dragging) return;
+        // Coalesced to one move per frame
+        if (!pending) requestAnimationFrame(report);
+        pending = [e.screenX, e.screenY];
+    }, true);
+    var end = function() {
+        if (!dragging) return;
+        dragging = false;
+        report();
+        window.__fltk_webview_app_region('end', 0, 0);
+    };
+    document.addEventListener('pointerup', end, true);
+    document.addEventListener('pointercancel', end, true);
+})()"#;
+
+/// Move the top-level window of the webview with the pointer of a drag reported by `APP_REGIONS_JS`.
+/// `start` holds the window position and the pointer position when the drag started
+fn app_region_drag(key: usize, req: &str, start: &Cell<Option<(i32, i32, f64, f64)>>) {
+    let args = crate::utils::parse_args(req);
+    let phase = args.first().and_then(|p| p.get::<String>()).cloned();
+    let coord = |i: usize| args.get(i).and_then(|c| c.get::<f64>()).copied();
+    let (x, y) = match (coord(1), coord(2)) {
+        (Some(x), Some(y)) => (x, y),
+        _ => return,
+    };
+    let top = state::with_key(key, |s| s.win.clone()).and_then(|win| win.top_window());
+    let mut top = match top {
+        Some(top) => top,
+        None => return,
+    };
+    match phase.as_deref() {
+        Some("start") => start.set(Some((top.x(), top.y(), x, y))),
+        Some("move") => {
+            if let Some((win_x, win_y, start_x, start_y)) = start.get() {
+                let dx = (x - start_x).round() as i32;
+                let dy = (y - start_y).round() as i32;
+                top.set_pos(win_x + dx, win_y + dy);
+            }
+        }
+        _ => start.set(None),
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+extern "C" fn engine_version_cb(version: *const raw::c_char, data: *mut raw::c_void) {
+    let out = unsafe { &mut *(data as *mut String) };
+    *out = unsafe { std::ffi::CStr::from_ptr(version) }
+        .to_string_lossy()
+        .into_owned();
+}
+
+/// The version of the engine library loaded at runtime
+#[cfg(not(target_os = "windows"))]
+fn native_engine_version() -> String {
+    let mut version = String::new();
+    unsafe {
+        fltk_webview_sys::my_get_engine_version(
+            engine_version_cb,
+            &mut version as *mut String as _,
+        );
+    }
+    version
+}
+
+/// The WebView2 runtime version, from the `Edg/` token of the user agent, cached in the state
+#[cfg(target_os = "windows")]
+fn webview2_version(wv: &Webview) -> String {
+    use std::{
+        rc::Rc,
+        time::{Duration, Instant},
+    };
+    if let Some(version) = state::with(wv, |s| s.engine_version.clone()) {
+        return version;
+    }
+    let agent: Rc<Cell<Option<String>>> = Rc::default();
+    crate::eval::eval_with(wv, "navigator.userAgent", {
+        let agent = agent.clone();
+        move |res| {
+            let parsed = res.ok().and_then(|r| r.parse::<tinyjson::JsonValue>().ok());
+            let agent_str = parsed.and_then(|p| p.get::<String>().cloned());
+            agent.set(Some(agent_str.unwrap_or_default()));
+        }
+    });
+    let end = Instant::now() + Duration::from_secs(1);
+    let agent = loop {
+        if let Some(agent) = agent.take() {
+            break agent;
+        }
+        if Instant::now() >= end {
+            return String::new();
+        }
+        let _ = app::wait_for(0.01);
+    };
+    let version = agent
+        .split_whitespace()
+        .find_map(|token| token.strip_prefix("Edg/"))
+        .unwrap_or_default()
+        .to_string();
+    state::with(wv, |s| s.engine_version = Some(version.clone()));
+    version
+}
+
+/// Show or hide the native window of the webview
+fn set_suspended(win: *mut raw::c_void, suspended: bool) {
+    #[cfg(target_os = "windows")]
+    unsafe {
+        const GW_CHILD: u32 = 5;
+        const SW_HIDE: i32 = 0;
+        const SW_SHOW: i32 = 5;
+        let child = GetWindow(win, GW_CHILD);
+        if !child.is_null() {
+            ShowWindow(child, if suspended { SW_HIDE } else { SW_SHOW });
+        }
+    }
+    #[cfg(not(target_os = "windows"))]
+    unsafe {
+        fltk_webview_sys::my_set_suspended(win, suspended as _);
+    }
+}
+
+/// Move the native window of the webview to its position relative to the embedding window, if one was set.
+/// Backends move it back to the corner of the embedding window when it's resized or reparented
+pub(crate) fn apply_position(native: *mut raw::c_void) {
+    let (position, host) = state::with_key(native as usize, |s| (s.position, s.win.clone()));
+    let (x, y) = match position {
+        Some(position) => position,
+        None => return,
+    };
+    #[cfg(target_os = "windows")]
+    unsafe {
+        // The window hosting the WebView2 controller
+        const GW_CHILD: u32 = 5;
+        const SWP_NOSIZE: u32 = 0x0001;
+        const SWP_NOZORDER: u32 = 0x0004;
+        const SWP_NOACTIVATE: u32 = 0x0010;
+        let _ = host;
+        let child = GetWindow(native, GW_CHILD);
+        if !child.is_null() {
+            SetWindowPos(
+                child,
+                std::ptr::null_mut(),
+                x,
+                y,
+                0,
+                0,
+                SWP_NOSIZE | SWP_NOZORDER | SWP_NOACTIVATE,
+            );
+        }
+    }
+    #[cfg(not(target_os = "windows"))]
+    unsafe {
+        let host = host.map(|h| h.raw_handle() as *mut raw::c_void);
+        fltk_webview_sys::my_set_position(native, host.unwrap_or(std::ptr::null_mut()), x, y);
+    }
+}
+
+/// The state of `top`, the top-level window of the webview of the native window `native`
+fn top_window_state(native: *mut raw::c_void, top: &window::Window) -> WindowState {
+    if !top.shown() {
+        return WindowState::Normal;
+    }
+    #[cfg(target_os = "windows")]
+    let state = unsafe {
+        let _ = native;
+        let hwnd = top.raw_handle() as *mut raw::c_void;
+        if IsIconic(hwnd) != 0 {
+            1
+        } else if IsZoomed(hwnd) != 0 {
+            2
+        } else {
+            0
+        }
+    };
+    #[cfg(not(target_os = "windows"))]
+    let state = unsafe { fltk_webview_sys::my_get_window_state(native, top.raw_handle() as _) };
+    match state {
+        1 => WindowState::Minimized,
+        2 => WindowState::Maximized,
+        _ => WindowState::Normal,
+    }
+}
+
+/// Poll the state of the top-level window, once per webview, until it's closed.
+/// The webview is resized when the state changes, then the handler is called
+fn watch_window_state(wv: &Webview) {
+    if !state::with(wv, |s| s.hooks.insert("window_state")) {
+        return;
+    }
+    let current = wv.window_state();
+    state::with(wv, |s| s.window_state = Some(current));
+    let native = wv.get_window();
+    let key = native as usize;
+    app::add_timeout3(WINDOW_STATE_INTERVAL, move |handle| {
+        if !state::is_running(key) {
+            return;
+        }
+        app::repeat_timeout3(WINDOW_STATE_INTERVAL, handle);
+        let win = match state::with_key(key, |s| s.win.clone()) {
+            Some(win) => win,
+            None => return,
+        };
+        let current = match win.top_window() {
+            Some(top) => top_window_state(native, &top),
+            None => return,
+        };
+        let (previous, handler) = state::with_key(key, |s| {
+            (
+                s.window_state.replace(current),
+                s.window_state_handler.clone(),
+            )
+        });
+        if previous == Some(current) {
+            return;
+        }
+        if current != WindowState::Minimized {
+            // The draw callback passes the new size to the backend
+            let mut win = win;
+            win.redraw();
+        }
+        if let Some(handler) = handler {
+            crate::utils::guard(|| (handler.borrow_mut())(current));
+        }
+    });
+}
+
+/// Apply the size limits of the webview to its top-level window
+fn apply_size_range(wv: &Webview) {
+    let (win, min, max) = state::with(wv, |s| (s.win.clone(), s.min_size, s.max_size));
+    let win = match win {
+        Some(win) => win,
+        None => return,
+    };
+    if let Some(mut top) = win.top_window() {
+        let (dw, dh) = (top.w() - win.w(), top.h() - win.h());
+        let (min_w, min_h) = min.map(|(w, h)| (w + dw, h + dh)).unwrap_or_default();
+        // 0 means unlimited to fltk
+        let (max_w, max_h) = max.map(|(w, h)| (w + dw, h + dh)).unwrap_or_default();
+        top.size_range(min_w, min_h, max_w, max_h);
+    }
+}
+
+/// Remove the size limits and position of the webview, and fit it to its embedding window again
+pub(crate) fn reset_size(wv: &Webview) {
+    let win = state::with(wv, |s| {
+        s.min_size = None;
+        s.max_size = None;
+        s.position = None;
+        s.win.clone()
+    });
+    apply_size_range(wv);
+    if let Some(mut win) = win {
+        // The draw callback resizes the webview without a size hint
+        win.redraw();
+    }
+}
+
+impl EmbedExt for Webview {
+    fn focus(&mut self) {
+        if let Some(win) = state::with(self, |s| s.win.clone()) {
+            app::set_focus(&win);
+        }
+        native_focus(self.get_window());
+    }
+
+    fn has_focus(&self) -> bool {
+        #[cfg(target_os = "windows")]
+        unsafe {
+            let win = self.get_window();
+            let focused = GetFocus();
+            !focused.is_null() && (focused == win || IsChild(win, focused) != 0)
+        }
+        #[cfg(not(target_os = "windows"))]
+        unsafe {
+            fltk_webview_sys::my_has_focus(self.get_window()) != 0
+        }
+    }
+
+    fn set_transparent(&mut self, transparent: bool) -> Result<(), WebviewError> {
+        #[cfg(target_os = "windows")]
+        {
+            let _ = transparent;
+            Err(WebviewError::Unsupported)
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            let (win, background) = state::with(self, |s| (s.win.clone(), s.background));
+            let background = background.unwrap_or(fltk::enums::Color::White);
+            if let Some(mut win) = win {
+                let color = match win.parent() {
+                    Some(parent) if transparent => parent.color(),
+                    _ => background,
+                };
+                win.set_color(color);
+                win.redraw();
+            }
+            unsafe {
+                let win = self.get_window();
+                if transparent {
+                    fltk_webview_sys::my_set_transparent(win, 1);
+                } else {
+                    let (r, g, b) = background.to_rgb();
+                    fltk_webview_sys::my_set_background(win, r, g, b);
+                }
+            }
+            Ok(())
+        }
+    }
+
+    fn set_device_scale(&mut self, scale: f64) {
+        if let Some(mut win) = state::with(self, |s| {
+            s.device_scale = Some(scale);
+            s.win.clone()
+        }) {
+            win.redraw();
+        }
+    }
+
+    fn native_window_handle(&self) -> NativeHandle {
+        let win = self.get_window();
+        #[cfg(target_os = "windows")]
+        return NativeHandle::Win32(win);
+        #[cfg(target_os = "macos")]
+        return NativeHandle::Cocoa(win);
+        #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+        return NativeHandle::Gtk(win);
+    }
+
+    fn as_raw(&self) -> fltk_webview_sys::webview_t {
+        state::with(self, |s| s.handle) as _
+    }
+
+    fn set_min_size(&mut self, w: i32, h: i32) {
+        state::with(self, |s| s.min_size = Some((w, h)));
+        apply_size_range(self);
+    }
+
+    fn set_max_size(&mut self, w: i32, h: i32) {
+        state::with(self, |s| s.max_size = Some((w, h)));
+        apply_size_range(self);
+    }
+
+    fn set_size_and_hint(&mut self, w: i32, h: i32, hint: SizeHint) {
+        let win = state::with(self, |s| {
+            s.size_hint = Some(hint);
+            match hint {
+                SizeHint::None => {
+                    s.min_size = None;
+                    s.max_size = None;
+                }
+                SizeHint::Min => s.min_size = Some((w, h)),
+                SizeHint::Max => s.max_size = Some((w, h)),
+                SizeHint::Fixed => {
+                    s.min_size = Some((w, h));
+                    s.max_size = Some((w, h));
+                }
+            }
+            s.win.clone()
+        });
+        // Also undoes a hint previously passed to the backend through `Webview::set_size`
+        self.set_size(w, h, hint);
+        apply_size_range(self);
+        if matches!(hint, SizeHint::Min | SizeHint::Max) {
+            return;
+        }
+        if let Some(mut win) = win {
+            match win.top_window() {
+                Some(mut top) if top.as_widget_ptr() != win.as_widget_ptr() => {
+                    let (dw, dh) = (top.w() - win.w(), top.h() - win.h());
+                    top.resize(top.x(), top.y(), w + dw, h + dh);
+                }
+                _ => win.resize(win.x(), win.y(), w, h),
+            }
+            win.redraw();
+        }
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        state::with(self, |s| s.size_hint).unwrap_or(SizeHint::None)
+    }
+
+    fn size(&self) -> (i32, i32) {
+        let win = self.get_window();
+        #[cfg(target_os = "windows")]
+        unsafe {
+            const GW_CHILD: u32 = 5;
+            let child = GetWindow(win, GW_CHILD);
+            let mut rect = [0; 4];
+            if child.is_null() || GetWindowRect(child, &mut rect) == 0 {
+                return (0, 0);
+            }
+            (rect[2] - rect[0], rect[3] - rect[1])
+        }
+        #[cfg(not(target_os = "windows"))]
+        unsafe {
+            let (mut w, mut h) = (0, 0);
+            fltk_webview_sys::my_get_size(win, &mut w, &mut h);
+            (w, h)
+        }
+    }
+
+    fn set_position(&mut self, x: i32, y: i32) {
+        state::with(self, |s| s.position = Some((x, y)));
+        apply_position(self.get_window());
+    }
+
+    fn position(&self) -> (i32, i32) {
+        let win = self.get_window();
+        #[cfg(target_os = "windows")]
+        unsafe {
+            const GW_CHILD: u32 = 5;
+            let child = GetWindow(win, GW_CHILD);
+            let mut rect = [0; 4];
+            if child.is_null() || GetWindowRect(child, &mut rect) == 0 {
+                return (0, 0);
+            }
+            let mut point = [rect[0], rect[1]];
+            ScreenToClient(win, &mut point);
+            (point[0], point[1])
+        }
+        #[cfg(not(target_os = "windows"))]
+        unsafe {
+            let host = state::with(self, |s| s.win.clone())
+                .map(|h| h.raw_handle() as *mut raw::c_void)
+                .unwrap_or(std::ptr::null_mut());
+            let (mut x, mut y) = (0, 0);
+            fltk_webview_sys::my_get_position(win, host, &mut x, &mut y);
+            (x, y)
+        }
+    }
+
+    fn close(self) {
+        if !crate::events::confirm_unload(self.get_window() as usize) {
+            return;
+        }
+        state::set_running(self.get_window() as usize, false);
+        let state = match state::remove(self.get_window() as usize) {
+            Some(state) if state.handle != 0 => state,
+            _ => return,
+        };
+        // The callbacks of the embedding window refer to the backend webview
+        if let Some(mut win) = state.win {
+            win.draw(|_| ());
+            win.handle(|_, _| false);
+            win.redraw();
+        }
+        unsafe {
+            fltk_webview_sys::webview_destroy(state.handle as _);
+        }
+        // Deletes the ephemeral data folder, unless another webview stores in it
+        drop(state.ephemeral_dir);
+    }
+
+    fn is_running(&self) -> bool {
+        state::is_running(self.get_window() as usize)
+    }
+
+    fn suspend(&mut self) {
+        if state::with(self, |s| std::mem::replace(&mut s.suspended, true)) {
+            return;
+        }
+        self.eval(SUSPEND_JS);
+        set_suspended(self.get_window(), true);
+    }
+
+    fn resume(&mut self) {
+        if !state::with(self, |s| std::mem::replace(&mut s.suspended, false)) {
+            return;
+        }
+        set_suspended(self.get_window(), false);
+        self.eval(RESUME_JS);
+    }
+
+    fn enable_app_regions(&mut self) {
+        if !state::with(self, |s| s.hooks.insert("app_regions")) {
+            return;
+        }
+        let key = self.get_window() as usize;
+        let start = Cell::new(None);
+        self.bind(APP_REGION_BINDING, move |_seq, req| {
+            app_region_drag(key, req, &start)
+        });
+        self.init(APP_REGIONS_JS);
+        self.eval(APP_REGIONS_JS);
+    }
+
+    fn engine_info(&self) -> EngineInfo {
+        #[cfg(target_os = "windows")]
+        let (name, version) = ("WebView2", webview2_version(self));
+        #[cfg(target_os = "macos")]
+        let (name, version) = ("WKWebView", native_engine_version());
+        #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+        let (name, version) = ("WebKitGTK", native_engine_version());
+        EngineInfo {
+            name,
+            version,
+            is_debug: state::with(self, |s| s.debug),
+        }
+    }
+
+    fn set_always_on_top(&mut self, on: bool) {
+        let top = state::with(self, |s| {
+            s.always_on_top = on;
+            s.win.clone()
+        })
+        .and_then(|win| win.top_window());
+        let top = match top {
+            Some(top) => top,
+            None => return,
+        };
+        #[cfg(target_os = "windows")]
+        unsafe {
+            const HWND_TOPMOST: isize = -1;
+            const HWND_NOTOPMOST: isize = -2;
+            const SWP_NOSIZE: u32 = 0x0001;
+            const SWP_NOMOVE: u32 = 0x0002;
+            const SWP_NOACTIVATE: u32 = 0x0010;
+            let after = if on { HWND_TOPMOST } else { HWND_NOTOPMOST };
+            SetWindowPos(
+                top.raw_handle() as _,
+                after as _,
+                0,
+                0,
+                0,
+                0,
+                SWP_NOSIZE | SWP_NOMOVE | SWP_NOACTIVATE,
+            );
+        }
+        #[cfg(not(target_os = "windows"))]
+        unsafe {
+            fltk_webview_sys::my_set_always_on_top(
+                self.get_window(),
+                top.raw_handle() as _,
+                on as _,
+            );
+        }
+    }
+
+    fn is_always_on_top(&self) -> bool {
+        state::with(self, |s| s.always_on_top)
+    }
+
+    fn set_window_state(&mut self, window_state: WindowState) {
+        let top = state::with(self, |s| s.win.clone()).and_then(|win| win.top_window());
+        let top = match top {
+            Some(top) => top,
+            None => return,
+        };
+        watch_window_state(self);
+        #[cfg(target_os = "windows")]
+        unsafe {
+            const SW_MAXIMIZE: i32 = 3;
+            const SW_MINIMIZE: i32 = 6;
+            const SW_RESTORE: i32 = 9;
+            let hwnd = top.raw_handle() as *mut raw::c_void;
+            match window_state {
+                WindowState::Minimized => {
+                    ShowWindow(hwnd, SW_MINIMIZE);
+                }
+                WindowState::Maximized => {
+                    ShowWindow(hwnd, SW_MAXIMIZE);
+                }
+                WindowState::Normal => {
+                    // Restoring a minimized window brings it back maximized if it was
+                    ShowWindow(hwnd, SW_RESTORE);
+                    if IsZoomed(hwnd) != 0 {
+                        ShowWindow(hwnd, SW_RESTORE);
+                    }
+                }
+            }
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            let mut top = top;
+            let native = self.get_window();
+            if window_state == WindowState::Minimized {
+                top.iconize();
+                return;
+            }
+            if top_window_state(native, &top) == WindowState::Minimized {
+                // Deiconifies shown windows
+                top.show();
+            }
+            unsafe {
+                fltk_webview_sys::my_set_maximized(
+                    native,
+                    top.raw_handle() as _,
+                    (window_state == WindowState::Maximized) as _,
+                );
+            }
+        }
+    }
+
+    fn window_state(&self) -> WindowState {
+        match state::with(self, |s| s.win.clone()).and_then(|win| win.top_window()) {
+            Some(top) => top_window_state(self.get_window(), &top),
+            None => WindowState::Normal,
+        }
+    }
+
+    fn on_window_state_changed<F: FnMut(WindowState) + 'static>(&mut self, f: F) {
+        state::with(self, |s| {
+            s.window_state_handler = Some(std::rc::Rc::new(std::cell::RefCell::new(f)))
+        });
+        watch_window_state(self);
+    }
+
+    fn move_to_screen(&mut self, screen: usize) {
+        let win = state::with(self, |s| s.win.clone());
+        let (mut win, mut top) = match win.and_then(|win| Some((win.clone(), win.top_window()?))) {
+            Some(found) => found,
+            None => return,
+        };
+        let last = app::screen_count().max(1) as usize - 1;
+        let (x, y, w, h) = app::screen_work_area(screen.min(last) as i32);
+        let maximized = self.window_state() == WindowState::Maximized;
+        if maximized {
+            self.set_window_state(WindowState::Normal);
+        }
+        let (tw, th) = (top.w().min(w), top.h().min(h));
+        top.resize(x + (w - tw) / 2, y + (h - th) / 2, tw, th);
+        if maximized {
+            self.set_window_state(WindowState::Maximized);
+        }
+        // The draw callback passes the size at the scale of the new screen to the backend
+        win.redraw();
+    }
+
+    fn screen(&self) -> usize {
+        match state::with(self, |s| s.win.clone()).and_then(|win| win.top_window()) {
+            Some(top) if top.shown() => {
+                app::screen_num(top.x() + top.w() / 2, top.y() + top.h() / 2).max(0) as usize
+            }
+            _ => 0,
+        }
+    }
+
+    fn reparent(&mut self, new_win: &mut window::Window) -> Result<(), WebviewError> {
+        crate::reparent(self, new_win)
+    }
+
+    fn fill_parent(&mut self, margin: i32) {
+        let mut win = match state::with(self, |s| s.win.clone()) {
+            Some(win) => win,
+            None => return,
+        };
+        let mut parent = match win.parent() {
+            Some(parent) => parent,
+            None => return,
+        };
+        // Children of windows are placed relative to them, children of groups relative to their window
+        let (x, y) = if parent.as_window().is_some() {
+            (0, 0)
+        } else {
+            (parent.x(), parent.y())
+        };
+        win.resize(
+            x + margin,
+            y + margin,
+            (parent.w() - 2 * margin).max(1),
+            (parent.h() - 2 * margin).max(1),
+        );
+        parent.resizable(&win);
+        parent.init_sizes();
+        win.redraw();
+    }
+
+    fn set_opacity(&mut self, opacity: f64) {
+        let opacity = opacity.max(0.0).min(1.0);
+        state::with(self, |s| s.opacity = Some(opacity));
+        #[cfg(target_os = "windows")]
+        unsafe {
+            const GWL_EXSTYLE: i32 = -20;
+            const WS_EX_LAYERED: i32 = 0x80000;
+            const LWA_ALPHA: u32 = 2;
+            let win = self.get_window();
+            let style = GetWindowLongW(win, GWL_EXSTYLE);
+            SetWindowLongW(win, GWL_EXSTYLE, style | WS_EX_LAYERED);
+            SetLayeredWindowAttributes(win, 0, (opacity * 255.0).round() as u8, LWA_ALPHA);
+        }
+        #[cfg(not(target_os = "windows"))]
+        unsafe {
+            fltk_webview_sys::my_set_opacity(self.get_window(), opacity);
+        }
+    }
+
+    fn opacity(&self) -> f64 {
+        state::with(self, |s| s.opacity.unwrap_or(1.0))
+    }
+
+    fn fade_in(&mut self, duration_ms: u32) {
+        fade(
+            self,
+            0.0,
+            1.0,
+            Duration::from_millis(duration_ms as u64),
+            None,
+        );
+    }
+
+    fn fade_out<F: FnOnce() + 'static>(&mut self, duration_ms: u32, then: F) {
+        let from = self.opacity();
+        fade(
+            self,
+            from,
+            0.0,
+            Duration::from_millis(duration_ms as u64),
+            Some(Box::new(then)),
+        );
+    }
+}
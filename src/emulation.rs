@@ -0,0 +1,255 @@
+use crate::{settings, utils, Webview, WebviewError};
+
+/// The metrics of an emulated device, see `EmulationExt::emulate_device`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeviceMetrics {
+    /// The viewport width in CSS pixels
+    pub width: u32,
+    /// The viewport height in CSS pixels
+    pub height: u32,
+    pub device_pixel_ratio: f64,
+    /// Replaces the backend's user agent
+    pub user_agent: Option<String>,
+    /// Whether the device reports a touch screen
+    pub touch: bool,
+}
+
+impl DeviceMetrics {
+    /// Create metrics for a `width`x`height` viewport, with a device pixel ratio of 1,
+    /// the backend's user agent and no touch screen
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            device_pixel_ratio: 1.0,
+            user_agent: None,
+            touch: false,
+        }
+    }
+
+    /// Set the device pixel ratio
+    pub fn with_device_pixel_ratio(mut self, ratio: f64) -> Self {
+        self.device_pixel_ratio = ratio;
+        self
+    }
+
+    /// Set the user agent
+    pub fn with_user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = Some(user_agent.to_string());
+        self
+    }
+
+    /// Report a touch screen
+    pub fn with_touch(mut self, touch: bool) -> Self {
+        self.touch = touch;
+        self
+    }
+}
+
+/// Device emulation methods, e.g. to test responsive pages
+pub trait EmulationExt {
+    /// Make the scripts of the current page and the next ones see the metrics of another device, or stop with `None`.
+    /// The layout and CSS media queries follow the real size, which `EmbedExt::set_size_and_hint` can set
+    fn emulate_device(&mut self, metrics: Option<DeviceMetrics>);
+    /// Make the scripts of the current page and the next ones use the IANA time zone `tz`, e.g. `America/New_York`,
+    /// or the system's again with `None`. Invalid names return `WebviewError::InvalidArgument`
+    fn override_timezone(&mut self, tz: Option<&str>) -> Result<(), WebviewError>;
+    /// Make the scripts of the current page and the next ones format with the BCP 47 `locale`, e.g. `de-DE`,
+    /// or with the system's again with `None`. Invalid tags return `WebviewError::InvalidArgument`
+    fn override_locale(&mut self, locale: Option<&str>) -> Result<(), WebviewError>;
+}
+
+/// Check that `tz` looks like an IANA time zone name, e.g. `UTC`, `Europe/Paris` or `Etc/GMT+5`
+fn is_timezone(tz: &str) -> bool {
+    tz.len() <= 64
+        && tz.split('/').all(|part| {
+            part.starts_with(|c: char| c.is_ascii_alphabetic())
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "_+-".contains(c))
+        })
+}
+
+/// Overrides the metrics scripts read with `window.__fltk_webview_device`, or restores them when it's null,
+/// when `window.__fltk_webview_device_apply()` is called
+const DEVICE_JS: &str = r#"(function() {
+    if (window.__fltk_webview_device_apply) return;
+    var saved = [];
+    var override = function(obj, name, value) {
+        saved.push([obj, name, Object.getOwnPropertyDescriptor(obj, name)]);
+        Object.defineProperty(obj, name, { get: function() { return value; }, configurable: true });
+    };
+    window.__fltk_webview_device_apply = function() {
+        saved.reverse().forEach(function(s) {
+            if (s[2]) Object.defineProperty(s[0], s[1], s[2]); else delete s[0][s[1]];
+        });
+        saved = [];
+        var d = window.__fltk_webview_device;
+        if (d) {
+            override(window, 'innerWidth', d.width);
+            override(window, 'outerWidth', d.width);
+            override(window, 'innerHeight', d.height);
+            override(window, 'outerHeight', d.height);
+            override(window, 'devicePixelRatio', d.ratio);
+            override(screen, 'width', d.width);
+            override(screen, 'availWidth', d.width);
+            override(screen, 'height', d.height);
+            override(screen, 'availHeight', d.height);
+            override(navigator, 'maxTouchPoints', d.touch ? 5 : 0);
+            if (d.touch) override(window, 'ontouchstart', null);
+            if (d.userAgent !== null) override(navigator, 'userAgent', d.userAgent);
+        }
+        window.dispatchEvent(new Event('resize'));
+    };
+})()"#;
+
+/// Gives scripts the time zone and locale of `window.__fltk_webview_intl`, where they're set,
+/// by wrapping the `Intl` constructors and the methods of `Date` depending on them
+const INTL_JS: &str = r#"(function() {
+    if (window.__fltk_webview_intl) return;
+    var o = window.__fltk_webview_intl = { timeZone: null, locale: null };
+    var locales = function(l) { return l === undefined && o.locale ? o.locale : l; };
+    var zoned = function(options) {
+        if (!o.timeZone || (options && options.timeZone !== undefined)) return options;
+        return Object.assign({}, options, { timeZone: o.timeZone });
+    };
+    var wrap = function(name, useZone) {
+        var Real = Intl[name];
+        if (!Real) return;
+        var Wrapped = function(l, options) {
+            var args = [locales(l), useZone ? zoned(options) : options];
+            return new.target ? Reflect.construct(Real, args, new.target) : Real.apply(this, args);
+        };
+        Wrapped.prototype = Real.prototype;
+        Wrapped.supportedLocalesOf = Real.supportedLocalesOf;
+        Object.defineProperty(Intl, name, { value: Wrapped, writable: true, configurable: true });
+    };
+    wrap('DateTimeFormat', true);
+    ['NumberFormat', 'Collator', 'PluralRules', 'RelativeTimeFormat', 'ListFormat'].forEach(function(n) {
+        wrap(n, false);
+    });
+    var proto = Date.prototype, getTime = proto.getTime, formats = {};
+    ['toLocaleString', 'toLocaleDateString', 'toLocaleTimeString'].forEach(function(m) {
+        var real = proto[m];
+        proto[m] = function(l, options) { return real.call(this, locales(l), zoned(options)); };
+    });
+    var toLocaleString = Number.prototype.toLocaleString;
+    Number.prototype.toLocaleString = function(l, options) {
+        return toLocaleString.call(this, locales(l), options);
+    };
+    var localeCompare = String.prototype.localeCompare;
+    String.prototype.localeCompare = function(that, l, options) {
+        return localeCompare.call(this, that, locales(l), options);
+    };
+    // The offset of the zone at the date, in minutes, as getTimezoneOffset reports it
+    var offset = function(date) {
+        var t = getTime.call(date);
+        if (isNaN(t)) return NaN;
+        var f = formats[o.timeZone] = formats[o.timeZone] || new Intl.DateTimeFormat('en-US', {
+            timeZone: o.timeZone, hourCycle: 'h23', year: 'numeric', month: 'numeric',
+            day: 'numeric', hour: 'numeric', minute: 'numeric', second: 'numeric'
+        });
+        var p = {};
+        f.formatToParts(t).forEach(function(part) { p[part.type] = +part.value; });
+        var local = Date.UTC(p.year, p.month - 1, p.day, p.hour % 24, p.minute, p.second);
+        return Math.round((Math.floor(t / 1000) * 1000 - local) / 60000);
+    };
+    var getTimezoneOffset = proto.getTimezoneOffset;
+    proto.getTimezoneOffset = function() {
+        return o.timeZone ? offset(this) : getTimezoneOffset.call(this);
+    };
+    // The date whose UTC components are the local ones in the zone
+    var shifted = function(date) { return new Date(getTime.call(date) - offset(date) * 60000); };
+    ['FullYear', 'Month', 'Date', 'Day', 'Hours', 'Minutes', 'Seconds', 'Milliseconds'].forEach(function(n) {
+        var real = proto['get' + n];
+        proto['get' + n] = function() {
+            return o.timeZone ? shifted(this)['getUTC' + n]() : real.call(this);
+        };
+    });
+    var pad = function(n) { return (n < 10 ? '0' : '') + n; };
+    var datePart = function(d) {
+        var s = shifted(d).toUTCString().split(' ');
+        return s[0].replace(',', '') + ' ' + s[2] + ' ' + s[1] + ' ' + s[3];
+    };
+    var timePart = function(d) {
+        var s = shifted(d), off = -offset(d), abs = Math.abs(off);
+        var name = new Intl.DateTimeFormat('en-US', { timeZone: o.timeZone, timeZoneName: 'long' })
+            .formatToParts(getTime.call(d)).filter(function(p) { return p.type === 'timeZoneName'; });
+        return pad(s.getUTCHours()) + ':' + pad(s.getUTCMinutes()) + ':' + pad(s.getUTCSeconds()) +
+            ' GMT' + (off < 0 ? '-' : '+') + pad(Math.floor(abs / 60)) + pad(abs % 60) +
+            (name.length ? ' (' + name[0].value + ')' : '');
+    };
+    var stringify = function(m, f) {
+        var real = proto[m];
+        proto[m] = function() {
+            if (!o.timeZone || isNaN(getTime.call(this))) return real.call(this);
+            return f(this);
+        };
+    };
+    stringify('toString', function(d) { return datePart(d) + ' ' + timePart(d); });
+    stringify('toDateString', datePart);
+    stringify('toTimeString', timePart);
+})()"#;
+
+impl EmulationExt for Webview {
+    fn emulate_device(&mut self, metrics: Option<DeviceMetrics>) {
+        let user_agent = metrics.as_ref().and_then(|m| m.user_agent.as_deref());
+        #[cfg(not(target_os = "windows"))]
+        unsafe {
+            let native = user_agent.and_then(|ua| std::ffi::CString::new(ua).ok());
+            fltk_webview_sys::my_set_user_agent(
+                self.get_window(),
+                native.as_ref().map_or(std::ptr::null(), |ua| ua.as_ptr()),
+            );
+        }
+        // Only overridden in the page on Windows
+        #[cfg(not(target_os = "windows"))]
+        let user_agent: Option<&str> = None;
+        let device = match &metrics {
+            Some(m) => format!(
+                "{{ width: {}, height: {}, ratio: {}, touch: {}, userAgent: {} }}",
+                m.width,
+                m.height,
+                if m.device_pixel_ratio > 0.0 && m.device_pixel_ratio.is_finite() {
+                    m.device_pixel_ratio
+                } else {
+                    1.0
+                },
+                m.touch,
+                user_agent.map_or("null".to_string(), utils::quote)
+            ),
+            None => "null".to_string(),
+        };
+        let js = format!(
+            "{};\nwindow.__fltk_webview_device = {};\nwindow.__fltk_webview_device_apply();",
+            DEVICE_JS, device
+        );
+        crate::scripts::set_script(self, "device", &js);
+    }
+
+    fn override_timezone(&mut self, tz: Option<&str>) -> Result<(), WebviewError> {
+        if let Some(tz) = tz.filter(|tz| !is_timezone(tz)) {
+            return Err(WebviewError::InvalidArgument(format!("time zone: {}", tz)));
+        }
+        let js = format!(
+            "{};\nwindow.__fltk_webview_intl.timeZone = {};",
+            INTL_JS,
+            tz.map_or("null".to_string(), utils::quote)
+        );
+        crate::scripts::set_script(self, "timezone", &js);
+        Ok(())
+    }
+
+    fn override_locale(&mut self, locale: Option<&str>) -> Result<(), WebviewError> {
+        if let Some(locale) = locale.filter(|l| !settings::is_language_tag(l)) {
+            return Err(WebviewError::InvalidArgument(format!("locale: {}", locale)));
+        }
+        let js = format!(
+            "{};\nwindow.__fltk_webview_intl.locale = {};",
+            INTL_JS,
+            locale.map_or("null".to_string(), utils::quote)
+        );
+        crate::scripts::set_script(self, "locale", &js);
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+use std::fmt;
+
+/// Errors reported by fltk-webview
+#[derive(Debug)]
+pub enum WebviewError {
+    /// The operation isn't supported by the current backend
+    Unsupported,
+    /// A JavaScript error was thrown
+    Js(String),
+    /// The URL was rejected
+    InvalidUrl(String),
+    /// An argument was rejected
+    InvalidArgument(String),
+    /// An error from the operating system
+    Io(std::io::Error),
+    /// The operation didn't complete in time
+    Timeout,
+    /// The embedding window wasn't shown in time
+    WindowNotShown,
+    /// The display server can't host webviews: on Linux, fltk or GTK run on Wayland,
+    /// while webviews are embedded through X11
+    UnsupportedDisplay,
+}
+
+impl fmt::Display for WebviewError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WebviewError::Unsupported => write!(f, "Operation not supported by this backend"),
+            WebviewError::Js(e) => write!(f, "JavaScript error: {}", e),
+            WebviewError::InvalidUrl(url) => write!(f, "Invalid URL: {}", url),
+            WebviewError::InvalidArgument(arg) => write!(f, "Invalid argument: {}", arg),
+            WebviewError::Io(e) => write!(f, "IO error: {}", e),
+            WebviewError::Timeout => write!(f, "Operation timed out"),
+            WebviewError::WindowNotShown => write!(f, "The embedding window isn't shown"),
+            WebviewError::UnsupportedDisplay => {
+                write!(
+                    f,
+                    "Webviews can only be embedded on X11 or XWayland, set FLTK_BACKEND=x11 \
+                     or call app::disable_wayland before creating the App"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for WebviewError {}
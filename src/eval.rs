@@ -0,0 +1,164 @@
+use crate::{state, utils, Webview, WebviewError};
+use std::collections::HashMap;
+use tinyjson::JsonValue;
+
+type EvalCallback = Box<dyn FnOnce(Result<String, String>)>;
+
+/// Pending evaluations of a webview
+#[derive(Default)]
+pub(crate) struct EvalState {
+    callbacks: HashMap<u64, EvalCallback>,
+    next_id: u64,
+    /// Whether the binding receiving the results is installed
+    bound: bool,
+}
+
+/// The reserved binding receiving evaluation results
+const RESULT_BINDING: &str = "__fltk_webview_eval";
+
+/// Evaluate `js` and pass its result to `f` on the main thread.
+/// The value is JSON-encoded, promises are awaited first, and thrown errors are passed as `Err`
+pub(crate) fn eval_with<F: FnOnce(Result<String, String>) + 'static>(wv: &Webview, js: &str, f: F) {
+    let (id, first) = state::with(wv, |s| {
+        let e = &mut s.eval;
+        e.next_id += 1;
+        e.callbacks.insert(e.next_id, Box::new(f));
+        (e.next_id, !std::mem::replace(&mut e.bound, true))
+    });
+    if first {
+        let key = wv.get_window() as usize;
+        wv.bind(RESULT_BINDING, move |_seq, req| {
+            let args = utils::parse_args(req);
+            let id = match args.first().and_then(|id| id.get::<f64>()) {
+                Some(id) => *id as u64,
+                None => return,
+            };
+            let ok = matches!(args.get(1).and_then(|ok| ok.get::<bool>()), Some(true));
+            let value = args
+                .get(2)
+                .and_then(|v| v.get::<String>())
+                .cloned()
+                .unwrap_or_default();
+            if let Some(cb) = state::with_key(key, |s| s.eval.callbacks.remove(&id)) {
+                utils::guard(|| cb(if ok { Ok(value) } else { Err(value) }));
+            }
+        });
+    }
+    wv.eval(&format!(
+        r#"(function() {{
+    var id = {};
+    Promise.resolve().then(function() {{ return (0, eval)({}); }}).then(function(r) {{
+        var s = JSON.stringify(r);
+        window.{}(id, true, s === undefined ? 'null' : s);
+    }}, function(e) {{
+        window.{}(id, false, String(e));
+    }});
+}})()"#,
+        id,
+        utils::quote(js),
+        RESULT_BINDING,
+        RESULT_BINDING
+    ));
+}
+
+/// Defines `window.__fltk_webview_batch`, which runs each script in its own function scope,
+/// resolving to the `[ok, value]` pair of each one, where `value` is JSON-encoded, or the error.
+/// Direct `eval` keeps the declarations of a script in its scope, and it's called from a function
+/// defined at the top level, so scripts only see globals
+const BATCH_JS: &str = r#"window.__fltk_webview_evaluate = window.__fltk_webview_evaluate ||
+    function(__fltk_webview_script) { return eval(__fltk_webview_script); };
+(function() {
+    if (window.__fltk_webview_batch) return;
+    var failed = function(e, report) {
+        if (report) console.error(e);
+        return [false, String(e)];
+    };
+    window.__fltk_webview_batch = function(scripts, report) {
+        return Promise.all(scripts.map(function(script) {
+            var result;
+            try {
+                result = window.__fltk_webview_evaluate(script);
+            } catch (e) {
+                return failed(e, report);
+            }
+            return Promise.resolve(result).then(function(r) {
+                var s = JSON.stringify(r);
+                return [true, s === undefined ? 'null' : s];
+            }, function(e) {
+                return failed(e, report);
+            });
+        }));
+    };
+})()"#;
+
+/// Evaluation of several scripts at once
+pub trait EvalExt {
+    /// Evaluate `scripts` in order, each in its own function scope, through a single evaluation instead of one per script.
+    /// A script throwing doesn't stop the next ones
+    fn eval_batch(&mut self, scripts: &[&str]);
+    /// Like `eval_batch`, passing the result of each script to `f` on the main thread, in order.
+    /// Results are JSON-encoded, promises are awaited, and thrown errors are passed as `WebviewError::Js`.
+    /// Should the batch itself fail, e.g. when the page forbids `eval` through its content security policy,
+    /// every result is that error
+    fn eval_batch_with_result<F: FnOnce(Vec<Result<String, WebviewError>>) + 'static>(
+        &mut self,
+        scripts: &[&str],
+        f: F,
+    );
+}
+
+/// The script running `scripts` through `window.__fltk_webview_batch`
+fn batch_js(scripts: &[&str], report: bool) -> String {
+    let scripts: Vec<String> = scripts.iter().map(|s| utils::quote(s)).collect();
+    format!(
+        "{};\nwindow.__fltk_webview_batch([{}], {})",
+        BATCH_JS,
+        scripts.join(", "),
+        report
+    )
+}
+
+impl EvalExt for Webview {
+    fn eval_batch(&mut self, scripts: &[&str]) {
+        if scripts.is_empty() {
+            return;
+        }
+        self.eval(&format!("{};", batch_js(scripts, true)));
+    }
+
+    fn eval_batch_with_result<F: FnOnce(Vec<Result<String, WebviewError>>) + 'static>(
+        &mut self,
+        scripts: &[&str],
+        f: F,
+    ) {
+        let count = scripts.len();
+        eval_with(self, &batch_js(scripts, false), move |res| {
+            let results = match res.map(|r| r.parse()) {
+                Ok(Ok(JsonValue::Array(results))) => results
+                    .iter()
+                    .map(|r| match r {
+                        JsonValue::Array(pair) => {
+                            let value = pair
+                                .get(1)
+                                .and_then(|v| v.get::<String>())
+                                .cloned()
+                                .unwrap_or_default();
+                            match pair.first() {
+                                Some(JsonValue::Boolean(true)) => Ok(value),
+                                _ => Err(WebviewError::Js(value)),
+                            }
+                        }
+                        _ => Err(WebviewError::Js("malformed result".to_string())),
+                    })
+                    .collect(),
+                Ok(_) => (0..count)
+                    .map(|_| Err(WebviewError::Js("malformed results".to_string())))
+                    .collect(),
+                Err(e) => (0..count)
+                    .map(|_| Err(WebviewError::Js(e.clone())))
+                    .collect(),
+            };
+            f(results);
+        });
+    }
+}
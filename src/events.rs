@@ -0,0 +1,439 @@
+use crate::{eval, state, utils, Webview};
+use fltk::app;
+use std::{
+    cell::RefCell,
+    ffi::CStr,
+    os::raw,
+    path::PathBuf,
+    rc::Rc,
+    sync::mpsc,
+    time::{Duration, Instant},
+};
+use tinyjson::JsonValue;
+
+/// An event of a webview, as received from `EventsExt::events`
+#[derive(Debug, Clone, PartialEq)]
+pub enum WebviewEvent {
+    /// The URL of the page changed
+    Navigated(String),
+    /// `document.title` changed
+    TitleChanged(String),
+    /// The load progress estimate changed, between 0.0 and 1.0
+    Progress(f64),
+    /// The page requested a new window for this URL, see `set_new_window_handler`
+    NewWindowRequested(String),
+    /// The navigation to a URL failed or timed out, with the reason,
+    /// see `set_error_page` and `set_navigation_timeout`
+    NavigationFailed(String, String),
+}
+
+/// Send an event to the receivers returned by `events`, dropping disconnected ones
+pub(crate) fn emit(key: usize, event: WebviewEvent) {
+    match &event {
+        WebviewEvent::Navigated(url) => crate::console::log(key, &format!("navigated: {}", url)),
+        WebviewEvent::NavigationFailed(url, error) => {
+            crate::console::log(key, &format!("navigation failed: {}: {}", url, error))
+        }
+        _ => (),
+    }
+    state::with_key(key, |s| {
+        s.event_senders.retain(|tx| tx.send(event.clone()).is_ok())
+    });
+}
+
+extern "C" fn title_cb(title: *const raw::c_char, data: *mut raw::c_void) {
+    let title = unsafe { CStr::from_ptr(title) }.to_string_lossy();
+    if let Some(handler) = state::with_key(data as usize, |s| s.title_handler.clone()) {
+        utils::guard(|| (handler.borrow_mut())(&title));
+    }
+    emit(
+        data as usize,
+        WebviewEvent::TitleChanged(title.into_owned()),
+    );
+}
+
+extern "C" fn progress_cb(progress: f64, data: *mut raw::c_void) {
+    let handler = state::with_key(data as usize, |s| {
+        s.progress = progress;
+        s.progress_handler.clone()
+    });
+    if let Some(handler) = handler {
+        utils::guard(|| (handler.borrow_mut())(progress));
+    }
+    emit(data as usize, WebviewEvent::Progress(progress));
+    // Pages report their progress from when their document is created until it's complete
+    #[cfg(target_os = "windows")]
+    loading_cb((progress < 1.0) as _, data);
+}
+
+/// Record the loading state, calling the handler when it changed
+pub(crate) extern "C" fn loading_cb(loading: raw::c_int, data: *mut raw::c_void) {
+    let loading = loading != 0;
+    let handler = state::with_key(data as usize, |s| {
+        if std::mem::replace(&mut s.loading, loading) == loading {
+            None
+        } else {
+            s.loading_handler.clone()
+        }
+    });
+    if let Some(handler) = handler {
+        utils::guard(|| (handler.borrow_mut())(loading));
+    }
+}
+
+extern "C" fn uri_cb(uri: *const raw::c_char, data: *mut raw::c_void) {
+    let uri = unsafe { CStr::from_ptr(uri) }.to_string_lossy();
+    emit(data as usize, WebviewEvent::Navigated(uri.into_owned()));
+}
+
+#[cfg(not(target_os = "windows"))]
+extern "C" fn drop_cb(paths: *const raw::c_char, data: *mut raw::c_void) {
+    let paths = unsafe { CStr::from_ptr(paths) }.to_string_lossy();
+    dropped(data as usize, &paths);
+}
+
+/// Pass newline-separated dropped files to the drop handler
+pub(crate) fn dropped(key: usize, paths: &str) {
+    let paths: Vec<PathBuf> = paths
+        .lines()
+        .filter(|p| !p.is_empty())
+        .map(|p| PathBuf::from(p.strip_prefix("file://").unwrap_or(p)))
+        .collect();
+    if let Some(handler) = state::with_key(key, |s| s.drop_handler.clone()) {
+        utils::guard(|| (handler.borrow_mut())(paths));
+    }
+}
+
+/// Page event handlers, all called on the main thread
+pub trait EventsExt {
+    /// Set the handler called when `document.title` changes
+    fn on_title_changed<F: FnMut(&str) + 'static>(&mut self, f: F);
+    /// Set the handler receiving load progress estimates, between 0.0 and 1.0
+    fn on_progress<F: FnMut(f64) + 'static>(&mut self, f: F);
+    /// Set the handler receiving the paths of files dropped onto the webview or its embedding window,
+    /// instead of letting the page handle the drop. Drops of anything else still reach the page
+    fn set_drop_handler<F: FnMut(Vec<PathBuf>) + 'static>(&mut self, f: F);
+    /// Set the handler called when the top-level fltk window embedding the webview is closed,
+    /// however deeply the webview is nested in groups. Returning false keeps the window open.
+    /// Only applies to webviews embedded in fltk windows
+    fn on_close<F: FnMut() -> bool + 'static>(&mut self, f: F);
+    /// Set the handler called with `true` when an element of the page enters fullscreen, e.g. a video player,
+    /// and `false` when it leaves it. Returning false when entering denies the request
+    fn on_fullscreen_request<F: FnMut(bool) -> bool + 'static>(&mut self, f: F);
+    /// Set the handler called when a page asks to stay through `beforeunload`, e.g. to warn about unsaved changes,
+    /// when it's navigated away from or the webview is closed, returning whether to leave the page anyway
+    fn set_before_unload_handler<F: FnMut(&str) -> bool + 'static>(&mut self, f: F);
+    /// Get a receiver of all the events of the webview, as an alternative to the handlers.
+    /// Events are sent on the main thread, and can be drained from there, e.g. in an fltk timeout.
+    /// Dropping the receiver stops its events
+    fn events(&mut self) -> mpsc::Receiver<WebviewEvent>;
+}
+
+/// Records the `beforeunload` listeners of the page, and defines `window.__fltk_webview_before_unload`,
+/// which calls them as if the page were unloaded, returning the message of the first one asking to stay,
+/// empty if it gave none, or null
+const BEFORE_UNLOAD_JS: &str = r#"(function() {
+    if (window.__fltk_webview_before_unload) return;
+    var listeners = [];
+    var add = window.addEventListener, remove = window.removeEventListener;
+    window.addEventListener = function(type, listener) {
+        if (type === 'beforeunload' && listener && listeners.indexOf(listener) < 0) listeners.push(listener);
+        return add.apply(this, arguments);
+    };
+    window.removeEventListener = function(type, listener) {
+        if (type === 'beforeunload') listeners = listeners.filter(function(l) { return l !== listener; });
+        return remove.apply(this, arguments);
+    };
+    window.__fltk_webview_before_unload = function() {
+        var handlers = listeners.slice();
+        if (typeof window.onbeforeunload === 'function') handlers.push(window.onbeforeunload);
+        for (var i = 0; i < handlers.length; i++) {
+            var prevented = false, message = '';
+            var e = {
+                type: 'beforeunload', target: window, currentTarget: window, cancelable: true,
+                preventDefault: function() { prevented = true; },
+                get returnValue() { return message; },
+                set returnValue(v) { message = String(v); }
+            };
+            var h = handlers[i], r;
+            try {
+                r = typeof h === 'function' ? h.call(window, e) : h.handleEvent(e);
+            } catch (err) {
+                continue;
+            }
+            // Only the return value of the event handler property asks to stay
+            if (h === window.onbeforeunload && r !== undefined && r !== null) return String(r);
+            if (message || prevented) return message;
+        }
+        return null;
+    };
+})()"#;
+
+/// How long closing waits for the page to answer whether it can be unloaded
+const UNLOAD_TIMEOUT: Duration = Duration::from_secs(1);
+
+#[cfg(not(target_os = "windows"))]
+extern "C" fn before_unload_cb(message: *const raw::c_char, data: *mut raw::c_void) -> raw::c_int {
+    let message = unsafe { CStr::from_ptr(message) }.to_string_lossy();
+    confirm_leave(data as usize, &message) as _
+}
+
+/// Ask the before unload handler whether to leave a page asking to stay with `message`,
+/// leaving it without a handler
+fn confirm_leave(key: usize, message: &str) -> bool {
+    match state::with_key(key, |s| s.before_unload.as_ref().map(|(h, _)| h.clone())) {
+        Some(handler) => utils::guard(|| (handler.borrow_mut())(message)).unwrap_or(true),
+        None => true,
+    }
+}
+
+/// Ask the page whether it can be unloaded, and if it asks to stay, the before unload handler.
+/// Runs the fltk event loop until the page answered, for up to `UNLOAD_TIMEOUT`,
+/// and pages which don't answer are left
+pub(crate) fn confirm_unload(key: usize) -> bool {
+    let wv = match state::with_key(key, |s| s.before_unload.as_ref().map(|(_, wv)| wv.clone())) {
+        Some(wv) => wv,
+        None => return true,
+    };
+    let answer: Rc<RefCell<Option<Option<String>>>> = Rc::default();
+    eval::eval_with(
+        &wv,
+        "window.__fltk_webview_before_unload ? window.__fltk_webview_before_unload() : null",
+        {
+            let answer = answer.clone();
+            move |res| {
+                let message = match res.ok().and_then(|r| r.parse().ok()) {
+                    Some(JsonValue::String(message)) => Some(message),
+                    _ => None,
+                };
+                *answer.borrow_mut() = Some(message);
+            }
+        },
+    );
+    let end = Instant::now() + UNLOAD_TIMEOUT;
+    while answer.borrow().is_none() && Instant::now() < end {
+        if app::wait_for(0.01).is_err() {
+            break;
+        }
+    }
+    let message = answer.borrow_mut().take().flatten();
+    match message {
+        Some(message) => confirm_leave(key, &message),
+        None => true,
+    }
+}
+
+/// Ask the fullscreen handler whether an element can enter fullscreen, allowing it without a handler
+fn fullscreen_request(key: usize, entering: bool) -> bool {
+    match state::with_key(key, |s| s.fullscreen_handler.clone()) {
+        Some(handler) => utils::guard(|| (handler.borrow_mut())(entering)).unwrap_or(false),
+        None => true,
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+extern "C" fn fullscreen_cb(entering: raw::c_int, data: *mut raw::c_void) -> raw::c_int {
+    fullscreen_request(data as usize, entering != 0) as _
+}
+
+/// Reports the fullscreen changes of the page, leaving fullscreen when the application denies it
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+const FULLSCREEN_JS: &str = r#"(function() {
+    if (window.__fltk_webview_fullscreen_hooked) return;
+    window.__fltk_webview_fullscreen_hooked = true;
+    var prefixed = !('onfullscreenchange' in document);
+    document.addEventListener(prefixed ? 'webkitfullscreenchange' : 'fullscreenchange', function() {
+        var entering = !!(prefixed ? document.webkitFullscreenElement : document.fullscreenElement);
+        window.__fltk_webview_fullscreen(entering).then(function(allowed) {
+            if (entering && !allowed) {
+                if (prefixed) document.webkitExitFullscreen(); else document.exitFullscreen();
+            }
+        });
+    });
+})()"#;
+
+/// Connect the native fullscreen signals, once per webview
+fn hook_fullscreen(wv: &Webview) {
+    if !state::with(wv, |s| s.hooks.insert("fullscreen")) {
+        return;
+    }
+    let win = wv.get_window();
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    unsafe {
+        fltk_webview_sys::my_on_fullscreen(win, fullscreen_cb, win);
+    }
+    #[cfg(any(target_os = "macos", target_os = "windows"))]
+    {
+        #[cfg(target_os = "macos")]
+        unsafe {
+            fltk_webview_sys::my_enable_fullscreen(win);
+        }
+        let w = wv.clone();
+        wv.bind("__fltk_webview_fullscreen", move |seq, req| {
+            let args = utils::parse_args(req);
+            let entering = args.first().and_then(|e| e.get::<bool>()) == Some(&true);
+            let allowed = fullscreen_request(win as usize, entering);
+            w.return_(seq, 0, &allowed.to_string());
+        });
+        wv.init(FULLSCREEN_JS);
+        wv.eval(FULLSCREEN_JS);
+    }
+}
+
+/// Connect the native title signal, once per webview
+fn hook_title(wv: &Webview) {
+    if !state::with(wv, |s| s.hooks.insert("title")) {
+        return;
+    }
+    let win = wv.get_window();
+    #[cfg(not(target_os = "windows"))]
+    unsafe {
+        fltk_webview_sys::my_on_title_changed(win, title_cb, win);
+    }
+    #[cfg(target_os = "windows")]
+    {
+        wv.bind("__fltk_webview_title", move |_seq, req| {
+            let args = crate::utils::parse_args(req);
+            if let Some(title) = args.first().and_then(|t| t.get::<String>()) {
+                let title = std::ffi::CString::new(title.as_str()).unwrap_or_default();
+                title_cb(title.as_ptr(), win);
+            }
+        });
+        let js = r#"(function() {
+    var last = null;
+    var report = function() {
+        if (document.title !== last) {
+            last = document.title;
+            window.__fltk_webview_title(last);
+        }
+    };
+    new MutationObserver(report).observe(document, { subtree: true, childList: true, characterData: true });
+    document.addEventListener('DOMContentLoaded', report);
+})()"#;
+        wv.init(js);
+        wv.eval(js);
+    }
+}
+
+/// Connect the native progress signal, once per webview
+pub(crate) fn hook_progress(wv: &Webview) {
+    if !state::with(wv, |s| s.hooks.insert("progress")) {
+        return;
+    }
+    let win = wv.get_window();
+    #[cfg(not(target_os = "windows"))]
+    unsafe {
+        fltk_webview_sys::my_on_progress(win, progress_cb, win);
+    }
+    #[cfg(target_os = "windows")]
+    {
+        wv.bind("__fltk_webview_progress", move |_seq, req| {
+            let args = crate::utils::parse_args(req);
+            if let Some(progress) = args.first().and_then(|p| p.get::<f64>()) {
+                progress_cb(*progress, win);
+            }
+        });
+        wv.init(
+            r#"(function() {
+    var states = { loading: 0.3, interactive: 0.7, complete: 1.0 };
+    window.__fltk_webview_progress(0.1);
+    var timer = setInterval(function() {
+        window.__fltk_webview_progress(states[document.readyState]);
+        if (document.readyState === 'complete') clearInterval(timer);
+    }, 50);
+})()"#,
+        );
+    }
+}
+
+/// Connect the native URL signal, once per webview.
+/// On Windows, pages report their URL when they start
+pub(crate) fn hook_uri(wv: &Webview) {
+    if !state::with(wv, |s| s.hooks.insert("uri")) {
+        return;
+    }
+    let win = wv.get_window();
+    #[cfg(not(target_os = "windows"))]
+    unsafe {
+        fltk_webview_sys::my_on_uri_changed(win, uri_cb, win);
+    }
+    #[cfg(target_os = "windows")]
+    {
+        wv.bind("__fltk_webview_uri", move |_seq, req| {
+            let args = crate::utils::parse_args(req);
+            if let Some(uri) = args.first().and_then(|u| u.get::<String>()) {
+                let uri = std::ffi::CString::new(uri.as_str()).unwrap_or_default();
+                uri_cb(uri.as_ptr(), win);
+            }
+        });
+        wv.init("window.__fltk_webview_uri(location.href)");
+    }
+}
+
+impl EventsExt for Webview {
+    fn on_title_changed<F: FnMut(&str) + 'static>(&mut self, f: F) {
+        state::with(self, |s| s.title_handler = Some(Rc::new(RefCell::new(f))));
+        hook_title(self);
+    }
+
+    fn on_progress<F: FnMut(f64) + 'static>(&mut self, f: F) {
+        state::with(self, |s| {
+            s.progress_handler = Some(Rc::new(RefCell::new(f)))
+        });
+        hook_progress(self);
+    }
+
+    fn events(&mut self) -> mpsc::Receiver<WebviewEvent> {
+        let (tx, rx) = mpsc::channel();
+        state::with(self, |s| s.event_senders.push(tx));
+        hook_uri(self);
+        hook_title(self);
+        hook_progress(self);
+        crate::navigation::hook_load_failed(self);
+        rx
+    }
+
+    fn set_drop_handler<F: FnMut(Vec<PathBuf>) + 'static>(&mut self, f: F) {
+        let first = state::with(self, |s| {
+            s.drop_handler.replace(Rc::new(RefCell::new(f))).is_none()
+        });
+        #[cfg(not(target_os = "windows"))]
+        if first {
+            let win = self.get_window();
+            unsafe {
+                fltk_webview_sys::my_on_drop(win, drop_cb, win);
+            }
+        }
+        #[cfg(target_os = "windows")]
+        let _ = first;
+    }
+
+    fn on_close<F: FnMut() -> bool + 'static>(&mut self, f: F) {
+        state::with(self, |s| s.close_handler = Some(Rc::new(RefCell::new(f))));
+    }
+
+    fn on_fullscreen_request<F: FnMut(bool) -> bool + 'static>(&mut self, f: F) {
+        state::with(self, |s| {
+            s.fullscreen_handler = Some(Rc::new(RefCell::new(f)))
+        });
+        hook_fullscreen(self);
+    }
+
+    fn set_before_unload_handler<F: FnMut(&str) -> bool + 'static>(&mut self, f: F) {
+        let first = state::with(self, |s| {
+            s.before_unload
+                .replace((Rc::new(RefCell::new(f)), self.clone()))
+                .is_none()
+        });
+        if !first {
+            return;
+        }
+        #[cfg(not(target_os = "windows"))]
+        unsafe {
+            let win = self.get_window();
+            fltk_webview_sys::my_on_before_unload(win, before_unload_cb, win);
+        }
+        self.init(BEFORE_UNLOAD_JS);
+        self.eval(BEFORE_UNLOAD_JS);
+    }
+}
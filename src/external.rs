@@ -0,0 +1,158 @@
+use crate::WebviewError;
+
+/// Schemes which can be handed to the system
+const SCHEMES: &[&str] = &["http", "https", "mailto"];
+
+/// Check that `url` is an absolute http(s) or mailto URL, which can't be mistaken for an option or a path
+pub(crate) fn validate(url: &str) -> Result<(), WebviewError> {
+    let invalid = || WebviewError::InvalidUrl(url.to_string());
+    let (scheme, rest) = url.split_once(':').ok_or_else(invalid)?;
+    if !SCHEMES.iter().any(|s| s.eq_ignore_ascii_case(scheme)) {
+        return Err(invalid());
+    }
+    if url.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return Err(invalid());
+    }
+    let valid = if scheme.eq_ignore_ascii_case("mailto") {
+        !rest.is_empty()
+    } else {
+        rest.strip_prefix("//")
+            .is_some_and(|r| !r.is_empty() && !r.starts_with('/'))
+    };
+    if !valid {
+        return Err(invalid());
+    }
+    Ok(())
+}
+
+/// Open `url` in the system's default browser, or mail client for mailto URLs.
+/// Only absolute http, https and mailto URLs are accepted, others return `WebviewError::InvalidUrl`
+/// before anything is spawned
+pub fn open_in_browser(url: &str) -> Result<(), WebviewError> {
+    validate(url)?;
+    #[cfg(target_os = "windows")]
+    {
+        use std::{os::windows::ffi::OsStrExt, ptr};
+        extern "system" {
+            fn ShellExecuteW(
+                hwnd: *mut (),
+                op: *const u16,
+                file: *const u16,
+                params: *const u16,
+                dir: *const u16,
+                show: i32,
+            ) -> isize;
+        }
+        const SW_SHOWNORMAL: i32 = 1;
+        let wide = |s: &str| {
+            std::ffi::OsStr::new(s)
+                .encode_wide()
+                .chain(Some(0))
+                .collect::<Vec<u16>>()
+        };
+        let (op, url) = (wide("open"), wide(url));
+        let ret = unsafe {
+            ShellExecuteW(
+                ptr::null_mut(),
+                op.as_ptr(),
+                url.as_ptr(),
+                ptr::null(),
+                ptr::null(),
+                SW_SHOWNORMAL,
+            )
+        };
+        // Values above 32 indicate success
+        if ret > 32 {
+            Ok(())
+        } else {
+            Err(WebviewError::Io(std::io::Error::last_os_error()))
+        }
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        #[cfg(target_os = "macos")]
+        let opener = "open";
+        #[cfg(not(target_os = "macos"))]
+        let opener = "xdg-open";
+        let mut child = std::process::Command::new(opener)
+            .arg(url)
+            .spawn()
+            .map_err(WebviewError::Io)?;
+        // Reaps the opener once it exits, so it doesn't linger as a zombie
+        std::thread::spawn(move || child.wait());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_web_and_mail_urls() {
+        assert!(validate("http://example.com").is_ok());
+        assert!(validate("HTTPS://example.com/path?q=1#top").is_ok());
+        assert!(validate("mailto:someone@example.com").is_ok());
+    }
+
+    #[test]
+    fn rejects_other_schemes() {
+        for url in [
+            "javascript:alert(1)",
+            "JavaScript://example.com/%0aalert(1)",
+            "file:///etc/passwd",
+            "file://host/share",
+            "data:text/html,hi",
+            "ftp://example.com",
+        ] {
+            assert!(
+                matches!(validate(url), Err(WebviewError::InvalidUrl(_))),
+                "{}",
+                url
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_options_and_paths() {
+        for url in [
+            "",
+            "-",
+            "--help",
+            "-http://example.com",
+            "/etc/passwd",
+            "example.com",
+            "http:",
+            "http:example.com",
+            "http://",
+            "http:///etc/passwd",
+            "mailto:",
+        ] {
+            assert!(
+                matches!(validate(url), Err(WebviewError::InvalidUrl(_))),
+                "{}",
+                url
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_whitespace_and_control_characters() {
+        for url in [
+            " http://example.com",
+            "http://example.com ",
+            "http://example.com/a b",
+            "http://example.com/\tb",
+            "http://example.com/\nb",
+            "http://example.com/\0",
+            "http://example.com/\u{7f}",
+            "http://example.com/\u{a0}",
+        ] {
+            assert!(
+                matches!(validate(url), Err(WebviewError::InvalidUrl(_))),
+                "{:?}",
+                url
+            );
+        }
+    }
+}
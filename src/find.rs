@@ -0,0 +1,126 @@
+use crate::{state, Webview};
+use std::{cell::RefCell, os::raw, rc::Rc};
+
+/// Options of a find-in-page query
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct FindOptions {
+    pub case_sensitive: bool,
+    pub wrap_around: bool,
+}
+
+/// Find-in-page methods.
+/// WebKitGTK uses its find controller, other backends fall back to `window.find`
+pub trait FindExt {
+    /// Highlight the first match of `text` and report the match count to the `on_find_count` handler
+    fn find(&mut self, text: &str, options: FindOptions);
+    /// Move to the next match of the last query
+    fn find_next(&mut self);
+    /// Move to the previous match of the last query
+    fn find_previous(&mut self);
+    /// Clear the highlighted match
+    fn find_clear(&mut self);
+    /// Set the handler receiving the match count of `find`, called on the main thread
+    fn on_find_count<F: FnMut(u32) + 'static>(&mut self, f: F);
+}
+
+extern "C" fn count_cb(count: raw::c_uint, data: *mut raw::c_void) {
+    if let Some(handler) = state::with_key(data as usize, |s| s.find_handler.clone()) {
+        crate::utils::guard(|| (handler.borrow_mut())(count));
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+impl FindExt for Webview {
+    fn find(&mut self, text: &str, options: FindOptions) {
+        let win = self.get_window();
+        let text = std::ffi::CString::new(text).unwrap();
+        unsafe {
+            fltk_webview_sys::my_find(
+                win,
+                text.as_ptr(),
+                options.case_sensitive as _,
+                options.wrap_around as _,
+                count_cb,
+                win,
+            );
+        }
+    }
+
+    fn find_next(&mut self) {
+        unsafe { fltk_webview_sys::my_find_next(self.get_window()) }
+    }
+
+    fn find_previous(&mut self) {
+        unsafe { fltk_webview_sys::my_find_previous(self.get_window()) }
+    }
+
+    fn find_clear(&mut self) {
+        unsafe { fltk_webview_sys::my_find_clear(self.get_window()) }
+    }
+
+    fn on_find_count<F: FnMut(u32) + 'static>(&mut self, f: F) {
+        state::with(self, |s| s.find_handler = Some(Rc::new(RefCell::new(f))));
+    }
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+impl FindExt for Webview {
+    fn find(&mut self, text: &str, options: FindOptions) {
+        state::with(self, |s| s.find_query = Some((text.to_string(), options)));
+        self.eval(&format!(
+            r#"(function() {{
+    var t = {}, cs = {}, hay = document.body ? document.body.innerText : '';
+    window.getSelection().removeAllRanges();
+    window.find(t, cs, false, {});
+    if (!cs) {{ hay = hay.toLowerCase(); t = t.toLowerCase(); }}
+    var n = 0, i = 0;
+    while (t.length && (i = hay.indexOf(t, i)) !== -1) {{ n++; i += t.length; }}
+    window.__fltk_webview_find(n);
+}})()"#,
+            crate::utils::quote(text),
+            options.case_sensitive,
+            options.wrap_around
+        ));
+    }
+
+    fn find_next(&mut self) {
+        find_again(self, false);
+    }
+
+    fn find_previous(&mut self) {
+        find_again(self, true);
+    }
+
+    fn find_clear(&mut self) {
+        state::with(self, |s| s.find_query = None);
+        self.eval("window.getSelection().removeAllRanges()");
+    }
+
+    fn on_find_count<F: FnMut(u32) + 'static>(&mut self, f: F) {
+        let first = state::with(self, |s| {
+            s.find_handler.replace(Rc::new(RefCell::new(f))).is_none()
+        });
+        if first {
+            let key = self.get_window();
+            self.bind("__fltk_webview_find", move |_seq, req| {
+                let args = crate::utils::parse_args(req);
+                if let Some(n) = args.first().and_then(|n| n.get::<f64>()) {
+                    count_cb(*n as _, key);
+                }
+            });
+        }
+    }
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn find_again(wv: &Webview, backwards: bool) {
+    if let Some((text, options)) = state::with(wv, |s| s.find_query.clone()) {
+        wv.eval(&format!(
+            "window.find({}, {}, {}, {})",
+            crate::utils::quote(&text),
+            options.case_sensitive,
+            backwards,
+            options.wrap_around
+        ));
+    }
+}
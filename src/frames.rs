@@ -0,0 +1,24 @@
+use crate::{utils, Webview};
+
+/// Subframe related methods
+pub trait FrameExt {
+    /// Evaluate `js` within a subframe of the top-level document, selected by its name,
+    /// or by its index in `window.frames` if `frame_selector` is a number. Only same-origin frames can be reached
+    fn eval_in_frame(&mut self, frame_selector: &str, js: &str);
+}
+
+impl FrameExt for Webview {
+    fn eval_in_frame(&mut self, frame_selector: &str, js: &str) {
+        let frame = match frame_selector.parse::<usize>() {
+            Ok(index) => index.to_string(),
+            Err(_) => utils::quote(frame_selector),
+        };
+        self.eval(&format!(
+            "(function() {{ var f = window.frames[{}]; \
+            if (!f) throw new Error('No frame ' + {}); f.eval({}); }})()",
+            frame,
+            utils::quote(frame_selector),
+            utils::quote(js)
+        ));
+    }
+}
@@ -0,0 +1,426 @@
+use crate::{Webview, WebviewError};
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+/// Controls whether pages of other origins can access the resources of a virtual host
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum HostAccess {
+    /// Other origins can't access the resources
+    Deny,
+    /// Other origins can access the resources
+    Allow,
+    /// Other origins can embed the resources, but not fetch them through CORS
+    DenyCors,
+}
+
+/// The scheme serving virtual hosts on WebKitGTK
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+const SCHEME: &str = "app";
+
+/// The scheme serving the pages of `register_internal_page`
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+const INTERNAL_SCHEME: &str = "internal";
+
+/// What a virtual host serves
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+#[derive(Clone)]
+pub(crate) enum HostSource {
+    Dir(std::path::PathBuf),
+    #[cfg(feature = "bundle")]
+    Bundle(std::rc::Rc<crate::bundle::Bundle>),
+}
+
+/// Virtual host methods
+pub trait VirtualHostExt {
+    /// Serve the files of `dir` under `https://<host>/`.
+    /// Paths without a file extension that don't exist fall back to `index.html`, for single page apps
+    fn map_virtual_host(
+        &mut self,
+        host: &str,
+        dir: &Path,
+        access: HostAccess,
+    ) -> Result<(), WebviewError>;
+    /// Serve the files of a zip or (gzip or brotli compressed) tar archive under `https://<host>/`,
+    /// as `map_virtual_host` does for a folder, requires the `bundle` feature.
+    /// Returns `WebviewError::InvalidArgument` if the archive can't be read
+    #[cfg(feature = "bundle")]
+    fn serve_bundle(&mut self, host: &str, bundle: &[u8]) -> Result<(), WebviewError>;
+    /// Serve `html` at `internal://<name>`, e.g. for settings or help pages, without a web server.
+    /// Names other than ASCII letters, digits and `-` return `WebviewError::InvalidArgument`
+    fn register_internal_page(&mut self, name: &str, html: &str) -> Result<(), WebviewError>;
+    /// Serve the files of `dir` over HTTP on a random loopback port until the webview is closed, returning the base URL,
+    /// e.g. `http://127.0.0.1:41234/`. Other processes of the machine can read the files too.
+    /// Returns `WebviewError::InvalidArgument` if `dir` isn't a folder, and `WebviewError::Io` if the server can't start
+    fn serve_dir(&mut self, dir: &Path) -> Result<String, WebviewError>;
+}
+
+/// A server started by `serve_dir`, stopped when dropped along with the state of its webview
+pub(crate) struct LocalServer {
+    stop: Arc<AtomicBool>,
+}
+
+impl Drop for LocalServer {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+impl VirtualHostExt for Webview {
+    fn map_virtual_host(
+        &mut self,
+        host: &str,
+        dir: &Path,
+        access: HostAccess,
+    ) -> Result<(), WebviewError> {
+        #[cfg(any(target_os = "macos", target_os = "windows"))]
+        {
+            let _ = (host, dir, access);
+            Err(WebviewError::Unsupported)
+        }
+        #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+        {
+            add_host(self, host, HostSource::Dir(dir.to_path_buf()), access);
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "bundle")]
+    fn serve_bundle(&mut self, host: &str, bundle: &[u8]) -> Result<(), WebviewError> {
+        #[cfg(any(target_os = "macos", target_os = "windows"))]
+        {
+            let _ = (host, bundle);
+            Err(WebviewError::Unsupported)
+        }
+        #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+        {
+            let bundle = crate::bundle::Bundle::parse(bundle)?;
+            let source = HostSource::Bundle(std::rc::Rc::new(bundle));
+            add_host(self, host, source, HostAccess::Deny);
+            Ok(())
+        }
+    }
+
+    fn register_internal_page(&mut self, name: &str, html: &str) -> Result<(), WebviewError> {
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            return Err(WebviewError::InvalidArgument(format!(
+                "internal page name: {:?}",
+                name
+            )));
+        }
+        #[cfg(any(target_os = "macos", target_os = "windows"))]
+        {
+            let _ = html;
+            Err(WebviewError::Unsupported)
+        }
+        #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+        {
+            let win = self.get_window();
+            static REGISTER: std::sync::Once = std::sync::Once::new();
+            REGISTER.call_once(|| unsafe {
+                let scheme = std::ffi::CString::new(INTERNAL_SCHEME).unwrap();
+                fltk_webview_sys::my_register_uri_scheme(win, scheme.as_ptr(), internal_cb);
+            });
+            crate::state::with(self, |s| {
+                s.internal_pages
+                    .insert(name.to_ascii_lowercase(), html.into())
+            });
+            Ok(())
+        }
+    }
+
+    fn serve_dir(&mut self, dir: &Path) -> Result<String, WebviewError> {
+        if !dir.is_dir() {
+            return Err(WebviewError::InvalidArgument(format!(
+                "not a folder: {}",
+                dir.display()
+            )));
+        }
+        let listener = TcpListener::bind("127.0.0.1:0").map_err(WebviewError::Io)?;
+        let addr = listener.local_addr().map_err(WebviewError::Io)?;
+        listener.set_nonblocking(true).map_err(WebviewError::Io)?;
+        let stop = Arc::new(AtomicBool::new(false));
+        thread::spawn({
+            let dir = dir.to_path_buf();
+            let stop = stop.clone();
+            move || serve(listener, dir, stop)
+        });
+        crate::state::with(self, |s| s.servers.push(LocalServer { stop }));
+        Ok(format!("http://{}/", addr))
+    }
+}
+
+/// Accept connections until `stop` is set, answering them in turn
+fn serve(listener: TcpListener, dir: PathBuf, stop: Arc<AtomicBool>) {
+    let host = listener
+        .local_addr()
+        .map(|a| a.to_string())
+        .unwrap_or_default();
+    while !stop.load(Ordering::Relaxed) {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                let _ = stream.set_nonblocking(false);
+                let _ = stream.set_read_timeout(Some(Duration::from_secs(5)));
+                let _ = respond(stream, &dir, &host);
+            }
+            Err(_) => thread::sleep(Duration::from_millis(20)),
+        }
+    }
+}
+
+/// Answer a GET or HEAD request for a file of `dir`
+fn respond(stream: TcpStream, dir: &Path, host: &str) -> std::io::Result<()> {
+    let mut request = String::new();
+    let mut host_matches = false;
+    for line in BufReader::new(&stream).lines() {
+        let line = line?;
+        if line.is_empty() {
+            break;
+        }
+        if request.is_empty() {
+            request = line;
+        } else if let Some((name, value)) = line.split_once(':') {
+            // Keeps other sites from reaching the server through DNS rebinding
+            if name.eq_ignore_ascii_case("host") {
+                host_matches = value.trim() == host;
+            }
+        }
+    }
+    let mut parts = request.split(' ');
+    let (method, target) = (
+        parts.next().unwrap_or_default(),
+        parts.next().unwrap_or("/"),
+    );
+    let path = target
+        .split(|c| c == '?' || c == '#')
+        .next()
+        .unwrap_or_default();
+    let (status, body, mime) = if !host_matches {
+        ("421 Misdirected Request", vec![], "text/plain")
+    } else if method != "GET" && method != "HEAD" {
+        ("405 Method Not Allowed", vec![], "text/plain")
+    } else {
+        match read_file(dir, &percent_decode(path)) {
+            Some((data, mime)) => ("200 OK", data, mime),
+            None => ("404 Not Found", vec![], "text/plain"),
+        }
+    };
+    let mut stream = &stream;
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-cache\r\nConnection: close\r\n\r\n",
+        status,
+        mime,
+        body.len()
+    )?;
+    if method != "HEAD" {
+        stream.write_all(&body)?;
+    }
+    stream.flush()
+}
+
+/// Serves the page registered under the host of `internal://` URLs, at their root
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+extern "C" fn internal_cb(
+    request: *mut std::os::raw::c_void,
+    uri: *const std::os::raw::c_char,
+    win: *mut std::os::raw::c_void,
+) {
+    use std::ffi::{CStr, CString};
+    let uri = unsafe { CStr::from_ptr(uri) }.to_string_lossy();
+    let rest = uri.split_once("://").map(|(_, r)| r).unwrap_or_default();
+    let rest = rest
+        .split(|c| c == '?' || c == '#')
+        .next()
+        .unwrap_or_default();
+    let name = rest.strip_suffix('/').unwrap_or(rest).to_ascii_lowercase();
+    let page = crate::state::with_key(win as usize, |s| s.internal_pages.get(&name).cloned());
+    match page {
+        Some(html) => {
+            let mime = CString::new("text/html").unwrap();
+            unsafe {
+                fltk_webview_sys::my_uri_request_finish(
+                    request,
+                    html.as_ptr(),
+                    html.len(),
+                    mime.as_ptr(),
+                );
+            }
+        }
+        None => {
+            let msg = CString::new(format!("{} not found", uri)).unwrap_or_default();
+            unsafe {
+                fltk_webview_sys::my_uri_request_fail(request, msg.as_ptr());
+            }
+        }
+    }
+}
+
+/// Serve `source` under the scheme of virtual hosts
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn add_host(wv: &Webview, host: &str, source: HostSource, access: HostAccess) {
+    use std::ffi::CString;
+    let win = wv.get_window();
+    let scheme = CString::new(SCHEME).unwrap();
+    static REGISTER: std::sync::Once = std::sync::Once::new();
+    REGISTER.call_once(|| unsafe {
+        fltk_webview_sys::my_register_uri_scheme(win, scheme.as_ptr(), request_cb);
+    });
+    if access == HostAccess::Allow {
+        unsafe {
+            fltk_webview_sys::my_enable_scheme_cors(win, scheme.as_ptr());
+        }
+    }
+    let host = host.to_ascii_lowercase();
+    crate::state::with(wv, |s| s.hosts.insert(host.clone(), source));
+    let host = CString::new(host).unwrap();
+    unsafe {
+        fltk_webview_sys::my_redirect_https_host(win, host.as_ptr(), scheme.as_ptr());
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+extern "C" fn request_cb(
+    request: *mut std::os::raw::c_void,
+    uri: *const std::os::raw::c_char,
+    win: *mut std::os::raw::c_void,
+) {
+    use std::ffi::{CStr, CString};
+    let uri = unsafe { CStr::from_ptr(uri) }.to_string_lossy();
+    let rest = uri.split_once("://").map(|(_, r)| r).unwrap_or_default();
+    let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+    let path = path
+        .split(|c| c == '?' || c == '#')
+        .next()
+        .unwrap_or_default();
+    let source = crate::state::with_key(win as usize, |s| {
+        s.hosts.get(&host.to_ascii_lowercase()).cloned()
+    });
+    let path = percent_decode(path);
+    let file = source.and_then(|source| match source {
+        HostSource::Dir(dir) => read_file(&dir, &path),
+        #[cfg(feature = "bundle")]
+        HostSource::Bundle(bundle) => read_bundle(&bundle, &path),
+    });
+    match file {
+        Some((data, mime)) => {
+            let mime = CString::new(mime).unwrap();
+            unsafe {
+                fltk_webview_sys::my_uri_request_finish(
+                    request,
+                    data.as_ptr(),
+                    data.len(),
+                    mime.as_ptr(),
+                );
+            }
+        }
+        None => {
+            let msg = CString::new(format!("{} not found", uri)).unwrap_or_default();
+            unsafe {
+                fltk_webview_sys::my_uri_request_fail(request, msg.as_ptr());
+            }
+        }
+    }
+}
+
+/// Read the file at `path` within `dir`, along with its MIME type
+fn read_file(dir: &Path, path: &str) -> Option<(Vec<u8>, &'static str)> {
+    use std::path::Component;
+    let rel = Path::new(path.trim_start_matches('/'));
+    // Don't let requests escape the mapped folder
+    if rel.components().any(|c| !matches!(c, Component::Normal(_))) {
+        return None;
+    }
+    let mut file = dir.join(rel);
+    if file.is_dir() {
+        file.push("index.html");
+    } else if !file.exists() && rel.extension().is_none() {
+        file = dir.join("index.html");
+    }
+    let data = std::fs::read(&file).ok()?;
+    Some((data, mime_type(&file)))
+}
+
+/// Read the file at `path` within `bundle`, with the fallbacks of `read_file`
+#[cfg(all(
+    feature = "bundle",
+    not(any(target_os = "macos", target_os = "windows"))
+))]
+fn read_bundle(bundle: &crate::bundle::Bundle, path: &str) -> Option<(Vec<u8>, &'static str)> {
+    let rel = path.trim_start_matches('/');
+    let file = if bundle.is_dir(rel) {
+        format!("{}/index.html", rel.trim_end_matches('/'))
+            .trim_start_matches('/')
+            .to_string()
+    } else if bundle.get(rel).is_none() && Path::new(rel).extension().is_none() {
+        "index.html".to_string()
+    } else {
+        rel.to_string()
+    };
+    let data = bundle.get(&file)?.into_owned();
+    Some((data, mime_type(Path::new(&file))))
+}
+
+fn mime_type(file: &Path) -> &'static str {
+    let ext = file
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    match ext.as_str() {
+        "html" | "htm" => "text/html",
+        "js" | "mjs" => "text/javascript",
+        "css" => "text/css",
+        "json" | "map" => "application/json",
+        "xml" => "application/xml",
+        "txt" => "text/plain",
+        "svg" => "image/svg+xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "ico" => "image/x-icon",
+        "wasm" => "application/wasm",
+        "woff" => "font/woff",
+        "woff2" => "font/woff2",
+        "ttf" => "font/ttf",
+        "otf" => "font/otf",
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        "pdf" => "application/pdf",
+        _ => "application/octet-stream",
+    }
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(b)) => {
+                out.push(b);
+                i += 3;
+            }
+            (b, _) => {
+                out.push(b);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
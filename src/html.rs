@@ -0,0 +1,190 @@
+use crate::{utils, Webview};
+use std::fmt;
+
+/// Wires the elements naming a binding in `data-bind` to the bindings of `window.__fltk_webview_html_bindings`
+const BINDINGS_JS: &str = r#"(function() {
+    var allowed = window.__fltk_webview_html_bindings;
+    var isField = function(el) { return /^(INPUT|SELECT|TEXTAREA)$/.test(el.tagName); };
+    var call = function(el, e) {
+        var name = el.getAttribute('data-bind');
+        if (allowed.indexOf(name) < 0 || typeof window[name] !== 'function') return;
+        var value;
+        if (el.tagName === 'FORM') {
+            e.preventDefault();
+            value = {};
+            new FormData(el).forEach(function(v, k) { if (typeof v === 'string') value[k] = v; });
+        } else if (el.type === 'checkbox' || el.type === 'radio') {
+            value = el.checked;
+        } else if (isField(el)) {
+            value = el.value;
+        } else {
+            value = el.getAttribute('data-value');
+        }
+        window[name](value);
+    };
+    var handle = function(e) {
+        var el = e.target.closest && e.target.closest('[data-bind]');
+        if (!el) return;
+        var form = el.tagName === 'FORM';
+        if (e.type === 'submit' && form || e.type === 'change' && isField(el) ||
+            e.type === 'click' && !form && !isField(el)) {
+            call(el, e);
+        }
+    };
+    ['click', 'change', 'submit'].forEach(function(type) { document.addEventListener(type, handle); });
+})()"#;
+
+/// A complete HTML document built piece by piece, shown through `HtmlExt::show_html`.
+/// Text passed as such is escaped, so user-supplied values can't inject markup or scripts,
+/// while markup, style sheets and scripts are inserted as given.
+/// Its `Display` implementation gives the document
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Html {
+    title: Option<String>,
+    lang: Option<String>,
+    styles: Vec<String>,
+    scripts: Vec<String>,
+    body: String,
+    vars: Vec<(String, String)>,
+    bindings: Vec<String>,
+}
+
+impl Html {
+    /// An empty document
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the title of the document, which is escaped
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    /// Set the language of the document, e.g. `en`
+    pub fn lang(mut self, lang: &str) -> Self {
+        self.lang = Some(lang.to_string());
+        self
+    }
+
+    /// Add a style sheet to the head of the document
+    pub fn style(mut self, css: &str) -> Self {
+        self.styles.push(css.to_string());
+        self
+    }
+
+    /// Add a script, run at the end of the body, once the elements are parsed, in the order of addition
+    pub fn script(mut self, js: &str) -> Self {
+        self.scripts.push(js.to_string());
+        self
+    }
+
+    /// Append trusted `markup` to the body. `{name}` placeholders are replaced with the values set through `var`
+    pub fn body(mut self, markup: &str) -> Self {
+        self.body.push_str(markup);
+        self
+    }
+
+    /// Append `text` to the body, escaped, so it shows as is
+    pub fn text(mut self, text: &str) -> Self {
+        self.body.push_str(&utils::escape_html(text));
+        self
+    }
+
+    /// Replace the `{name}` placeholders of the body with `value`, escaped, including within attribute values.
+    /// Placeholders without a value, and other braces, are left as they are
+    pub fn var(mut self, name: &str, value: &str) -> Self {
+        match self.vars.iter_mut().find(|(n, _)| n == name) {
+            Some(var) => var.1 = value.to_string(),
+            None => self.vars.push((name.to_string(), value.to_string())),
+        }
+        self
+    }
+
+    /// Let the elements of the body call the bound functions named in `names` through their `data-bind` attribute,
+    /// e.g. `<button data-bind="save">`, with the value of fields, the text fields of forms, or their `data-value`
+    pub fn with_bindings(mut self, names: &[&str]) -> Self {
+        self.bindings.extend(names.iter().map(|n| n.to_string()));
+        self
+    }
+
+    /// Fill the placeholders of the body
+    fn fill(&self) -> String {
+        if self.vars.is_empty() {
+            return self.body.clone();
+        }
+        let mut out = String::with_capacity(self.body.len());
+        let mut rest = self.body.as_str();
+        'outer: while let Some(i) = rest.find('{') {
+            out.push_str(&rest[..i]);
+            rest = &rest[i..];
+            for (name, value) in &self.vars {
+                if let Some(r) = rest
+                    .strip_prefix('{')
+                    .and_then(|r| r.strip_prefix(name.as_str()))
+                    .and_then(|r| r.strip_prefix('}'))
+                {
+                    out.push_str(&utils::escape_html(value));
+                    rest = r;
+                    continue 'outer;
+                }
+            }
+            out.push('{');
+            rest = &rest[1..];
+        }
+        out.push_str(rest);
+        out
+    }
+}
+
+/// Keep the contents of a `style` or `script` element from closing it
+fn raw_text(s: &str) -> String {
+    s.replace("</", "<\\/")
+}
+
+impl fmt::Display for Html {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "<!DOCTYPE html>")?;
+        match &self.lang {
+            Some(lang) => writeln!(f, "<html lang=\"{}\">", utils::escape_html(lang))?,
+            None => writeln!(f, "<html>")?,
+        }
+        writeln!(f, "<head>")?;
+        writeln!(f, "<meta charset=\"utf-8\">")?;
+        if let Some(title) = &self.title {
+            writeln!(f, "<title>{}</title>", utils::escape_html(title))?;
+        }
+        for css in &self.styles {
+            writeln!(f, "<style>\n{}\n</style>", raw_text(css))?;
+        }
+        writeln!(f, "</head>")?;
+        writeln!(f, "<body>")?;
+        writeln!(f, "{}", self.fill())?;
+        if !self.bindings.is_empty() {
+            let names: Vec<String> = self.bindings.iter().map(|n| utils::quote(n)).collect();
+            writeln!(
+                f,
+                "<script>\nwindow.__fltk_webview_html_bindings = [{}];\n{};\n</script>",
+                raw_text(&names.join(", ")),
+                BINDINGS_JS
+            )?;
+        }
+        for js in &self.scripts {
+            writeln!(f, "<script>\n{}\n</script>", raw_text(js))?;
+        }
+        writeln!(f, "</body>")?;
+        write!(f, "</html>")
+    }
+}
+
+/// Showing documents built with `Html`
+pub trait HtmlExt {
+    /// Show the document built by `html`, like `Webview::set_html`
+    fn show_html(&mut self, html: Html);
+}
+
+impl HtmlExt for Webview {
+    fn show_html(&mut self, html: Html) {
+        self.set_html(&html.to_string());
+    }
+}
@@ -0,0 +1,226 @@
+use crate::{state, utils, EmbedExt, Webview, WebviewError};
+use fltk::prelude::WidgetExt;
+use std::{
+    cell::RefCell,
+    path::PathBuf,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+#[cfg(target_os = "windows")]
+extern "system" {
+    fn CreateMutexW(attributes: *mut (), initial_owner: i32, name: *const u16) -> *mut ();
+    fn GetLastError() -> u32;
+    fn CloseHandle(handle: *mut ()) -> i32;
+}
+
+#[cfg(target_os = "windows")]
+type Listener = std::net::TcpListener;
+#[cfg(not(target_os = "windows"))]
+type Listener = std::os::unix::net::UnixListener;
+
+type ActivateHandler = Rc<RefCell<Option<Box<dyn FnMut()>>>>;
+
+/// Check that `id` can be part of a file name
+fn validate(id: &str) -> Result<(), WebviewError> {
+    if id.is_empty()
+        || id.len() > 64
+        || !id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_.".contains(c))
+    {
+        return Err(WebviewError::InvalidArgument(format!(
+            "instance id: {:?}",
+            id
+        )));
+    }
+    Ok(())
+}
+
+/// Where the instance of `id` is reached, with the given extension.
+/// Sockets go to the user's runtime directory on Linux, falling back to the shared temporary directory,
+/// where the user name keeps the instances of different users apart
+fn path(id: &str, ext: &str) -> PathBuf {
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    if let Some(dir) = std::env::var_os("XDG_RUNTIME_DIR") {
+        return PathBuf::from(dir).join(format!("fltk-webview-{}.{}", id, ext));
+    }
+    let user = std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_default();
+    std::env::temp_dir().join(format!("fltk-webview-{}-{}.{}", user, id, ext))
+}
+
+/// Keeps an application to a single running instance per user, e.g. so that launching it again
+/// focuses its existing webview window instead of opening another one
+pub struct SingleInstance {
+    stop: Arc<AtomicBool>,
+    activations: Option<mpsc::Receiver<()>>,
+    handler: ActivateHandler,
+    path: PathBuf,
+    #[cfg(target_os = "windows")]
+    mutex: *mut (),
+}
+
+impl SingleInstance {
+    /// Claim `id`, which identifies the application and may only hold ASCII letters, digits, `-`, `_` and `.`,
+    /// returning `Ok(None)` if another instance holds it, which is then asked to activate.
+    /// The application would usually exit in that case.
+    /// Other ids return `WebviewError::InvalidArgument`, and failing to listen returns `WebviewError::Io`
+    pub fn acquire(id: &str) -> Result<Option<SingleInstance>, WebviewError> {
+        validate(id)?;
+        #[cfg(not(target_os = "windows"))]
+        let (listener, path) = {
+            use std::os::unix::net::{UnixListener, UnixStream};
+            let path = path(id, "sock");
+            let listener = match UnixListener::bind(&path) {
+                Ok(listener) => listener,
+                Err(e) if e.kind() == std::io::ErrorKind::AddrInUse => {
+                    if UnixStream::connect(&path).is_ok() {
+                        return Ok(None);
+                    }
+                    // Nothing listens, the socket was left by a crashed instance
+                    let _ = std::fs::remove_file(&path);
+                    UnixListener::bind(&path).map_err(WebviewError::Io)?
+                }
+                Err(e) => return Err(WebviewError::Io(e)),
+            };
+            (listener, path)
+        };
+        #[cfg(target_os = "windows")]
+        let (listener, path, mutex) = {
+            use std::{io::Write, net::TcpListener, os::windows::ffi::OsStrExt};
+            const ERROR_ALREADY_EXISTS: u32 = 183;
+            let name: Vec<u16> = std::ffi::OsStr::new(&format!("Local\\fltk-webview-{}", id))
+                .encode_wide()
+                .chain(Some(0))
+                .collect();
+            let mutex = unsafe { CreateMutexW(std::ptr::null_mut(), 0, name.as_ptr()) };
+            if mutex.is_null() {
+                return Err(WebviewError::Io(std::io::Error::last_os_error()));
+            }
+            let path = path(id, "port");
+            if unsafe { GetLastError() } == ERROR_ALREADY_EXISTS {
+                unsafe { CloseHandle(mutex) };
+                activate(&path);
+                return Ok(None);
+            }
+            let listener = TcpListener::bind("127.0.0.1:0")
+                .and_then(|l| {
+                    let port = l.local_addr()?.port();
+                    std::fs::File::create(&path)?.write_all(port.to_string().as_bytes())?;
+                    Ok(l)
+                })
+                .map_err(|e| {
+                    unsafe { CloseHandle(mutex) };
+                    WebviewError::Io(e)
+                })?;
+            (listener, path, mutex)
+        };
+        listener.set_nonblocking(true).map_err(WebviewError::Io)?;
+        let stop = Arc::new(AtomicBool::new(false));
+        let (tx, rx) = mpsc::channel();
+        thread::spawn({
+            let stop = stop.clone();
+            move || listen(listener, stop, tx)
+        });
+        Ok(Some(SingleInstance {
+            stop,
+            activations: Some(rx),
+            handler: Rc::default(),
+            path,
+            #[cfg(target_os = "windows")]
+            mutex,
+        }))
+    }
+
+    /// Set the handler called on the main thread when another instance claims the same id.
+    /// Activations received before a handler is set are delivered to the first one
+    pub fn on_activate<F: FnMut() + 'static>(&mut self, f: F) {
+        *self.handler.borrow_mut() = Some(Box::new(f));
+        let rx = match self.activations.take() {
+            Some(rx) => rx,
+            None => return,
+        };
+        let handler = self.handler.clone();
+        fltk::app::add_timeout3(0.1, move |handle| loop {
+            match rx.try_recv() {
+                Ok(()) => {
+                    if let Some(f) = handler.borrow_mut().as_mut() {
+                        utils::guard(f);
+                    }
+                }
+                Err(mpsc::TryRecvError::Empty) => {
+                    fltk::app::repeat_timeout3(0.1, handle);
+                    return;
+                }
+                // The instance was dropped
+                Err(mpsc::TryRecvError::Disconnected) => return,
+            }
+        });
+    }
+
+    /// Show and raise the top-level window of `wv`, and focus its web content, when another instance
+    /// claims the same id, replacing the handler set through `on_activate`.
+    /// Window managers may keep the window from taking the focus from the application in use,
+    /// and flash its taskbar entry instead
+    pub fn focus_on_activate(&mut self, wv: &Webview) {
+        let mut wv = wv.clone();
+        self.on_activate(move || {
+            if !wv.is_running() {
+                return;
+            }
+            let win = state::with(&wv, |s| s.win.clone());
+            if let Some(mut top) = win.and_then(|w| w.top_window()) {
+                // Deiconifies and raises shown windows
+                top.show();
+            }
+            wv.focus();
+        });
+    }
+}
+
+impl Drop for SingleInstance {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        let _ = std::fs::remove_file(&self.path);
+        #[cfg(target_os = "windows")]
+        unsafe {
+            CloseHandle(self.mutex);
+        }
+    }
+}
+
+/// Accept the connections of other instances until `stop` is set, each one being an activation
+fn listen(listener: Listener, stop: Arc<AtomicBool>, tx: mpsc::Sender<()>) {
+    while !stop.load(Ordering::Relaxed) {
+        match listener.accept() {
+            Ok(_) => {
+                let _ = tx.send(());
+                fltk::app::awake();
+            }
+            Err(_) => thread::sleep(Duration::from_millis(50)),
+        }
+    }
+}
+
+/// Connect to the instance whose port is written at `path`, which may not have written it yet
+#[cfg(target_os = "windows")]
+fn activate(path: &std::path::Path) {
+    for _ in 0..20 {
+        let port = std::fs::read_to_string(path)
+            .ok()
+            .and_then(|p| p.trim().parse::<u16>().ok());
+        if let Some(port) = port {
+            if std::net::TcpStream::connect(("127.0.0.1", port)).is_ok() {
+                return;
+            }
+        }
+        thread::sleep(Duration::from_millis(50));
+    }
+}
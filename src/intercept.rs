@@ -0,0 +1,248 @@
+use crate::{state, utils, Webview};
+use std::{cell::RefCell, rc::Rc};
+use tinyjson::JsonValue;
+
+/// A request made by the page through `fetch` or `XMLHttpRequest`, see `InterceptExt::intercept`
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct InterceptedRequest {
+    /// The absolute URL
+    pub url: String,
+    /// The method, e.g. `GET`
+    pub method: String,
+    /// The headers set by the page, with lowercase names
+    pub headers: Vec<(String, String)>,
+    /// The body, empty for requests without one
+    pub body: Vec<u8>,
+}
+
+/// A response answering an intercepted request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InterceptedResponse {
+    /// The HTTP status, e.g. 200
+    pub status: u16,
+    /// The response headers
+    pub headers: Vec<(String, String)>,
+    /// The body
+    pub body: Vec<u8>,
+}
+
+impl InterceptedResponse {
+    /// A response with `status`, and `body` of the MIME type `content_type`, e.g. `application/json`
+    pub fn new(status: u16, content_type: &str, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status,
+            headers: vec![("Content-Type".to_string(), content_type.to_string())],
+            body: body.into(),
+        }
+    }
+}
+
+const INTERCEPT_BINDING: &str = "__fltk_webview_intercept";
+
+/// Wraps `fetch` and `XMLHttpRequest`, asking `__fltk_webview_intercept` for the responses of the URLs
+/// matching the patterns of `window.__fltk_webview_intercepts`, or letting the requests proceed
+const INTERCEPT_JS: &str = r#"(function() {
+    if (window.__fltk_webview_intercepts) return;
+    window.__fltk_webview_intercepts = [];
+    var match = function(url) {
+        var patterns = window.__fltk_webview_intercepts;
+        for (var i = 0; i < patterns.length; i++) {
+            var re = new RegExp('^' + patterns[i].split('*').map(function(p) {
+                return p.replace(/[.+?^${}()|[\]\\]/g, '\\$&');
+            }).join('.*') + '$');
+            if (re.test(url)) return patterns[i];
+        }
+        return null;
+    };
+    var toBase64 = function(buffer) {
+        var bytes = new Uint8Array(buffer), s = '';
+        for (var i = 0; i < bytes.length; i += 0x8000) {
+            s += String.fromCharCode.apply(null, bytes.subarray(i, i + 0x8000));
+        }
+        return btoa(s);
+    };
+    var fromBase64 = function(s) {
+        var bin = atob(s), bytes = new Uint8Array(bin.length);
+        for (var i = 0; i < bin.length; i++) bytes[i] = bin.charCodeAt(i);
+        return bytes;
+    };
+    // Resolves to the response of the application, or null
+    var ask = function(pattern, url, method, headers, body) {
+        var asked = window.__fltk_webview_intercept(pattern, url, method, headers, toBase64(body));
+        return asked.then(function(res) {
+            if (!res) return null;
+            var nullBody = [101, 204, 205, 304].indexOf(res.status) >= 0;
+            return {
+                status: res.status,
+                headers: res.headers,
+                body: nullBody ? null : fromBase64(res.body)
+            };
+        });
+    };
+    var fetch = window.fetch;
+    window.fetch = function(input, init) {
+        var req;
+        try {
+            req = new Request(input, init);
+        } catch (e) {
+            return fetch.apply(this, arguments);
+        }
+        var pattern = match(req.url);
+        if (!pattern) return fetch.apply(this, arguments);
+        var args = arguments, self = this, headers = [];
+        req.headers.forEach(function(v, k) { headers.push([k, v]); });
+        return req.clone().arrayBuffer().then(function(body) {
+            return ask(pattern, req.url, req.method, headers, body);
+        }).then(function(res) {
+            if (!res) return fetch.apply(self, args);
+            return new Response(res.body, { status: res.status, headers: res.headers });
+        });
+    };
+    var XHR = window.XMLHttpRequest.prototype;
+    var open = XHR.open, send = XHR.send, setHeader = XHR.setRequestHeader;
+    XHR.open = function(method, url) {
+        this.__fltk_webview_request = {
+            method: String(method).toUpperCase(),
+            url: new URL(url, document.baseURI).href,
+            headers: []
+        };
+        return open.apply(this, arguments);
+    };
+    XHR.setRequestHeader = function(name, value) {
+        if (this.__fltk_webview_request) {
+            this.__fltk_webview_request.headers.push([String(name).toLowerCase(), String(value)]);
+        }
+        return setHeader.apply(this, arguments);
+    };
+    XHR.send = function(body) {
+        var xhr = this, args = arguments, r = xhr.__fltk_webview_request;
+        var pattern = r && match(r.url);
+        if (!pattern) return send.apply(xhr, args);
+        new Response(body === undefined ? null : body).arrayBuffer().then(function(buffer) {
+            return ask(pattern, r.url, r.method, r.headers, buffer);
+        }).then(function(res) {
+            if (!res) return send.apply(xhr, args);
+            var bytes = res.body || new Uint8Array(0);
+            var text = new TextDecoder().decode(bytes);
+            var type = xhr.responseType, response = text;
+            if (type === 'arraybuffer') response = bytes.buffer;
+            else if (type === 'blob') response = new Blob([bytes]);
+            else if (type === 'json') {
+                try { response = JSON.parse(text); } catch (e) { response = null; }
+            }
+            var lines = res.headers.map(function(h) { return h[0].toLowerCase() + ': ' + h[1]; });
+            var define = function(name, value) {
+                Object.defineProperty(xhr, name, { configurable: true, value: value });
+            };
+            define('readyState', 4);
+            define('status', res.status);
+            define('statusText', '');
+            define('responseURL', r.url);
+            define('response', response);
+            if (type === '' || type === 'text') define('responseText', text);
+            define('getAllResponseHeaders', function() {
+                return lines.map(function(l) { return l + '\r\n'; }).join('');
+            });
+            define('getResponseHeader', function(name) {
+                var values = res.headers.filter(function(h) {
+                    return h[0].toLowerCase() === String(name).toLowerCase();
+                });
+                return values.length ? values.map(function(h) { return h[1]; }).join(', ') : null;
+            });
+            ['readystatechange', 'load', 'loadend'].forEach(function(e) { xhr.dispatchEvent(new Event(e)); });
+        });
+    };
+})()"#;
+
+/// Interception of the requests of pages
+pub trait InterceptExt {
+    /// Answer the requests of the URLs matching `url_pattern`, in which `*` matches any characters,
+    /// e.g. `https://api.example/*`, with `f`, called on the main thread.
+    /// `f` returns the response, or `None` to let the request proceed to the network.
+    /// Only the page's `fetch` and `XMLHttpRequest` calls are intercepted, not navigations, elements or workers
+    fn intercept<F: FnMut(&InterceptedRequest) -> Option<InterceptedResponse> + 'static>(
+        &mut self,
+        url_pattern: &str,
+        f: F,
+    );
+}
+
+impl InterceptExt for Webview {
+    fn intercept<F: FnMut(&InterceptedRequest) -> Option<InterceptedResponse> + 'static>(
+        &mut self,
+        url_pattern: &str,
+        f: F,
+    ) {
+        let handler: state::InterceptHandler = Rc::new(RefCell::new(f));
+        let first = state::with(self, |s| {
+            match s.interceptors.iter_mut().find(|(p, _)| p == url_pattern) {
+                Some(entry) => entry.1 = handler,
+                None => s.interceptors.push((url_pattern.to_string(), handler)),
+            }
+            s.hooks.insert("intercept")
+        });
+        if first {
+            let wv = self.clone();
+            self.bind(INTERCEPT_BINDING, move |seq, req| {
+                let response = respond(&wv, req).unwrap_or_else(|| "null".to_string());
+                wv.return_(seq, 0, &response);
+            });
+        }
+        let patterns: Vec<String> = state::with(self, |s| {
+            s.interceptors
+                .iter()
+                .map(|(p, _)| utils::quote(p))
+                .collect()
+        });
+        let js = format!(
+            "{};\nwindow.__fltk_webview_intercepts = [{}];",
+            INTERCEPT_JS,
+            patterns.join(", ")
+        );
+        crate::scripts::set_script(self, "intercepts", &js);
+    }
+}
+
+/// Ask the handler of the intercepted pattern for a response, as JSON
+fn respond(wv: &Webview, req: &str) -> Option<String> {
+    let args = utils::parse_args(req);
+    let string = |i: usize| args.get(i).and_then(|a| a.get::<String>()).cloned();
+    let pattern = string(0)?;
+    let handler = state::with(wv, |s| {
+        s.interceptors
+            .iter()
+            .find(|(p, _)| *p == pattern)
+            .map(|(_, h)| h.clone())
+    })?;
+    let headers = match args.get(3) {
+        Some(JsonValue::Array(headers)) => headers
+            .iter()
+            .filter_map(|h| match h {
+                JsonValue::Array(pair) => Some((
+                    pair.first()?.get::<String>()?.clone(),
+                    pair.get(1)?.get::<String>()?.clone(),
+                )),
+                _ => None,
+            })
+            .collect(),
+        _ => vec![],
+    };
+    let request = InterceptedRequest {
+        url: string(1)?,
+        method: string(2)?,
+        headers,
+        body: utils::base64_decode(&string(4).unwrap_or_default()).unwrap_or_default(),
+    };
+    let response = utils::guard(|| (handler.borrow_mut())(&request)).flatten()?;
+    let headers: Vec<String> = response
+        .headers
+        .iter()
+        .map(|(k, v)| format!("[{}, {}]", utils::quote(k), utils::quote(v)))
+        .collect();
+    Some(format!(
+        "{{\"status\": {}, \"headers\": [{}], \"body\": \"{}\"}}",
+        response.status,
+        headers.join(", "),
+        utils::base64_encode(&response.body)
+    ))
+}
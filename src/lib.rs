@@ -1,16 +1,95 @@
 #![doc = include_str!("../README.md")]
 
 use fltk::{
-    app, enums,
+    app, enums, group,
     prelude::{GroupExt, WidgetBase, WidgetExt, WindowExt},
     window,
 };
 use fltk_webview_sys as wv;
 pub use wv::Webview;
 pub use wv::SizeHint;
+
+#[cfg(feature = "async")]
+mod awaitable;
+#[cfg(feature = "async")]
+pub use awaitable::*;
+mod accel;
+pub use accel::*;
+mod automation;
+pub use automation::*;
+mod bind;
+pub use bind::*;
+mod builder;
+pub use builder::*;
+#[cfg(feature = "serde")]
+mod call;
+#[cfg(feature = "serde")]
+pub use call::*;
+#[cfg(all(
+    feature = "bundle",
+    not(any(target_os = "macos", target_os = "windows"))
+))]
+mod bundle;
+mod console;
+pub use console::*;
+mod devtools;
+pub use devtools::*;
+mod download;
+pub use download::*;
+mod embed;
+pub use embed::*;
+mod emulation;
+pub use emulation::*;
+mod error;
+pub use error::*;
+mod eval;
+pub use eval::*;
+mod events;
+pub use events::*;
+mod external;
+pub use external::*;
+mod find;
+pub use find::*;
+mod frames;
+pub use frames::*;
+mod host;
+pub use host::*;
+mod html;
+pub use html::*;
+mod instance;
+pub use instance::*;
+mod intercept;
+pub use intercept::*;
+mod messages;
+pub use messages::*;
+mod navigation;
+pub use navigation::*;
+mod page;
+pub use page::*;
+mod permissions;
+pub use permissions::*;
+mod pool;
+pub use pool::*;
+mod rules;
+pub use rules::*;
+mod scripts;
+pub use scripts::*;
+mod scroll;
+pub use scroll::*;
+mod session;
+pub use session::*;
+mod settings;
+pub use settings::*;
+mod state;
+mod storage;
+pub use storage::*;
+mod usage;
+pub use usage::*;
+mod utils;
 use std::{
     os::raw,
     sync::Arc,
+    time::{Duration, Instant},
 };
 
 #[cfg(not(any(target_os = "macos", target_os = "windows")))]
@@ -45,102 +124,658 @@ pub trait FromFltkWindow {
 }
 
 impl FromFltkWindow for Webview {
-    /// Create a Webview from an embedded fltk window. Requires that the window is already shown
+    /// Create a Webview from an embedded fltk window. Requires that the window is already shown.
+    /// Panics if the webview can't be created, see `WebviewBuilder::try_build` to handle the error
     fn create(debug: bool, win: &mut window::Window) -> Webview {
-        assert!(win.shown());
-        win.end();
-        win.set_color(enums::Color::White);
-        let inner;
-        unsafe {
-            #[cfg(target_os = "windows")]
-            {
-                extern "system" {
-                    pub fn SetFocus(child: *mut ()) -> *mut ();
-                }
-                inner = wv::webview_create(
-                    debug as i32,
-                    &mut win.raw_handle() as *mut *mut raw::c_void as *mut raw::c_void,
-                );
-                win.draw(move |w| wv::webview_set_size(inner, w.w(), w.h(), 0));
-                let mut topwin =
-                    window::Window::from_widget_ptr(win.top_window().unwrap().as_widget_ptr());
-                SetFocus(topwin.raw_handle() as _);
-                topwin.set_callback(|t| {
-                    if app::event() == enums::Event::Close {
-                        t.hide();
-                    }
-                });
-                topwin.assume_derived();
-                topwin.handle(|w, ev| match ev {
-                    fltk::enums::Event::Push => {
-                        SetFocus(w.raw_handle() as _);
-                        true
-                    },
-                    _ => false
+        WebviewBuilder::new().debug(debug).build(win)
+    }
+}
+
+pub trait CreateOffscreen {
+    fn create_offscreen(debug: bool, width: i32, height: i32) -> Result<Webview, WebviewError>;
+}
+
+impl CreateOffscreen for Webview {
+    /// Create a Webview that isn't visible on any screen, see `WebviewBuilder::build_offscreen`
+    fn create_offscreen(debug: bool, width: i32, height: i32) -> Result<Webview, WebviewError> {
+        WebviewBuilder::new()
+            .debug(debug)
+            .build_offscreen(width, height)
+    }
+}
+
+pub trait CreateInGroup {
+    fn create_in_group(debug: bool, group: &mut group::Group) -> Result<Webview, WebviewError>;
+}
+
+impl CreateInGroup for Webview {
+    /// Create a Webview over the area of an fltk group, see `WebviewBuilder::build_in_group`
+    fn create_in_group(debug: bool, group: &mut group::Group) -> Result<Webview, WebviewError> {
+        WebviewBuilder::new().debug(debug).build_in_group(group)
+    }
+}
+
+/// Create the backend webview, applying the options which are only read at creation.
+/// `parent` is the native parent window, which WebKitGTK doesn't take
+unsafe fn create_backend(opts: &WebviewBuilder, parent: *mut raw::c_void) -> wv::webview_t {
+    #[cfg(target_os = "windows")]
+    {
+        // WebView2 only reads its background color at creation
+        let (r, g, b) = opts.background.to_rgb();
+        std::env::set_var(
+            "WEBVIEW2_DEFAULT_BACKGROUND_COLOR",
+            format!("0xFF{:02X}{:02X}{:02X}", r, g, b),
+        );
+        let mut args = opts.browser_args.clone();
+        match opts.hardware_acceleration {
+            HardwareAcceleration::Never => args.push("--disable-gpu".to_string()),
+            HardwareAcceleration::Always => args.push("--ignore-gpu-blocklist".to_string()),
+            HardwareAcceleration::OnDemand => (),
+        }
+        if opts.ignore_tls_errors {
+            args.push("--ignore-certificate-errors".to_string());
+        }
+        if !opts.webgl {
+            args.push("--disable-webgl".to_string());
+        }
+        if !opts.safe_browsing {
+            args.push("--disable-features=msSmartScreenProtection".to_string());
+        }
+        // Set or cleared for every webview, so it doesn't inherit the arguments of the previous one
+        if args.is_empty() {
+            std::env::remove_var("WEBVIEW2_ADDITIONAL_BROWSER_ARGUMENTS");
+        } else {
+            std::env::set_var("WEBVIEW2_ADDITIONAL_BROWSER_ARGUMENTS", args.join(" "));
+        }
+        // Takes precedence over the folder webview passes to WebView2.
+        // Cleared otherwise, so the webview doesn't store in the folder of the previous one
+        match &opts.storage {
+            Some(dir) => std::env::set_var("WEBVIEW2_USER_DATA_FOLDER", dir),
+            None => std::env::remove_var("WEBVIEW2_USER_DATA_FOLDER"),
+        }
+        // WebView2 always sandboxes its web processes, and manages them itself
+        let _ = (opts.sandbox, opts.process_model);
+        let mut parent = parent;
+        wv::webview_create(
+            opts.debug as i32,
+            &mut parent as *mut *mut raw::c_void as *mut raw::c_void,
+        )
+    }
+    #[cfg(target_os = "macos")]
+    {
+        // WKWebView always sandboxes its web processes, and its data store is chosen by webview
+        let _ = (&opts.storage, opts.sandbox, opts.process_model);
+        let inner = wv::webview_create(opts.debug as i32, parent as _);
+        if !opts.safe_browsing {
+            wv::my_set_safe_browsing(wv::webview_get_window(inner), 0);
+        }
+        inner
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        // WebKitGTK doesn't check sites against safe browsing lists
+        let _ = (parent, opts.safe_browsing);
+        // WebKitGTK's default data manager stores under the XDG directories, which glib reads once,
+        // so the first webview chooses the location of all of them.
+        // The variables are restored afterwards, so they don't leak into the processes the application spawns
+        let first = !DATA_CHOSEN.with(|chosen| chosen.replace(true));
+        let vars = ["XDG_DATA_HOME", "XDG_CACHE_HOME"];
+        let saved = vars.map(std::env::var_os);
+        if let (true, Some(dir)) = (first, &opts.storage) {
+            std::env::set_var(vars[0], dir.join("data"));
+            std::env::set_var(vars[1], dir.join("cache"));
+            if opts.ephemeral {
+                SHARED_DIR.with(|shared| {
+                    *shared.borrow_mut() = Some((dir.clone(), std::rc::Weak::new()))
                 });
             }
+        }
+        if opts.sandbox {
+            wv::my_enable_sandbox();
+        }
+        // Favicons are only recorded for the pages loaded once the database is enabled
+        wv::my_enable_favicons();
+        match opts.process_model {
+            Some(ProcessModel::SharedSecondaryProcess) => wv::my_set_process_model(1, 0),
+            Some(ProcessModel::MultipleSecondaryProcesses { max }) => {
+                wv::my_set_process_model(0, max)
+            }
+            None => (),
+        }
+        let inner = wv::webview_create(opts.debug as i32, std::ptr::null_mut() as _);
+        assert!(!inner.is_null());
+        if first {
+            for (var, value) in vars.iter().zip(saved) {
+                match value {
+                    Some(value) => std::env::set_var(var, value),
+                    None => std::env::remove_var(var),
+                }
+            }
+        }
+        // Values of WebKitHardwareAccelerationPolicy
+        let policy = match opts.hardware_acceleration {
+            HardwareAcceleration::OnDemand => 0,
+            HardwareAcceleration::Always => 1,
+            HardwareAcceleration::Never => 2,
+        };
+        wv::my_set_hardware_acceleration(wv::webview_get_window(inner), policy);
+        if opts.ignore_tls_errors {
+            wv::my_ignore_tls_errors(wv::webview_get_window(inner));
+        }
+        start_pump();
+        inner
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+thread_local! {
+    /// Whether the first webview chose the data location of WebKitGTK's default context
+    static DATA_CHOSEN: std::cell::Cell<bool> = std::cell::Cell::new(false);
+    /// The temporary data folder of the first webview, if it was ephemeral,
+    /// which all the webviews store in, with their `EphemeralDir` while any is alive
+    static SHARED_DIR: std::cell::RefCell<
+        Option<(std::path::PathBuf, std::rc::Weak<builder::EphemeralDir>)>,
+    > = std::cell::RefCell::new(None);
+}
+
+/// The temporary folder an ephemeral webview stores in, deleted once the webviews storing in it are gone.
+/// WebKitGTK's webviews all store under the location chosen by the first one,
+/// so they share its folder if it was ephemeral, whether they are or not
+fn ephemeral_dir(opts: &WebviewBuilder) -> Option<std::rc::Rc<builder::EphemeralDir>> {
+    #[cfg(target_os = "windows")]
+    return opts
+        .storage
+        .clone()
+        .filter(|_| opts.ephemeral)
+        .map(|dir| std::rc::Rc::new(builder::EphemeralDir(dir)));
+    // WKWebView ignores the data folder
+    #[cfg(target_os = "macos")]
+    {
+        let _ = opts;
+        None
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        let _ = opts;
+        SHARED_DIR.with(|shared| {
+            let mut shared = shared.borrow_mut();
+            let (dir, weak) = shared.as_mut()?;
+            Some(weak.upgrade().unwrap_or_else(|| {
+                let ephemeral = std::rc::Rc::new(builder::EphemeralDir(dir.clone()));
+                *weak = std::rc::Rc::downgrade(&ephemeral);
+                ephemeral
+            }))
+        })
+    }
+}
+
+/// Initialize GTK on X11 once, unless another library did, keeping the locale the application set.
+/// GTK running on another display, e.g. on Wayland, returns `WebviewError::UnsupportedDisplay`
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn init_gtk() -> Result<(), WebviewError> {
+    extern "C" {
+        pub fn gtk_init_check(argc: *mut i32, argv: *mut *mut raw::c_char) -> i32;
+        pub fn gtk_disable_setlocale();
+        pub fn gdk_display_get_default() -> *mut raw::c_void;
+        pub fn gdk_set_allowed_backends(backends: *const raw::c_char);
+    }
+    static GTK_INIT: std::sync::Once = std::sync::Once::new();
+    GTK_INIT.call_once(|| unsafe {
+        if gdk_display_get_default().is_null() {
+            gtk_disable_setlocale();
+            gdk_set_allowed_backends(b"x11\0".as_ptr() as _);
+            // Fails without an X server, leaving no default display
+            gtk_init_check(&mut 0, std::ptr::null_mut());
+        }
+    });
+    if unsafe { wv::my_is_x11_display() } == 0 {
+        return Err(WebviewError::UnsupportedDisplay);
+    }
+    Ok(())
+}
+
+/// Check that fltk runs on X11, see `init_gtk`.
+/// fltk built with Wayland support uses it when available, unless `app::disable_wayland` is called
+/// before the application opens its display, or the `FLTK_BACKEND` environment variable is `x11`
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn check_fltk_display() -> Result<(), WebviewError> {
+    extern "C" {
+        pub fn Fl_using_wayland() -> raw::c_int;
+    }
+    if unsafe { Fl_using_wayland() } != 0 {
+        return Err(WebviewError::UnsupportedDisplay);
+    }
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+thread_local! {
+    static PUMPING: std::cell::Cell<bool> = std::cell::Cell::new(false);
+}
+
+/// Run GTK's main loop from fltk's, unless it already runs.
+/// A single pump serves all webviews, and stops once they're closed and GTK has no pending work,
+/// so that fltk applications don't keep waking up after their last webview
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub(crate) fn start_pump() {
+    extern "C" {
+        pub fn gtk_main_iteration_do(blocking: bool);
+        pub fn g_main_context_pending(context: *mut raw::c_void) -> i32;
+    }
+    if PUMPING.with(|p| p.replace(true)) {
+        return;
+    }
+    app::add_timeout3(0.001, |handle| unsafe {
+        gtk_main_iteration_do(false);
+        // Closed webviews still get their destruction processed
+        if !state::any_running() && g_main_context_pending(std::ptr::null_mut()) == 0 {
+            PUMPING.with(|p| p.set(false));
+        } else {
+            app::repeat_timeout3(0.001, handle);
+        }
+    });
+}
+
+thread_local! {
+    /// The hidden webview started by `prewarm`, until another webview is created
+    static PREWARMED: std::cell::RefCell<Option<Webview>> = std::cell::RefCell::new(None);
+}
+
+/// Start the web engine ahead of the first webview, e.g. while a splash screen is shown, through a hidden
+/// offscreen webview closed once the next webview is created. Call it on the main thread, after the fltk `App` is created.
+/// Returns `WebviewError::Unsupported` if the offscreen webview can't be shown
+pub fn prewarm() -> Result<(), WebviewError> {
+    if PREWARMED.with(|p| p.borrow().is_some()) {
+        return Ok(());
+    }
+    let wv = WebviewBuilder::new()
+        .url("about:blank")
+        .build_offscreen(1, 1)?;
+    PREWARMED.with(|p| *p.borrow_mut() = Some(wv));
+    Ok(())
+}
+
+/// Close the webview started by `prewarm`, and its offscreen window
+fn release_prewarmed() {
+    let wv = match PREWARMED.with(|p| p.borrow_mut().take()) {
+        Some(wv) => wv,
+        None => return,
+    };
+    let win = state::with(&wv, |s| s.win.clone());
+    wv.close();
+    if let Some(win) = win {
+        window::Window::delete(win);
+    }
+}
+
+/// Wrap the backend webview, and set up what every webview needs
+fn finish(opts: &WebviewBuilder, inner: wv::webview_t) -> Webview {
+    assert!(!inner.is_null());
+    let wv = Webview::from_raw(Arc::new(inner));
+    state::set_running(wv.get_window() as usize, true);
+    state::with(&wv, |s| {
+        s.handle = inner as usize;
+        s.background = Some(opts.background);
+        s.debug = opts.debug;
+        s.ephemeral_dir = ephemeral_dir(opts);
+    });
+    #[cfg(target_os = "windows")]
+    {
+        navigation::track_history(&wv);
+        navigation::track_response_status(&wv);
+        // Progress is the only load state available on Windows
+        events::hook_progress(&wv);
+    }
+    navigation::hook_new_window(&wv);
+    #[cfg(target_os = "macos")]
+    unsafe {
+        wv::my_track_response_status(wv.get_window());
+    }
+    #[cfg(target_os = "macos")]
+    if opts.ignore_tls_errors {
+        wv.clone()
+            .set_tls_error_handler(|_, _| true)
+            .expect("TLS error handlers are supported on macOS");
+    }
+    #[cfg(not(target_os = "windows"))]
+    unsafe {
+        let (r, g, b) = opts.background.to_rgb();
+        wv::my_set_background(wv.get_window(), r, g, b);
+    }
+    #[cfg(not(target_os = "windows"))]
+    if !opts.webgl {
+        wv.clone().set_webgl_enabled(false);
+    }
+    // The scripts must be installed before the first page loads
+    for js in &opts.scripts {
+        wv.init(js);
+    }
+    match &opts.content {
+        Some(builder::InitialContent::Url(url)) => wv.navigate(url),
+        Some(builder::InitialContent::Html(html)) => wv.set_html(html),
+        None => (),
+    }
+    // The new webview holds the engine's processes from now on
+    release_prewarmed();
+    wv
+}
+
+pub trait CreateInHandle {
+    fn create_in_handle(
+        debug: bool,
+        parent: NativeHandle,
+        w: i32,
+        h: i32,
+    ) -> Result<Webview, WebviewError>;
+}
+
+impl CreateInHandle for Webview {
+    /// Create a Webview within a native window not managed by fltk, see `WebviewBuilder::build_in_handle`
+    fn create_in_handle(
+        debug: bool,
+        parent: NativeHandle,
+        w: i32,
+        h: i32,
+    ) -> Result<Webview, WebviewError> {
+        WebviewBuilder::new()
+            .debug(debug)
+            .build_in_handle(parent, w, h)
+    }
+}
+
+/// Run the event loop until `win` is shown and has a native window,
+/// since a window isn't always mapped right after `show`
+fn wait_shown(win: &window::Window) -> Result<(), WebviewError> {
+    let end = Instant::now() + Duration::from_secs(2);
+    while !win.shown() || win.raw_handle() as usize == 0 {
+        if Instant::now() >= end {
+            return Err(WebviewError::WindowNotShown);
+        }
+        let _ = app::wait_for(0.01);
+    }
+    Ok(())
+}
+
+/// Embed the backend webview in `win`, resizing it with the window.
+/// `first` is false when the webview is moved from another window, see `EmbedExt::reparent`
+#[cfg(not(target_os = "windows"))]
+unsafe fn embed(inner: wv::webview_t, win: &mut window::Window, first: bool) {
+    #[cfg(target_os = "macos")]
+    {
+        pub enum NSWindow {}
+        extern "C" {
+            pub fn make_delegate(child: *mut NSWindow, parent: *mut NSWindow, add_menu: i32);
+        }
+        let native = wv::webview_get_window(inner) as usize;
+        make_delegate(native as _, win.raw_handle() as _, first as i32);
+        win.draw(move |w| {
+            let (bw, bh) = embed::backend_size(native as _, w);
+            wv::webview_set_size(inner, bw, bh, 0);
+            embed::apply_position(native as _);
+        });
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        pub enum GdkWindow {}
+        pub enum GtkWindow {}
+        pub enum Display {}
+        extern "C" {
+            pub fn my_get_win(wid: *mut GtkWindow) -> *mut GdkWindow;
+            pub fn my_get_xid(w: *mut GdkWindow) -> u64;
+            pub fn x_init(disp: *mut Display, child: u64, parent: u64);
+        }
+        let temp_win = wv::webview_get_window(inner);
+        assert!(!temp_win.is_null());
+        let temp = my_get_win(temp_win as _);
+        assert!(!temp.is_null());
+        let xid = my_get_xid(temp as _);
+        let flxid = win.raw_handle();
+        let native = temp_win as usize;
+        if win_manager("gnome-session") {
+            // Reparent on the first draw, or when the fltk window is recreated,
+            // and coalesce the resizes of a drag into a single one
+            let embedded_in = std::rc::Rc::new(std::cell::Cell::new(0));
+            let resize_pending = std::rc::Rc::new(std::cell::Cell::new(false));
+            win.draw(move |w| {
+                let parent = w.raw_handle();
+                if embedded_in.replace(parent) != parent {
+                    x_init(app::display() as _, xid, parent);
+                    app::sleep(0.03);
+                }
+                if !resize_pending.replace(true) {
+                    let resize_pending = resize_pending.clone();
+                    let w = w.clone();
+                    app::add_timeout3(0.0, move |_| {
+                        resize_pending.set(false);
+                        if state::is_running(native) {
+                            let (bw, bh) = embed::backend_size(native as _, &w);
+                            wv::webview_set_size(inner, bw, bh, 0);
+                            embed::apply_position(native as _);
+                        }
+                    });
+                }
+            });
+            win.flush();
+        } else {
+            x_init(app::display() as _, xid, flxid);
+            win.draw(move |w| {
+                let (bw, bh) = embed::backend_size(native as _, w);
+                wv::webview_set_size(inner, bw, bh, 0);
+                embed::apply_position(native as _);
+            });
+        }
+    }
+    #[cfg(not(target_os = "macos"))]
+    let _ = first;
+}
+
+pub(crate) fn create(
+    opts: &WebviewBuilder,
+    win: &mut window::Window,
+) -> Result<Webview, WebviewError> {
+    wait_shown(win)?;
+    let opts = &opts.resolve();
+    win.end();
+    win.set_color(opts.background);
+    let inner;
+    unsafe {
+        #[cfg(target_os = "windows")]
+        {
+            extern "system" {
+                pub fn SetFocus(child: *mut ()) -> *mut ();
+            }
+            inner = create_backend(opts, win.raw_handle() as _);
+            let native = wv::webview_get_window(inner) as usize;
+            win.draw(move |w| {
+                let (bw, bh) = embed::backend_size(native as _, w);
+                wv::webview_set_size(inner, bw, bh, 0);
+                embed::apply_position(native as _);
+            });
+            let mut topwin =
+                window::Window::from_widget_ptr(win.top_window().unwrap().as_widget_ptr());
+            SetFocus(topwin.raw_handle() as _);
+            topwin.assume_derived();
+            topwin.handle(|w, ev| match ev {
+                fltk::enums::Event::Push => {
+                    SetFocus(w.raw_handle() as _);
+                    true
+                },
+                _ => false
+            });
+        }
+        #[cfg(target_os = "macos")]
+        {
+            inner = create_backend(opts, win.raw_handle() as _);
+            embed(inner, win, true);
+        }
+        #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+        {
+            check_fltk_display()?;
+            init_gtk()?;
+            inner = create_backend(opts, std::ptr::null_mut());
+            embed(inner, win, true);
+        }
+    }
+    let wv = finish(opts, inner);
+    state::with(&wv, |s| s.win = Some(win.clone()));
+    hook_close(win);
+    hook_events(win, wv.get_window() as usize);
+    Ok(wv)
+}
+
+/// Pass the focus and the dropped files of `win` to the webview embedded in it
+fn hook_events(win: &mut window::Window, native: usize) {
+    win.handle(move |_, ev| match ev {
+        enums::Event::Focus => {
+            embed::native_focus(native as _);
+            true
+        }
+        enums::Event::DndEnter | enums::Event::DndDrag | enums::Event::DndRelease => {
+            state::with_key(native, |s| s.drop_handler.is_some())
+        }
+        enums::Event::Paste if state::with_key(native, |s| s.drop_handler.is_some()) => {
+            events::dropped(native, &app::event_text());
+            true
+        }
+        _ => false,
+    });
+}
+
+/// Move a webview into `win`, see `EmbedExt::reparent`
+pub(crate) fn reparent(wv: &Webview, win: &mut window::Window) -> Result<(), WebviewError> {
+    #[cfg(target_os = "windows")]
+    {
+        let _ = (wv, win);
+        Err(WebviewError::Unsupported)
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        wait_shown(win)?;
+        let (inner, old, background) = state::with(wv, |s| (s.handle, s.win.clone(), s.background));
+        if let Some(mut old) = old {
+            if old.as_widget_ptr() == win.as_widget_ptr() {
+                return Ok(());
+            }
+            // The callbacks of the old window refer to the backend webview
+            old.draw(|_| ());
+            old.handle(|_, _| false);
+            old.redraw();
             #[cfg(target_os = "macos")]
             {
                 pub enum NSWindow {}
                 extern "C" {
-                    pub fn make_delegate(child: *mut NSWindow, parent: *mut NSWindow, add_menu: i32);
-                    pub fn my_close_win(win: *mut NSWindow);
+                    pub fn my_clear_delegate(win: *mut NSWindow);
                 }
-                let handle = win.raw_handle();
-                inner = wv::webview_create(debug as i32, handle as _);
-                make_delegate(wv::webview_get_window(inner) as _, handle as _, 1);
-                win.draw(move |w| wv::webview_set_size(inner, w.w(), w.h(), 0));
-                let mut topwin =
-                    window::Window::from_widget_ptr(win.top_window().unwrap().as_widget_ptr());
-                let inner = inner.clone();
-                topwin.set_callback(move |t| {
-                    if app::event() == enums::Event::Close {
-                        my_close_win(wv::webview_get_window(inner) as _);
-                        t.hide();
-                    }
-                });
+                unsafe { my_clear_delegate(old.raw_handle() as _) };
+            }
+        }
+        win.end();
+        win.set_color(background.unwrap_or(enums::Color::White));
+        unsafe { embed(inner as _, win, false) };
+        state::with(wv, |s| s.win = Some(win.clone()));
+        hook_close(win);
+        hook_events(win, wv.get_window() as usize);
+        // The draw callback sizes the webview
+        win.redraw();
+        Ok(())
+    }
+}
+
+/// Handle the close event of the top-level window of `win`, which may be nested in groups.
+/// Other webviews may share the top window, so all of them are asked through their `on_close` handlers,
+/// then their pages through their before unload handlers, and any of them can keep the window open
+fn hook_close(win: &window::Window) {
+    let mut topwin = match win.top_window() {
+        Some(t) => unsafe { window::Window::from_widget_ptr(t.as_widget_ptr()) },
+        None => return,
+    };
+    let top = topwin.as_widget_ptr() as usize;
+    topwin.set_callback(move |t| {
+        if app::event() != enums::Event::Close {
+            return;
+        }
+        for key in state::embedded_in(top) {
+            if let Some(handler) = state::with_key(key, |s| s.close_handler.clone()) {
+                // A panicking handler doesn't keep the window open
+                if !utils::guard(|| (handler.borrow_mut())()).unwrap_or(true) {
+                    return;
+                }
+            }
+            if !events::confirm_unload(key) {
+                return;
+            }
+        }
+        #[cfg(target_os = "macos")]
+        {
+            pub enum NSWindow {}
+            extern "C" {
+                pub fn my_close_win(win: *mut NSWindow);
+            }
+            for native in state::take_embedded_in(top) {
+                unsafe { my_close_win(native as _) };
+            }
+        }
+        t.hide();
+    });
+}
+
+/// Create a webview within a native window which isn't managed by fltk, see `WebviewBuilder::build_in_handle`
+pub(crate) fn create_in_handle(
+    opts: &WebviewBuilder,
+    parent: NativeHandle,
+    w: i32,
+    h: i32,
+) -> Result<Webview, WebviewError> {
+    let opts = &opts.resolve();
+    let inner;
+    unsafe {
+        match parent {
+            #[cfg(target_os = "windows")]
+            NativeHandle::Win32(hwnd) if !hwnd.is_null() => {
+                inner = create_backend(opts, hwnd);
+            }
+            #[cfg(target_os = "macos")]
+            NativeHandle::Cocoa(ns_window) if !ns_window.is_null() => {
+                pub enum NSWindow {}
+                extern "C" {
+                    pub fn make_delegate(
+                        child: *mut NSWindow,
+                        parent: *mut NSWindow,
+                        add_menu: i32,
+                    );
+                }
+                inner = create_backend(opts, ns_window);
+                make_delegate(wv::webview_get_window(inner) as _, ns_window as _, 0);
             }
             #[cfg(not(any(target_os = "macos", target_os = "windows")))]
-            {
+            NativeHandle::Gtk(gtk_window) if !gtk_window.is_null() => {
                 pub enum GdkWindow {}
                 pub enum GtkWindow {}
                 pub enum Display {}
                 extern "C" {
-                    pub fn gtk_init(argc: *mut i32, argv: *mut *mut raw::c_char);
                     pub fn my_get_win(wid: *mut GtkWindow) -> *mut GdkWindow;
                     pub fn my_get_xid(w: *mut GdkWindow) -> u64;
+                    pub fn gdk_x11_display_get_xdisplay(disp: *mut raw::c_void) -> *mut Display;
+                    pub fn gdk_window_get_display(w: *mut GdkWindow) -> *mut raw::c_void;
                     pub fn x_init(disp: *mut Display, child: u64, parent: u64);
-                    pub fn gtk_main_iteration_do(blocking: bool);
                 }
-                gtk_init(&mut 0, std::ptr::null_mut());
-                inner = wv::webview_create(debug as i32, std::ptr::null_mut() as _);
-                assert!(!inner.is_null());
-                let temp_win = wv::webview_get_window(inner);
-                assert!(!temp_win.is_null());
-                let temp = my_get_win(temp_win as _);
-                assert!(!temp.is_null());
-                let xid = my_get_xid(temp as _);
-                let flxid = win.raw_handle();
-                if win_manager("gnome-session") {
-                    win.draw(move |w| {
-                        x_init(app::display() as _, xid, flxid);
-                        app::sleep(0.03);
-                        wv::webview_set_size(inner, w.w(), w.h(), 0);
-                    });
-                    win.flush();
-                } else {
-                    x_init(app::display() as _, xid, flxid);
-                    win.draw(move |w| wv::webview_set_size(inner, w.w(), w.h(), 0));
+                // The parent must be realized to have an X window
+                let parent = my_get_win(gtk_window as _);
+                if parent.is_null() {
+                    return Err(WebviewError::InvalidArgument(
+                        "the parent GtkWindow isn't realized".to_string(),
+                    ));
                 }
-
-                app::add_timeout3(0.001, |handle| {
-                    gtk_main_iteration_do(false);
-                    app::repeat_timeout3(0.001, handle);
-                });
+                init_gtk()?;
+                inner = create_backend(opts, std::ptr::null_mut());
+                let child = my_get_win(wv::webview_get_window(inner) as _);
+                assert!(!child.is_null());
+                let disp = gdk_x11_display_get_xdisplay(gdk_window_get_display(parent));
+                x_init(disp, my_get_xid(child), my_get_xid(parent));
             }
+            _ => return Err(WebviewError::Unsupported),
         }
-        assert!(!inner.is_null());
-        let inner = Arc::new(inner);
-        Webview::from_raw(inner)
+        wv::webview_set_size(inner, w, h, 0);
     }
+    Ok(finish(opts, inner))
 }
@@ -0,0 +1,55 @@
+use crate::{state, utils, Webview};
+use std::{cell::RefCell, rc::Rc};
+
+/// The reserved binding receiving the messages of pages
+const MESSAGE_BINDING: &str = "__fltk_webview_message";
+
+/// Exposes `window.external.sendMessage` to pages
+const MESSAGE_JS: &str = r#"(function() {
+    if (!window.external) window.external = {};
+    window.external.sendMessage = function(msg) { window.__fltk_webview_message(String(msg)); };
+})()"#;
+
+/// A string channel between the application and its pages, carried by a single binding
+pub trait MessageExt {
+    /// Deliver `msg` to the handler the page registered as `window.external.receiveMessage`.
+    /// The message is dropped if the page hasn't registered one
+    fn post_message(&mut self, msg: &str);
+    /// Set the handler of the messages pages send with `window.external.sendMessage(msg)`,
+    /// which converts `msg` to a string.
+    /// Messages are carried by the `__fltk_webview_message` binding, which shouldn't be bound otherwise
+    fn on_message<F: FnMut(&str) + 'static>(&mut self, f: F);
+}
+
+impl MessageExt for Webview {
+    fn post_message(&mut self, msg: &str) {
+        self.eval(&format!(
+            "window.external && typeof window.external.receiveMessage === 'function' \
+            && window.external.receiveMessage({});",
+            utils::quote(msg)
+        ));
+    }
+
+    fn on_message<F: FnMut(&str) + 'static>(&mut self, f: F) {
+        let first = state::with(self, |s| {
+            s.message_handler = Some(Rc::new(RefCell::new(f)));
+            s.hooks.insert("message")
+        });
+        if !first {
+            return;
+        }
+        let key = self.get_window() as usize;
+        self.bind(MESSAGE_BINDING, move |_seq, req| {
+            let args = utils::parse_args(req);
+            let msg = match args.first().and_then(|m| m.get::<String>()) {
+                Some(msg) => msg,
+                None => return,
+            };
+            if let Some(handler) = state::with_key(key, |s| s.message_handler.clone()) {
+                utils::guard(|| (handler.borrow_mut())(msg));
+            }
+        });
+        self.init(MESSAGE_JS);
+        self.eval(MESSAGE_JS);
+    }
+}
@@ -0,0 +1,1414 @@
+use crate::{events, state, Webview, WebviewError, WebviewEvent};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    ffi::CStr,
+    os::raw,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+/// What to do with a page requesting a new window, through `target="_blank"` links or `window.open`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum NewWindowDecision {
+    /// Load the page in this webview
+    OpenInSame,
+    /// Open the page in the system's default browser
+    OpenExternally,
+    /// Ignore the request
+    Deny,
+}
+
+extern "C" fn new_window_cb(uri: *const raw::c_char, data: *mut raw::c_void) -> raw::c_int {
+    let uri = unsafe { CStr::from_ptr(uri) }.to_string_lossy();
+    let key = data as usize;
+    events::emit(key, WebviewEvent::NewWindowRequested(uri.to_string()));
+    let decision = match state::with_key(key, |s| s.new_window_handler.clone()) {
+        // A panicking handler denies the request
+        Some(handler) => {
+            crate::utils::guard(|| (handler.borrow_mut())(&uri)).unwrap_or(NewWindowDecision::Deny)
+        }
+        None => NewWindowDecision::OpenInSame,
+    };
+    match decision {
+        NewWindowDecision::OpenInSame => 1,
+        NewWindowDecision::OpenExternally => {
+            let _ = crate::open_in_browser(&uri);
+            0
+        }
+        NewWindowDecision::Deny => 0,
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+extern "C" fn tls_error_cb(
+    host: *const raw::c_char,
+    cert: *const raw::c_char,
+    data: *mut raw::c_void,
+) -> raw::c_int {
+    let host = unsafe { CStr::from_ptr(host) }.to_string_lossy();
+    let cert = unsafe { CStr::from_ptr(cert) }.to_string_lossy();
+    match state::with_key(data as usize, |s| s.tls_error_handler.clone()) {
+        // A panicking handler rejects the certificate
+        Some(handler) => {
+            crate::utils::guard(|| (handler.borrow_mut())(&host, &cert)).unwrap_or(false) as _
+        }
+        None => 0,
+    }
+}
+
+/// The authentication scheme a server asks credentials for
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AuthKind {
+    Basic,
+    Digest,
+    Ntlm,
+    Negotiate,
+}
+
+/// A user name and password answering an authentication request
+#[derive(Clone, PartialEq, Eq)]
+pub struct Credentials {
+    pub username: String,
+    pub password: String,
+}
+
+impl Credentials {
+    pub fn new(username: &str, password: &str) -> Self {
+        Self {
+            username: username.to_string(),
+            password: password.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Credentials")
+            .field("username", &self.username)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+extern "C" fn auth_cb(
+    host: *const raw::c_char,
+    kind: raw::c_int,
+    user: *mut *const raw::c_char,
+    password: *mut *const raw::c_char,
+    data: *mut raw::c_void,
+) -> raw::c_int {
+    let host = unsafe { CStr::from_ptr(host) }.to_string_lossy();
+    let kind = match kind {
+        0 => AuthKind::Basic,
+        1 => AuthKind::Digest,
+        2 => AuthKind::Ntlm,
+        _ => AuthKind::Negotiate,
+    };
+    let key = data as usize;
+    let handler = match state::with_key(key, |s| s.auth_handler.clone()) {
+        Some(handler) => handler,
+        None => return 0,
+    };
+    // A panicking handler cancels the request
+    let credentials = crate::utils::guard(|| (handler.borrow_mut())(&host, kind)).flatten();
+    let credentials = credentials.and_then(|c| {
+        Some((
+            std::ffi::CString::new(c.username).ok()?,
+            std::ffi::CString::new(c.password).ok()?,
+        ))
+    });
+    match credentials {
+        Some((u, p)) => state::with_key(key, |s| {
+            let (u, p) = s.auth_credentials.insert((u, p));
+            unsafe {
+                *user = u.as_ptr();
+                *password = p.as_ptr();
+            }
+            1
+        }),
+        None => 0,
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+extern "C" fn load_failed_cb(
+    uri: *const raw::c_char,
+    error: *const raw::c_char,
+    data: *mut raw::c_void,
+) -> raw::c_int {
+    let uri = unsafe { CStr::from_ptr(uri) }.to_string_lossy();
+    let error = unsafe { CStr::from_ptr(error) }.to_string_lossy();
+    let key = data as usize;
+    events::emit(
+        key,
+        WebviewEvent::NavigationFailed(uri.to_string(), error.to_string()),
+    );
+    show_error_page(key, &uri, &error) as _
+}
+
+/// Fill the `{url}` and `{error}` placeholders of an error page, escaping their values
+#[cfg(not(target_os = "windows"))]
+fn fill_error_page(page: &str, url: &str, error: &str) -> String {
+    let escape = crate::utils::escape_html;
+    let mut out = String::with_capacity(page.len());
+    let mut rest = page;
+    while let Some(i) = rest.find('{') {
+        out.push_str(&rest[..i]);
+        rest = &rest[i..];
+        if let Some(r) = rest.strip_prefix("{url}") {
+            out.push_str(&escape(url));
+            rest = r;
+        } else if let Some(r) = rest.strip_prefix("{error}") {
+            out.push_str(&escape(error));
+            rest = r;
+        } else {
+            out.push('{');
+            rest = &rest[1..];
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Load the error page in place of a failed page, returning whether one is set
+#[cfg(not(target_os = "windows"))]
+fn show_error_page(key: usize, url: &str, error: &str) -> bool {
+    let page = match state::with_key(key, |s| s.error_page.clone()) {
+        Some(page) => page,
+        None => return false,
+    };
+    let html = std::ffi::CString::new(fill_error_page(&page, url, error)).unwrap_or_default();
+    let url = std::ffi::CString::new(url).unwrap_or_default();
+    unsafe {
+        fltk_webview_sys::my_load_alternate_html(key as _, html.as_ptr(), url.as_ptr());
+    }
+    true
+}
+
+/// Report the failed navigations as `WebviewEvent::NavigationFailed`, once per webview
+pub(crate) fn hook_load_failed(wv: &Webview) {
+    #[cfg(not(target_os = "windows"))]
+    if state::with(wv, |s| s.hooks.insert("load_failed")) {
+        let win = wv.get_window();
+        unsafe {
+            fltk_webview_sys::my_on_load_failed(win, load_failed_cb, win);
+        }
+    }
+    #[cfg(target_os = "windows")]
+    let _ = wv;
+}
+
+/// Handle new window requests, by default loading them in the same webview.
+/// On Windows, `window.open` and clicks on `target="_blank"` links are intercepted by the page
+pub(crate) fn hook_new_window(wv: &Webview) {
+    let win = wv.get_window();
+    #[cfg(not(target_os = "windows"))]
+    unsafe {
+        fltk_webview_sys::my_on_new_window(win, new_window_cb, win);
+    }
+    #[cfg(target_os = "windows")]
+    {
+        let w = wv.clone();
+        wv.bind("__fltk_webview_new_window", move |_seq, req| {
+            let args = crate::utils::parse_args(req);
+            if let Some(uri) = args.first().and_then(|u| u.get::<String>()) {
+                let c_uri = std::ffi::CString::new(uri.as_str()).unwrap_or_default();
+                if new_window_cb(c_uri.as_ptr(), win) != 0 {
+                    w.navigate(uri);
+                }
+            }
+        });
+        wv.init(
+            r#"(function() {
+    window.open = function(url) {
+        if (url) window.__fltk_webview_new_window(new URL(url, location.href).href);
+        return null;
+    };
+    document.addEventListener('click', function(e) {
+        var a = e.target.closest && e.target.closest('a[target="_blank"]');
+        if (a && a.href) {
+            e.preventDefault();
+            window.__fltk_webview_new_window(a.href);
+        }
+    }, true);
+})()"#,
+        );
+    }
+}
+
+/// A navigation request carrying a method, headers and an optional body
+#[derive(Debug, Clone)]
+pub struct NavigationRequest {
+    pub method: String,
+    pub url: String,
+    pub headers: HashMap<String, String>,
+    pub body: Option<Vec<u8>>,
+}
+
+impl NavigationRequest {
+    /// Create a request without headers or body
+    pub fn new(method: &str, url: &str) -> Self {
+        Self {
+            method: method.to_string(),
+            url: url.to_string(),
+            headers: HashMap::new(),
+            body: None,
+        }
+    }
+
+    /// Add a header to the request
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    /// Set the body of the request
+    pub fn with_body(mut self, body: &[u8]) -> Self {
+        self.body = Some(body.to_vec());
+        self
+    }
+}
+
+/// Options of a navigation, see `NavigationExt::navigate_with_options`
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct NavigateOptions {
+    /// Revalidate the page with the server instead of using a cached copy
+    pub bypass_cache: bool,
+    /// Replace the current entry of the session history instead of adding one
+    pub replace_current_entry: bool,
+    /// Send this absolute http(s) URL as the `Referer` of the request of the page
+    pub referrer: Option<String>,
+}
+
+/// How much of their URL pages send as the `Referer` of their requests, see `NavigationExt::set_referrer_policy`.
+/// The values are those of the `Referrer-Policy` header
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ReferrerPolicy {
+    /// Never send a referrer
+    NoReferrer,
+    /// Send the URL, except from HTTPS pages to HTTP ones
+    NoReferrerWhenDowngrade,
+    /// Only send the origin
+    Origin,
+    /// Send the URL to the same origin, and the origin to others
+    OriginWhenCrossOrigin,
+    /// Only send the URL to the same origin
+    SameOrigin,
+    /// Only send the origin, except from HTTPS pages to HTTP ones
+    StrictOrigin,
+    /// Send the URL to the same origin, and the origin to others, except from HTTPS pages to HTTP ones.
+    /// The engines' default
+    StrictOriginWhenCrossOrigin,
+    /// Always send the URL, even from HTTPS pages to HTTP ones
+    UnsafeUrl,
+}
+
+impl ReferrerPolicy {
+    /// The token of the policy in `Referrer-Policy` headers
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReferrerPolicy::NoReferrer => "no-referrer",
+            ReferrerPolicy::NoReferrerWhenDowngrade => "no-referrer-when-downgrade",
+            ReferrerPolicy::Origin => "origin",
+            ReferrerPolicy::OriginWhenCrossOrigin => "origin-when-cross-origin",
+            ReferrerPolicy::SameOrigin => "same-origin",
+            ReferrerPolicy::StrictOrigin => "strict-origin",
+            ReferrerPolicy::StrictOriginWhenCrossOrigin => "strict-origin-when-cross-origin",
+            ReferrerPolicy::UnsafeUrl => "unsafe-url",
+        }
+    }
+}
+
+/// Whether secure pages may load subresources over plain HTTP, see `NavigationExt::set_mixed_content_policy`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum MixedContentPolicy {
+    /// Keep the engine's behavior, which blocks insecure scripts, style sheets, frames and requests,
+    /// and loads or upgrades to HTTPS insecure images and media
+    #[default]
+    Allow,
+    /// Also block insecure images and media
+    Block,
+}
+
+/// Navigation related methods
+pub trait NavigationExt {
+    /// Navigate using a full request. Returns `WebviewError::InvalidArgument` for invalid methods, headers and URLs,
+    /// and `WebviewError::Unsupported` if the backend can't carry the request
+    fn navigate_request(&mut self, req: NavigationRequest) -> Result<(), WebviewError>;
+    /// Navigate to `url` with `opts`, sending the headers of `bypass_cache` and `referrer` through `navigate_request`.
+    /// `replace_current_entry` can't be combined with them, which returns `WebviewError::Unsupported`
+    fn navigate_with_options(
+        &mut self,
+        url: &str,
+        opts: NavigateOptions,
+    ) -> Result<(), WebviewError>;
+    /// Send `headers` with the GET navigations of the main frame to `origins`, e.g. `https://api.example.com`,
+    /// replacing the previous default headers. Returns `WebviewError::InvalidArgument` for invalid headers and origins
+    fn set_default_headers(
+        &mut self,
+        headers: HashMap<String, String>,
+        origins: &[&str],
+    ) -> Result<(), WebviewError>;
+    /// Reload the current page, revalidating cached resources with the server
+    fn reload_bypass_cache(&mut self);
+    /// Load `html`, resolving its relative URLs against `base_url`, whose origin the page gets
+    fn set_html_with_base(&mut self, html: &str, base_url: &str);
+    /// Whether the session history has an entry to go back to
+    fn can_go_back(&self) -> bool;
+    /// Whether the session history has an entry to go forward to
+    fn can_go_forward(&self) -> bool;
+    /// Set the handler deciding what to do with the pages requesting a new window,
+    /// which are loaded in this webview while no handler is set
+    fn set_new_window_handler<F: FnMut(&str) -> NewWindowDecision + 'static>(&mut self, f: F);
+    /// Run the fltk event loop until the page finished loading, or `timeout` elapsed,
+    /// returning `WebviewError::Timeout` in that case. Call it on the main thread, after `navigate`
+    fn wait_until_loaded(&mut self, timeout: Duration) -> Result<(), WebviewError>;
+    /// Run the fltk event loop until no request has been in flight for `idle_ms` milliseconds,
+    /// or `timeout` elapsed, returning `WebviewError::Timeout` in that case. Call it on the main thread
+    fn wait_for_network_idle(
+        &mut self,
+        idle_ms: u64,
+        timeout: Duration,
+    ) -> Result<(), WebviewError>;
+    /// Whether a page is loading, from the start of its navigation, through its redirects,
+    /// until it finished loading or failed, e.g. to toggle a stop and a reload button
+    fn is_loading(&self) -> bool;
+    /// Set the handler called on the main thread when `is_loading` changes
+    fn on_loading_changed<F: FnMut(bool) + 'static>(&mut self, f: F);
+    /// Stop loading the current page
+    fn stop(&mut self);
+    /// Set the handler called on the main thread with the URLs before and after each server redirect
+    /// of the navigations of the main frame, such as the hops of a login through an identity provider
+    fn on_redirect<F: FnMut(&str, &str) + 'static>(&mut self, f: F) -> Result<(), WebviewError>;
+    /// The URLs of the last navigation of the main frame, through each of its server redirects,
+    /// recorded from the first call to this or `on_redirect`
+    fn last_redirect_chain(&self) -> Vec<String>;
+    /// Stop the navigations which don't complete within `timeout`, sending
+    /// `WebviewEvent::NavigationFailed` to the receivers of `EventsExt::events`.
+    /// `None` lets navigations take as long as they need, which is the default
+    fn set_navigation_timeout(&mut self, timeout: Option<Duration>) -> Result<(), WebviewError>;
+    /// Set the handler receiving the host and a summary of the rejected server certificates, e.g. self-signed ones,
+    /// and returning whether to accept them. **Only meant for development against local servers, check the host**
+    fn set_tls_error_handler<F: FnMut(&str, &str) -> bool + 'static>(
+        &mut self,
+        f: F,
+    ) -> Result<(), WebviewError>;
+    /// Set the handler answering the HTTP authentication requests of servers, receiving the host and the scheme,
+    /// and returning the credentials, or `None` to cancel. Rejected credentials ask the handler again
+    fn set_auth_handler<F: FnMut(&str, AuthKind) -> Option<Credentials> + 'static>(
+        &mut self,
+        f: F,
+    ) -> Result<(), WebviewError>;
+    /// The HTTP status code of the response to the last navigation of the main frame,
+    /// or `None` before any response, and for pages not loaded over HTTP, such as `set_html`'s
+    fn last_response_status(&self) -> Option<u16>;
+    /// Show this HTML in place of the pages which fail to load, such as unreachable hosts,
+    /// with `{url}` and `{error}` replaced by the HTML-escaped URL of the failed page and the error
+    fn set_error_page(&mut self, html: &str) -> Result<(), WebviewError>;
+    /// Set the referrer policy of the current page and the next ones, through a `<meta name="referrer">` element,
+    /// which the page's own `Referrer-Policy` headers and `referrerpolicy` attributes override
+    fn set_referrer_policy(&mut self, policy: ReferrerPolicy);
+    /// Set the handler called on the main thread with the URLs of the insecure resources requested by secure pages,
+    /// such as an `http://` image on an `https://` page, whether the engine loaded or blocked them, once per page
+    fn on_insecure_content<F: FnMut(&str) + 'static>(&mut self, f: F);
+    /// Set whether secure pages may load images and media over plain HTTP, for the next pages.
+    /// The engines block the other kinds of insecure content whatever the policy
+    fn set_mixed_content_policy(&mut self, policy: MixedContentPolicy);
+}
+
+const INSECURE_CONTENT_BINDING: &str = "__fltk_webview_insecure_content";
+
+/// Reports the insecure URLs requested by a secure page to `__fltk_webview_insecure_content`, once each
+const INSECURE_CONTENT_JS: &str = r#"(function() {
+    if (window.__fltk_webview_insecure || location.protocol !== 'https:') return;
+    window.__fltk_webview_insecure = true;
+    var reported = {};
+    var report = function(url) {
+        try {
+            url = new URL(url, document.baseURI).href;
+        } catch (e) {
+            return;
+        }
+        if (!/^(http|ws):/.test(url) || reported[url]) return;
+        reported[url] = true;
+        window.__fltk_webview_insecure_content(url);
+    };
+    var attrs = ['src', 'data', 'poster'];
+    var selector = 'img, script, iframe, frame, video, audio, source, track, object, embed, input, link';
+    var check = function(el) {
+        if (el.nodeType !== 1 || !el.matches(selector)) return;
+        attrs.forEach(function(a) {
+            var v = el.getAttribute(a);
+            if (v) report(v);
+        });
+        var srcset = el.getAttribute('srcset');
+        if (srcset) srcset.split(',').forEach(function(c) { report(c.trim().split(/\s+/)[0]); });
+        // Links to other pages are navigations, not subresources
+        if (el.tagName === 'LINK' && /stylesheet|icon|preload|prefetch|manifest/.test(el.rel)) {
+            report(el.getAttribute('href'));
+        }
+    };
+    var scan = function(root) {
+        check(root);
+        if (root.querySelectorAll) root.querySelectorAll(selector).forEach(check);
+    };
+    new MutationObserver(function(records) {
+        records.forEach(function(r) {
+            if (r.type === 'attributes') check(r.target);
+            else r.addedNodes.forEach(scan);
+        });
+    }).observe(document, {
+        childList: true, subtree: true, attributes: true,
+        attributeFilter: attrs.concat(['srcset', 'href'])
+    });
+    if (document.documentElement) scan(document.documentElement);
+    if (window.PerformanceObserver) {
+        try {
+            new PerformanceObserver(function(list) {
+                list.getEntries().forEach(function(e) { report(e.name); });
+            }).observe({ type: 'resource', buffered: true });
+        } catch (e) {}
+    }
+    var fetch = window.fetch;
+    window.fetch = function(input) {
+        report(input && input.url ? input.url : String(input));
+        return fetch.apply(this, arguments);
+    };
+    var open = XMLHttpRequest.prototype.open;
+    XMLHttpRequest.prototype.open = function(method, url) {
+        report(String(url));
+        return open.apply(this, arguments);
+    };
+    var Socket = window.WebSocket;
+    window.WebSocket = function(url, protocols) {
+        report(String(url));
+        return protocols === undefined ? new Socket(url) : new Socket(url, protocols);
+    };
+    window.WebSocket.prototype = Socket.prototype;
+    ['CONNECTING', 'OPEN', 'CLOSING', 'CLOSED'].forEach(function(k) { window.WebSocket[k] = Socket[k]; });
+})()"#;
+
+/// Defines `window.__fltk_webview_apply_referrer()`, which gives the document
+/// the referrer policy of `window.__fltk_webview_referrer_policy`, once its head is parsed
+const REFERRER_POLICY_JS: &str = r#"(function() {
+    if (window.__fltk_webview_apply_referrer) return;
+    var add = function() {
+        if (!document.head) return false;
+        var meta = document.querySelector('meta[data-fltk-webview-referrer]');
+        if (!meta) {
+            meta = document.createElement('meta');
+            meta.name = 'referrer';
+            meta.setAttribute('data-fltk-webview-referrer', '');
+            document.head.insertBefore(meta, document.head.firstChild);
+        }
+        meta.content = window.__fltk_webview_referrer_policy;
+        return true;
+    };
+    window.__fltk_webview_apply_referrer = function() {
+        if (add()) return;
+        var observer = new MutationObserver(function() {
+            if (add()) observer.disconnect();
+        });
+        observer.observe(document, { childList: true, subtree: true });
+    };
+})()"#;
+
+/// Defines `window.__fltk_webview_apply_mixed()`, which adds a `block-all-mixed-content` policy to secure pages
+/// once their head is parsed, if `window.__fltk_webview_block_mixed` is set by then
+const MIXED_CONTENT_JS: &str = r#"(function() {
+    if (window.__fltk_webview_apply_mixed) return;
+    var add = function() {
+        if (!document.head) return false;
+        if (!window.__fltk_webview_block_mixed || location.protocol !== 'https:') return true;
+        if (document.querySelector('meta[data-fltk-webview-mixed]')) return true;
+        var meta = document.createElement('meta');
+        meta.httpEquiv = 'Content-Security-Policy';
+        meta.content = 'block-all-mixed-content';
+        meta.setAttribute('data-fltk-webview-mixed', '');
+        document.head.insertBefore(meta, document.head.firstChild);
+        return true;
+    };
+    window.__fltk_webview_apply_mixed = function() {
+        if (add()) return;
+        var observer = new MutationObserver(function() {
+            if (add()) observer.disconnect();
+        });
+        observer.observe(document, { childList: true, subtree: true });
+    };
+})()"#;
+
+/// Watch the load state of a webview until its navigation timeout is unset, or it's closed
+#[cfg(not(target_os = "windows"))]
+fn watch_navigation(key: usize) {
+    fltk::app::add_timeout3(0.1, move |handle| {
+        if !state::is_running(key) {
+            return;
+        }
+        let timeout = match state::with_key(key, |s| s.navigation_timeout) {
+            Some(timeout) => timeout,
+            None => {
+                state::with_key(key, |s| {
+                    s.hooks.remove("navigation_timeout");
+                    s.loading_since = None;
+                });
+                return;
+            }
+        };
+        let loading = unsafe { fltk_webview_sys::my_is_loading(key as _) != 0 };
+        let since = state::with_key(key, |s| {
+            if loading {
+                Some(*s.loading_since.get_or_insert_with(Instant::now))
+            } else {
+                s.loading_since = None;
+                None
+            }
+        });
+        if since.map_or(false, |since| since.elapsed() >= timeout) {
+            state::with_key(key, |s| s.loading_since = None);
+            let url = unsafe { CStr::from_ptr(fltk_webview_sys::my_get_uri(key as _)) }
+                .to_string_lossy()
+                .into_owned();
+            unsafe {
+                fltk_webview_sys::my_stop_loading(key as _);
+            }
+            events::emit(
+                key,
+                WebviewEvent::NavigationFailed(url.clone(), "timed out".to_string()),
+            );
+            show_error_page(key, &url, "timed out");
+        }
+        fltk::app::repeat_timeout3(0.1, handle);
+    });
+}
+
+/// Have pages report their history state through the Navigation API
+#[cfg(target_os = "windows")]
+pub(crate) fn track_history(wv: &Webview) {
+    let win = wv.get_window() as usize;
+    wv.bind("__fltk_webview_history", move |_seq, req| {
+        let args = crate::utils::parse_args(req);
+        let flag = |i: usize| args.get(i).and_then(|v| v.get::<bool>()).copied();
+        if let (Some(back), Some(forward)) = (flag(0), flag(1)) {
+            state::with_key(win, |s| s.history = (back, forward));
+        }
+    });
+    wv.init(
+        r#"(function() {
+    if (!window.navigation) return;
+    var report = function() {
+        window.__fltk_webview_history(navigation.canGoBack, navigation.canGoForward);
+    };
+    navigation.addEventListener('currententrychange', report);
+    report();
+})()"#,
+    );
+}
+
+/// Record the URLs of navigations, reporting their redirects to the handler of `on_redirect`
+#[cfg(not(target_os = "windows"))]
+extern "C" fn redirect_cb(
+    from: *const raw::c_char,
+    to: *const raw::c_char,
+    data: *mut raw::c_void,
+) {
+    let to = unsafe { CStr::from_ptr(to) }.to_string_lossy().to_string();
+    let key = data as usize;
+    if from.is_null() {
+        state::with_key(key, |s| s.redirect_chain = vec![to]);
+        return;
+    }
+    let from = unsafe { CStr::from_ptr(from) }.to_string_lossy();
+    let handler = state::with_key(key, |s| {
+        s.redirect_chain.push(to.clone());
+        s.redirect_handler.clone()
+    })
+    .flatten();
+    if let Some(handler) = handler {
+        crate::utils::guard(|| (handler.borrow_mut())(&from, &to));
+    }
+}
+
+/// Record the navigations and their redirects, once per webview
+fn hook_redirects(wv: &Webview) {
+    #[cfg(not(target_os = "windows"))]
+    if state::with(wv, |s| s.hooks.insert("redirect")) {
+        let win = wv.get_window();
+        unsafe {
+            fltk_webview_sys::my_on_redirect(win, redirect_cb, win);
+        }
+    }
+    #[cfg(target_os = "windows")]
+    let _ = wv;
+}
+
+/// Have pages report the status of their response from their navigation timing entry
+#[cfg(target_os = "windows")]
+pub(crate) fn track_response_status(wv: &Webview) {
+    let win = wv.get_window() as usize;
+    wv.bind("__fltk_webview_status", move |_seq, req| {
+        let args = crate::utils::parse_args(req);
+        let status = args.first().and_then(|v| v.get::<f64>()).copied();
+        state::with_key(win, |s| {
+            s.response_status = status.filter(|s| *s > 0.0).map(|s| s as u16)
+        });
+    });
+    wv.init(
+        r#"(function() {
+    if (window !== window.top) return;
+    var entry = performance.getEntriesByType('navigation')[0];
+    window.__fltk_webview_status(entry && entry.responseStatus || 0);
+})()"#,
+    );
+}
+
+/// Count the requests in flight in `State::network_requests`
+fn track_network(wv: &Webview) {
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        extern "C" fn resource_cb(delta: raw::c_int, data: *mut raw::c_void) {
+            let key = data as usize;
+            if state::is_running(key) {
+                state::with_key(key, |s| {
+                    s.network_requests = s.network_requests.saturating_add_signed(delta as isize)
+                });
+            }
+        }
+        let win = wv.get_window();
+        unsafe {
+            fltk_webview_sys::my_on_resource_load(win, resource_cb, win);
+        }
+    }
+    #[cfg(any(target_os = "macos", target_os = "windows"))]
+    {
+        let win = wv.get_window() as usize;
+        wv.bind("__fltk_webview_network", move |_seq, req| {
+            let args = crate::utils::parse_args(req);
+            if let Some(count) = args.first().and_then(|v| v.get::<f64>()) {
+                state::with_key(win, |s| s.network_requests = *count as usize);
+            }
+        });
+        let js = r#"(function() {
+    if (window !== window.top || window.__fltk_webview_network_tracked) return;
+    window.__fltk_webview_network_tracked = true;
+    var active = 0;
+    var change = function(delta) {
+        active += delta;
+        window.__fltk_webview_network(active);
+    };
+    var fetch = window.fetch;
+    if (fetch) window.fetch = function() {
+        change(1);
+        var done = function() { change(-1); };
+        var p = fetch.apply(this, arguments);
+        p.then(done, done);
+        return p;
+    };
+    var send = XMLHttpRequest.prototype.send;
+    XMLHttpRequest.prototype.send = function() {
+        change(1);
+        this.addEventListener('loadend', function() { change(-1); });
+        try {
+            return send.apply(this, arguments);
+        } catch (e) {
+            change(-1);
+            throw e;
+        }
+    };
+    // Forgets the requests of the previous page
+    change(0);
+})()"#;
+        wv.init(js);
+        wv.eval(js);
+    }
+}
+
+impl NavigationExt for Webview {
+    fn navigate_request(&mut self, req: NavigationRequest) -> Result<(), WebviewError> {
+        if !is_token(&req.method) {
+            return Err(WebviewError::InvalidArgument(format!(
+                "invalid method: {:?}",
+                req.method
+            )));
+        }
+        if req.url.contains(|c: char| c.is_control()) {
+            return Err(WebviewError::InvalidArgument(format!(
+                "invalid url: {:?}",
+                req.url
+            )));
+        }
+        check_headers(&req.headers)?;
+        #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+        if req.method.eq_ignore_ascii_case("POST") {
+            return post_form(self, &req);
+        }
+        #[cfg(target_os = "windows")]
+        {
+            if req.method.eq_ignore_ascii_case("GET")
+                && req.headers.is_empty()
+                && req.body.is_none()
+            {
+                self.navigate(&req.url);
+                Ok(())
+            } else {
+                Err(WebviewError::Unsupported)
+            }
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            use std::ffi::CString;
+            let method = CString::new(req.method).unwrap();
+            let url = CString::new(req.url).unwrap();
+            let (names, values): (Vec<_>, Vec<_>) = req
+                .headers
+                .into_iter()
+                .map(|(k, v)| (CString::new(k).unwrap(), CString::new(v).unwrap()))
+                .unzip();
+            let names: Vec<_> = names.iter().map(|n| n.as_ptr()).collect();
+            let values: Vec<_> = values.iter().map(|v| v.as_ptr()).collect();
+            let (body, body_len) = match &req.body {
+                Some(body) => (body.as_ptr(), body.len()),
+                None => (std::ptr::null(), 0),
+            };
+            let ret = unsafe {
+                fltk_webview_sys::my_load_request(
+                    self.get_window(),
+                    method.as_ptr(),
+                    url.as_ptr(),
+                    names.as_ptr(),
+                    values.as_ptr(),
+                    names.len() as _,
+                    body,
+                    body_len,
+                )
+            };
+            if ret != 0 {
+                Ok(())
+            } else {
+                Err(WebviewError::Unsupported)
+            }
+        }
+    }
+
+    fn navigate_with_options(
+        &mut self,
+        url: &str,
+        opts: NavigateOptions,
+    ) -> Result<(), WebviewError> {
+        if let Some(referrer) = &opts.referrer {
+            let is_http = ["http://", "https://"].iter().any(|s| {
+                referrer.len() > s.len()
+                    && referrer
+                        .get(..s.len())
+                        .map_or(false, |p| p.eq_ignore_ascii_case(s))
+            });
+            if !is_http || referrer.contains(|c: char| c.is_control() || c == ' ') {
+                return Err(WebviewError::InvalidArgument(format!(
+                    "referrer: {:?}",
+                    referrer
+                )));
+            }
+        }
+        let mut req = NavigationRequest::new("GET", url);
+        if opts.bypass_cache {
+            req = req
+                .with_header("Cache-Control", "no-cache")
+                .with_header("Pragma", "no-cache");
+        }
+        if let Some(referrer) = &opts.referrer {
+            req = req.with_header("Referer", referrer);
+        }
+        match (req.headers.is_empty(), opts.replace_current_entry) {
+            (true, false) => {
+                self.navigate(url);
+                Ok(())
+            }
+            (false, false) => self.navigate_request(req),
+            (true, true) => {
+                self.eval(&format!("location.replace({})", crate::utils::quote(url)));
+                Ok(())
+            }
+            (false, true) => Err(WebviewError::Unsupported),
+        }
+    }
+
+    fn set_default_headers(
+        &mut self,
+        headers: HashMap<String, String>,
+        origins: &[&str],
+    ) -> Result<(), WebviewError> {
+        check_headers(&headers)?;
+        let origins = origins
+            .iter()
+            .map(|o| {
+                parse_origin(o).ok_or_else(|| {
+                    WebviewError::InvalidArgument(format!("invalid origin: {:?}", o))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        #[cfg(target_os = "windows")]
+        {
+            let _ = origins;
+            Err(WebviewError::Unsupported)
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            use std::ffi::CString;
+            let (names, values): (Vec<_>, Vec<_>) = headers
+                .into_iter()
+                .map(|(k, v)| (CString::new(k).unwrap(), CString::new(v).unwrap()))
+                .unzip();
+            let origins: Vec<_> = origins
+                .into_iter()
+                .map(|o| CString::new(o).unwrap())
+                .collect();
+            let names: Vec<_> = names.iter().map(|n| n.as_ptr()).collect();
+            let values: Vec<_> = values.iter().map(|v| v.as_ptr()).collect();
+            let origins: Vec<_> = origins.iter().map(|o| o.as_ptr()).collect();
+            unsafe {
+                fltk_webview_sys::my_set_default_headers(
+                    self.get_window(),
+                    names.as_ptr(),
+                    values.as_ptr(),
+                    names.len() as _,
+                    origins.as_ptr(),
+                    origins.len() as _,
+                );
+            }
+            Ok(())
+        }
+    }
+
+    fn reload_bypass_cache(&mut self) {
+        #[cfg(target_os = "windows")]
+        self.eval("location.reload()");
+        #[cfg(not(target_os = "windows"))]
+        unsafe {
+            fltk_webview_sys::my_reload_bypass_cache(self.get_window());
+        }
+    }
+
+    fn set_html_with_base(&mut self, html: &str, base_url: &str) {
+        #[cfg(target_os = "windows")]
+        self.set_html(&format!(
+            "<base href=\"{}\">{}",
+            base_url.replace('&', "&amp;").replace('"', "&quot;"),
+            html
+        ));
+        #[cfg(not(target_os = "windows"))]
+        {
+            use std::ffi::CString;
+            let html = CString::new(html).unwrap();
+            let base_url = CString::new(base_url).unwrap();
+            unsafe {
+                fltk_webview_sys::my_load_html(self.get_window(), html.as_ptr(), base_url.as_ptr());
+            }
+        }
+    }
+
+    fn can_go_back(&self) -> bool {
+        #[cfg(target_os = "windows")]
+        return state::with(self, |s| s.history.0);
+        #[cfg(not(target_os = "windows"))]
+        unsafe {
+            fltk_webview_sys::my_can_go_back(self.get_window()) != 0
+        }
+    }
+
+    fn can_go_forward(&self) -> bool {
+        #[cfg(target_os = "windows")]
+        return state::with(self, |s| s.history.1);
+        #[cfg(not(target_os = "windows"))]
+        unsafe {
+            fltk_webview_sys::my_can_go_forward(self.get_window()) != 0
+        }
+    }
+
+    fn set_new_window_handler<F: FnMut(&str) -> NewWindowDecision + 'static>(&mut self, f: F) {
+        state::with(self, |s| {
+            s.new_window_handler = Some(Rc::new(RefCell::new(f)))
+        });
+    }
+
+    fn wait_until_loaded(&mut self, timeout: Duration) -> Result<(), WebviewError> {
+        let end = Instant::now() + timeout;
+        loop {
+            #[cfg(target_os = "windows")]
+            let loaded = state::with(self, |s| s.progress) >= 1.0;
+            #[cfg(not(target_os = "windows"))]
+            let loaded = unsafe {
+                let win = self.get_window();
+                fltk_webview_sys::my_is_loading(win) == 0
+                    && fltk_webview_sys::my_get_progress(win) >= 1.0
+            };
+            if loaded {
+                return Ok(());
+            }
+            if Instant::now() >= end {
+                return Err(WebviewError::Timeout);
+            }
+            let _ = fltk::app::wait_for(0.01);
+        }
+    }
+
+    fn wait_for_network_idle(
+        &mut self,
+        idle_ms: u64,
+        timeout: Duration,
+    ) -> Result<(), WebviewError> {
+        if state::with(self, |s| s.hooks.insert("network")) {
+            track_network(self);
+        }
+        let idle = Duration::from_millis(idle_ms);
+        let end = Instant::now() + timeout;
+        let mut idle_since: Option<Instant> = None;
+        loop {
+            let now = Instant::now();
+            let requests = state::with(self, |s| s.network_requests);
+            #[cfg(target_os = "windows")]
+            let loading = state::with(self, |s| s.progress) < 1.0;
+            #[cfg(not(target_os = "windows"))]
+            let loading = unsafe { fltk_webview_sys::my_is_loading(self.get_window()) != 0 };
+            if requests > 0 || loading {
+                idle_since = None;
+            } else if now - *idle_since.get_or_insert(now) >= idle {
+                return Ok(());
+            }
+            if now >= end {
+                return Err(WebviewError::Timeout);
+            }
+            let _ = fltk::app::wait_for(0.01);
+        }
+    }
+
+    fn is_loading(&self) -> bool {
+        #[cfg(target_os = "windows")]
+        return state::with(self, |s| s.loading);
+        #[cfg(not(target_os = "windows"))]
+        unsafe {
+            fltk_webview_sys::my_is_loading(self.get_window()) != 0
+        }
+    }
+
+    fn on_loading_changed<F: FnMut(bool) + 'static>(&mut self, f: F) {
+        let first = state::with(self, |s| {
+            s.loading_handler
+                .replace(Rc::new(RefCell::new(f)))
+                .is_none()
+        });
+        #[cfg(not(target_os = "windows"))]
+        if first {
+            let win = self.get_window();
+            let loading = self.is_loading();
+            state::with(self, |s| s.loading = loading);
+            unsafe {
+                fltk_webview_sys::my_on_loading_changed(win, events::loading_cb, win);
+            }
+        }
+        #[cfg(target_os = "windows")]
+        let _ = first;
+    }
+
+    fn stop(&mut self) {
+        #[cfg(target_os = "windows")]
+        self.eval("window.stop()");
+        #[cfg(not(target_os = "windows"))]
+        unsafe {
+            fltk_webview_sys::my_stop_loading(self.get_window());
+        }
+    }
+
+    fn on_redirect<F: FnMut(&str, &str) + 'static>(&mut self, f: F) -> Result<(), WebviewError> {
+        #[cfg(target_os = "windows")]
+        {
+            let _ = f;
+            Err(WebviewError::Unsupported)
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            state::with(self, |s| {
+                s.redirect_handler = Some(Rc::new(RefCell::new(f)))
+            });
+            hook_redirects(self);
+            Ok(())
+        }
+    }
+
+    fn last_redirect_chain(&self) -> Vec<String> {
+        hook_redirects(self);
+        state::with(self, |s| s.redirect_chain.clone())
+    }
+
+    fn set_navigation_timeout(&mut self, timeout: Option<Duration>) -> Result<(), WebviewError> {
+        #[cfg(target_os = "windows")]
+        {
+            let _ = timeout;
+            Err(WebviewError::Unsupported)
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            let first = state::with(self, |s| {
+                s.navigation_timeout = timeout;
+                timeout.is_some() && s.hooks.insert("navigation_timeout")
+            });
+            if first {
+                watch_navigation(self.get_window() as usize);
+            }
+            Ok(())
+        }
+    }
+
+    fn set_tls_error_handler<F: FnMut(&str, &str) -> bool + 'static>(
+        &mut self,
+        f: F,
+    ) -> Result<(), WebviewError> {
+        #[cfg(target_os = "windows")]
+        {
+            let _ = f;
+            Err(WebviewError::Unsupported)
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            let first = state::with(self, |s| {
+                s.tls_error_handler
+                    .replace(Rc::new(RefCell::new(f)))
+                    .is_none()
+            });
+            if first {
+                let win = self.get_window();
+                unsafe {
+                    fltk_webview_sys::my_on_tls_error(win, tls_error_cb, win);
+                }
+            }
+            Ok(())
+        }
+    }
+
+    fn set_auth_handler<F: FnMut(&str, AuthKind) -> Option<Credentials> + 'static>(
+        &mut self,
+        f: F,
+    ) -> Result<(), WebviewError> {
+        #[cfg(target_os = "windows")]
+        {
+            let _ = f;
+            Err(WebviewError::Unsupported)
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            let first = state::with(self, |s| {
+                s.auth_handler.replace(Rc::new(RefCell::new(f))).is_none()
+            });
+            if first {
+                let win = self.get_window();
+                unsafe {
+                    fltk_webview_sys::my_on_auth(win, auth_cb, win);
+                }
+            }
+            Ok(())
+        }
+    }
+
+    fn set_error_page(&mut self, html: &str) -> Result<(), WebviewError> {
+        #[cfg(target_os = "windows")]
+        {
+            let _ = html;
+            Err(WebviewError::Unsupported)
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            state::with(self, |s| s.error_page = Some(html.to_string()));
+            hook_load_failed(self);
+            Ok(())
+        }
+    }
+
+    fn on_insecure_content<F: FnMut(&str) + 'static>(&mut self, f: F) {
+        let first = state::with(self, |s| {
+            s.insecure_content_handler
+                .replace(Rc::new(RefCell::new(f)))
+                .is_none()
+        });
+        if !first {
+            return;
+        }
+        let key = self.get_window() as usize;
+        self.bind(INSECURE_CONTENT_BINDING, move |_seq, req| {
+            let args = crate::utils::parse_args(req);
+            let url = match args.first().and_then(|u| u.get::<String>()) {
+                Some(url) => url.clone(),
+                None => return,
+            };
+            if let Some(handler) = state::with_key(key, |s| s.insecure_content_handler.clone()) {
+                crate::utils::guard(|| (handler.borrow_mut())(&url));
+            }
+        });
+        self.init(INSECURE_CONTENT_JS);
+        self.eval(INSECURE_CONTENT_JS);
+    }
+
+    fn set_referrer_policy(&mut self, policy: ReferrerPolicy) {
+        let js = format!(
+            "{};\nwindow.__fltk_webview_referrer_policy = {};\nwindow.__fltk_webview_apply_referrer();",
+            REFERRER_POLICY_JS,
+            crate::utils::quote(policy.as_str())
+        );
+        crate::scripts::set_script(self, "referrer_policy", &js);
+    }
+
+    fn set_mixed_content_policy(&mut self, policy: MixedContentPolicy) {
+        let js = format!(
+            "{};\nwindow.__fltk_webview_block_mixed = {};\nwindow.__fltk_webview_apply_mixed();",
+            MIXED_CONTENT_JS,
+            policy == MixedContentPolicy::Block
+        );
+        crate::scripts::set_script(self, "mixed_content", &js);
+    }
+
+    fn last_response_status(&self) -> Option<u16> {
+        #[cfg(target_os = "windows")]
+        return state::with(self, |s| s.response_status);
+        #[cfg(not(target_os = "windows"))]
+        match unsafe { fltk_webview_sys::my_get_response_status(self.get_window()) } {
+            0 => None,
+            status => Some(status as u16),
+        }
+    }
+}
+
+/// Whether `name` is a valid HTTP header name or method
+fn is_token(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b))
+}
+
+/// The origin `origin`, e.g. `https://example.com:8443`, lowercased and without the default port,
+/// or `None` if it isn't the origin of http(s) URLs, optionally followed by a slash
+fn parse_origin(origin: &str) -> Option<String> {
+    let (scheme, authority) = origin.split_once("://")?;
+    let scheme = scheme.to_ascii_lowercase();
+    let default_port = match scheme.as_str() {
+        "http" => ":80",
+        "https" => ":443",
+        _ => return None,
+    };
+    let authority = authority
+        .strip_suffix('/')
+        .unwrap_or(authority)
+        .to_ascii_lowercase();
+    // The port follows the closing bracket of IPv6 addresses
+    let (host, port) = match authority.rfind(':') {
+        Some(i) if !authority[i..].contains(']') => (&authority[..i], Some(&authority[i + 1..])),
+        _ => (&authority[..], None),
+    };
+    let valid_host = !host.is_empty()
+        && host
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"-._~%[]:".contains(&b));
+    let valid_port = port.map_or(true, |p| {
+        !p.is_empty() && p.bytes().all(|b| b.is_ascii_digit())
+    });
+    if !valid_host || !valid_port {
+        return None;
+    }
+    let authority = authority.strip_suffix(default_port).unwrap_or(&authority);
+    Some(format!("{}://{}", scheme, authority))
+}
+
+/// Returns `WebviewError::InvalidArgument` for header names which aren't tokens, and values spanning lines
+fn check_headers(headers: &HashMap<String, String>) -> Result<(), WebviewError> {
+    if let Some(name) = headers.keys().find(|n| !is_token(n)) {
+        return Err(WebviewError::InvalidArgument(format!(
+            "invalid header name: {:?}",
+            name
+        )));
+    }
+    if let Some(value) = headers.values().find(|v| v.contains(['\r', '\n', '\0'])) {
+        return Err(WebviewError::InvalidArgument(format!(
+            "invalid header value: {:?}",
+            value
+        )));
+    }
+    Ok(())
+}
+
+/// Submits a hidden form posting the fields `fields` to `url`, bypassing overrides of `submit` by the page
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+const POST_FORM_JS: &str = r#"(function(url, fields) {
+    var form = document.createElement('form');
+    form.method = 'POST';
+    form.action = url;
+    form.enctype = 'application/x-www-form-urlencoded';
+    form.style.display = 'none';
+    fields.forEach(function(field) {
+        var input = document.createElement('input');
+        input.type = 'hidden';
+        input.name = field[0];
+        input.value = field[1];
+        form.appendChild(input);
+    });
+    (document.body || document.documentElement).appendChild(form);
+    HTMLFormElement.prototype.submit.call(form);
+})"#;
+
+/// Post `req` from the current page as a form, if it only carries form fields
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn post_form(wv: &Webview, req: &NavigationRequest) -> Result<(), WebviewError> {
+    let form_type = |v: &str| {
+        let mime = v.split(';').next().unwrap_or_default().trim();
+        mime.eq_ignore_ascii_case("application/x-www-form-urlencoded")
+    };
+    let only_form = req
+        .headers
+        .iter()
+        .all(|(n, v)| n.eq_ignore_ascii_case("Content-Type") && form_type(v));
+    let fields = match form_fields(req.body.as_deref().unwrap_or_default()) {
+        Some(fields) if only_form => fields,
+        _ => return Err(WebviewError::Unsupported),
+    };
+    let fields: Vec<_> = fields
+        .iter()
+        .map(|(n, v)| format!("[{}, {}]", crate::utils::quote(n), crate::utils::quote(v)))
+        .collect();
+    wv.eval(&format!(
+        "{}({}, [{}]);",
+        POST_FORM_JS,
+        crate::utils::quote(&req.url),
+        fields.join(", ")
+    ));
+    Ok(())
+}
+
+/// The fields of an `application/x-www-form-urlencoded` body, or None if they aren't UTF-8
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn form_fields(body: &[u8]) -> Option<Vec<(String, String)>> {
+    let decode = |s: &[u8]| {
+        let mut out = Vec::with_capacity(s.len());
+        let mut i = 0;
+        while i < s.len() {
+            match s[i] {
+                b'+' => out.push(b' '),
+                b'%' => {
+                    let hex = s
+                        .get(i + 1..i + 3)
+                        .filter(|h| h.iter().all(u8::is_ascii_hexdigit))?;
+                    out.push(u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok()?);
+                    i += 2;
+                }
+                b => out.push(b),
+            }
+            i += 1;
+        }
+        String::from_utf8(out).ok()
+    };
+    body.split(|b| *b == b'&')
+        .filter(|field| !field.is_empty())
+        .map(|field| {
+            let (name, value) = match field.iter().position(|b| *b == b'=') {
+                Some(i) => (&field[..i], &field[i + 1..]),
+                None => (field, &b""[..]),
+            };
+            Some((decode(name)?, decode(value)?))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn fills_error_pages() {
+        assert_eq!(
+            fill_error_page("<p>{url}: {error}</p>", "http://a/?b&c", "<refused>"),
+            "<p>http://a/?b&amp;c: &lt;refused&gt;</p>"
+        );
+        assert_eq!(fill_error_page("{url}{url}", "x", ""), "xx");
+        // Other braces, e.g. of styles and scripts, are kept
+        assert_eq!(
+            fill_error_page("p { color: red } {err} {", "x", "y"),
+            "p { color: red } {err} {"
+        );
+        // The values aren't filled in again
+        assert_eq!(fill_error_page("{url}", "{error}", "y"), "{error}");
+        assert_eq!(fill_error_page("{é}{url}", "x", "y"), "{é}x");
+    }
+
+    #[test]
+    fn validates_header_names() {
+        assert!(is_token("X-Custom-Header"));
+        assert!(is_token("x_a.b~1"));
+        assert!(!is_token(""));
+        assert!(!is_token("Bad Header"));
+        assert!(!is_token("Bad:Header"));
+        assert!(!is_token("Bad\r\nHeader"));
+        assert!(!is_token("Bädheader"));
+    }
+
+    #[test]
+    fn checks_headers() {
+        let headers = |name: &str, value: &str| HashMap::from([(name.into(), value.into())]);
+        assert!(check_headers(&HashMap::new()).is_ok());
+        assert!(check_headers(&headers("X-A", "b; c=\"d\"")).is_ok());
+        assert!(check_headers(&headers("X A", "b")).is_err());
+        assert!(check_headers(&headers("X-A", "b\r\nX-B: c")).is_err());
+        assert!(check_headers(&headers("X-A", "b\0")).is_err());
+    }
+
+    #[test]
+    fn parses_origins() {
+        let origin = |o: &str| parse_origin(o);
+        assert_eq!(
+            origin("HTTPS://Example.com/").as_deref(),
+            Some("https://example.com")
+        );
+        assert_eq!(
+            origin("https://example.com:443").as_deref(),
+            Some("https://example.com")
+        );
+        assert_eq!(
+            origin("http://127.0.0.1:8080").as_deref(),
+            Some("http://127.0.0.1:8080")
+        );
+        assert_eq!(origin("http://[::1]:80").as_deref(), Some("http://[::1]"));
+        assert_eq!(
+            origin("http://[::1]:81").as_deref(),
+            Some("http://[::1]:81")
+        );
+        assert_eq!(origin("https://example.com/path"), None);
+        assert_eq!(origin("https://user@example.com"), None);
+        assert_eq!(origin("https://example.com:x"), None);
+        assert_eq!(origin("ftp://example.com"), None);
+        assert_eq!(origin("example.com"), None);
+    }
+
+    #[test]
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    fn decodes_form_fields() {
+        let fields = |body: &str| form_fields(body.as_bytes());
+        let field = |n: &str, v: &str| (n.to_string(), v.to_string());
+        assert_eq!(fields(""), Some(vec![]));
+        assert_eq!(
+            fields("a=1&b=two+words&c=%26%3D%C3%A9"),
+            Some(vec![
+                field("a", "1"),
+                field("b", "two words"),
+                field("c", "&=é")
+            ])
+        );
+        assert_eq!(
+            fields("flag&&empty=&x=a=b"),
+            Some(vec![
+                field("flag", ""),
+                field("empty", ""),
+                field("x", "a=b")
+            ])
+        );
+        assert_eq!(fields("a=%zz"), None);
+        assert_eq!(fields("a=%+1"), None);
+        assert_eq!(fields("a=%4"), None);
+        assert_eq!(fields("a=%FF"), None);
+    }
+}
@@ -0,0 +1,534 @@
+use crate::{eval, state, utils, ScriptExt, Webview, WebviewError};
+use fltk::enums;
+use std::{
+    cell::RefCell,
+    path::Path,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+use tinyjson::JsonValue;
+
+/// Methods reading the content of the current page, through JavaScript
+pub trait PageExt {
+    /// Pass the serialized DOM of the top-level document, `document.documentElement.outerHTML`, to `f` on the main thread.
+    /// `f` isn't called if the document can't be read
+    fn get_html<F: FnOnce(String) + 'static>(&mut self, f: F);
+    /// Pass the text selected in the current page to `f` on the main thread, empty without a selection.
+    /// The selection of a focused text field, or of a focused frame of the same origin, is read instead.
+    /// Frames of other origins can't be read, so their selection is passed as empty.
+    /// `f` isn't called if the page can't be read
+    fn selected_text<F: FnOnce(String) + 'static>(&mut self, f: F);
+    /// Pass the favicon of the current page, encoded as PNG, to `f` on the main thread, or `None` without one.
+    /// WebKitGTK provides the favicons it recorded in its favicon database, enabled when the first webview is created.
+    /// Otherwise, the page draws the icon of its `<link rel="icon">`, or its `/favicon.ico`, into a canvas,
+    /// which fails for icons of other origins which don't allow CORS
+    fn favicon<F: FnOnce(Option<Vec<u8>>) + 'static>(&mut self, f: F);
+    /// Wait until an element matching the CSS `selector` is in the current document, pumping the fltk event loop.
+    /// Returns `Ok(false)` if none appeared within `timeout`, and `WebviewError::Js` if `selector` is invalid.
+    /// The document is queried every 50ms, a query lost to a navigation is sent again after 500ms
+    fn wait_for_selector(
+        &mut self,
+        selector: &str,
+        timeout: Duration,
+    ) -> Result<bool, WebviewError>;
+    /// Show only the main content of pages, such as the text and images of an article, in a `.reader-content`
+    /// container with a simplified layout, or restore the page. It applies to the current page and the next ones
+    fn set_reader_mode(&mut self, enabled: bool);
+    /// Scale the text of pages by `factor`, clamped to 0.25..=5.0, leaving images and the rest of the layout unchanged.
+    /// 1.0 restores the sizes of the page
+    fn set_text_zoom(&mut self, factor: f64);
+    /// Let users select and copy the text of pages, the default, or prevent it, e.g. for kiosks.
+    /// Form fields and editable content stay selectable
+    fn set_selection_enabled(&mut self, enabled: bool);
+    /// Show `cursor` over the pages, in place of the cursors they choose, e.g. a wait cursor while the application is busy,
+    /// through a user style sheet, see `ScriptExt::add_user_style`. The scrollbars keep the engine's cursor
+    fn set_cursor(&mut self, cursor: enums::Cursor);
+    /// Let pages choose their cursors again, after `set_cursor`
+    fn reset_cursor(&mut self);
+    /// Make the element of the page which is fullscreen leave fullscreen, if any,
+    /// e.g. when the application leaves fullscreen itself, see `EventsExt::on_fullscreen_request`
+    fn exit_fullscreen(&mut self);
+    /// Save a snapshot of the current page to `path` as a single HTML file, with its stylesheets and images inlined
+    /// and its scripts removed, then pass the outcome to `f` on the main thread.
+    /// Returns `WebviewError::Js` if the page couldn't be serialized, and `WebviewError::Io` if the file can't be written
+    fn save_complete<F: FnOnce(Result<(), WebviewError>) + 'static>(&mut self, path: &Path, f: F);
+}
+
+/// Resolves to the document as a standalone HTML file, with its resources inlined
+const SAVE_COMPLETE_JS: &str = r#"new Promise(function(resolve) {
+    var dataUrl = function(url) {
+        if (!url || /^data:/.test(url)) return Promise.resolve(url);
+        return fetch(url).then(function(r) {
+            if (!r.ok) throw new Error(r.status);
+            return r.blob();
+        }).then(function(blob) {
+            return new Promise(function(res) {
+                var reader = new FileReader();
+                reader.onload = function() { res(reader.result); };
+                reader.onerror = function() { res(url); };
+                reader.readAsDataURL(blob);
+            });
+        }).catch(function() { return url; });
+    };
+    // Inlines the url() of a stylesheet, resolved against its own URL
+    var inlineCss = function(css, base) {
+        var urls = [];
+        css.replace(/url\(\s*(['"]?)([^'")]+)\s*\)/g, function(m, q, url) { urls.push(url); });
+        return Promise.all(urls.map(function(url) {
+            return dataUrl(new URL(url, base).href);
+        })).then(function(data) {
+            var i = 0;
+            return css.replace(/url\(\s*(['"]?)([^'")]+)\s*\)/g, function() {
+                return 'url("' + data[i++] + '")';
+            });
+        });
+    };
+    var sheetText = function(sheet) {
+        try {
+            return Promise.resolve(Array.from(sheet.cssRules).map(function(r) { return r.cssText; }).join('
+'));
+        } catch (e) {
+            // Stylesheets of other origins don't expose their rules
+            return fetch(sheet.href).then(function(r) { return r.text(); }).catch(function() { return null; });
+        }
+    };
+    var root = document.documentElement.cloneNode(true);
+    var pairs = function(selector) {
+        var live = document.querySelectorAll(selector);
+        return Array.from(root.querySelectorAll(selector)).map(function(el, i) { return [live[i], el]; });
+    };
+    var jobs = [];
+    pairs('input, textarea, select').forEach(function(p) {
+        var live = p[0], el = p[1];
+        if (live.tagName === 'TEXTAREA') el.textContent = live.value;
+        else if (live.tagName === 'SELECT') {
+            Array.from(el.options).forEach(function(o, i) {
+                if (live.options[i].selected) o.setAttribute('selected', '');
+                else o.removeAttribute('selected');
+            });
+        } else if (live.type === 'checkbox' || live.type === 'radio') {
+            if (live.checked) el.setAttribute('checked', ''); else el.removeAttribute('checked');
+        } else if (live.type !== 'file' && live.type !== 'password') {
+            el.setAttribute('value', live.value);
+        }
+    });
+    pairs('canvas').forEach(function(p) {
+        try {
+            var img = document.createElement('img');
+            img.src = p[0].toDataURL();
+            img.width = p[0].width;
+            img.height = p[0].height;
+            p[1].replaceWith(img);
+        } catch (e) {}
+    });
+    pairs('img').forEach(function(p) {
+        p[1].removeAttribute('srcset');
+        p[1].removeAttribute('loading');
+        jobs.push(dataUrl(p[0].currentSrc || p[0].src).then(function(url) { if (url) p[1].src = url; }));
+    });
+    pairs('link[rel~="stylesheet"]').forEach(function(p) {
+        if (!p[0].sheet) return;
+        jobs.push(sheetText(p[0].sheet).then(function(css) {
+            if (css === null) return;
+            return inlineCss(css, p[0].sheet.href).then(function(css) {
+                var style = document.createElement('style');
+                style.textContent = css;
+                if (p[0].media) style.media = p[0].media;
+                p[1].replaceWith(style);
+            });
+        }));
+    });
+    pairs('style').forEach(function(p) {
+        jobs.push(inlineCss(p[0].textContent, document.baseURI).then(function(css) { p[1].textContent = css; }));
+    });
+    pairs('[style]').forEach(function(p) {
+        var css = p[0].getAttribute('style');
+        if (css.indexOf('url(') < 0) return;
+        jobs.push(inlineCss(css, document.baseURI).then(function(css) { p[1].setAttribute('style', css); }));
+    });
+    root.querySelectorAll('script, noscript, link[rel~="preload"], link[rel~="modulepreload"]').forEach(function(el) {
+        el.remove();
+    });
+    Promise.all(jobs).then(function() {
+        var head = root.querySelector('head');
+        if (head && !head.querySelector('meta[charset]')) {
+            var meta = document.createElement('meta');
+            meta.setAttribute('charset', 'utf-8');
+            head.insertBefore(meta, head.firstChild);
+        }
+        resolve('<!DOCTYPE html>
+' + root.outerHTML);
+    });
+})"#;
+
+/// Evaluates to the selected text, following the focus into text fields and same-origin frames
+const SELECTED_TEXT_JS: &str = r#"(function() {
+    var read = function(doc) {
+        var el = doc.activeElement;
+        if (el && (el.tagName === 'INPUT' || el.tagName === 'TEXTAREA')) {
+            try {
+                var text = el.value.substring(el.selectionStart, el.selectionEnd);
+                if (text) return text;
+            } catch (e) {}
+        }
+        if (el && (el.tagName === 'IFRAME' || el.tagName === 'FRAME')) {
+            try {
+                return read(el.contentDocument);
+            } catch (e) {
+                // The frame has another origin
+                return '';
+            }
+        }
+        var sel = doc.getSelection();
+        return sel ? sel.toString() : '';
+    };
+    return read(document);
+})()"#;
+
+/// Resolves to the base64 PNG of the page's icon, or null
+const FAVICON_JS: &str = r#"new Promise(function(resolve) {
+    var link = document.querySelector('link[rel~="icon"]');
+    var img = new Image();
+    img.crossOrigin = 'anonymous';
+    img.onload = function() {
+        try {
+            var canvas = document.createElement('canvas');
+            canvas.width = img.naturalWidth || 16;
+            canvas.height = img.naturalHeight || 16;
+            canvas.getContext('2d').drawImage(img, 0, 0, canvas.width, canvas.height);
+            resolve(canvas.toDataURL('image/png').split(',')[1]);
+        } catch (e) {
+            resolve(null);
+        }
+    };
+    img.onerror = function() { resolve(null); };
+    img.src = link ? link.href : new URL('/favicon.ico', location.href).href;
+})"#;
+
+/// Scales the font sizes of the elements of the document by `window.__fltk_webview_text_zoom`,
+/// when `window.__fltk_webview_text_zoom_apply()` is called
+const TEXT_ZOOM_JS: &str = r#"(function() {
+    if (window.__fltk_webview_text_zoom_apply) return;
+    var original = new Map(), pending = false;
+    var observer = new MutationObserver(function() {
+        if (!pending) {
+            pending = true;
+            requestAnimationFrame(apply);
+        }
+    });
+    var apply = window.__fltk_webview_text_zoom_apply = function() {
+        pending = false;
+        if (!document.body) return;
+        original.forEach(function(size, el) { el.style.setProperty('font-size', size[0], size[1]); });
+        original.clear();
+        observer.disconnect();
+        var factor = window.__fltk_webview_text_zoom || 1;
+        if (factor === 1) return;
+        var els = Array.prototype.slice.call(document.querySelectorAll('body, body *'));
+        // Read before writing, so that inherited sizes aren't scaled twice
+        var sizes = els.map(function(el) { return parseFloat(getComputedStyle(el).fontSize); });
+        els.forEach(function(el, i) {
+            if (!sizes[i]) return;
+            original.set(el, [el.style.getPropertyValue('font-size'), el.style.getPropertyPriority('font-size')]);
+            el.style.setProperty('font-size', (sizes[i] * factor) + 'px', 'important');
+        });
+        observer.observe(document.body, { subtree: true, childList: true });
+    };
+    document.addEventListener('DOMContentLoaded', apply);
+})()"#;
+
+/// Shows the main content of the page in a `.reader-content` container while `window.__fltk_webview_reader` is set,
+/// when `window.__fltk_webview_reader_apply()` is called
+const READER_JS: &str = r#"(function() {
+    if (window.__fltk_webview_reader_apply) return;
+    var container = null, hidden = [];
+    var unlikely = /comment|footer|sidebar|nav|menu|share|social|related|promo|banner|ad-|sponsor/i;
+    var extract = function() {
+        var scores = new Map();
+        document.querySelectorAll('p, pre, blockquote').forEach(function(p) {
+            var text = p.textContent.trim();
+            if (text.length < 25) return;
+            var points = 1 + text.split(',').length + Math.min(Math.floor(text.length / 100), 3);
+            var parent = p.parentElement, grand = parent && parent.parentElement;
+            if (parent) scores.set(parent, (scores.get(parent) || 0) + points);
+            if (grand) scores.set(grand, (scores.get(grand) || 0) + points / 2);
+        });
+        var best = null, bestScore = 0;
+        scores.forEach(function(score, el) {
+            var length = el.textContent.length || 1, links = 0;
+            el.querySelectorAll('a').forEach(function(a) { links += a.textContent.length; });
+            score *= 1 - links / length;
+            if (unlikely.test(el.className + ' ' + el.id)) score *= 0.2;
+            if (score > bestScore) {
+                best = el;
+                bestScore = score;
+            }
+        });
+        return best || document.querySelector('article, main') || document.body;
+    };
+    var simplify = function(content) {
+        content.querySelectorAll('script, style, link, noscript, iframe, form, button, input, select, textarea, nav, aside, footer')
+            .forEach(function(e) { e.remove(); });
+        [content].concat(Array.prototype.slice.call(content.querySelectorAll('*'))).forEach(function(e) {
+            ['style', 'class', 'id', 'width', 'height', 'align'].forEach(function(a) { e.removeAttribute(a); });
+        });
+        return content;
+    };
+    var apply = function() {
+        if (window.__fltk_webview_reader && !container) {
+            container = document.createElement('div');
+            container.className = 'reader-content';
+            var style = document.createElement('style');
+            style.textContent = '.reader-content { max-width: 40em; margin: 2em auto; padding: 0 1em; '
+                + 'font: 1.15em/1.6 Georgia, serif; color: #222; background: #fff; } '
+                + '.reader-content img, .reader-content video { max-width: 100%; height: auto; } '
+                + '.reader-content pre { white-space: pre-wrap; }';
+            var title = document.createElement('h1');
+            title.textContent = document.title;
+            container.appendChild(style);
+            container.appendChild(title);
+            container.appendChild(simplify(extract().cloneNode(true)));
+            Array.prototype.forEach.call(document.body.children, function(c) {
+                hidden.push([c, c.style.display]);
+                c.style.display = 'none';
+            });
+            document.body.appendChild(container);
+            window.scrollTo(0, 0);
+        } else if (!window.__fltk_webview_reader && container) {
+            container.remove();
+            container = null;
+            hidden.forEach(function(h) { h[0].style.display = h[1]; });
+            hidden = [];
+        }
+    };
+    window.__fltk_webview_reader_apply = function() {
+        if (document.readyState === 'loading') document.addEventListener('DOMContentLoaded', apply);
+        else apply();
+    };
+})()"#;
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+extern "C" fn favicon_cb(data: *const u8, len: usize, user: *mut std::os::raw::c_void) {
+    let icon = unsafe { &mut *(user as *mut Option<Vec<u8>>) };
+    *icon = Some(unsafe { std::slice::from_raw_parts(data, len) }.to_vec());
+}
+
+/// Prevents selecting and copying text outside of editable elements
+const NO_SELECTION_CSS: &str = r#"* { -webkit-user-select: none !important; user-select: none !important; }
+input, textarea, [contenteditable], [contenteditable] * {
+    -webkit-user-select: text !important;
+    user-select: text !important;
+}"#;
+
+/// The CSS name of an fltk cursor
+fn css_cursor(cursor: enums::Cursor) -> &'static str {
+    use enums::Cursor;
+    match cursor {
+        Cursor::Default | Cursor::Arrow => "default",
+        Cursor::Cross => "crosshair",
+        Cursor::Wait => "wait",
+        Cursor::Insert => "text",
+        Cursor::Hand => "pointer",
+        Cursor::Help => "help",
+        Cursor::Move => "move",
+        Cursor::NS => "ns-resize",
+        Cursor::WE => "ew-resize",
+        Cursor::NWSE => "nwse-resize",
+        Cursor::NESW => "nesw-resize",
+        Cursor::N => "n-resize",
+        Cursor::NE => "ne-resize",
+        Cursor::E => "e-resize",
+        Cursor::SE => "se-resize",
+        Cursor::S => "s-resize",
+        Cursor::SW => "sw-resize",
+        Cursor::W => "w-resize",
+        Cursor::NW => "nw-resize",
+        Cursor::None => "none",
+    }
+}
+
+/// Cancels selecting and copying text outside of editable elements while `window.__fltk_webview_selection` is false.
+/// `window.__fltk_webview_selection_apply()` clears the current selection in that case
+const SELECTION_JS: &str = r#"(function() {
+    if (window.__fltk_webview_selection_apply) return;
+    var editable = function(n) {
+        var el = n && n.nodeType === 3 ? n.parentElement : n;
+        return !!el && (el.isContentEditable || el.tagName === 'INPUT' || el.tagName === 'TEXTAREA');
+    };
+    var clear = function() {
+        var s = window.getSelection();
+        if (s && s.rangeCount && !editable(document.activeElement)) s.removeAllRanges();
+    };
+    ['copy', 'cut', 'selectstart'].forEach(function(type) {
+        document.addEventListener(type, function(e) {
+            if (!window.__fltk_webview_selection && !editable(e.target)) e.preventDefault();
+        }, true);
+    });
+    document.addEventListener('selectionchange', function() {
+        if (!window.__fltk_webview_selection) clear();
+    });
+    window.__fltk_webview_selection_apply = function() {
+        if (!window.__fltk_webview_selection) clear();
+    };
+})()"#;
+
+impl PageExt for Webview {
+    fn get_html<F: FnOnce(String) + 'static>(&mut self, f: F) {
+        eval::eval_with(self, "document.documentElement.outerHTML", move |res| {
+            if let Some(JsonValue::String(html)) = res.ok().and_then(|r| r.parse().ok()) {
+                f(html);
+            }
+        });
+    }
+
+    fn save_complete<F: FnOnce(Result<(), WebviewError>) + 'static>(&mut self, path: &Path, f: F) {
+        let path = path.to_path_buf();
+        eval::eval_with(self, SAVE_COMPLETE_JS, move |res| {
+            let html = res.map_err(WebviewError::Js).and_then(|r| match r.parse() {
+                Ok(JsonValue::String(html)) => Ok(html),
+                _ => Err(WebviewError::Js(
+                    "the page didn't serialize to a string".to_string(),
+                )),
+            });
+            f(html.and_then(|html| std::fs::write(&path, html).map_err(WebviewError::Io)));
+        });
+    }
+
+    fn selected_text<F: FnOnce(String) + 'static>(&mut self, f: F) {
+        eval::eval_with(self, SELECTED_TEXT_JS, move |res| {
+            if let Some(JsonValue::String(text)) = res.ok().and_then(|r| r.parse().ok()) {
+                f(text);
+            }
+        });
+    }
+
+    fn favicon<F: FnOnce(Option<Vec<u8>>) + 'static>(&mut self, f: F) {
+        #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+        {
+            let mut icon: Option<Vec<u8>> = None;
+            unsafe {
+                fltk_webview_sys::my_get_favicon(
+                    self.get_window(),
+                    favicon_cb,
+                    &mut icon as *mut _ as _,
+                );
+            }
+            if icon.is_some() {
+                f(icon);
+                return;
+            }
+        }
+        eval::eval_with(self, FAVICON_JS, move |res| {
+            let icon = match res.ok().and_then(|r| r.parse().ok()) {
+                Some(JsonValue::String(png)) => utils::base64_decode(&png),
+                _ => None,
+            };
+            f(icon);
+        });
+    }
+
+    fn wait_for_selector(
+        &mut self,
+        selector: &str,
+        timeout: Duration,
+    ) -> Result<bool, WebviewError> {
+        let js = format!("!!document.querySelector({})", utils::quote(selector));
+        let result: Rc<RefCell<Option<Result<String, String>>>> = Rc::default();
+        let end = Instant::now() + timeout;
+        // When to send the next query, and when the pending one was sent
+        let mut next = Instant::now();
+        let mut sent: Option<Instant> = None;
+        loop {
+            match result.borrow_mut().take() {
+                Some(Ok(found)) if found == "true" => return Ok(true),
+                Some(Err(e)) => return Err(WebviewError::Js(e)),
+                Some(Ok(_)) => {
+                    sent = None;
+                    next = Instant::now() + Duration::from_millis(50);
+                }
+                None => (),
+            }
+            let now = Instant::now();
+            if now >= end {
+                return Ok(false);
+            }
+            let lost = sent.is_some_and(|at| now - at >= Duration::from_millis(500));
+            if (sent.is_none() && now >= next) || lost {
+                sent = Some(now);
+                eval::eval_with(self, &js, {
+                    let result = result.clone();
+                    move |res| *result.borrow_mut() = Some(res)
+                });
+            }
+            let _ = fltk::app::wait_for(0.01);
+        }
+    }
+
+    fn set_reader_mode(&mut self, enabled: bool) {
+        let js = format!(
+            "{};\nwindow.__fltk_webview_reader = {};\nwindow.__fltk_webview_reader_apply();",
+            READER_JS, enabled
+        );
+        crate::scripts::set_script(self, "reader", &js);
+    }
+
+    fn set_text_zoom(&mut self, factor: f64) {
+        let factor = if factor.is_finite() {
+            factor.clamp(0.25, 5.0)
+        } else {
+            1.0
+        };
+        #[cfg(target_os = "macos")]
+        let native = unsafe { fltk_webview_sys::my_set_text_zoom(self.get_window(), factor) != 0 };
+        #[cfg(not(target_os = "macos"))]
+        let native = false;
+        if !native {
+            let js = format!(
+                "{};\nwindow.__fltk_webview_text_zoom = {};\nwindow.__fltk_webview_text_zoom_apply();",
+                TEXT_ZOOM_JS, factor
+            );
+            crate::scripts::set_script(self, "text_zoom", &js);
+        }
+    }
+
+    fn set_selection_enabled(&mut self, enabled: bool) {
+        let style = state::with(self, |s| s.selection_style.take());
+        let style = match (style, enabled) {
+            (Some(style), true) => {
+                self.remove_user_style(style);
+                None
+            }
+            (None, false) => Some(self.add_user_style(NO_SELECTION_CSS)),
+            (style, _) => style,
+        };
+        state::with(self, |s| s.selection_style = style);
+        let js = format!(
+            "{};\nwindow.__fltk_webview_selection = {};\nwindow.__fltk_webview_selection_apply();",
+            SELECTION_JS, enabled
+        );
+        crate::scripts::set_script(self, "selection", &js);
+    }
+
+    fn set_cursor(&mut self, cursor: enums::Cursor) {
+        self.reset_cursor();
+        let css = format!(
+            "html, html * {{ cursor: {} !important; }}",
+            css_cursor(cursor)
+        );
+        let style = self.add_user_style(&css);
+        state::with(self, |s| s.cursor_style = Some(style));
+    }
+
+    fn reset_cursor(&mut self) {
+        if let Some(style) = state::with(self, |s| s.cursor_style.take()) {
+            self.remove_user_style(style);
+        }
+    }
+
+    fn exit_fullscreen(&mut self) {
+        self.eval(
+            "if (document.fullscreenElement) document.exitFullscreen(); \
+            else if (document.webkitFullscreenElement) document.webkitExitFullscreen();",
+        );
+    }
+}
@@ -0,0 +1,338 @@
+use crate::{state, Webview, WebviewError};
+#[cfg(not(target_os = "windows"))]
+use std::{cell::RefCell, ffi::CStr, os::raw, rc::Rc};
+
+/// Whether media can start playing without a user gesture
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AutoplayPolicy {
+    /// Media can play as soon as the page starts it
+    Allow,
+    /// Media only plays after the user interacted with the page
+    RequireUserGesture,
+}
+
+/// The kind of a permission requested by a page
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PermissionKind {
+    Geolocation,
+    Camera,
+    Microphone,
+    Notifications,
+    Other,
+    /// Reading the clipboard, see `PermissionExt::set_clipboard_access`
+    Clipboard,
+}
+
+/// Which clipboard operations scripts can perform, through `navigator.clipboard` and `document.execCommand`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ClipboardAccess {
+    Denied,
+    AllowRead,
+    AllowWrite,
+    AllowBoth,
+}
+
+impl ClipboardAccess {
+    fn can_read(self) -> bool {
+        matches!(
+            self,
+            ClipboardAccess::AllowRead | ClipboardAccess::AllowBoth
+        )
+    }
+
+    fn can_write(self) -> bool {
+        matches!(
+            self,
+            ClipboardAccess::AllowWrite | ClipboardAccess::AllowBoth
+        )
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+extern "C" fn permission_cb(
+    kind: raw::c_int,
+    origin: *const raw::c_char,
+    data: *mut raw::c_void,
+) -> raw::c_int {
+    let kind = match kind {
+        0 => PermissionKind::Geolocation,
+        1 => PermissionKind::Camera,
+        2 => PermissionKind::Microphone,
+        3 => PermissionKind::Notifications,
+        5 => PermissionKind::Clipboard,
+        _ => PermissionKind::Other,
+    };
+    let (handler, clipboard) = state::with_key(data as usize, |s| {
+        (s.permission_handler.clone(), s.clipboard_access)
+    });
+    if let (PermissionKind::Clipboard, Some(access)) = (kind, clipboard) {
+        return access.can_read() as _;
+    }
+    let origin = unsafe { CStr::from_ptr(origin) }.to_string_lossy();
+    match handler {
+        Some(handler) => {
+            crate::utils::guard(|| (handler.borrow_mut())(kind, &origin)).unwrap_or(false) as _
+        }
+        None => 0,
+    }
+}
+
+/// Connect the native permission requests, once per webview
+fn hook_permissions(wv: &Webview) {
+    if !state::with(wv, |s| s.hooks.insert("permission")) {
+        return;
+    }
+    #[cfg(not(target_os = "windows"))]
+    unsafe {
+        let win = wv.get_window();
+        fltk_webview_sys::my_on_permission(win, permission_cb, win);
+    }
+}
+
+/// Media and permission policies
+pub trait PermissionExt {
+    /// Set whether media can autoplay. WebKitGTK requires a user gesture by default
+    fn set_autoplay_policy(&mut self, policy: AutoplayPolicy) -> Result<(), WebviewError>;
+    /// Set the handler deciding the permissions requested by pages, it receives the requesting origin
+    /// and returns whether to grant the permission. Requests are denied while no handler is set
+    fn set_permission_handler<F: FnMut(PermissionKind, &str) -> bool + 'static>(
+        &mut self,
+        f: F,
+    ) -> Result<(), WebviewError>;
+    /// Set which clipboard operations scripts can perform without a user gesture or the permission handler.
+    /// Until it's called, the backend's default applies
+    fn set_clipboard_access(&mut self, access: ClipboardAccess);
+    /// Mute or unmute the audio of the page, without pausing playback. Webviews start unmuted
+    fn set_muted(&mut self, muted: bool);
+    /// Whether the audio was muted through `set_muted`
+    fn is_muted(&self) -> bool;
+    /// Pause the `<audio>` and `<video>` elements playing in the current page.
+    /// Media started afterwards, in frames, or through Web Audio, isn't paused
+    fn pause_media(&mut self);
+    /// Resume the media paused by `pause_media`, leaving the media the page paused itself
+    fn resume_media(&mut self);
+    /// Route the play/pause, stop, previous and next track keys to the page's Media Session handlers,
+    /// or its media elements, while the application has focus, disabled by default
+    fn set_media_keys_enabled(&mut self, enabled: bool);
+}
+
+/// Records the Media Session action handlers, and runs the action passed to `window.__fltk_webview_media_key`
+/// while `window.__fltk_webview_media_keys` is set, which media keys pressed in the page also do
+const MEDIA_KEYS_JS: &str = r#"(function() {
+    if (window.__fltk_webview_media_key) return;
+    window.__fltk_webview_media_keys = false;
+    var handlers = {};
+    var session = navigator.mediaSession;
+    if (session && session.setActionHandler) {
+        var set = session.setActionHandler.bind(session);
+        session.setActionHandler = function(action, handler) {
+            handlers[action] = handler;
+            return set(action, handler);
+        };
+    }
+    var media = function() { return Array.from(document.querySelectorAll('audio, video')); };
+    window.__fltk_webview_media_key = function(action) {
+        if (!window.__fltk_webview_media_keys) return false;
+        var playing = media().filter(function(m) { return !m.paused; });
+        if (action === 'playpause') action = playing.length ? 'pause' : 'play';
+        if (handlers[action]) {
+            handlers[action]({ action: action });
+        } else if (action === 'pause' || action === 'stop') {
+            playing.forEach(function(m) { m.pause(); });
+        } else if (action === 'play') {
+            var paused = media().filter(function(m) { return m.paused && m.currentSrc; });
+            if (paused.length) paused[0].play().catch(function() {});
+        }
+        return true;
+    };
+    var keys = {
+        MediaPlayPause: 'playpause', MediaPlay: 'play', MediaPause: 'pause', MediaStop: 'stop',
+        MediaTrackNext: 'nexttrack', MediaTrackPrevious: 'previoustrack'
+    };
+    document.addEventListener('keydown', function(e) {
+        var action = keys[e.key];
+        if (action && window.__fltk_webview_media_key(action)) e.preventDefault();
+    }, true);
+})()"#;
+
+/// The media keys of fltk, with the Media Session actions they trigger
+const MEDIA_KEYS: [(i32, &str); 4] = [
+    (0xef14, "playpause"),
+    (0xef15, "stop"),
+    (0xef16, "previoustrack"),
+    (0xef17, "nexttrack"),
+];
+
+/// Passes the media keys reaching fltk to the webviews which enabled them
+fn media_key_handler(ev: fltk::enums::Event) -> bool {
+    if ev != fltk::enums::Event::KeyDown {
+        return false;
+    }
+    let key = fltk::app::event_key().bits();
+    let action = match MEDIA_KEYS.iter().find(|(k, _)| *k == key) {
+        Some((_, action)) => action,
+        None => return false,
+    };
+    let handles = state::handles_where(|s| s.media_keys);
+    let js =
+        std::ffi::CString::new(format!("window.__fltk_webview_media_key('{}')", action)).unwrap();
+    for handle in &handles {
+        unsafe {
+            fltk_webview_sys::webview_eval(*handle as _, js.as_ptr());
+        }
+    }
+    !handles.is_empty()
+}
+
+/// Mutes the media elements, including the ones played later, and unmutes the ones it muted
+const MUTE_JS: &str = r#"(function(muted) {
+    window.__fltk_webview_muted = muted;
+    var apply = function(m) {
+        if (window.__fltk_webview_muted && !m.muted) {
+            m.muted = true;
+            m.__fltk_webview_muted = true;
+        } else if (!window.__fltk_webview_muted && m.__fltk_webview_muted) {
+            m.muted = false;
+            m.__fltk_webview_muted = false;
+        }
+    };
+    if (!window.__fltk_webview_mute_hooked) {
+        window.__fltk_webview_mute_hooked = true;
+        document.addEventListener('play', function(e) { apply(e.target); }, true);
+    }
+    Array.prototype.forEach.call(document.querySelectorAll('audio, video'), apply);
+})"#;
+
+/// Makes the clipboard operations it's called with denied fail, keeping the original methods
+/// to restore them when they're allowed again
+const CLIPBOARD_JS: &str = r#"(function(read, write) {
+    var clipboard = navigator.clipboard && Object.getPrototypeOf(navigator.clipboard);
+    var original = window.__fltk_webview_clipboard;
+    if (!original) {
+        original = window.__fltk_webview_clipboard = { execCommand: document.execCommand };
+        if (clipboard) ['read', 'readText', 'write', 'writeText'].forEach(function(m) {
+            original[m] = clipboard[m];
+        });
+        document.execCommand = function(command) {
+            var c = String(command).toLowerCase();
+            var denied = window.__fltk_webview_clipboard_denied;
+            if ((c === 'paste' && denied.read) || ((c === 'copy' || c === 'cut') && denied.write))
+                return false;
+            return original.execCommand.apply(document, arguments);
+        };
+    }
+    window.__fltk_webview_clipboard_denied = { read: !read, write: !write };
+    if (!clipboard) return;
+    var deny = function() {
+        return Promise.reject(new DOMException('Clipboard access is denied', 'NotAllowedError'));
+    };
+    ['read', 'readText'].forEach(function(m) {
+        if (original[m]) clipboard[m] = read ? original[m] : deny;
+    });
+    ['write', 'writeText'].forEach(function(m) {
+        if (original[m]) clipboard[m] = write ? original[m] : deny;
+    });
+})"#;
+
+/// Pauses the playing media elements, or resumes the ones it paused
+const PAUSE_JS: &str = r#"(function(paused) {
+    Array.prototype.forEach.call(document.querySelectorAll('audio, video'), function(m) {
+        if (paused && !m.paused) {
+            m.pause();
+            m.__fltk_webview_paused = true;
+        } else if (!paused && m.__fltk_webview_paused) {
+            m.__fltk_webview_paused = false;
+            var playing = m.play();
+            if (playing) playing.catch(function() {});
+        }
+    });
+})"#;
+
+impl PermissionExt for Webview {
+    fn set_autoplay_policy(&mut self, policy: AutoplayPolicy) -> Result<(), WebviewError> {
+        #[cfg(any(target_os = "macos", target_os = "windows"))]
+        {
+            let _ = policy;
+            Err(WebviewError::Unsupported)
+        }
+        #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+        unsafe {
+            fltk_webview_sys::my_set_autoplay(
+                self.get_window(),
+                (policy == AutoplayPolicy::Allow) as _,
+            );
+            Ok(())
+        }
+    }
+
+    fn set_permission_handler<F: FnMut(PermissionKind, &str) -> bool + 'static>(
+        &mut self,
+        f: F,
+    ) -> Result<(), WebviewError> {
+        #[cfg(target_os = "windows")]
+        {
+            let _ = f;
+            Err(WebviewError::Unsupported)
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            state::with(self, |s| {
+                s.permission_handler = Some(Rc::new(RefCell::new(f)))
+            });
+            hook_permissions(self);
+            Ok(())
+        }
+    }
+
+    fn set_clipboard_access(&mut self, access: ClipboardAccess) {
+        state::with(self, |s| s.clipboard_access = Some(access));
+        hook_permissions(self);
+        #[cfg(not(target_os = "windows"))]
+        unsafe {
+            let allowed = access != ClipboardAccess::Denied;
+            fltk_webview_sys::my_set_clipboard_access(self.get_window(), allowed as _);
+        }
+        let js = format!(
+            "{}({}, {})",
+            CLIPBOARD_JS,
+            access.can_read(),
+            access.can_write()
+        );
+        crate::scripts::set_script(self, "clipboard", &js);
+    }
+
+    fn set_muted(&mut self, muted: bool) {
+        state::with(self, |s| s.muted = muted);
+        #[cfg(not(target_os = "windows"))]
+        let native = unsafe { fltk_webview_sys::my_set_muted(self.get_window(), muted as _) != 0 };
+        #[cfg(target_os = "windows")]
+        let native = false;
+        if !native {
+            let js = format!("{}({})", MUTE_JS, muted);
+            crate::scripts::set_script(self, "muted", &js);
+        }
+    }
+
+    fn is_muted(&self) -> bool {
+        state::with(self, |s| s.muted)
+    }
+
+    fn pause_media(&mut self) {
+        self.eval(&format!("{}(true)", PAUSE_JS));
+    }
+
+    fn resume_media(&mut self) {
+        self.eval(&format!("{}(false)", PAUSE_JS));
+    }
+
+    fn set_media_keys_enabled(&mut self, enabled: bool) {
+        state::with(self, |s| s.media_keys = enabled);
+        static HANDLER: std::sync::Once = std::sync::Once::new();
+        HANDLER.call_once(|| fltk::app::add_handler(media_key_handler));
+        let js = format!(
+            "{};\nwindow.__fltk_webview_media_keys = {};",
+            MEDIA_KEYS_JS, enabled
+        );
+        crate::scripts::set_script(self, "media_keys", &js);
+    }
+}
@@ -0,0 +1,114 @@
+use crate::{embed, state, BindExt, EmbedExt, NavigationExt, Webview, WebviewBuilder};
+use fltk::{prelude::*, window};
+use std::{
+    cell::RefCell,
+    ops::{Deref, DerefMut},
+    rc::Rc,
+};
+
+/// Recycles webviews, created through the pool's builder, for the later acquires of the same window.
+/// Released webviews navigate to `about:blank` and lose their tracked bindings, keeping their scripts, handlers and data
+pub struct WebviewPool {
+    builder: WebviewBuilder,
+    idle: Rc<RefCell<Vec<Webview>>>,
+}
+
+impl Default for WebviewPool {
+    fn default() -> Self {
+        Self::new(WebviewBuilder::new())
+    }
+}
+
+impl WebviewPool {
+    /// Create an empty pool, creating webviews with the options of `builder`
+    pub fn new(builder: WebviewBuilder) -> Self {
+        Self {
+            builder,
+            idle: Rc::default(),
+        }
+    }
+
+    /// Take an idle webview created in `win`, or create one like `WebviewBuilder::build`,
+    /// which panics if `win` isn't shown within 2 seconds.
+    /// Idle webviews whose window was deleted are closed
+    pub fn acquire(&mut self, win: &mut window::Window) -> PooledWebview {
+        let target = win.as_widget_ptr() as usize;
+        let mut found = None;
+        self.idle.borrow_mut().retain(|wv| {
+            let host = state::with(wv, |s| s.win.clone());
+            match host {
+                Some(host) if host.was_deleted() => {
+                    // Keeps close from resetting the callbacks of the deleted window
+                    state::with(wv, |s| s.win = None);
+                    wv.clone().close();
+                    false
+                }
+                Some(host) if found.is_none() && host.as_widget_ptr() as usize == target => {
+                    found = Some(wv.clone());
+                    false
+                }
+                _ => true,
+            }
+        });
+        let reused = found.is_some();
+        let wv = found.unwrap_or_else(|| self.builder.clone().build(win));
+        PooledWebview {
+            wv,
+            reused,
+            idle: self.idle.clone(),
+        }
+    }
+
+    /// The number of webviews waiting to be reused
+    pub fn idle_count(&self) -> usize {
+        self.idle.borrow().len()
+    }
+
+    /// Close the idle webviews. Dropping the pool leaves them open, like dropping a `Webview`
+    pub fn clear(&mut self) {
+        for wv in self.idle.borrow_mut().drain(..) {
+            wv.close();
+        }
+    }
+}
+
+/// A webview borrowed from a `WebviewPool`, which it's reset and returned to when dropped
+pub struct PooledWebview {
+    wv: Webview,
+    reused: bool,
+    idle: Rc<RefCell<Vec<Webview>>>,
+}
+
+impl PooledWebview {
+    /// Whether the webview was reused rather than created by `acquire`
+    pub fn is_reused(&self) -> bool {
+        self.reused
+    }
+}
+
+impl Deref for PooledWebview {
+    type Target = Webview;
+    fn deref(&self) -> &Webview {
+        &self.wv
+    }
+}
+
+impl DerefMut for PooledWebview {
+    fn deref_mut(&mut self) -> &mut Webview {
+        &mut self.wv
+    }
+}
+
+impl Drop for PooledWebview {
+    fn drop(&mut self) {
+        // Closed webviews can't be reused
+        if !self.wv.is_running() {
+            return;
+        }
+        self.wv.stop();
+        self.wv.navigate("about:blank");
+        self.wv.clear_bindings();
+        embed::reset_size(&self.wv);
+        self.idle.borrow_mut().push(self.wv.clone());
+    }
+}
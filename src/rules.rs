@@ -0,0 +1,145 @@
+use crate::{Webview, WebviewError};
+use tinyjson::JsonValue;
+
+/// Identifies content rules added through `add_content_rules`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct RulesHandle(u64);
+
+/// The actions of the content blocker format
+const ACTIONS: &[&str] = &[
+    "block",
+    "block-cookies",
+    "css-display-none",
+    "ignore-previous-rules",
+    "make-https",
+];
+
+/// Check that `json` follows the content blocker format:
+/// an array of rules, each with a trigger holding a `url-filter`, and an action of a known type
+fn validate(json: &str) -> Result<(), WebviewError> {
+    let invalid = |msg: &str| WebviewError::InvalidArgument(format!("content rules: {}", msg));
+    let rules = match json.parse() {
+        Ok(JsonValue::Array(rules)) if !rules.is_empty() => rules,
+        Ok(_) => return Err(invalid("expected a non-empty array of rules")),
+        Err(e) => return Err(invalid(&e.to_string())),
+    };
+    for (i, rule) in rules.iter().enumerate() {
+        let field = |name: &str| match rule {
+            JsonValue::Object(rule) => rule.get(name),
+            _ => None,
+        };
+        let filter = match field("trigger") {
+            Some(JsonValue::Object(trigger)) => trigger.get("url-filter"),
+            _ => None,
+        };
+        if !matches!(filter, Some(JsonValue::String(_))) {
+            return Err(invalid(&format!("rule {} has no trigger url-filter", i)));
+        }
+        let action = match field("action") {
+            Some(JsonValue::Object(action)) => action,
+            _ => return Err(invalid(&format!("rule {} has no action", i))),
+        };
+        match action.get("type") {
+            Some(JsonValue::String(kind)) if ACTIONS.contains(&kind.as_str()) => {
+                if kind == "css-display-none"
+                    && !matches!(action.get("selector"), Some(JsonValue::String(_)))
+                {
+                    return Err(invalid(&format!("rule {} has no selector", i)));
+                }
+            }
+            _ => return Err(invalid(&format!("rule {} has an unknown action type", i))),
+        }
+    }
+    Ok(())
+}
+
+/// Content blocking methods
+pub trait ContentRulesExt {
+    /// Compile and add content blocking rules, in the JSON format of WebKit's content blockers, e.g.
+    /// `[{"trigger": {"url-filter": ".*ads.*"}, "action": {"type": "block"}}]`.
+    /// Rules which don't follow the format, or fail to compile, return `WebviewError::InvalidArgument`.
+    /// Compilation runs the fltk event loop until it's done, and applies to the next loads
+    fn add_content_rules(&mut self, json: &str) -> Result<RulesHandle, WebviewError>;
+    /// Remove content rules added through `add_content_rules`
+    fn remove_content_rules(&mut self, handle: RulesHandle);
+}
+
+#[cfg(not(target_os = "windows"))]
+type Compiled = std::rc::Rc<std::cell::RefCell<Option<Result<usize, String>>>>;
+
+#[cfg(not(target_os = "windows"))]
+extern "C" fn rules_cb(
+    rules: *mut std::os::raw::c_void,
+    error: *const std::os::raw::c_char,
+    data: *mut std::os::raw::c_void,
+) {
+    let compiled: Compiled = unsafe { std::rc::Rc::from_raw(data as *const _) };
+    let res = if rules.is_null() {
+        Err(unsafe { std::ffi::CStr::from_ptr(error) }
+            .to_string_lossy()
+            .into_owned())
+    } else {
+        Ok(rules as usize)
+    };
+    *compiled.borrow_mut() = Some(res);
+}
+
+impl ContentRulesExt for Webview {
+    fn add_content_rules(&mut self, json: &str) -> Result<RulesHandle, WebviewError> {
+        validate(json)?;
+        #[cfg(target_os = "windows")]
+        {
+            Err(WebviewError::Unsupported)
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            use crate::state;
+            use std::ffi::CString;
+            let id = state::with(self, |s| {
+                s.next_content_rules += 1;
+                s.next_content_rules
+            });
+            let ident = CString::new(format!(
+                "fltk-webview-{}-{}",
+                self.get_window() as usize,
+                id
+            ))
+            .unwrap();
+            let compiled = Compiled::default();
+            unsafe {
+                fltk_webview_sys::my_add_content_rules(
+                    self.get_window(),
+                    ident.as_ptr(),
+                    json.as_ptr() as _,
+                    json.len(),
+                    rules_cb,
+                    std::rc::Rc::into_raw(compiled.clone()) as _,
+                );
+            }
+            let res = loop {
+                if let Some(res) = compiled.borrow_mut().take() {
+                    break res;
+                }
+                let _ = fltk::app::wait_for(0.01);
+            };
+            match res {
+                Ok(rules) => {
+                    state::with(self, |s| s.content_rules.insert(id, rules as _));
+                    Ok(RulesHandle(id))
+                }
+                Err(e) => Err(WebviewError::InvalidArgument(e)),
+            }
+        }
+    }
+
+    fn remove_content_rules(&mut self, handle: RulesHandle) {
+        #[cfg(target_os = "windows")]
+        let _ = handle;
+        #[cfg(not(target_os = "windows"))]
+        if let Some(rules) = crate::state::with(self, |s| s.content_rules.remove(&handle.0)) {
+            unsafe {
+                fltk_webview_sys::my_remove_content_rules(self.get_window(), rules);
+            }
+        }
+    }
+}
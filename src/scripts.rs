@@ -0,0 +1,219 @@
+use crate::{state, Webview, WebviewError};
+
+/// Determines when an init script runs relative to the parsing of the document
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum InjectionTiming {
+    /// Runs before any of the page's own scripts, `document.body` isn't available yet
+    DocumentStart,
+    /// Runs once the DOM has been parsed
+    DocumentEnd,
+}
+
+/// The JavaScript world an init script runs in
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum ContentWorld {
+    /// The page's own world, sharing its globals
+    #[default]
+    Page,
+    /// A world of fltk-webview, sharing the DOM with the page but not its globals,
+    /// so the page can't observe or tamper with the script's variables.
+    /// Bound functions and `window.external` only exist in the page world
+    Isolated,
+}
+
+/// Identifies a style sheet added through `add_user_style`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct StyleHandle(u64);
+
+/// Adds the style sheet as a `<style>` element, unless it was removed
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn style_js(id: u64, css: &str) -> String {
+    format!(
+        r#"(function() {{
+    var id = '__fltk_webview_style_{0}';
+    var add = function() {{
+        var removed = window.__fltk_webview_removed_styles || {{}};
+        if (removed[id] || document.getElementById(id)) return;
+        var style = document.createElement('style');
+        style.id = id;
+        style.textContent = {1};
+        (document.head || document.documentElement).appendChild(style);
+    }};
+    if (document.documentElement) add(); else document.addEventListener('DOMContentLoaded', add);
+}})()"#,
+        id,
+        crate::utils::quote(css)
+    )
+}
+
+/// Removes the `<style>` element of the style sheet, and keeps it from being added later
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn remove_style_js(id: u64) -> String {
+    format!(
+        r#"(function() {{
+    var id = '__fltk_webview_style_{}';
+    window.__fltk_webview_removed_styles = window.__fltk_webview_removed_styles || {{}};
+    window.__fltk_webview_removed_styles[id] = true;
+    var style = document.getElementById(id);
+    if (style) style.remove();
+}})()"#,
+        id
+    )
+}
+
+/// Install `js` as the init script of the setting `key`, replacing the one previously installed for it,
+/// and run it in the current page, so a setting changed repeatedly doesn't pile up scripts.
+/// WebView2's scripts can't be removed, so on Windows, `js` is only added when it changed,
+/// running after the previous scripts of the setting, which it overrides
+pub(crate) fn set_script(wv: &Webview, key: &'static str, js: &str) {
+    #[cfg(not(target_os = "windows"))]
+    {
+        let source = std::ffi::CString::new(js).unwrap();
+        let script =
+            unsafe { fltk_webview_sys::my_add_user_script(wv.get_window(), source.as_ptr(), 0, 0) };
+        if let Some(previous) = state::with(wv, |s| s.setting_scripts.insert(key, script)) {
+            unsafe {
+                fltk_webview_sys::my_remove_user_script(wv.get_window(), previous);
+            }
+        }
+    }
+    #[cfg(target_os = "windows")]
+    if state::with(wv, |s| s.setting_scripts.insert(key, js.to_string())).as_deref() != Some(js) {
+        wv.init(js);
+    }
+    wv.eval(js);
+}
+
+/// Init script related methods
+pub trait ScriptExt {
+    /// Injects JavaScript code at the initialization of new pages, at the requested timing.
+    /// `init` is equivalent to using `InjectionTiming::DocumentStart`
+    fn init_with_timing(&mut self, js: &str, timing: InjectionTiming);
+    /// Like `init_with_timing`, running the script in `world`
+    fn init_in_world(
+        &mut self,
+        js: &str,
+        timing: InjectionTiming,
+        world: ContentWorld,
+    ) -> Result<(), WebviewError>;
+    /// Removes the scripts registered via `init_with_timing` and `init_in_world`, taking effect on the next page load.
+    /// Scripts passed to `init`, including the ones installed by fltk-webview itself, aren't tracked and remain installed
+    fn clear_init_scripts(&mut self) -> Result<(), WebviewError>;
+    /// Add a user style sheet, applied to the current page and the next ones
+    fn add_user_style(&mut self, css: &str) -> StyleHandle;
+    /// Remove a style sheet added through `add_user_style`, from the current page and the next ones
+    fn remove_user_style(&mut self, handle: StyleHandle);
+}
+
+impl ScriptExt for Webview {
+    fn init_with_timing(&mut self, js: &str, timing: InjectionTiming) {
+        self.init_in_world(js, timing, ContentWorld::Page)
+            .expect("the page world is available on every backend");
+    }
+
+    fn init_in_world(
+        &mut self,
+        js: &str,
+        timing: InjectionTiming,
+        world: ContentWorld,
+    ) -> Result<(), WebviewError> {
+        #[cfg(target_os = "windows")]
+        {
+            if world == ContentWorld::Isolated {
+                return Err(WebviewError::Unsupported);
+            }
+            match timing {
+                InjectionTiming::DocumentStart => self.init(js),
+                // Indirect eval runs the code in the global scope, like a top-level script
+                InjectionTiming::DocumentEnd => self.init(&format!(
+                    "(function() {{ var f = function() {{ (0, eval)({}); }}; if (document.readyState === 'loading') {{ document.addEventListener('DOMContentLoaded', f); }} else {{ f(); }} }})();",
+                    crate::utils::quote(js)
+                )),
+            }
+            Ok(())
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            let js = std::ffi::CString::new(js).unwrap();
+            let script = unsafe {
+                fltk_webview_sys::my_add_user_script(
+                    self.get_window(),
+                    js.as_ptr(),
+                    (timing == InjectionTiming::DocumentEnd) as _,
+                    (world == ContentWorld::Isolated) as _,
+                )
+            };
+            if script.is_null() {
+                return Err(WebviewError::Unsupported);
+            }
+            state::with(self, |s| s.scripts.push(script));
+            Ok(())
+        }
+    }
+
+    fn clear_init_scripts(&mut self) -> Result<(), WebviewError> {
+        #[cfg(target_os = "windows")]
+        return Err(WebviewError::Unsupported);
+        #[cfg(not(target_os = "windows"))]
+        {
+            let scripts = state::with(self, |s| std::mem::take(&mut s.scripts));
+            for script in scripts {
+                unsafe {
+                    fltk_webview_sys::my_remove_user_script(self.get_window(), script);
+                }
+            }
+            Ok(())
+        }
+    }
+
+    fn add_user_style(&mut self, css: &str) -> StyleHandle {
+        let id = state::with(self, |s| {
+            s.next_style += 1;
+            s.next_style
+        });
+        #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+        let native = unsafe {
+            let css = std::ffi::CString::new(css).unwrap();
+            fltk_webview_sys::my_add_user_style(self.get_window(), css.as_ptr())
+        };
+        #[cfg(target_os = "macos")]
+        let native = unsafe {
+            let js = style_js(id, css);
+            self.eval(&js);
+            let js = std::ffi::CString::new(js).unwrap();
+            fltk_webview_sys::my_add_user_script(self.get_window(), js.as_ptr(), 0, 0)
+        };
+        #[cfg(target_os = "windows")]
+        let native = {
+            let js = style_js(id, css);
+            self.init(&js);
+            self.eval(&js);
+            std::ptr::null_mut()
+        };
+        state::with(self, |s| s.styles.insert(id, native));
+        StyleHandle(id)
+    }
+
+    fn remove_user_style(&mut self, handle: StyleHandle) {
+        let native = match state::with(self, |s| s.styles.remove(&handle.0)) {
+            Some(native) => native,
+            None => return,
+        };
+        #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+        unsafe {
+            fltk_webview_sys::my_remove_user_style(self.get_window(), native);
+        }
+        #[cfg(target_os = "macos")]
+        unsafe {
+            fltk_webview_sys::my_remove_user_script(self.get_window(), native);
+            self.eval(&remove_style_js(handle.0));
+        }
+        #[cfg(target_os = "windows")]
+        {
+            let _ = native;
+            let js = remove_style_js(handle.0);
+            self.init(&js);
+            self.eval(&js);
+        }
+    }
+}
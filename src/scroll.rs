@@ -0,0 +1,108 @@
+use crate::{eval, state, ScriptExt, Webview};
+use tinyjson::JsonValue;
+
+/// Layout metrics of the top-level document, in CSS pixels, see `ScrollExt::viewport_metrics`
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct ViewportMetrics {
+    /// `window.innerWidth`, the width of the viewport including its scrollbar
+    pub inner_width: f64,
+    /// `window.innerHeight`
+    pub inner_height: f64,
+    /// `window.devicePixelRatio`, the device pixels per CSS pixel, including the page zoom
+    pub device_pixel_ratio: f64,
+    /// The `scrollWidth` of the document element, the width of its content
+    pub scroll_width: f64,
+    /// The `scrollHeight` of the document element
+    pub scroll_height: f64,
+}
+
+/// Scrolling methods, operating on the top-level document only, through JavaScript
+pub trait ScrollExt {
+    /// Scroll the document to `x`, `y` in CSS pixels
+    fn scroll_to(&mut self, x: f64, y: f64);
+    /// Pass the current scroll position of the document, in CSS pixels, to `f` on the main thread
+    fn scroll_position<F: FnOnce(f64, f64) + 'static>(&mut self, f: F);
+    /// Pass the viewport size, device pixel ratio and content size of the document to `f` on the main thread,
+    /// read in a single evaluation
+    fn viewport_metrics<F: FnOnce(ViewportMetrics) + 'static>(&mut self, f: F);
+    /// Show or hide the scrollbars, keeping the document scrollable.
+    /// Hiding adds a user style sheet, see `ScriptExt::add_user_style`
+    fn set_scrollbars_visible(&mut self, visible: bool);
+    /// Enable or disable smooth scrolling, through the `scroll-behavior` of the documents.
+    /// Until this is called, the backend's default applies
+    fn set_smooth_scrolling(&mut self, enabled: bool);
+}
+
+/// Hides the scrollbars of WebKit and Chromium
+const HIDE_SCROLLBARS_CSS: &str =
+    "::-webkit-scrollbar { display: none; } html, body { scrollbar-width: none; }";
+
+impl ScrollExt for Webview {
+    fn scroll_to(&mut self, x: f64, y: f64) {
+        self.eval(&format!("window.scrollTo({}, {})", x, y));
+    }
+
+    fn scroll_position<F: FnOnce(f64, f64) + 'static>(&mut self, f: F) {
+        eval::eval_with(self, "[window.scrollX, window.scrollY]", move |res| {
+            let pos = match res.ok().and_then(|r| r.parse().ok()) {
+                Some(JsonValue::Array(pos)) => pos,
+                _ => return,
+            };
+            let coord = |i: usize| pos.get(i).and_then(|c| c.get::<f64>()).copied();
+            if let (Some(x), Some(y)) = (coord(0), coord(1)) {
+                f(x, y);
+            }
+        });
+    }
+
+    fn viewport_metrics<F: FnOnce(ViewportMetrics) + 'static>(&mut self, f: F) {
+        const METRICS_JS: &str =
+            "[window.innerWidth, window.innerHeight, window.devicePixelRatio, \
+             document.documentElement.scrollWidth, document.documentElement.scrollHeight]";
+        eval::eval_with(self, METRICS_JS, move |res| {
+            let values = match res.ok().and_then(|r| r.parse().ok()) {
+                Some(JsonValue::Array(values)) => values,
+                _ => return,
+            };
+            let value = |i: usize| values.get(i).and_then(|v| v.get::<f64>()).copied();
+            let metrics = || {
+                Some(ViewportMetrics {
+                    inner_width: value(0)?,
+                    inner_height: value(1)?,
+                    device_pixel_ratio: value(2)?,
+                    scroll_width: value(3)?,
+                    scroll_height: value(4)?,
+                })
+            };
+            if let Some(metrics) = metrics() {
+                f(metrics);
+            }
+        });
+    }
+
+    fn set_scrollbars_visible(&mut self, visible: bool) {
+        let style = state::with(self, |s| s.scrollbar_style.take());
+        let style = match (style, visible) {
+            (Some(style), true) => {
+                self.remove_user_style(style);
+                None
+            }
+            (None, false) => Some(self.add_user_style(HIDE_SCROLLBARS_CSS)),
+            (style, _) => style,
+        };
+        state::with(self, |s| s.scrollbar_style = style);
+    }
+
+    fn set_smooth_scrolling(&mut self, enabled: bool) {
+        #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+        unsafe {
+            fltk_webview_sys::my_set_smooth_scrolling(self.get_window(), enabled as _);
+        }
+        if let Some(style) = state::with(self, |s| s.scroll_behavior_style.take()) {
+            self.remove_user_style(style);
+        }
+        let behavior = if enabled { "smooth" } else { "auto" };
+        let style = self.add_user_style(&format!("html {{ scroll-behavior: {}; }}", behavior));
+        state::with(self, |s| s.scroll_behavior_style = Some(style));
+    }
+}
@@ -0,0 +1,160 @@
+use crate::{eval, external, state, utils, Webview, WebviewError};
+use std::{
+    cell::Cell,
+    rc::Rc,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+/// Starts the blobs holding the backend's own session state
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+const NATIVE_TAG: &[u8] = b"fltk-webview-session:webkitgtk\n";
+#[cfg(target_os = "macos")]
+const NATIVE_TAG: &[u8] = b"fltk-webview-session:wkwebview\n";
+/// Starts the blobs holding the state read from the page, as JSON
+const PAGE_TAG: &[u8] = b"fltk-webview-session:page\n";
+
+/// Reads the URL, scroll position and form fields of the page
+const SAVE_JS: &str = r#"(function() {
+    var fields = Array.from(document.querySelectorAll('input, textarea, select')).map(function(el) {
+        if (el.type === 'password' || el.type === 'file') return null;
+        return el.type === 'checkbox' || el.type === 'radio' ? el.checked : el.value;
+    });
+    return { url: location.href, scroll: [window.scrollX, window.scrollY], fields: fields };
+})()"#;
+
+/// Applies the page state it's called with once its page loaded, at most once per document,
+/// and returns whether it's done. Pages which started loading before the restore are skipped
+const RESTORE_JS: &str = r#"(function(s) {
+    if (location.href !== s.url || document.readyState !== 'complete') return false;
+    if (performance.timeOrigin < s.since) return false;
+    if (window.__fltk_webview_session === s.id) return true;
+    window.__fltk_webview_session = s.id;
+    var els = document.querySelectorAll('input, textarea, select');
+    s.fields.forEach(function(v, i) {
+        var el = els[i];
+        if (!el || v === null) return;
+        if (typeof v === 'boolean') el.checked = v;
+        else el.value = v;
+        el.dispatchEvent(new Event('input', { bubbles: true }));
+        el.dispatchEvent(new Event('change', { bubbles: true }));
+    });
+    window.scrollTo(s.scroll[0], s.scroll[1]);
+    return true;
+})"#;
+
+/// How long a restored page may take to load before its state is given up on
+const RESTORE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Evaluate `js` every 100ms until it returns true, the restore timed out, or the webview closed
+fn apply_when_loaded(wv: Webview, js: String) {
+    let key = wv.get_window() as usize;
+    let end = Instant::now() + RESTORE_TIMEOUT;
+    let applied = Rc::new(Cell::new(false));
+    fltk::app::add_timeout3(0.1, move |handle| {
+        if applied.get() || Instant::now() >= end || !state::is_running(key) {
+            return;
+        }
+        // Results lost to a navigation are simply asked again, the page applies the state once
+        let applied = applied.clone();
+        eval::eval_with(&wv, &js, move |res| {
+            if res.as_deref() == Ok("true") {
+                applied.set(true);
+            }
+        });
+        fltk::app::repeat_timeout3(0.1, handle);
+    });
+}
+
+#[cfg(not(target_os = "windows"))]
+extern "C" fn session_cb(data: *const u8, len: usize, user: *mut std::os::raw::c_void) {
+    let state = unsafe { &mut *(user as *mut Option<Vec<u8>>) };
+    *state = Some(unsafe { std::slice::from_raw_parts(data, len) }.to_vec());
+}
+
+/// Saving and restoring the state of a webview, e.g. to reopen tabs, or recover from crashes
+pub trait SessionExt {
+    /// Pass a snapshot of the state of the webview to `f` on the main thread, as an opaque blob
+    /// which `restore_session_state` takes on the same backend, and which can be stored, e.g. in a file
+    fn save_session_state<F: FnOnce(Result<Vec<u8>, WebviewError>) + 'static>(&self, f: F);
+    /// Restore the state saved by `save_session_state`, loading its current page, e.g. in a new webview.
+    /// Returns `WebviewError::InvalidArgument` if the blob isn't a session state, or was saved by another backend
+    fn restore_session_state(&mut self, blob: &[u8]) -> Result<(), WebviewError>;
+}
+
+impl SessionExt for Webview {
+    fn save_session_state<F: FnOnce(Result<Vec<u8>, WebviewError>) + 'static>(&self, f: F) {
+        #[cfg(not(target_os = "windows"))]
+        {
+            let mut state: Option<Vec<u8>> = None;
+            unsafe {
+                fltk_webview_sys::my_get_session_state(
+                    self.get_window(),
+                    session_cb,
+                    &mut state as *mut _ as _,
+                );
+            }
+            if let Some(state) = state {
+                f(Ok([NATIVE_TAG, &state[..]].concat()));
+                return;
+            }
+        }
+        eval::eval_with(self, SAVE_JS, move |res| {
+            f(res
+                .map(|json| [PAGE_TAG, json.as_bytes()].concat())
+                .map_err(WebviewError::Js));
+        });
+    }
+
+    fn restore_session_state(&mut self, blob: &[u8]) -> Result<(), WebviewError> {
+        let invalid = || WebviewError::InvalidArgument("session state".to_string());
+        #[cfg(not(target_os = "windows"))]
+        if let Some(state) = blob.strip_prefix(NATIVE_TAG) {
+            let restored = unsafe {
+                fltk_webview_sys::my_restore_session_state(
+                    self.get_window(),
+                    state.as_ptr(),
+                    state.len(),
+                )
+            };
+            return if restored != 0 {
+                Ok(())
+            } else {
+                Err(invalid())
+            };
+        }
+        let json = blob
+            .strip_prefix(PAGE_TAG)
+            .and_then(|json| std::str::from_utf8(json).ok())
+            .ok_or_else(invalid)?;
+        let state: JsonValue = json.parse().map_err(|_| invalid())?;
+        let mut fields = match state {
+            JsonValue::Object(fields) => fields,
+            _ => return Err(invalid()),
+        };
+        let url = match fields.get("url") {
+            Some(JsonValue::String(url)) => url.clone(),
+            _ => return Err(invalid()),
+        };
+        // Only web pages are loaded, not e.g. file or javascript URLs a tampered blob could carry
+        external::validate(&url).map_err(|_| invalid())?;
+        if url.to_ascii_lowercase().starts_with("mailto:") {
+            return Err(invalid());
+        }
+        // Tells the restores apart, so a page applies each at most once
+        fields.insert("id".to_string(), JsonValue::String(utils::timestamp()));
+        // Pages whose loading started before now are the ones being navigated away from
+        let since = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        fields.insert("since".to_string(), JsonValue::Number(since as f64));
+        // Generated again, so the blob can only carry data into the page
+        let json = JsonValue::Object(fields)
+            .stringify()
+            .map_err(|_| invalid())?;
+        self.navigate(&url);
+        apply_when_loaded(self.clone(), format!("{}({})", RESTORE_JS, json));
+        Ok(())
+    }
+}
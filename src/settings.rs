@@ -0,0 +1,854 @@
+use crate::{state, ScriptExt, Webview, WebviewError};
+
+/// How aggressively the web engine caches resources
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CachePolicy {
+    /// The backend's default
+    Default,
+    /// No persistent cache, for displaying local or generated content
+    DocumentViewer,
+    /// Large memory and disk caches, for browsing many pages
+    WebBrowser,
+}
+
+/// The color scheme pages see through `prefers-color-scheme`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum ColorScheme {
+    /// Follow the system
+    #[default]
+    Auto,
+    /// Prefer a light scheme
+    Light,
+    /// Prefer a dark scheme
+    Dark,
+}
+
+/// The oldest TLS version connections can negotiate
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TlsVersion {
+    Tls12,
+    Tls13,
+}
+
+/// Settings applied together by `SettingsExt::with_settings`.
+/// Fields left to `None` keep their current value
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct Settings {
+    /// Run JavaScript, see `SettingsExt::set_javascript_enabled`
+    pub javascript: Option<bool>,
+    /// Load images automatically
+    pub images: Option<bool>,
+    /// Run plugins. WebKitGTK 2.32 and later don't have plugins, so this is ignored there
+    pub plugins: Option<bool>,
+    /// Enable WebGL
+    pub webgl: Option<bool>,
+    /// Enable the inspector, like `debug` at creation
+    pub developer_extras: Option<bool>,
+    /// Let pages loaded from `file://` URLs access other files,
+    /// see `SettingsExt::set_allow_file_access_from_file_urls`
+    pub allow_file_access_from_file_urls: Option<bool>,
+    /// Let pages loaded from `file://` URLs access any origin,
+    /// see `SettingsExt::set_allow_universal_access_from_file_urls`
+    pub allow_universal_access_from_file_urls: Option<bool>,
+}
+
+impl Settings {
+    /// The values passed to the backend, -1 keeping a setting
+    #[cfg(not(target_os = "windows"))]
+    fn values(&self) -> [std::os::raw::c_int; 7] {
+        [
+            self.javascript,
+            self.images,
+            self.plugins,
+            self.webgl,
+            self.developer_extras,
+            self.allow_file_access_from_file_urls,
+            self.allow_universal_access_from_file_urls,
+        ]
+        .map(|v| v.map_or(-1, |v| v as _))
+    }
+}
+
+/// The default fonts of pages, used for text whose style doesn't name a font,
+/// applied by `SettingsExt::set_font_defaults`.
+/// Fields left to `None` keep their current value
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct FontConfig {
+    /// The family of text without a font family, e.g. `Noto Sans`
+    pub default_family: Option<String>,
+    /// The family of the generic `serif` family
+    pub serif: Option<String>,
+    /// The family of the generic `sans-serif` family
+    pub sans_serif: Option<String>,
+    /// The family of the generic `monospace` family, also used for `pre` and `code` elements
+    pub monospace: Option<String>,
+    /// The size of text without a font size, in CSS pixels
+    pub default_size: Option<u32>,
+    /// The size of monospace text without a font size, in CSS pixels
+    pub monospace_size: Option<u32>,
+}
+
+/// The font sizes accepted by `SettingsExt::set_font_defaults`, in CSS pixels
+const FONT_SIZES: std::ops::RangeInclusive<u32> = 6..=96;
+
+impl FontConfig {
+    /// The families, in the order of the backends
+    fn families(&self) -> [&Option<String>; 4] {
+        [
+            &self.default_family,
+            &self.serif,
+            &self.sans_serif,
+            &self.monospace,
+        ]
+    }
+
+    /// Check that the families are names, and the sizes within `FONT_SIZES`
+    fn validate(&self) -> Result<(), WebviewError> {
+        for family in self.families().into_iter().flatten() {
+            if family.trim().is_empty() || family.chars().any(|c| c.is_control()) {
+                return Err(WebviewError::InvalidArgument(format!(
+                    "font family: {:?}",
+                    family
+                )));
+            }
+        }
+        for size in [self.default_size, self.monospace_size]
+            .into_iter()
+            .flatten()
+        {
+            if !FONT_SIZES.contains(&size) {
+                return Err(WebviewError::InvalidArgument(format!(
+                    "font size: {}, expected {} to {}",
+                    size,
+                    FONT_SIZES.start(),
+                    FONT_SIZES.end()
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Override the fields of `self` set in `other`
+    fn merge(&mut self, other: FontConfig) {
+        let FontConfig {
+            default_family,
+            serif,
+            sans_serif,
+            monospace,
+            default_size,
+            monospace_size,
+        } = other;
+        self.default_family = default_family.or(self.default_family.take());
+        self.serif = serif.or(self.serif.take());
+        self.sans_serif = sans_serif.or(self.sans_serif.take());
+        self.monospace = monospace.or(self.monospace.take());
+        self.default_size = default_size.or(self.default_size);
+        self.monospace_size = monospace_size.or(self.monospace_size);
+    }
+
+    /// The style sheet standing in for the backend's settings.
+    /// `:where` gives the rules no specificity, so any rule of the page overrides them.
+    /// CSS can't redefine the generic families, so `serif` and `sans-serif` aren't applied
+    fn css(&self) -> String {
+        let quote =
+            |family: &str| format!("\"{}\"", family.replace('\\', "\\\\").replace('"', "\\\""));
+        let mut css = String::new();
+        let mut root = vec![];
+        if let Some(family) = &self.default_family {
+            root.push(format!("font-family: {};", quote(family)));
+        }
+        if let Some(size) = self.default_size {
+            root.push(format!("font-size: {}px;", size));
+        }
+        if !root.is_empty() {
+            css += &format!(":where(html) {{ {} }}\n", root.join(" "));
+        }
+        let mut mono = vec![];
+        if let Some(family) = &self.monospace {
+            mono.push(format!("font-family: {}, monospace;", quote(family)));
+        }
+        if let Some(size) = self.monospace_size {
+            mono.push(format!("font-size: {}px;", size));
+        }
+        if !mono.is_empty() {
+            css += &format!(
+                ":where(pre, code, kbd, samp, tt, xmp, listing, plaintext) {{ {} }}\n",
+                mono.join(" ")
+            );
+        }
+        css
+    }
+}
+
+/// Settings of the web engine
+pub trait SettingsExt {
+    /// Change several settings at once, through `f`.
+    /// Requesting unsupported settings returns `WebviewError::Unsupported` and nothing is changed
+    fn with_settings<F: FnOnce(&mut Settings)>(&mut self, f: F) -> Result<(), WebviewError>;
+    /// Enable or disable JavaScript in pages, enabled by default.
+    /// Disabling it also breaks `bind`, `eval` and `init`, which rely on JavaScript
+    fn set_javascript_enabled(&mut self, enabled: bool) -> Result<(), WebviewError>;
+    /// Make the inspector available from the context menu, independently of the `debug` flag at creation.
+    /// Equivalent to setting `Settings::developer_extras` through `with_settings`
+    fn set_developer_extras(&mut self, enabled: bool) -> Result<(), WebviewError>;
+    /// Let pages loaded from `file://` URLs read other `file://` URLs, disabled by default.
+    /// **Any local page can then read the user's files**, so only enable it for the application's own pages
+    fn set_allow_file_access_from_file_urls(&mut self, allow: bool) -> Result<(), WebviewError>;
+    /// Let pages loaded from `file://` URLs read any origin, disabled by default. **Local pages can then act
+    /// on the user's behalf on any site**, so only enable it for the application's own pages
+    fn set_allow_universal_access_from_file_urls(
+        &mut self,
+        allow: bool,
+    ) -> Result<(), WebviewError>;
+    /// Set the color scheme this webview's pages see through `prefers-color-scheme`,
+    /// in their style sheets and `matchMedia` queries, without affecting the other webviews
+    fn set_color_scheme(&mut self, scheme: ColorScheme);
+    /// Throttle the page's `requestAnimationFrame` and `setInterval` callbacks to about once a second
+    /// while the window doesn't have focus, disabled by default
+    fn set_background_throttling(&mut self, enabled: bool);
+    /// Set the cache policy. On WebKitGTK, this applies to all webviews of the process
+    fn set_cache_policy(&mut self, policy: CachePolicy) -> Result<(), WebviewError>;
+    /// Enable or disable spellchecking in editable content, disabled by default.
+    /// `languages` are BCP 47 tags, like `en-US` or `fr`, and invalid ones return `WebviewError::InvalidArgument`.
+    /// An empty slice keeps the current languages
+    fn set_spellcheck(&mut self, enabled: bool, languages: &[&str]) -> Result<(), WebviewError>;
+    /// Set the encoding of pages which don't declare one, e.g. `ISO-8859-1` or `windows-1252`.
+    /// Names which aren't valid charset names return `WebviewError::InvalidArgument`
+    fn set_default_charset(&mut self, charset: &str) -> Result<(), WebviewError>;
+    /// Enable or disable the back-forward cache, which restores pages intact on back and forward navigations,
+    /// without running their init scripts again. The backend's default, enabled, applies until this is called
+    fn set_back_forward_cache(&mut self, enabled: bool);
+    /// Refuse connections negotiating a TLS version older than `version`
+    fn set_minimum_tls_version(&mut self, version: TlsVersion) -> Result<(), WebviewError>;
+    /// Set the languages pages are requested in, most preferred first, as BCP 47 tags like `fr-FR` or `en`.
+    /// An empty slice, or invalid tags, return `WebviewError::InvalidArgument`
+    fn set_languages(&mut self, languages: &[&str]) -> Result<(), WebviewError>;
+    /// Enable or disable the prompts offering to save the passwords typed in pages, disabled by default.
+    /// None of the backends can enable them, so enabling returns `WebviewError::Unsupported`
+    fn set_password_autosave(&mut self, enabled: bool) -> Result<(), WebviewError>;
+    /// Enable or disable suggesting previously entered values, such as addresses, in form fields,
+    /// through their `autocomplete` attribute
+    fn set_general_autofill(&mut self, enabled: bool) -> Result<(), WebviewError>;
+    /// Enable or disable WebGL, which the backends enable by default where the GPU supports it
+    fn set_webgl_enabled(&mut self, enabled: bool);
+    /// Enable or disable loading the images of pages, enabled by default
+    fn set_auto_load_images(&mut self, enabled: bool) -> Result<(), WebviewError>;
+    /// Enforce the content security policy `policy`, e.g. `script-src 'self'`, on the current page and the next ones,
+    /// through a `<meta http-equiv>` element, so `frame-ancestors`, `report-uri` and `sandbox` are ignored.
+    /// An empty policy stops enforcing it on the next pages
+    fn set_csp(&mut self, policy: &str);
+    /// Enable or disable caret browsing, disabled by default, which lets keyboard users move a caret
+    /// through the text of pages with the arrow, Home and End keys, and select with Shift
+    fn set_caret_browsing(&mut self, enabled: bool);
+    /// Set the default fonts of pages, e.g. for consistent typography in a document viewer,
+    /// independently of the system's font configuration. Fields of `config` left to `None` keep their value.
+    /// Returns `WebviewError::InvalidArgument` for empty families, or sizes outside of 6 to 96 pixels
+    fn set_font_defaults(&mut self, config: FontConfig) -> Result<(), WebviewError>;
+}
+
+/// Moves a caret through the text of the page with the arrow, Home and End keys,
+/// while `window.__fltk_webview_caret` is set, drawing it as a blinking element.
+/// `window.__fltk_webview_caret_apply()` updates the caret
+const CARET_JS: &str = r#"(function() {
+    if (window.__fltk_webview_caret !== undefined) return;
+    window.__fltk_webview_caret = false;
+    var caret = null;
+    var moves = {
+        ArrowLeft: ['backward', 'character'], ArrowRight: ['forward', 'character'],
+        ArrowUp: ['backward', 'line'], ArrowDown: ['forward', 'line'],
+        Home: ['backward', 'lineboundary'], End: ['forward', 'lineboundary']
+    };
+    var editable = function(el) {
+        return el && (el.isContentEditable || /^(INPUT|TEXTAREA|SELECT)$/.test(el.tagName));
+    };
+    var draw = function() {
+        var sel = window.getSelection();
+        if (!window.__fltk_webview_caret || !sel.rangeCount || editable(document.activeElement)) {
+            if (caret) caret.style.display = 'none';
+            return;
+        }
+        var range = document.createRange();
+        range.setStart(sel.focusNode, sel.focusOffset);
+        var rect = range.getClientRects()[0];
+        if (!rect && sel.focusNode.getBoundingClientRect) rect = sel.focusNode.getBoundingClientRect();
+        if (!rect) return;
+        if (!caret) {
+            caret = document.createElement('div');
+            caret.style.cssText = 'position: fixed; width: 1px; background: currentColor; ' +
+                'pointer-events: none; z-index: 2147483647; animation: __fltk_webview_blink 1s step-end infinite';
+            var style = document.createElement('style');
+            style.textContent = '@keyframes __fltk_webview_blink { 50% { opacity: 0; } }';
+            document.documentElement.appendChild(style);
+            document.documentElement.appendChild(caret);
+        }
+        caret.style.display = 'block';
+        caret.style.left = rect.left + 'px';
+        caret.style.top = rect.top + 'px';
+        caret.style.height = Math.max(rect.height, 12) + 'px';
+    };
+    document.addEventListener('keydown', function(e) {
+        var move = moves[e.key];
+        if (!window.__fltk_webview_caret || !move || e.altKey || e.metaKey || editable(e.target)) return;
+        var sel = window.getSelection();
+        if (!sel.rangeCount) sel.collapse(document.body, 0);
+        var granularity = e.ctrlKey && move[1] === 'character' ? 'word' : move[1];
+        sel.modify(e.shiftKey ? 'extend' : 'move', move[0], granularity);
+        var el = sel.focusNode.nodeType === 1 ? sel.focusNode : sel.focusNode.parentElement;
+        if (el) el.scrollIntoView({ block: 'nearest', inline: 'nearest' });
+        e.preventDefault();
+        draw();
+    }, true);
+    document.addEventListener('selectionchange', draw);
+    window.addEventListener('scroll', draw, true);
+    window.addEventListener('resize', draw);
+    window.__fltk_webview_caret_apply = draw;
+})()"#;
+
+/// Adds the policy of `window.__fltk_webview_csp` to the head of the document, as soon as it's parsed.
+/// Parsed scripts run after a microtask checkpoint, so the observer adds it before the first one
+const CSP_JS: &str = r#"(function() {
+    if (window.__fltk_webview_apply_csp) return;
+    var applied = {};
+    var add = function() {
+        var policy = window.__fltk_webview_csp;
+        if (!policy || applied[policy]) return true;
+        if (!document.head) return false;
+        var meta = document.createElement('meta');
+        meta.httpEquiv = 'Content-Security-Policy';
+        meta.content = policy;
+        document.head.insertBefore(meta, document.head.firstChild);
+        applied[policy] = true;
+        return true;
+    };
+    window.__fltk_webview_apply_csp = function() {
+        if (add()) return;
+        var observer = new MutationObserver(function() {
+            if (add()) observer.disconnect();
+        });
+        observer.observe(document, { childList: true, subtree: true });
+    };
+})()"#;
+
+/// Reloads the pages restored from the back-forward cache while `window.__fltk_webview_bfcache` is false
+const BFCACHE_JS: &str = r#"(function() {
+    if (window.__fltk_webview_bfcache !== undefined) return;
+    window.__fltk_webview_bfcache = true;
+    window.addEventListener('pageshow', function(e) {
+        if (e.persisted && !window.__fltk_webview_bfcache) location.reload();
+    });
+})()"#;
+
+/// Reports the languages of the array it's called with through `navigator`
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+const LANGUAGES_JS: &str = r#"(function(languages) {
+    Object.defineProperty(Navigator.prototype, 'language', {
+        get: function() { return languages[0]; },
+        configurable: true
+    });
+    Object.defineProperty(Navigator.prototype, 'languages', {
+        get: function() { return languages.slice(); },
+        configurable: true
+    });
+})"#;
+
+/// Rewrites the `prefers-color-scheme` conditions of `matchMedia` queries and of the media rules
+/// of the page's style sheets, including the ones added later, for `window.__fltk_webview_color_scheme`,
+/// or restores them when it isn't set, when run again.
+/// Cross-origin style sheets can't be read, and keep the system's scheme
+const COLOR_SCHEME_JS: &str = r#"(function() {
+    if (window.__fltk_webview_apply_color_scheme) return window.__fltk_webview_apply_color_scheme();
+    var feature = /\(\s*prefers-color-scheme\s*:\s*(dark|light)\s*\)/gi;
+    var rewrite = function(query) {
+        var scheme = window.__fltk_webview_color_scheme;
+        if (!scheme) return query;
+        return query.replace(feature, function(_, s) {
+            return s.toLowerCase() === scheme ? '(min-width: 0px)' : '(grid)';
+        });
+    };
+    var matchMedia = window.matchMedia.bind(window);
+    window.matchMedia = function(query) { return matchMedia(rewrite(String(query))); };
+    var originals = new WeakMap();
+    var update = function(media) {
+        if (!media) return;
+        var original = originals.has(media) ? originals.get(media) : media.mediaText;
+        if (original.search(feature) < 0) return;
+        originals.set(media, original);
+        var text = rewrite(original);
+        if (media.mediaText !== text) media.mediaText = text;
+    };
+    var visit = function(sheet) {
+        update(sheet.media);
+        var rules;
+        try { rules = sheet.cssRules; } catch (e) { return; }
+        for (var i = 0; rules && i < rules.length; i++) {
+            if (rules[i].media) update(rules[i].media);
+            if (rules[i].styleSheet) visit(rules[i].styleSheet);
+            if (rules[i].cssRules) visit(rules[i]);
+        }
+    };
+    var apply = window.__fltk_webview_apply_color_scheme = function() {
+        for (var i = 0; i < document.styleSheets.length; i++) visit(document.styleSheets[i]);
+    };
+    new MutationObserver(function(records) {
+        for (var i = 0; i < records.length; i++) {
+            for (var j = 0; j < records[i].addedNodes.length; j++) {
+                var name = records[i].addedNodes[j].nodeName;
+                if (name === 'STYLE' || name === 'LINK') return apply();
+            }
+        }
+    }).observe(document, { childList: true, subtree: true });
+    // Linked style sheets only have rules once loaded
+    document.addEventListener('load', apply, true);
+    document.addEventListener('DOMContentLoaded', apply);
+    apply();
+})()"#;
+
+/// Skips animation frames and interval callbacks within a second of the last one,
+/// while `window.__fltk_webview_throttle` is set and the page doesn't have focus
+const THROTTLE_JS: &str = r#"(function() {
+    if (window.__fltk_webview_throttle !== undefined) return;
+    window.__fltk_webview_throttle = false;
+    var throttled = function() { return window.__fltk_webview_throttle && !document.hasFocus(); };
+    var raf = window.requestAnimationFrame.bind(window);
+    var caf = window.cancelAnimationFrame.bind(window);
+    var next = 0, pending = {};
+    window.requestAnimationFrame = function(cb) {
+        var id = ++next, start = performance.now();
+        var frame = function(t) {
+            if (throttled() && t - start < 1000) {
+                pending[id] = raf(frame);
+                return;
+            }
+            delete pending[id];
+            cb(t);
+        };
+        pending[id] = raf(frame);
+        return id;
+    };
+    window.cancelAnimationFrame = function(id) {
+        if (pending[id] !== undefined) {
+            caf(pending[id]);
+            delete pending[id];
+        }
+    };
+    var setInterval = window.setInterval.bind(window);
+    window.setInterval = function(cb, delay) {
+        var args = Array.prototype.slice.call(arguments, 2), last = 0;
+        return setInterval(function() {
+            var now = performance.now();
+            if (throttled() && now - last < 1000) return;
+            last = now;
+            if (typeof cb === 'function') cb.apply(window, args); else (0, eval)(cb);
+        }, delay);
+    };
+})()"#;
+
+/// Makes `getContext` return `null` for WebGL contexts while `window.__fltk_webview_webgl` is false
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+const WEBGL_JS: &str = r#"(function() {
+    if (window.__fltk_webview_webgl !== undefined) return;
+    window.__fltk_webview_webgl = true;
+    var wrap = function(proto) {
+        var getContext = proto.getContext;
+        proto.getContext = function(type) {
+            if (!window.__fltk_webview_webgl && /^(experimental-)?webgl2?$/.test(type)) return null;
+            return getContext.apply(this, arguments);
+        };
+    };
+    wrap(HTMLCanvasElement.prototype);
+    if (window.OffscreenCanvas) wrap(OffscreenCanvas.prototype);
+})()"#;
+
+/// Content rules blocking every image
+#[cfg(target_os = "macos")]
+const BLOCK_IMAGES_RULES: &str = r#"[{
+    "trigger": {"url-filter": ".*", "resource-type": ["image"]},
+    "action": {"type": "block"}
+}]"#;
+
+/// Check that `tag` looks like a BCP 47 language tag, e.g. `en`, `en-US` or `zh-Hant-TW`
+pub(crate) fn is_language_tag(tag: &str) -> bool {
+    let mut subtags = tag.split('-');
+    let primary = subtags.next().unwrap_or_default();
+    (2..=8).contains(&primary.len())
+        && primary.chars().all(|c| c.is_ascii_alphabetic())
+        && subtags
+            .all(|s| (1..=8).contains(&s.len()) && s.chars().all(|c| c.is_ascii_alphanumeric()))
+}
+
+/// Check that `name` only has the characters allowed in IANA charset names
+fn is_charset(name: &str) -> bool {
+    (1..=40).contains(&name.len())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_.:+()".contains(c))
+}
+
+/// Sets `autocomplete="off"` on the forms and fields of the document while `window.__fltk_webview_autofill`
+/// is false, or restores their attribute, when `window.__fltk_webview_autofill_apply()` is called
+const AUTOFILL_JS: &str = r#"(function() {
+    if (window.__fltk_webview_autofill_apply) return;
+    var SAVED = 'data-fltk-webview-autocomplete';
+    var FIELDS = 'form, input, select, textarea';
+    var disable = function(el) {
+        if (el.hasAttribute(SAVED)) return;
+        el.setAttribute(SAVED, JSON.stringify(el.getAttribute('autocomplete')));
+        el.setAttribute('autocomplete', 'off');
+    };
+    var observer = new MutationObserver(function(records) {
+        records.forEach(function(r) {
+            r.addedNodes.forEach(function(n) {
+                if (n.nodeType !== 1) return;
+                if (n.matches(FIELDS)) disable(n);
+                n.querySelectorAll(FIELDS).forEach(disable);
+            });
+        });
+    });
+    window.__fltk_webview_autofill_apply = function() {
+        observer.disconnect();
+        if (window.__fltk_webview_autofill) {
+            document.querySelectorAll('[' + SAVED + ']').forEach(function(el) {
+                var value = JSON.parse(el.getAttribute(SAVED));
+                el.removeAttribute(SAVED);
+                if (value === null) el.removeAttribute('autocomplete');
+                else el.setAttribute('autocomplete', value);
+            });
+        } else {
+            document.querySelectorAll(FIELDS).forEach(disable);
+            observer.observe(document, { childList: true, subtree: true });
+        }
+    };
+})()"#;
+
+impl SettingsExt for Webview {
+    fn with_settings<F: FnOnce(&mut Settings)>(&mut self, f: F) -> Result<(), WebviewError> {
+        let mut settings = Settings::default();
+        f(&mut settings);
+        #[cfg(target_os = "windows")]
+        {
+            if settings == Settings::default() {
+                Ok(())
+            } else {
+                Err(WebviewError::Unsupported)
+            }
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            #[cfg(target_os = "macos")]
+            if settings.images.is_some()
+                || settings.webgl.is_some()
+                || settings.allow_universal_access_from_file_urls.is_some()
+            {
+                return Err(WebviewError::Unsupported);
+            }
+            unsafe {
+                fltk_webview_sys::my_apply_settings(self.get_window(), settings.values().as_ptr());
+            }
+            Ok(())
+        }
+    }
+
+    fn set_javascript_enabled(&mut self, enabled: bool) -> Result<(), WebviewError> {
+        #[cfg(target_os = "windows")]
+        {
+            let _ = enabled;
+            Err(WebviewError::Unsupported)
+        }
+        #[cfg(not(target_os = "windows"))]
+        unsafe {
+            fltk_webview_sys::my_set_javascript_enabled(self.get_window(), enabled as _);
+            Ok(())
+        }
+    }
+
+    fn set_developer_extras(&mut self, enabled: bool) -> Result<(), WebviewError> {
+        self.with_settings(|s| s.developer_extras = Some(enabled))
+    }
+
+    fn set_allow_file_access_from_file_urls(&mut self, allow: bool) -> Result<(), WebviewError> {
+        self.with_settings(|s| s.allow_file_access_from_file_urls = Some(allow))
+    }
+
+    fn set_allow_universal_access_from_file_urls(
+        &mut self,
+        allow: bool,
+    ) -> Result<(), WebviewError> {
+        self.with_settings(|s| s.allow_universal_access_from_file_urls = Some(allow))
+    }
+
+    fn set_color_scheme(&mut self, scheme: ColorScheme) {
+        // WKWebView also draws its scrollbars and form controls in the scheme
+        #[cfg(target_os = "macos")]
+        unsafe {
+            let native = match scheme {
+                ColorScheme::Auto => 0,
+                ColorScheme::Light => 1,
+                ColorScheme::Dark => 2,
+            };
+            fltk_webview_sys::my_set_color_scheme(self.get_window(), native);
+        }
+        let scheme = match scheme {
+            ColorScheme::Auto => "null",
+            ColorScheme::Light => "'light'",
+            ColorScheme::Dark => "'dark'",
+        };
+        let js = format!(
+            "window.__fltk_webview_color_scheme = {};\n{}",
+            scheme, COLOR_SCHEME_JS
+        );
+        crate::scripts::set_script(self, "color_scheme", &js);
+    }
+
+    fn set_background_throttling(&mut self, enabled: bool) {
+        let js = format!(
+            "{};\nwindow.__fltk_webview_throttle = {};",
+            THROTTLE_JS, enabled
+        );
+        crate::scripts::set_script(self, "throttle", &js);
+    }
+
+    fn set_cache_policy(&mut self, policy: CachePolicy) -> Result<(), WebviewError> {
+        #[cfg(any(target_os = "macos", target_os = "windows"))]
+        {
+            let _ = policy;
+            Err(WebviewError::Unsupported)
+        }
+        #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+        unsafe {
+            let model = match policy {
+                CachePolicy::DocumentViewer => 0,
+                // WebKitGTK defaults to its web browser model
+                CachePolicy::WebBrowser | CachePolicy::Default => 1,
+            };
+            fltk_webview_sys::my_set_cache_model(self.get_window(), model);
+            Ok(())
+        }
+    }
+
+    fn set_spellcheck(&mut self, enabled: bool, languages: &[&str]) -> Result<(), WebviewError> {
+        if let Some(tag) = languages.iter().find(|l| !is_language_tag(l)) {
+            return Err(WebviewError::InvalidArgument(tag.to_string()));
+        }
+        #[cfg(target_os = "windows")]
+        {
+            let js = format!(
+                "(function() {{ var f = function() {{ document.documentElement.spellcheck = {}; }}; \
+                if (document.documentElement) f(); else document.addEventListener('DOMContentLoaded', f); }})()",
+                enabled
+            );
+            crate::scripts::set_script(self, "spellcheck", &js);
+            Ok(())
+        }
+        #[cfg(not(target_os = "windows"))]
+        unsafe {
+            use std::ffi::CString;
+            // The backends take locales, like en_US
+            let locales: Vec<CString> = languages
+                .iter()
+                .map(|l| CString::new(l.replace('-', "_")).unwrap())
+                .collect();
+            let mut ptrs: Vec<*const std::os::raw::c_char> =
+                locales.iter().map(|l| l.as_ptr()).collect();
+            ptrs.push(std::ptr::null());
+            fltk_webview_sys::my_set_spellcheck(self.get_window(), enabled as _, ptrs.as_ptr());
+            Ok(())
+        }
+    }
+
+    fn set_default_charset(&mut self, charset: &str) -> Result<(), WebviewError> {
+        if !is_charset(charset) {
+            return Err(WebviewError::InvalidArgument(charset.to_string()));
+        }
+        #[cfg(any(target_os = "macos", target_os = "windows"))]
+        {
+            Err(WebviewError::Unsupported)
+        }
+        #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+        unsafe {
+            let charset = std::ffi::CString::new(charset).unwrap();
+            fltk_webview_sys::my_set_default_charset(self.get_window(), charset.as_ptr());
+            Ok(())
+        }
+    }
+
+    fn set_back_forward_cache(&mut self, enabled: bool) {
+        #[cfg(not(target_os = "windows"))]
+        let native =
+            unsafe { fltk_webview_sys::my_set_page_cache(self.get_window(), enabled as _) != 0 };
+        #[cfg(target_os = "windows")]
+        let native = false;
+        if !native {
+            let js = format!(
+                "{};\nwindow.__fltk_webview_bfcache = {};",
+                BFCACHE_JS, enabled
+            );
+            crate::scripts::set_script(self, "bfcache", &js);
+        }
+    }
+
+    fn set_minimum_tls_version(&mut self, version: TlsVersion) -> Result<(), WebviewError> {
+        // WebView2 refuses older versions itself
+        #[cfg(target_os = "windows")]
+        {
+            match version {
+                TlsVersion::Tls12 => Ok(()),
+                TlsVersion::Tls13 => Err(WebviewError::Unsupported),
+            }
+        }
+        #[cfg(target_os = "macos")]
+        {
+            let _ = version;
+            Err(WebviewError::Unsupported)
+        }
+        #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+        {
+            let versions = match version {
+                TlsVersion::Tls12 => "+VERS-TLS1.3:+VERS-TLS1.2",
+                TlsVersion::Tls13 => "+VERS-TLS1.3",
+            };
+            std::env::set_var(
+                "G_TLS_GNUTLS_PRIORITY",
+                format!("NORMAL:-VERS-ALL:{}", versions),
+            );
+            Ok(())
+        }
+    }
+
+    fn set_languages(&mut self, languages: &[&str]) -> Result<(), WebviewError> {
+        if languages.is_empty() {
+            return Err(WebviewError::InvalidArgument(
+                "no language was given".to_string(),
+            ));
+        }
+        if let Some(tag) = languages.iter().find(|l| !is_language_tag(l)) {
+            return Err(WebviewError::InvalidArgument(tag.to_string()));
+        }
+        #[cfg(any(target_os = "macos", target_os = "windows"))]
+        {
+            let tags: Vec<String> = languages.iter().map(|l| crate::utils::quote(l)).collect();
+            let js = format!("{}([{}])", LANGUAGES_JS, tags.join(", "));
+            crate::scripts::set_script(self, "languages", &js);
+            Ok(())
+        }
+        #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+        unsafe {
+            use std::ffi::CString;
+            let tags: Vec<CString> = languages
+                .iter()
+                .map(|l| CString::new(*l).unwrap())
+                .collect();
+            let mut ptrs: Vec<*const std::os::raw::c_char> =
+                tags.iter().map(|l| l.as_ptr()).collect();
+            ptrs.push(std::ptr::null());
+            fltk_webview_sys::my_set_preferred_languages(self.get_window(), ptrs.as_ptr());
+            Ok(())
+        }
+    }
+
+    fn set_password_autosave(&mut self, enabled: bool) -> Result<(), WebviewError> {
+        if enabled {
+            Err(WebviewError::Unsupported)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn set_general_autofill(&mut self, enabled: bool) -> Result<(), WebviewError> {
+        let js = format!(
+            "{};\nwindow.__fltk_webview_autofill = {};\nwindow.__fltk_webview_autofill_apply();",
+            AUTOFILL_JS, enabled
+        );
+        crate::scripts::set_script(self, "autofill", &js);
+        Ok(())
+    }
+
+    fn set_webgl_enabled(&mut self, enabled: bool) {
+        #[cfg(any(target_os = "macos", target_os = "windows"))]
+        {
+            let js = format!("{};\nwindow.__fltk_webview_webgl = {};", WEBGL_JS, enabled);
+            crate::scripts::set_script(self, "webgl", &js);
+        }
+        #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+        let _ = self.with_settings(|s| s.webgl = Some(enabled));
+    }
+
+    fn set_auto_load_images(&mut self, enabled: bool) -> Result<(), WebviewError> {
+        #[cfg(target_os = "macos")]
+        {
+            use crate::{state, ContentRulesExt};
+            if let Some(rules) = state::with(self, |s| s.image_rules.take()) {
+                self.remove_content_rules(rules);
+            }
+            if !enabled {
+                let rules = self.add_content_rules(BLOCK_IMAGES_RULES)?;
+                state::with(self, |s| s.image_rules = Some(rules));
+            }
+            Ok(())
+        }
+        #[cfg(not(target_os = "macos"))]
+        self.with_settings(|s| s.images = Some(enabled))
+    }
+
+    fn set_caret_browsing(&mut self, enabled: bool) {
+        #[cfg(not(target_os = "windows"))]
+        let native = unsafe {
+            fltk_webview_sys::my_set_caret_browsing(self.get_window(), enabled as _) != 0
+        };
+        #[cfg(target_os = "windows")]
+        let native = false;
+        if !native {
+            let js = format!(
+                "{};\nwindow.__fltk_webview_caret = {};\nwindow.__fltk_webview_caret_apply();",
+                CARET_JS, enabled
+            );
+            crate::scripts::set_script(self, "caret", &js);
+        }
+    }
+
+    fn set_font_defaults(&mut self, config: FontConfig) -> Result<(), WebviewError> {
+        config.validate()?;
+        #[cfg(not(target_os = "windows"))]
+        let native = unsafe {
+            use std::ffi::CString;
+            let families: Vec<Option<CString>> = config
+                .families()
+                .iter()
+                .map(|f| f.as_deref().map(|f| CString::new(f).unwrap()))
+                .collect();
+            let ptrs: Vec<*const std::os::raw::c_char> = families
+                .iter()
+                .map(|f| f.as_ref().map_or(std::ptr::null(), |f| f.as_ptr()))
+                .collect();
+            fltk_webview_sys::my_set_font_defaults(
+                self.get_window(),
+                ptrs.as_ptr(),
+                config.default_size.unwrap_or(0) as _,
+                config.monospace_size.unwrap_or(0) as _,
+            ) != 0
+        };
+        #[cfg(target_os = "windows")]
+        let native = false;
+        let (css, previous) = state::with(self, |s| {
+            s.font_defaults.merge(config);
+            (s.font_defaults.css(), s.font_style.take())
+        });
+        if native {
+            return Ok(());
+        }
+        if let Some(previous) = previous {
+            self.remove_user_style(previous);
+        }
+        let style = self.add_user_style(&css);
+        state::with(self, |s| s.font_style = Some(style));
+        Ok(())
+    }
+
+    fn set_csp(&mut self, policy: &str) {
+        let js = format!(
+            "{};\nwindow.__fltk_webview_csp = {};\nwindow.__fltk_webview_apply_csp();",
+            CSP_JS,
+            crate::utils::quote(policy)
+        );
+        crate::scripts::set_script(self, "csp", &js);
+    }
+}
@@ -0,0 +1,282 @@
+use crate::Webview;
+use fltk::{enums, window};
+use std::{cell::RefCell, collections::HashMap, os::raw, rc::Rc};
+
+/// A user callback stored in the state.
+/// It's cloned out of the state before being called, so it can call back into fltk-webview
+pub(crate) type Handler<T> = Rc<RefCell<dyn FnMut(T)>>;
+pub(crate) type StrHandler = Rc<RefCell<dyn FnMut(&str)>>;
+pub(crate) type NewWindowHandler = Rc<RefCell<dyn FnMut(&str) -> crate::NewWindowDecision>>;
+pub(crate) type PermissionHandler = Rc<RefCell<dyn FnMut(crate::PermissionKind, &str) -> bool>>;
+pub(crate) type TlsErrorHandler = Rc<RefCell<dyn FnMut(&str, &str) -> bool>>;
+pub(crate) type AuthHandler =
+    Rc<RefCell<dyn FnMut(&str, crate::AuthKind) -> Option<crate::Credentials>>>;
+pub(crate) type DownloadHandler =
+    Rc<RefCell<dyn FnMut(crate::DownloadRequest) -> crate::DownloadDecision>>;
+pub(crate) type DownloadProgressHandler = Rc<RefCell<dyn FnMut(&crate::DownloadProgress)>>;
+pub(crate) type CloseHandler = Rc<RefCell<dyn FnMut() -> bool>>;
+pub(crate) type FullscreenHandler = Rc<RefCell<dyn FnMut(bool) -> bool>>;
+pub(crate) type BeforeUnloadHandler = Rc<RefCell<dyn FnMut(&str) -> bool>>;
+pub(crate) type ConsoleHandler = Rc<RefCell<dyn FnMut(crate::ConsoleLevel, &str)>>;
+pub(crate) type RedirectHandler = Rc<RefCell<dyn FnMut(&str, &str)>>;
+pub(crate) type BindHandler = Rc<RefCell<dyn FnMut(&str, &str)>>;
+pub(crate) type InterceptHandler =
+    Rc<RefCell<dyn FnMut(&crate::InterceptedRequest) -> Option<crate::InterceptedResponse>>>;
+
+/// State fltk-webview keeps per webview instance.
+/// Since Webview is cheaply cloned, and clones share the same native window,
+/// the state is keyed by the window returned by `get_window`
+#[derive(Default)]
+pub(crate) struct State {
+    /// The backend webview, as a `webview_t`
+    pub handle: usize,
+    /// Whether the webview was created with developer tools enabled
+    pub debug: bool,
+    /// The WebView2 runtime version, once the page reported it
+    #[cfg(target_os = "windows")]
+    pub engine_version: Option<String>,
+    /// The temporary data folder of an ephemeral webview, shared with the other webviews storing in it
+    pub ephemeral_dir: Option<std::rc::Rc<crate::builder::EphemeralDir>>,
+    /// Whether the webview was suspended through `suspend`
+    pub suspended: bool,
+    /// Whether the top-level window was kept above others through `set_always_on_top`
+    pub always_on_top: bool,
+    /// The state of the top-level window when it was last polled
+    pub window_state: Option<crate::WindowState>,
+    /// Whether the audio was muted through `set_muted`
+    pub muted: bool,
+    /// Whether media keys are routed to the page through `set_media_keys_enabled`
+    pub media_keys: bool,
+    /// The opacity set through `set_opacity`
+    pub opacity: Option<f64>,
+    /// Counts the fades started, so that a fade stops once another one starts
+    pub fades: u64,
+    /// The fltk window the webview is embedded in
+    pub win: Option<window::Window>,
+    /// Overrides the conversion of fltk sizes into backend sizes
+    pub device_scale: Option<f64>,
+    /// The position set through `set_position`, reapplied when the webview is resized
+    pub position: Option<(i32, i32)>,
+    /// The hint last passed to `set_size_and_hint`
+    pub size_hint: Option<crate::SizeHint>,
+    /// Size limits set through `set_min_size` and `set_max_size`
+    pub min_size: Option<(i32, i32)>,
+    pub max_size: Option<(i32, i32)>,
+    /// The background color set at creation
+    pub background: Option<enums::Color>,
+    /// Native user scripts registered through `init_with_timing` and `init_in_world`
+    pub scripts: Vec<*mut raw::c_void>,
+    /// The init scripts of settings by name, see `scripts::set_script`.
+    /// Native user scripts, or on Windows, the source of the last one
+    #[cfg(not(target_os = "windows"))]
+    pub setting_scripts: HashMap<&'static str, *mut raw::c_void>,
+    #[cfg(target_os = "windows")]
+    pub setting_scripts: HashMap<&'static str, String>,
+    /// Style sheets added through `add_user_style`, by id.
+    /// Native style sheets on WebKitGTK, native scripts on macOS, null on Windows
+    pub styles: HashMap<u64, *mut raw::c_void>,
+    pub next_style: u64,
+    /// The style sheet hiding the scrollbars
+    pub scrollbar_style: Option<crate::StyleHandle>,
+    /// The style sheet setting `scroll-behavior`
+    pub scroll_behavior_style: Option<crate::StyleHandle>,
+    /// The style sheet preventing text selection
+    pub selection_style: Option<crate::StyleHandle>,
+    /// The style sheet overriding the cursor of pages, see `set_cursor`
+    pub cursor_style: Option<crate::StyleHandle>,
+    /// The default fonts set through `set_font_defaults`
+    pub font_defaults: crate::FontConfig,
+    /// The style sheet applying the default fonts, where the backend can't
+    pub font_style: Option<crate::StyleHandle>,
+    /// Compiled content rules added through `add_content_rules`, by id
+    #[cfg(not(target_os = "windows"))]
+    pub content_rules: HashMap<u64, *mut raw::c_void>,
+    #[cfg(not(target_os = "windows"))]
+    pub next_content_rules: u64,
+    /// The content rules blocking images, see `set_auto_load_images`
+    #[cfg(target_os = "macos")]
+    pub image_rules: Option<crate::RulesHandle>,
+    /// Receives the match count of `find`
+    pub find_handler: Option<Handler<u32>>,
+    /// The last `find` query, used by the JavaScript fallback
+    #[cfg(any(target_os = "macos", target_os = "windows"))]
+    pub find_query: Option<(String, crate::FindOptions)>,
+    /// Called when the page title changes
+    pub title_handler: Option<StrHandler>,
+    /// Receives load progress estimates
+    pub progress_handler: Option<Handler<f64>>,
+    /// The last load progress estimate
+    pub progress: f64,
+    /// Whether a load was in progress when last reported
+    pub loading: bool,
+    /// Receives the changes of the loading state
+    pub loading_handler: Option<Handler<bool>>,
+    /// Receives the server redirects of navigations, with the URLs before and after
+    pub redirect_handler: Option<RedirectHandler>,
+    /// The URLs of the last navigation, from its start through its redirects
+    pub redirect_chain: Vec<String>,
+    /// The requests in flight, once tracked by `wait_for_network_idle`
+    pub network_requests: usize,
+    /// How long navigations can take, see `set_navigation_timeout`
+    #[cfg(not(target_os = "windows"))]
+    pub navigation_timeout: Option<std::time::Duration>,
+    /// When the current load started, while navigations are timed
+    #[cfg(not(target_os = "windows"))]
+    pub loading_since: Option<std::time::Instant>,
+    /// Shown in place of failed pages, see `set_error_page`
+    #[cfg(not(target_os = "windows"))]
+    pub error_page: Option<String>,
+    /// The functions bound through `bind_tracked`, by name
+    pub bindings: HashMap<String, BindHandler>,
+    /// Receives the messages of pages
+    pub message_handler: Option<StrHandler>,
+    /// Receives the console output of pages
+    pub console_handler: Option<ConsoleHandler>,
+    /// The file console messages and navigations are logged to, see `set_log_file`
+    pub log_file: Option<std::fs::File>,
+    /// The URL patterns of `intercept` with their handlers, in the order they were added
+    pub interceptors: Vec<(String, InterceptHandler)>,
+    /// The senders of the receivers returned by `events`
+    pub event_senders: Vec<std::sync::mpsc::Sender<crate::WebviewEvent>>,
+    /// The native signals already connected
+    pub hooks: std::collections::HashSet<&'static str>,
+    /// Whether the page can go back and forward, as last reported by the page
+    #[cfg(target_os = "windows")]
+    pub history: (bool, bool),
+    /// The status of the last response, as last reported by the page
+    #[cfg(target_os = "windows")]
+    pub response_status: Option<u16>,
+    /// What virtual hosts serve, keyed by lowercase host
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    pub hosts: HashMap<String, crate::host::HostSource>,
+    /// The servers started through `serve_dir`, stopped with the webview
+    pub servers: Vec<crate::host::LocalServer>,
+    /// The pages served under `internal://`, keyed by lowercase name
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    pub internal_pages: HashMap<String, std::rc::Rc<str>>,
+    /// Decides what to do with new window requests
+    pub new_window_handler: Option<NewWindowHandler>,
+    /// Receives the paths of dropped files
+    pub drop_handler: Option<Handler<Vec<std::path::PathBuf>>>,
+    /// Decides whether closing the top window goes ahead
+    pub close_handler: Option<CloseHandler>,
+    /// Receives the changes of the state of the top-level window
+    pub window_state_handler: Option<Handler<crate::WindowState>>,
+    /// Decides whether page elements can go fullscreen
+    pub fullscreen_handler: Option<FullscreenHandler>,
+    /// Decides whether to leave pages asking to stay, with a clone of the webview to ask its page
+    pub before_unload: Option<(BeforeUnloadHandler, Webview)>,
+    /// Decides the permissions requested by pages
+    pub permission_handler: Option<PermissionHandler>,
+    /// The clipboard operations allowed through `set_clipboard_access`
+    pub clipboard_access: Option<crate::ClipboardAccess>,
+    /// Decides whether to accept rejected server certificates
+    pub tls_error_handler: Option<TlsErrorHandler>,
+    /// Receives the insecure URLs requested by secure pages
+    pub insecure_content_handler: Option<StrHandler>,
+    /// Supplies the credentials servers ask for
+    pub auth_handler: Option<AuthHandler>,
+    /// The last credentials supplied, until the backend copied them
+    #[cfg(not(target_os = "windows"))]
+    pub auth_credentials: Option<(std::ffi::CString, std::ffi::CString)>,
+    /// Decides where downloads are saved
+    pub download_handler: Option<DownloadHandler>,
+    /// Receives the progress of downloads
+    pub download_progress_handler: Option<DownloadProgressHandler>,
+    /// The destination of the last download, until the backend copied it
+    #[cfg(not(target_os = "windows"))]
+    pub download_path: Option<std::ffi::CString>,
+    /// The localStorage items set on every page load through `set_local_storage`
+    pub local_storage: Vec<(String, String)>,
+    /// Which shortcuts are passed to fltk
+    pub accel_mode: crate::AcceleratorMode,
+    /// Whether key events are intercepted
+    pub accel_hooked: bool,
+    /// Evaluations waiting for their result
+    pub eval: crate::eval::EvalState,
+}
+
+thread_local! {
+    static STATES: RefCell<HashMap<usize, State>> = RefCell::new(HashMap::new());
+}
+
+/// The webviews which weren't closed. Unlike the state, it's readable from any thread
+static RUNNING: std::sync::Mutex<Vec<usize>> = std::sync::Mutex::new(Vec::new());
+
+/// Mark a webview as running, or as closed
+pub(crate) fn set_running(key: usize, running: bool) {
+    let mut keys = RUNNING.lock().unwrap();
+    keys.retain(|k| *k != key);
+    if running {
+        keys.push(key);
+    }
+}
+
+pub(crate) fn is_running(key: usize) -> bool {
+    RUNNING.lock().unwrap().contains(&key)
+}
+
+/// Whether any webview is running
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub(crate) fn any_running() -> bool {
+    !RUNNING.lock().unwrap().is_empty()
+}
+
+/// Access the state of a webview. The closure shouldn't call back into user code
+pub(crate) fn with<R>(wv: &Webview, f: impl FnOnce(&mut State) -> R) -> R {
+    with_key(wv.get_window() as usize, f)
+}
+
+/// Access the state using the key passed to native callbacks
+pub(crate) fn with_key<R>(key: usize, f: impl FnOnce(&mut State) -> R) -> R {
+    STATES.with(|s| f(s.borrow_mut().entry(key).or_default()))
+}
+
+/// Remove the state of a webview
+pub(crate) fn remove(key: usize) -> Option<State> {
+    STATES.with(|s| s.borrow_mut().remove(&key))
+}
+
+/// The backend webviews, as `webview_t`, whose state matches `f`
+pub(crate) fn handles_where(f: impl Fn(&State) -> bool) -> Vec<usize> {
+    STATES.with(|s| {
+        s.borrow()
+            .values()
+            .filter(|state| f(state))
+            .map(|state| state.handle)
+            .collect()
+    })
+}
+
+/// The keys of the webviews embedded in the top window `top`, however deeply nested
+pub(crate) fn embedded_in(top: usize) -> Vec<usize> {
+    use fltk::prelude::WidgetExt;
+    STATES.with(|s| {
+        s.borrow()
+            .iter()
+            .filter(|(_, state)| {
+                state
+                    .win
+                    .as_ref()
+                    .and_then(|w| w.top_window())
+                    .map(|t| t.as_widget_ptr() as usize)
+                    == Some(top)
+            })
+            .map(|(k, _)| *k)
+            .collect()
+    })
+}
+
+/// Remove the state of the webviews embedded in the top window `top`, returning their keys
+#[cfg(target_os = "macos")]
+pub(crate) fn take_embedded_in(top: usize) -> Vec<usize> {
+    let keys = embedded_in(top);
+    STATES.with(|s| {
+        let mut states = s.borrow_mut();
+        for k in &keys {
+            states.remove(k);
+            set_running(*k, false);
+        }
+    });
+    keys
+}
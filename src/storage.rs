@@ -0,0 +1,205 @@
+use crate::{eval, state, utils, Webview, WebviewError};
+use fltk::app;
+use std::time::{Duration, Instant};
+use tinyjson::JsonValue;
+
+/// A cookie of the website data of webviews, as listed by `StorageExt::export_cookies`.
+/// With the `serde` feature, it can be serialized, e.g. to move a session to another machine
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cookie {
+    pub name: String,
+    pub value: String,
+    /// The host the cookie is sent to, with a leading `.` when it's also sent to its subdomains
+    pub domain: String,
+    pub path: String,
+    /// The expiry date in seconds since the Unix epoch, `None` for session cookies
+    pub expires: Option<i64>,
+    /// Only sent over HTTPS
+    pub secure: bool,
+    /// Hidden from `document.cookie`
+    pub http_only: bool,
+}
+
+/// Defines `window.__fltk_webview_apply_local_storage`, which stores the items of
+/// `window.__fltk_webview_local_storage` in the localStorage of the page's origin
+const LOCAL_STORAGE_JS: &str = r#"(function() {
+    if (window.__fltk_webview_apply_local_storage) return;
+    window.__fltk_webview_apply_local_storage = function() {
+        try {
+            (window.__fltk_webview_local_storage || []).forEach(function(item) {
+                localStorage.setItem(item[0], item[1]);
+            });
+        } catch (e) {
+            // Opaque origins, like those of data URLs, have no localStorage
+        }
+    };
+})()"#;
+
+/// Website data related methods
+pub trait StorageExt {
+    /// Give the backend time to write pending website data to disk, by running the fltk event loop.
+    /// Call it before quitting when data was just written
+    fn flush_storage(&mut self);
+    /// Pass the cookies of the webview's website data, including `HttpOnly` ones, to `f` on the main thread
+    fn export_cookies<F: FnOnce(Result<Vec<Cookie>, WebviewError>) + 'static>(&self, f: F);
+    /// Store `cookies` in the webview's website data, e.g. ones from `export_cookies`, running the fltk event loop
+    /// until it's done. A cookie the backend refuses returns `WebviewError::InvalidArgument` with its name
+    fn import_cookies(&mut self, cookies: &[Cookie]) -> Result<(), WebviewError>;
+    /// Store `value` under `key` in the localStorage of the current page, and of every origin loaded afterwards,
+    /// before its own scripts run, e.g. to seed the configuration of a web application
+    fn set_local_storage(&mut self, key: &str, value: &str);
+    /// Pass the value stored under `key` in the localStorage of the current page's origin to `f`
+    /// on the main thread, `None` if there's none.
+    /// Pages without localStorage pass `WebviewError::Js`
+    fn get_local_storage<F: FnOnce(Result<Option<String>, WebviewError>) + 'static>(
+        &self,
+        key: &str,
+        f: F,
+    );
+}
+
+#[cfg(not(target_os = "windows"))]
+type Exported<F> = (Vec<Cookie>, F);
+
+#[cfg(not(target_os = "windows"))]
+#[allow(clippy::too_many_arguments)]
+extern "C" fn cookie_cb<F: FnOnce(Result<Vec<Cookie>, WebviewError>)>(
+    name: *const std::os::raw::c_char,
+    value: *const std::os::raw::c_char,
+    domain: *const std::os::raw::c_char,
+    path: *const std::os::raw::c_char,
+    expires: std::os::raw::c_longlong,
+    secure: std::os::raw::c_int,
+    http_only: std::os::raw::c_int,
+    data: *mut std::os::raw::c_void,
+) {
+    let string = |s: *const std::os::raw::c_char| {
+        unsafe { std::ffi::CStr::from_ptr(s) }
+            .to_string_lossy()
+            .into_owned()
+    };
+    if name.is_null() {
+        let (cookies, f) = *unsafe { Box::from_raw(data as *mut Exported<F>) };
+        crate::utils::guard(move || f(Ok(cookies)));
+        return;
+    }
+    let exported = unsafe { &mut *(data as *mut Exported<F>) };
+    exported.0.push(Cookie {
+        name: string(name),
+        value: string(value),
+        domain: string(domain),
+        path: string(path),
+        expires: (expires >= 0).then_some(expires),
+        secure: secure != 0,
+        http_only: http_only != 0,
+    });
+}
+
+#[cfg(not(target_os = "windows"))]
+type Added = std::rc::Rc<std::cell::Cell<Option<bool>>>;
+
+#[cfg(not(target_os = "windows"))]
+extern "C" fn added_cb(ok: std::os::raw::c_int, data: *mut std::os::raw::c_void) {
+    let added: Added = unsafe { std::rc::Rc::from_raw(data as *const _) };
+    added.set(Some(ok != 0));
+}
+
+impl StorageExt for Webview {
+    fn flush_storage(&mut self) {
+        let end = Instant::now() + Duration::from_secs(1);
+        while Instant::now() < end {
+            if app::wait_for(0.05).is_err() {
+                break;
+            }
+        }
+    }
+
+    fn export_cookies<F: FnOnce(Result<Vec<Cookie>, WebviewError>) + 'static>(&self, f: F) {
+        #[cfg(target_os = "windows")]
+        f(Err(WebviewError::Unsupported));
+        #[cfg(not(target_os = "windows"))]
+        unsafe {
+            let exported: Box<Exported<F>> = Box::new((Vec::new(), f));
+            fltk_webview_sys::my_get_cookies(
+                self.get_window(),
+                cookie_cb::<F>,
+                Box::into_raw(exported) as _,
+            );
+        }
+    }
+
+    fn import_cookies(&mut self, cookies: &[Cookie]) -> Result<(), WebviewError> {
+        #[cfg(target_os = "windows")]
+        {
+            let _ = cookies;
+            Err(WebviewError::Unsupported)
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            use std::ffi::CString;
+            let invalid = |c: &Cookie| WebviewError::InvalidArgument(c.name.clone());
+            for cookie in cookies {
+                let c_str = |s: &str| CString::new(s).map_err(|_| invalid(cookie));
+                let added = Added::default();
+                unsafe {
+                    fltk_webview_sys::my_add_cookie(
+                        self.get_window(),
+                        c_str(&cookie.name)?.as_ptr(),
+                        c_str(&cookie.value)?.as_ptr(),
+                        c_str(&cookie.domain)?.as_ptr(),
+                        c_str(&cookie.path)?.as_ptr(),
+                        cookie.expires.unwrap_or(-1),
+                        cookie.secure as _,
+                        cookie.http_only as _,
+                        added_cb,
+                        std::rc::Rc::into_raw(added.clone()) as _,
+                    );
+                }
+                let ok = loop {
+                    if let Some(ok) = added.get() {
+                        break ok;
+                    }
+                    let _ = app::wait_for(0.01);
+                };
+                if !ok {
+                    return Err(invalid(cookie));
+                }
+            }
+            Ok(())
+        }
+    }
+
+    fn set_local_storage(&mut self, key: &str, value: &str) {
+        let items: Vec<String> = state::with(self, |s| {
+            match s.local_storage.iter_mut().find(|(k, _)| k == key) {
+                Some(item) => item.1 = value.to_string(),
+                None => s.local_storage.push((key.to_string(), value.to_string())),
+            }
+            s.local_storage
+                .iter()
+                .map(|(k, v)| format!("[{}, {}]", utils::quote(k), utils::quote(v)))
+                .collect()
+        });
+        let js = format!(
+            "{};\nwindow.__fltk_webview_local_storage = [{}];\nwindow.__fltk_webview_apply_local_storage();",
+            LOCAL_STORAGE_JS,
+            items.join(", ")
+        );
+        crate::scripts::set_script(self, "local_storage", &js);
+    }
+
+    fn get_local_storage<F: FnOnce(Result<Option<String>, WebviewError>) + 'static>(
+        &self,
+        key: &str,
+        f: F,
+    ) {
+        let js = format!("localStorage.getItem({})", utils::quote(key));
+        eval::eval_with(self, &js, move |res| {
+            f(res.map_err(WebviewError::Js).map(|r| match r.parse() {
+                Ok(JsonValue::String(value)) => Some(value),
+                _ => None,
+            }));
+        });
+    }
+}
@@ -0,0 +1,257 @@
+use crate::{state, Webview};
+use std::time::{Duration, Instant};
+
+#[cfg(target_os = "windows")]
+#[repr(C)]
+#[allow(dead_code)]
+struct ProcessEntry {
+    size: u32,
+    usage: u32,
+    pid: u32,
+    heap: usize,
+    module: u32,
+    threads: u32,
+    ppid: u32,
+    priority: i32,
+    flags: u32,
+    exe: [u16; 260],
+}
+
+#[cfg(target_os = "windows")]
+#[repr(C)]
+#[derive(Default)]
+#[allow(dead_code)]
+struct MemoryCounters {
+    size: u32,
+    page_faults: u32,
+    peak_working_set: usize,
+    working_set: usize,
+    pools: [usize; 4],
+    pagefile: usize,
+    peak_pagefile: usize,
+}
+
+#[cfg(target_os = "windows")]
+extern "system" {
+    fn CreateToolhelp32Snapshot(flags: u32, pid: u32) -> *mut ();
+    fn Process32FirstW(snapshot: *mut (), entry: *mut ProcessEntry) -> i32;
+    fn Process32NextW(snapshot: *mut (), entry: *mut ProcessEntry) -> i32;
+    fn OpenProcess(access: u32, inherit: i32, pid: u32) -> *mut ();
+    fn K32GetProcessMemoryInfo(process: *mut (), counters: *mut MemoryCounters, size: u32) -> i32;
+    fn GetProcessTimes(
+        process: *mut (),
+        creation: *mut u64,
+        exit: *mut u64,
+        kernel: *mut u64,
+        user: *mut u64,
+    ) -> i32;
+    fn CloseHandle(handle: *mut ()) -> i32;
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+extern "C" {
+    fn sysconf(name: i32) -> i64;
+}
+
+/// How long the CPU time of the engine is measured for
+const INTERVAL: Duration = Duration::from_millis(250);
+
+/// The resources used by the processes of the web engine, see `ResourceUsageExt::resource_usage`
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct ResourceUsage {
+    /// The resident memory of the processes, in bytes
+    pub memory: u64,
+    /// The CPU use of the processes over the measurement, in percent of a core,
+    /// so it exceeds 100 when they keep several cores busy
+    pub cpu: f64,
+    /// The number of processes measured, 0 if none could be found
+    pub processes: usize,
+}
+
+/// A measurement of the engine processes, with their total CPU time
+struct Sample {
+    memory: u64,
+    cpu: Duration,
+    processes: usize,
+    at: Instant,
+}
+
+/// Monitoring of the web engine
+pub trait ResourceUsageExt {
+    /// Measure the memory and CPU use of the web engine's processes for about 250ms,
+    /// without blocking the event loop, and pass it to `f` on the main thread
+    fn resource_usage<F: FnOnce(ResourceUsage) + 'static>(&self, f: F);
+}
+
+impl ResourceUsageExt for Webview {
+    fn resource_usage<F: FnOnce(ResourceUsage) + 'static>(&self, f: F) {
+        let key = self.get_window() as usize;
+        let first = sample(self);
+        let wv = self.clone();
+        let mut f = Some(f);
+        fltk::app::add_timeout3(INTERVAL.as_secs_f64(), move |_| {
+            if !state::is_running(key) {
+                return;
+            }
+            let last = sample(&wv);
+            let elapsed = last.at.duration_since(first.at).as_secs_f64();
+            let cpu = last.cpu.saturating_sub(first.cpu).as_secs_f64();
+            let usage = ResourceUsage {
+                memory: last.memory,
+                cpu: if elapsed > 0.0 {
+                    cpu / elapsed * 100.0
+                } else {
+                    0.0
+                },
+                processes: last.processes,
+            };
+            if let Some(f) = f.take() {
+                f(usage);
+            }
+        });
+    }
+}
+
+/// The processes descending from `root` among `procs`, pairs of process and parent ids
+#[cfg(not(target_os = "macos"))]
+fn descendants(procs: &[(u32, u32)], root: u32) -> Vec<u32> {
+    let mut found = vec![root];
+    let mut i = 0;
+    while i < found.len() {
+        let parent = found[i];
+        found.extend(
+            procs
+                .iter()
+                .filter(|(pid, ppid)| *ppid == parent && !found.contains(pid))
+                .map(|(pid, _)| *pid)
+                .collect::<Vec<_>>(),
+        );
+        i += 1;
+    }
+    found.remove(0);
+    found
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn sample(_wv: &Webview) -> Sample {
+    // _SC_CLK_TCK
+    let ticks = unsafe { sysconf(2) }.max(1) as f64;
+    // The id, parent id, name, CPU ticks, and resident pages of each process
+    let mut procs = vec![];
+    for entry in std::fs::read_dir("/proc").into_iter().flatten().flatten() {
+        let pid = match entry
+            .file_name()
+            .to_str()
+            .and_then(|n| n.parse::<u32>().ok())
+        {
+            Some(pid) => pid,
+            None => continue,
+        };
+        let stat = match std::fs::read_to_string(entry.path().join("stat")) {
+            Ok(stat) => stat,
+            Err(_) => continue,
+        };
+        // The name is in parentheses, and may contain spaces and parentheses itself
+        let (name, rest) = match (stat.find('('), stat.rfind(')')) {
+            (Some(start), Some(end)) if start < end => (&stat[start + 1..end], &stat[end + 1..]),
+            _ => continue,
+        };
+        let fields: Vec<&str> = rest.split_whitespace().collect();
+        let field = |i: usize| fields.get(i).and_then(|f| f.parse::<u64>().ok());
+        // The fields after the name start at the state, the 3rd field
+        let (ppid, utime, stime, rss) = match (field(1), field(11), field(12), field(21)) {
+            (Some(ppid), Some(utime), Some(stime), Some(rss)) => (ppid, utime, stime, rss),
+            _ => continue,
+        };
+        procs.push((pid, ppid as u32, name.to_string(), utime + stime, rss));
+    }
+    // _SC_PAGESIZE
+    let page = unsafe { sysconf(30) }.max(0) as u64;
+    let tree: Vec<(u32, u32)> = procs.iter().map(|p| (p.0, p.1)).collect();
+    let engine: Vec<_> = descendants(&tree, std::process::id())
+        .into_iter()
+        .filter_map(|pid| procs.iter().find(|p| p.0 == pid))
+        .filter(|p| p.2.starts_with("WebKit"))
+        .collect();
+    Sample {
+        memory: engine.iter().map(|p| p.4 * page).sum(),
+        cpu: Duration::from_secs_f64(engine.iter().map(|p| p.3).sum::<u64>() as f64 / ticks),
+        processes: engine.len(),
+        at: Instant::now(),
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn sample(wv: &Webview) -> Sample {
+    let (mut memory, mut cpu) = (0, 0);
+    let found =
+        unsafe { fltk_webview_sys::my_web_process_usage(wv.get_window(), &mut memory, &mut cpu) }
+            != 0;
+    Sample {
+        memory,
+        cpu: Duration::from_nanos(cpu),
+        processes: found as usize,
+        at: Instant::now(),
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn sample(_wv: &Webview) -> Sample {
+    const TH32CS_SNAPPROCESS: u32 = 0x2;
+    const PROCESS_QUERY_LIMITED_INFORMATION: u32 = 0x1000;
+    const PROCESS_VM_READ: u32 = 0x0010;
+    let mut procs = vec![];
+    unsafe {
+        let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0);
+        // INVALID_HANDLE_VALUE
+        if snapshot as isize != -1 {
+            let mut entry: ProcessEntry = std::mem::zeroed();
+            entry.size = std::mem::size_of::<ProcessEntry>() as u32;
+            let mut more = Process32FirstW(snapshot, &mut entry) != 0;
+            while more {
+                let len = entry.exe.iter().position(|c| *c == 0).unwrap_or(260);
+                let exe = String::from_utf16_lossy(&entry.exe[..len]);
+                procs.push((entry.pid, entry.ppid, exe));
+                more = Process32NextW(snapshot, &mut entry) != 0;
+            }
+            CloseHandle(snapshot);
+        }
+    }
+    let tree: Vec<(u32, u32)> = procs.iter().map(|p| (p.0, p.1)).collect();
+    let mut sample = Sample {
+        memory: 0,
+        cpu: Duration::ZERO,
+        processes: 0,
+        at: Instant::now(),
+    };
+    for pid in descendants(&tree, std::process::id()) {
+        let is_engine = procs
+            .iter()
+            .any(|p| p.0 == pid && p.2.eq_ignore_ascii_case("msedgewebview2.exe"));
+        if !is_engine {
+            continue;
+        }
+        unsafe {
+            let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION | PROCESS_VM_READ, 0, pid);
+            if process.is_null() {
+                continue;
+            }
+            let mut counters = MemoryCounters {
+                size: std::mem::size_of::<MemoryCounters>() as u32,
+                ..Default::default()
+            };
+            if K32GetProcessMemoryInfo(process, &mut counters, counters.size) != 0 {
+                sample.memory += counters.working_set as u64;
+            }
+            // In 100ns units
+            let (mut creation, mut exit, mut kernel, mut user) = (0, 0, 0, 0);
+            if GetProcessTimes(process, &mut creation, &mut exit, &mut kernel, &mut user) != 0 {
+                sample.cpu += Duration::from_nanos((kernel + user) * 100);
+            }
+            CloseHandle(process);
+        }
+        sample.processes += 1;
+    }
+    sample.at = Instant::now();
+    sample
+}
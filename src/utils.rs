@@ -0,0 +1,200 @@
+use tinyjson::JsonValue;
+
+/// Quote a string as a JavaScript string literal
+pub(crate) fn quote(s: &str) -> String {
+    JsonValue::from(s.to_string()).stringify().unwrap()
+}
+
+/// Escape text for HTML content and quoted attribute values
+pub(crate) fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// Parse the JSON array of arguments passed to a bound function
+pub(crate) fn parse_args(req: &str) -> Vec<JsonValue> {
+    match req.parse() {
+        Ok(JsonValue::Array(args)) => args,
+        _ => vec![],
+    }
+}
+
+/// Run a user callback called from native code, catching its panics, which mustn't unwind into C.
+/// The panic is still reported by the panic hook
+pub(crate) fn guard<R>(f: impl FnOnce() -> R) -> Option<R> {
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).ok()
+}
+
+/// The current UTC time in the ISO 8601 format, with milliseconds, e.g. `2024-01-31T12:00:00.000Z`
+pub(crate) fn timestamp() -> String {
+    format_timestamp(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default(),
+    )
+}
+
+/// Format the time elapsed since the Unix epoch like `timestamp`
+fn format_timestamp(now: std::time::Duration) -> String {
+    let secs = now.as_secs();
+    let (days, rem) = ((secs / 86400) as i64, secs % 86400);
+    // Converts days since the epoch to a civil date, after Howard Hinnant's algorithm
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + (month <= 2) as i64;
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60,
+        now.subsec_millis()
+    )
+}
+
+/// Decode standard base64, as produced by JavaScript's `btoa` and `toDataURL`
+pub(crate) fn base64_decode(s: &str) -> Option<Vec<u8>> {
+    let value = |c: u8| match c {
+        b'A'..=b'Z' => Some(c - b'A'),
+        b'a'..=b'z' => Some(c - b'a' + 26),
+        b'0'..=b'9' => Some(c - b'0' + 52),
+        b'+' => Some(62),
+        b'/' => Some(63),
+        _ => None,
+    };
+    let s = s.trim_end_matches('=').as_bytes();
+    let mut out = Vec::with_capacity(s.len() * 3 / 4);
+    for chunk in s.chunks(4) {
+        let mut n = 0u32;
+        for (i, c) in chunk.iter().enumerate() {
+            n |= (value(*c)? as u32) << (18 - 6 * i);
+        }
+        let bytes = n.to_be_bytes();
+        match chunk.len() {
+            4 => out.extend_from_slice(&bytes[1..4]),
+            3 => out.extend_from_slice(&bytes[1..3]),
+            2 => out.push(bytes[1]),
+            _ => return None,
+        }
+    }
+    Some(out)
+}
+
+/// Encode standard base64, which JavaScript's `atob` decodes
+pub(crate) fn base64_encode(data: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity((data.len() + 2) / 3 * 4);
+    for chunk in data.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, b)| n | ((*b as u32) << (16 - 8 * i)));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[((n >> (18 - 6 * i)) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn quotes_strings() {
+        assert_eq!(quote("plain"), r#""plain""#);
+        assert_eq!(quote("a\"b\\c"), r#""a\"b\\c""#);
+        assert_eq!(quote("line\nbreak"), r#""line\nbreak""#);
+        assert_eq!(quote("</script>"), r#""</script>""#);
+    }
+
+    #[test]
+    fn escapes_html() {
+        assert_eq!(escape_html("plain"), "plain");
+        assert_eq!(
+            escape_html(r#"<a href="x">'&'</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;&#39;&amp;&#39;&lt;/a&gt;"
+        );
+        // Ampersands are escaped first, so entities aren't escaped twice
+        assert_eq!(escape_html("&lt;"), "&amp;lt;");
+    }
+
+    #[test]
+    fn parses_args() {
+        let args = parse_args(r#"["a", 1, true, null]"#);
+        assert_eq!(args.len(), 4);
+        assert_eq!(args[0].get::<String>().unwrap(), "a");
+        assert_eq!(*args[1].get::<f64>().unwrap(), 1.0);
+        assert!(*args[2].get::<bool>().unwrap());
+        assert!(parse_args(r#"{"a": 1}"#).is_empty());
+        assert!(parse_args("not json").is_empty());
+        assert!(parse_args("").is_empty());
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(Duration::ZERO), "1970-01-01T00:00:00.000Z");
+        assert_eq!(
+            format_timestamp(Duration::from_secs(951_782_400)),
+            "2000-02-29T00:00:00.000Z"
+        );
+        assert_eq!(
+            format_timestamp(Duration::from_millis(1_706_702_400_500)),
+            "2024-01-31T12:00:00.500Z"
+        );
+        assert_eq!(
+            format_timestamp(Duration::from_secs(1_735_689_599)),
+            "2024-12-31T23:59:59.000Z"
+        );
+        assert_eq!(timestamp().len(), "1970-01-01T00:00:00.000Z".len());
+    }
+
+    #[test]
+    fn encodes_base64() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64_encode(&[0xfb, 0xff]), "+/8=");
+    }
+
+    #[test]
+    fn decodes_base64() {
+        assert_eq!(base64_decode("").unwrap(), b"");
+        assert_eq!(base64_decode("Zg==").unwrap(), b"f");
+        assert_eq!(base64_decode("Zm8=").unwrap(), b"fo");
+        assert_eq!(base64_decode("Zm9vYmFy").unwrap(), b"foobar");
+        assert_eq!(base64_decode("+/8=").unwrap(), [0xfb, 0xff]);
+        // Padding is optional
+        assert_eq!(base64_decode("Zm8").unwrap(), b"fo");
+        assert!(base64_decode("Z").is_none());
+        assert!(base64_decode("Zm9v!").is_none());
+        assert!(base64_decode("-_8").is_none());
+    }
+
+    #[test]
+    fn base64_round_trips() {
+        let data: Vec<u8> = (0..=255).collect();
+        for len in 0..data.len() {
+            let encoded = base64_encode(&data[..len]);
+            assert_eq!(base64_decode(&encoded).unwrap(), &data[..len]);
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use fltk_webview::*;
+use std::{cell::RefCell, rc::Rc};
+
+mod common;
+
+fn typing_and_clicking_drive_the_page() {
+    common::with_webview(|wv| {
+        let added = Rc::new(RefCell::new(vec![]));
+        wv.bind_result("add", {
+            let added = added.clone();
+            move |req| {
+                added.borrow_mut().push(req.to_string());
+                Ok(added.borrow().len().to_string())
+            }
+        });
+        common::load_html(
+            wv,
+            "<input id='name'><textarea></textarea>\
+             <button id='add' onclick=\"add(document.getElementById('name').value)\">Add</button>\
+             <script>window.keys = []; document.addEventListener('keydown', \
+             function(e) { keys.push(e.key + ':' + e.keyCode); });</script>",
+        );
+        // Quotes and backslashes are escaped
+        wv.type_text("#name", "it's \"fltk\\\"");
+        wv.click("#add");
+        assert!(common::wait_until(|| added.borrow().len() == 1));
+        assert_eq!(added.borrow()[0], r#"["it's \"fltk\\\""]"#);
+        // Keys apply to the focused element, here the input focused by the click
+        wv.click("#name");
+        wv.dispatch_key("Backspace");
+        wv.click("#add");
+        assert!(common::wait_until(|| added.borrow().len() == 2));
+        assert_eq!(added.borrow()[1], r#"["it's \"fltk\\"]"#);
+        assert!(common::wait_js(wv, "keys.indexOf('Backspace:8') >= 0"));
+        // Newlines are typed as Enter
+        wv.type_text("textarea", "a\nb");
+        assert!(common::wait_js(
+            wv,
+            "document.querySelector('textarea').value === 'a\\nb'"
+        ));
+        // Missing elements only report to the console
+        wv.click("#missing");
+        assert_eq!(common::eval(wv, "1 + 1").unwrap(), "2");
+    });
+}
+
+fn main() {
+    common::run(&[(
+        "typing_and_clicking_drive_the_page",
+        typing_and_clicking_drive_the_page,
+    )]);
+}
@@ -0,0 +1,61 @@
+use fltk::app;
+use fltk_webview::*;
+use std::{
+    future::Future,
+    sync::Arc,
+    task::{Context, Poll, Wake, Waker},
+    time::Instant,
+};
+
+mod common;
+
+/// Poll `fut` while running the fltk event loop, which completes it
+fn block_on<T>(fut: impl Future<Output = T>) -> T {
+    struct Noop;
+    impl Wake for Noop {
+        fn wake(self: Arc<Self>) {}
+    }
+    let waker = Waker::from(Arc::new(Noop));
+    let mut cx = Context::from_waker(&waker);
+    let mut fut = std::pin::pin!(fut);
+    let end = Instant::now() + common::TIMEOUT;
+    loop {
+        if let Poll::Ready(value) = fut.as_mut().poll(&mut cx) {
+            return value;
+        }
+        assert!(Instant::now() < end, "the future didn't complete");
+        let _ = app::wait_for(0.01);
+    }
+}
+
+fn eval_future_resolves() {
+    common::with_webview(|wv| {
+        common::load_html(wv, "<p>Async</p>");
+        assert_eq!(
+            block_on(wv.eval_future("Promise.resolve(6 * 7)")).unwrap(),
+            "42"
+        );
+        assert!(matches!(
+            block_on(wv.eval_future("throw new Error('boom')")),
+            Err(WebviewError::Js(_))
+        ));
+    });
+}
+
+fn next_call_resolves() {
+    common::with_webview(|wv| {
+        common::load_html(wv, "<p>Async</p>");
+        let call = wv.next_call("ping");
+        wv.eval("setTimeout(function() { ping('hi'); }, 0)");
+        let (seq, req) = block_on(call);
+        assert_eq!(req, r#"["hi"]"#);
+        wv.return_(&seq, 0, "1");
+    });
+}
+
+fn main() {
+    common::run(&[
+        ("eval_future_resolves", eval_future_resolves),
+        ("next_call_resolves", next_call_resolves),
+    ]);
+}
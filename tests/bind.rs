@@ -0,0 +1,292 @@
+use fltk::app;
+use fltk_webview::*;
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+mod common;
+
+/// Settles the promise of `call`, to `"resolved: <value>"` or `"rejected: <error>"`
+fn settle(call: &str) -> String {
+    format!(
+        "{}.then(function(v) {{ return 'resolved: ' + v; }}, \
+         function(e) {{ return 'rejected: ' + e; }})",
+        call
+    )
+}
+
+fn panics_reject_the_call() {
+    common::with_webview(|wv| {
+        wv.bind_result("explode", |_req| panic!("boom"));
+        wv.bind_tracked("explode_raw", |_seq, _req| panic!("boom"));
+        wv.bind_result("answer", |_req| Ok("42".to_string()));
+        common::load_html(wv, "<p>Bindings</p>");
+        assert_eq!(
+            common::eval(wv, &settle("explode()")).unwrap(),
+            "\"rejected: the bound function panicked\""
+        );
+        assert_eq!(
+            common::eval(wv, &settle("explode_raw()")).unwrap(),
+            "\"rejected: the bound function panicked\""
+        );
+        // The process survived, and the bindings still answer
+        assert_eq!(
+            common::eval(wv, &settle("answer()")).unwrap(),
+            "\"resolved: 42\""
+        );
+    });
+}
+
+fn reentrant_calls_are_rejected() {
+    common::with_webview(|wv| {
+        wv.bind_tracked("slow", {
+            let wv = wv.clone();
+            move |seq, _req| {
+                // Lets the page's second call arrive while this one runs
+                let _ = app::wait_for(0.5);
+                wv.return_(seq, 0, "1");
+            }
+        });
+        common::load_html(wv, "<p>Bindings</p>");
+        assert_eq!(
+            common::eval(
+                wv,
+                &format!("Promise.all([{}, {}])", settle("slow()"), settle("slow()"))
+            )
+            .unwrap(),
+            "[\"resolved: 1\",\"rejected: the bound function is already running\"]"
+        );
+    });
+}
+
+fn dispatched_panics_are_caught() {
+    common::with_webview(|wv| {
+        let ran = Arc::new(AtomicBool::new(false));
+        wv.dispatch_guarded(|_wv| panic!("boom"));
+        wv.dispatch_guarded({
+            let ran = ran.clone();
+            move |_wv| ran.store(true, Ordering::SeqCst)
+        });
+        assert!(common::wait_until(|| ran.load(Ordering::SeqCst)));
+    });
+}
+
+fn bind_result_resolves_and_rejects() {
+    common::with_webview(|wv| {
+        wv.bind_result("half", |req| {
+            let n: f64 = req
+                .trim_matches(|c| c == '[' || c == ']')
+                .parse()
+                .map_err(|_| "not a number".to_string())?;
+            Ok((n / 2.0).to_string())
+        });
+        common::load_html(wv, "<p>Bindings</p>");
+        assert_eq!(
+            common::eval(wv, &settle("half(5)")).unwrap(),
+            "\"resolved: 2.5\""
+        );
+        assert_eq!(
+            common::eval(wv, &settle("half('x')")).unwrap(),
+            "\"rejected: not a number\""
+        );
+    });
+}
+
+fn await_call_returns_the_arguments() {
+    common::with_webview(|wv| {
+        common::load_html(wv, "<p>Bindings</p>");
+        wv.eval("setTimeout(function() { window.callResult = submit('form', 42); }, 300)");
+        assert_eq!(
+            wv.await_call("submit", common::TIMEOUT).unwrap(),
+            r#"["form",42]"#
+        );
+        assert_eq!(
+            common::eval(wv, "window.callResult.then(function(v) { return v; })").unwrap(),
+            "null"
+        );
+        // Unbound afterwards, and unanswered calls time out
+        assert_eq!(
+            common::eval(wv, "typeof window.submit").unwrap(),
+            "\"undefined\""
+        );
+        assert!(matches!(
+            wv.await_call("never", Duration::from_millis(200)),
+            Err(WebviewError::Timeout)
+        ));
+    });
+}
+
+fn responders_settle_calls_once() {
+    common::with_webview(|wv| {
+        let kept = Rc::new(RefCell::new(None));
+        wv.bind_with_responder("respond", {
+            let kept = kept.clone();
+            move |responder, req| match req {
+                r#"["resolve"]"# => responder.resolve("\"done\""),
+                r#"["reject"]"# => responder.reject("refused"),
+                r#"["later"]"# => *kept.borrow_mut() = Some(responder),
+                _ => drop(responder),
+            }
+        });
+        common::load_html(wv, "<p>Responders</p>");
+        assert_eq!(
+            common::eval(wv, &settle("respond('resolve')")).unwrap(),
+            "\"resolved: done\""
+        );
+        assert_eq!(
+            common::eval(wv, &settle("respond('reject')")).unwrap(),
+            "\"rejected: refused\""
+        );
+        assert_eq!(
+            common::eval(wv, &settle("respond('drop')")).unwrap(),
+            "\"rejected: the bound function didn't respond\""
+        );
+        // Kept responders respond later, from the main thread
+        wv.eval(&format!("window.later = {}", settle("respond('later')")));
+        assert!(common::wait_until(|| kept.borrow().is_some()));
+        kept.take().unwrap().resolve("1");
+        assert_eq!(common::eval(wv, "window.later").unwrap(), "\"resolved: 1\"");
+    });
+}
+
+fn flush_waits_for_queued_work() {
+    common::with_webview(|wv| {
+        common::load_html(wv, "<p>Flush</p>");
+        let dispatched = Arc::new(AtomicBool::new(false));
+        wv.eval("window.count = 1");
+        wv.dispatch({
+            let dispatched = dispatched.clone();
+            move |_wv| dispatched.store(true, Ordering::SeqCst)
+        });
+        wv.eval("window.count++");
+        wv.flush(common::TIMEOUT).unwrap();
+        assert!(dispatched.load(Ordering::SeqCst));
+        assert_eq!(common::eval(wv, "window.count").unwrap(), "2");
+        // A blocked page doesn't catch up
+        wv.eval("var end = Date.now() + 1000; while (Date.now() < end);");
+        assert!(matches!(
+            wv.flush(Duration::from_millis(100)),
+            Err(WebviewError::Timeout)
+        ));
+        wv.flush(common::TIMEOUT).unwrap();
+    });
+}
+
+fn run_on_main_runs_closures_on_the_main_thread() {
+    common::with_webview(|wv| {
+        let main = std::thread::current().id();
+        let ran_on = Arc::new(Mutex::new(None));
+        let handle = std::thread::spawn({
+            let wv = wv.clone();
+            let ran_on = ran_on.clone();
+            move || {
+                wv.run_on_main(|| panic!("boom"));
+                wv.run_on_main(move || {
+                    *ran_on.lock().unwrap() = Some(std::thread::current().id());
+                });
+            }
+        });
+        assert!(common::wait_until(|| ran_on.lock().unwrap().is_some()));
+        handle.join().unwrap();
+        assert_eq!(ran_on.lock().unwrap().take(), Some(main));
+    });
+}
+
+fn clear_bindings_unbinds_and_drops_closures() {
+    common::with_webview(|wv| {
+        let alive = Rc::new(());
+        for name in ["c", "a", "b"] {
+            let alive = alive.clone();
+            wv.bind_result(name, move |_req| {
+                let _ = &alive;
+                Ok("1".to_string())
+            });
+        }
+        // Untracked
+        wv.bind("raw", |_seq, _req| ());
+        assert_eq!(wv.bindings(), ["a", "b", "c"]);
+        common::load_html(wv, "<p>Bindings</p>");
+        assert_eq!(common::eval(wv, "typeof window.a").unwrap(), "\"function\"");
+        wv.clear_bindings();
+        assert!(wv.bindings().is_empty());
+        assert_eq!(Rc::strong_count(&alive), 1);
+        assert_eq!(
+            common::eval(wv, "typeof window.a").unwrap(),
+            "\"undefined\""
+        );
+    });
+}
+
+fn bind_async_answers_from_workers() {
+    common::with_webview(|wv| {
+        let main = std::thread::current().id();
+        let worker = Arc::new(Mutex::new(None));
+        wv.bind_async("slow", {
+            let worker = worker.clone();
+            move |req| {
+                *worker.lock().unwrap() = Some(std::thread::current().id());
+                std::thread::sleep(Duration::from_millis(500));
+                match req.as_str() {
+                    "[\"fail\"]" => Err("failed".to_string()),
+                    "[\"panic\"]" => panic!("boom"),
+                    _ => Ok(req),
+                }
+            }
+        });
+        common::load_html(wv, "<p>Bindings</p>");
+        // The page and the event loop keep running meanwhile
+        let start = Instant::now();
+        wv.eval(&format!("window.slowResult = {}", settle("slow(1, 2)")));
+        assert_eq!(common::eval(wv, "1 + 1").unwrap(), "2");
+        assert!(start.elapsed() < Duration::from_millis(400));
+        assert!(common::wait_js(
+            wv,
+            "window.slowResult.then(function(r) { return r === 'resolved: 1,2'; })"
+        ));
+        assert_ne!(worker.lock().unwrap().unwrap(), main);
+        assert_eq!(
+            common::eval(wv, &settle("slow('fail')")).unwrap(),
+            "\"rejected: failed\""
+        );
+        assert_eq!(
+            common::eval(wv, &settle("slow('panic')")).unwrap(),
+            "\"rejected: the bound function panicked\""
+        );
+    });
+}
+
+fn main() {
+    common::run(&[
+        ("panics_reject_the_call", panics_reject_the_call),
+        ("reentrant_calls_are_rejected", reentrant_calls_are_rejected),
+        ("dispatched_panics_are_caught", dispatched_panics_are_caught),
+        (
+            "bind_result_resolves_and_rejects",
+            bind_result_resolves_and_rejects,
+        ),
+        (
+            "await_call_returns_the_arguments",
+            await_call_returns_the_arguments,
+        ),
+        ("responders_settle_calls_once", responders_settle_calls_once),
+        ("flush_waits_for_queued_work", flush_waits_for_queued_work),
+        (
+            "run_on_main_runs_closures_on_the_main_thread",
+            run_on_main_runs_closures_on_the_main_thread,
+        ),
+        (
+            "clear_bindings_unbinds_and_drops_closures",
+            clear_bindings_unbinds_and_drops_closures,
+        ),
+        (
+            "bind_async_answers_from_workers",
+            bind_async_answers_from_workers,
+        ),
+    ]);
+}
@@ -0,0 +1,62 @@
+use fltk::window;
+use fltk_webview::*;
+use std::{fs, path::Path};
+
+mod common;
+
+/// The total size of the files under `dir`
+fn dir_size(dir: &Path) -> u64 {
+    fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|e| {
+            let path = e.path();
+            if path.is_dir() {
+                dir_size(&path)
+            } else {
+                e.metadata().map(|m| m.len()).unwrap_or_default()
+            }
+        })
+        .sum()
+}
+
+// The cache location is chosen by the first webview of the process on WebKitGTK,
+// so this runs in its own test binary
+fn document_viewer_policy_keeps_no_disk_cache() {
+    let dir = std::env::temp_dir().join(format!("fltk-webview-cache-{}", std::process::id()));
+    let base = common::serve(|req| match req.path() {
+        "/large.js" => common::Response::new(
+            "200 OK",
+            "text/javascript",
+            format!("window.loaded = true; /*{}*/", "x".repeat(500_000)),
+        )
+        .with_header("Cache-Control", "max-age=3600"),
+        _ => common::Response::html("<script src=/large.js></script>"),
+    });
+    let (win, mut wv_win) = common::window();
+    let mut wv = WebviewBuilder::new()
+        .persistent_storage(dir.clone())
+        .build(&mut wv_win);
+    let result = wv.set_cache_policy(CachePolicy::DocumentViewer);
+    if cfg!(any(target_os = "macos", target_os = "windows")) {
+        assert!(matches!(result, Err(WebviewError::Unsupported)));
+    } else {
+        result.unwrap();
+        common::navigate(&mut wv, &format!("{}/", base));
+        assert_eq!(common::eval(&mut wv, "window.loaded").unwrap(), "true");
+        wv.flush_storage();
+        // The script would take 500KB in a disk cache
+        assert!(dir_size(&dir.join("cache")) < 100_000);
+    }
+    wv.close();
+    window::Window::delete(win);
+    let _ = fs::remove_dir_all(dir);
+}
+
+fn main() {
+    common::run(&[(
+        "document_viewer_policy_keeps_no_disk_cache",
+        document_viewer_policy_keeps_no_disk_cache,
+    )]);
+}
@@ -0,0 +1,55 @@
+use fltk_webview::*;
+use std::{cell::RefCell, rc::Rc};
+
+mod common;
+
+/// Call `fn_name` with `args`, waiting for its result
+fn call<A: serde::Serialize, R: serde::de::DeserializeOwned + 'static>(
+    wv: &mut Webview,
+    fn_name: &str,
+    args: A,
+) -> Result<R, WebviewError> {
+    let result = Rc::new(RefCell::new(None));
+    wv.call_js(fn_name, args, {
+        let result = result.clone();
+        move |res| *result.borrow_mut() = Some(res)
+    })?;
+    assert!(common::wait_until(|| result.borrow().is_some()));
+    result.take().unwrap()
+}
+
+fn call_js_passes_typed_arguments() {
+    common::with_webview(|wv| {
+        common::load_html(
+            wv,
+            "<script>window.multiply = function(a, b) { return a * b; }; \
+             window.app = { greet: function(name) { return Promise.resolve('Hello ' + name); }, \
+             fail: function() { throw new Error('failed'); } };</script>",
+        );
+        assert_eq!(call::<_, i32>(wv, "multiply", (6, 7)).unwrap(), 42);
+        // A single value is the only argument, and promises are awaited
+        assert_eq!(
+            call::<_, String>(wv, "app.greet", "\"quoted\"").unwrap(),
+            "Hello \"quoted\""
+        );
+        assert!(matches!(
+            call::<_, ()>(wv, "app.fail", ()),
+            Err(WebviewError::Js(_))
+        ));
+        assert!(matches!(
+            call::<_, String>(wv, "multiply", (6, 7)),
+            Err(WebviewError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            call::<_, ()>(wv, "alert('x');multiply", ()),
+            Err(WebviewError::InvalidArgument(_))
+        ));
+    });
+}
+
+fn main() {
+    common::run(&[(
+        "call_js_passes_typed_arguments",
+        call_js_passes_typed_arguments,
+    )]);
+}
@@ -0,0 +1,186 @@
+//! Running the integration tests on the main thread, which the backends need,
+//! with helpers waiting on the page through the fltk event loop
+#![allow(dead_code)]
+
+#[path = "../../examples/common/mod.rs"]
+mod server;
+
+pub use server::{serve, serve_tls, Request, Response};
+
+use fltk::{app, prelude::*, window};
+use fltk_webview::*;
+use std::{
+    cell::RefCell,
+    net::TcpStream,
+    process::Child,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+/// How long the conditions of the tests are waited for
+pub const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A 1x1 green PNG, e.g. to serve as an image or an icon
+pub const PIXEL_PNG: &[u8] = &[
+    0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44, 0x52,
+    0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x06, 0x00, 0x00, 0x00, 0x1f, 0x15, 0xc4,
+    0x89, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x44, 0x41, 0x54, 0x78, 0x9c, 0x63, 0xf8, 0xcf, 0xc0, 0xf0,
+    0x1f, 0x00, 0x05, 0x00, 0x01, 0xff, 0x89, 0x99, 0x3d, 0x1d, 0x00, 0x00, 0x00, 0x00, 0x49, 0x45,
+    0x4e, 0x44, 0xae, 0x42, 0x60, 0x82,
+];
+
+/// Whether webviews can be created, X11 being needed on Linux and the BSDs
+fn has_display() -> bool {
+    cfg!(any(target_os = "macos", target_os = "windows")) || std::env::var_os("DISPLAY").is_some()
+}
+
+/// Run `tests` one after the other on the main thread, or the ones whose name contains
+/// the first argument, e.g. `cargo test --test scripts document_end`.
+/// Without a display, e.g. on headless CI, they're skipped
+pub fn run(tests: &[(&str, fn())]) {
+    if !has_display() {
+        println!("no display, skipping {} tests", tests.len());
+        return;
+    }
+    let filter = std::env::args().skip(1).find(|a| !a.starts_with('-'));
+    let _app = app::App::default();
+    let mut failed = vec![];
+    for (name, test) in tests {
+        if filter.as_deref().is_some_and(|f| !name.contains(f)) {
+            continue;
+        }
+        let ok = std::panic::catch_unwind(test).is_ok();
+        println!("test {} ... {}", name, if ok { "ok" } else { "FAILED" });
+        if !ok {
+            failed.push(*name);
+        }
+    }
+    if !failed.is_empty() {
+        println!("failures: {}", failed.join(", "));
+        std::process::exit(1);
+    }
+}
+
+/// An HTTPS server for localhost with a self-signed certificate, killed when dropped
+pub struct TlsServer {
+    child: Child,
+    /// The URL of its status page
+    pub url: String,
+}
+
+impl Drop for TlsServer {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Start a `TlsServer` on `port` once it accepts connections, or `None` without openssl
+pub fn tls_server(port: u16) -> Option<TlsServer> {
+    let server = TlsServer {
+        child: serve_tls(port).ok()?,
+        url: format!("https://localhost:{}/", port),
+    };
+    let end = Instant::now() + TIMEOUT;
+    while TcpStream::connect(("127.0.0.1", port)).is_err() {
+        if Instant::now() > end {
+            return None;
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    Some(server)
+}
+
+/// A shown top-level window, with a child window to embed a webview in
+pub fn window() -> (window::Window, window::Window) {
+    let mut win = window::Window::default().with_size(400, 300);
+    let wv_win = window::Window::default_fill();
+    win.end();
+    win.show();
+    (win, wv_win)
+}
+
+/// Run `f` with a webview in a new window, closing both afterwards
+pub fn with_webview<F: FnOnce(&mut Webview)>(f: F) {
+    let (win, mut wv_win) = window();
+    let mut wv = Webview::create(false, &mut wv_win);
+    f(&mut wv);
+    wv.close();
+    window::Window::delete(win);
+}
+
+/// Run the event loop until `f` returns true, returning false after `TIMEOUT`
+pub fn wait_until<F: FnMut() -> bool>(mut f: F) -> bool {
+    let end = Instant::now() + TIMEOUT;
+    while Instant::now() < end {
+        if f() {
+            return true;
+        }
+        let _ = app::wait_for(0.01);
+    }
+    f()
+}
+
+/// Evaluate `js`, returning its JSON-encoded result,
+/// or `None` if it didn't arrive within `timeout`, e.g. when the page navigated away meanwhile
+fn try_eval(wv: &mut Webview, js: &str, timeout: Duration) -> Option<Result<String, WebviewError>> {
+    let result = Rc::new(RefCell::new(None));
+    wv.eval_batch_with_result(&[js], {
+        let result = result.clone();
+        move |mut results| *result.borrow_mut() = results.pop()
+    });
+    let end = Instant::now() + timeout;
+    while result.borrow().is_none() && Instant::now() < end {
+        let _ = app::wait_for(0.01);
+    }
+    result.take()
+}
+
+/// Evaluate `js`, returning its JSON-encoded result, e.g. `"\"text\""` for a string
+pub fn eval(wv: &mut Webview, js: &str) -> Result<String, WebviewError> {
+    try_eval(wv, js, TIMEOUT).unwrap_or_else(|| panic!("no result for {}", js))
+}
+
+/// Run the event loop until `js` evaluates to true in the page, returning false after `TIMEOUT`
+pub fn wait_js(wv: &mut Webview, js: &str) -> bool {
+    let end = Instant::now() + TIMEOUT;
+    while Instant::now() < end {
+        if let Some(Ok(r)) = try_eval(wv, js, Duration::from_secs(1)) {
+            if r == "true" {
+                return true;
+            }
+        }
+        let _ = app::wait_for(0.05);
+    }
+    false
+}
+
+/// Mark the current page, so `wait_loaded` can tell it from the next one
+pub fn mark_page(wv: &mut Webview) {
+    wv.eval("window.__test_unloaded = true");
+}
+
+/// Wait until the page marked by `mark_page` was replaced by one which finished loading
+pub fn wait_loaded(wv: &mut Webview) {
+    assert!(
+        wait_js(
+            wv,
+            "!window.__test_unloaded && document.readyState === 'complete'"
+        ),
+        "the page didn't load"
+    );
+}
+
+/// Load `url`, waiting until it's loaded
+pub fn navigate(wv: &mut Webview, url: &str) {
+    mark_page(wv);
+    wv.navigate(url);
+    wait_loaded(wv);
+}
+
+/// Load `html`, waiting until it's loaded
+pub fn load_html(wv: &mut Webview, html: &str) {
+    mark_page(wv);
+    wv.set_html(html);
+    wait_loaded(wv);
+}
@@ -0,0 +1,72 @@
+use fltk_webview::*;
+use std::{cell::RefCell, rc::Rc};
+
+mod common;
+
+fn console_messages_reach_the_handler() {
+    common::with_webview(|wv| {
+        let messages = Rc::new(RefCell::new(vec![]));
+        wv.on_console_message({
+            let messages = messages.clone();
+            move |level, msg| messages.borrow_mut().push((level, msg.to_string()))
+        });
+        common::load_html(wv, "<p>Console</p>");
+        wv.eval(
+            "console.log('text', 1, { a: [true] }); console.warn('careful'); console.error('x')",
+        );
+        assert!(common::wait_until(|| messages.borrow().len() == 3));
+        assert_eq!(
+            *messages.borrow(),
+            [
+                (ConsoleLevel::Log, r#"text 1 {"a":[true]}"#.to_string()),
+                (ConsoleLevel::Warn, "careful".to_string()),
+                (ConsoleLevel::Error, "x".to_string()),
+            ]
+        );
+    });
+}
+
+fn log_files_record_console_messages_and_navigations() {
+    let base = common::serve(|_req| common::Response::html("<title>Logged</title>"));
+    let path = std::env::temp_dir().join(format!("fltk-webview-log-{}.txt", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let url = format!("{}/logged", base);
+    common::with_webview(|wv| {
+        assert!(matches!(
+            wv.set_log_file(&std::env::temp_dir().join("missing-folder/log.txt")),
+            Err(WebviewError::Io(_))
+        ));
+        wv.set_log_file(&path).unwrap();
+        common::navigate(wv, &url);
+        wv.eval("console.error('something', 'failed')");
+        assert!(common::wait_until(|| std::fs::read_to_string(&path)
+            .unwrap_or_default()
+            .contains("console.error: something failed")));
+    });
+    let log = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let navigated = format!("navigated: {}", url);
+    let line = log.lines().find(|l| l.ends_with(&navigated)).unwrap();
+    // Starting with the UTC time, e.g. `2024-01-31T12:00:00.000Z`
+    assert!(
+        line.get(4..5) == Some("-")
+            && line.get(10..11) == Some("T")
+            && line.get(23..25) == Some("Z "),
+        "{}",
+        line
+    );
+    assert!(log.find(&navigated) < log.find("console.error"));
+}
+
+fn main() {
+    common::run(&[
+        (
+            "console_messages_reach_the_handler",
+            console_messages_reach_the_handler,
+        ),
+        (
+            "log_files_record_console_messages_and_navigations",
+            log_files_record_console_messages_and_navigations,
+        ),
+    ]);
+}
@@ -0,0 +1,63 @@
+use fltk_webview::*;
+use std::{cell::RefCell, rc::Rc};
+
+mod common;
+
+/// Call `method`, waiting for its result
+fn call(wv: &mut Webview, method: &str, params: &str) -> Result<String, WebviewError> {
+    let result = Rc::new(RefCell::new(None));
+    wv.call_cdp(method, params, {
+        let result = result.clone();
+        move |res| *result.borrow_mut() = Some(res)
+    });
+    assert!(common::wait_until(|| result.borrow().is_some()));
+    result.take().unwrap()
+}
+
+fn runtime_evaluate_answers_like_chrome() {
+    common::with_webview(|wv| {
+        common::load_html(wv, "<p>DevTools</p>");
+        assert_eq!(
+            call(
+                wv,
+                "Runtime.evaluate",
+                r#"{"expression": "6 * 7", "returnByValue": true}"#
+            )
+            .unwrap(),
+            r#"{"result":{"type":"number","value":42,"description":"42"}}"#
+        );
+        assert_eq!(
+            call(
+                wv,
+                "Runtime.evaluate",
+                r#"{"expression": "Promise.resolve([1, 2])", "returnByValue": true, "awaitPromise": true}"#
+            )
+            .unwrap(),
+            r#"{"result":{"type":"object","className":"Array","subtype":"array","description":"Array","value":[1,2]}}"#
+        );
+        let thrown = call(
+            wv,
+            "Runtime.evaluate",
+            r#"{"expression": "throw new TypeError('bad')"}"#,
+        )
+        .unwrap();
+        assert!(thrown.contains(r#""exceptionDetails":{"exceptionId":1,"text":"Uncaught""#));
+        assert!(thrown.contains(r#""className":"TypeError""#));
+        // Only Runtime.evaluate is answered
+        assert!(matches!(
+            call(wv, "Page.reload", ""),
+            Err(WebviewError::Unsupported)
+        ));
+        assert!(matches!(
+            call(wv, "Runtime.evaluate", "[]"),
+            Err(WebviewError::InvalidArgument(_))
+        ));
+    });
+}
+
+fn main() {
+    common::run(&[(
+        "runtime_evaluate_answers_like_chrome",
+        runtime_evaluate_answers_like_chrome,
+    )]);
+}
@@ -0,0 +1,53 @@
+use fltk_webview::*;
+use std::{cell::RefCell, fs, rc::Rc};
+
+mod common;
+
+const LINKS_HTML: &str =
+    "<a id='save' href='data:text/plain,saved%20text' download='note.txt'>Save</a>\
+    <a id='cancel' href='data:text/plain,cancelled' download='other.txt'>Cancel</a>";
+
+fn downloads_are_saved_where_the_handler_decides() {
+    let dir = std::env::temp_dir().join(format!("fltk-webview-download-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("saved.txt");
+    common::with_webview(|wv| {
+        let requests = Rc::new(RefCell::new(vec![]));
+        let states = Rc::new(RefCell::new(vec![]));
+        wv.set_download_handler({
+            let requests = requests.clone();
+            let path = path.clone();
+            move |req| {
+                let decision = match req.suggested_filename.as_str() {
+                    "note.txt" => DownloadDecision::Save(path.clone()),
+                    _ => DownloadDecision::Cancel,
+                };
+                requests.borrow_mut().push(req.suggested_filename);
+                decision
+            }
+        });
+        wv.on_download_progress({
+            let states = states.clone();
+            move |progress| states.borrow_mut().push(progress.state.clone())
+        });
+        common::load_html(wv, LINKS_HTML);
+        wv.eval("document.getElementById('save').click()");
+        assert!(common::wait_until(|| states
+            .borrow()
+            .contains(&DownloadState::Finished)));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "saved text");
+        wv.eval("document.getElementById('cancel').click()");
+        assert!(common::wait_until(|| states
+            .borrow()
+            .contains(&DownloadState::Cancelled)));
+        assert_eq!(*requests.borrow(), ["note.txt", "other.txt"]);
+    });
+    let _ = fs::remove_dir_all(dir);
+}
+
+fn main() {
+    common::run(&[(
+        "downloads_are_saved_where_the_handler_decides",
+        downloads_are_saved_where_the_handler_decides,
+    )]);
+}
@@ -0,0 +1,686 @@
+use fltk::{app, enums::Color, group, prelude::*, window};
+use fltk_webview::*;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+mod common;
+
+// Whether the page itself is drawn transparent can't be read back: the backends
+// composite it outside of fltk, so only the embedding window is checked
+fn transparent_pages_show_the_parent() {
+    let (mut win, mut wv_win) = common::window();
+    win.set_color(Color::Red);
+    let mut wv = Webview::create(false, &mut wv_win);
+    let result = wv.set_transparent(true);
+    if cfg!(target_os = "windows") {
+        assert!(matches!(result, Err(WebviewError::Unsupported)));
+    } else {
+        result.unwrap();
+        assert_eq!(wv_win.color(), Color::Red);
+        wv.set_transparent(false).unwrap();
+        assert_eq!(wv_win.color(), Color::White);
+    }
+    wv.close();
+    window::Window::delete(win);
+}
+
+fn focus_moves_into_the_page() {
+    common::with_webview(|wv| {
+        common::load_html(wv, "<input autofocus>");
+        wv.focus();
+        assert!(common::wait_until(|| wv.has_focus()));
+        assert!(common::wait_js(wv, "document.hasFocus()"));
+    });
+}
+
+fn webviews_in_one_window_are_independent() {
+    let mut win = window::Window::default().with_size(400, 300);
+    let mut left = window::Window::new(0, 0, 200, 300, None);
+    left.end();
+    let mut right = window::Window::new(200, 0, 200, 300, None);
+    right.end();
+    win.end();
+    win.show();
+    let mut left = Webview::create(false, &mut left);
+    let mut right = Webview::create(false, &mut right);
+    left.bind_result("who", |_req| Ok("\"left\"".to_string()));
+    right.bind_result("who", |_req| Ok("\"right\"".to_string()));
+    common::load_html(&mut left, "<p>Left</p>");
+    common::load_html(&mut right, "<p>Right</p>");
+    assert_eq!(common::eval(&mut left, "who()").unwrap(), "\"left\"");
+    assert_eq!(common::eval(&mut right, "who()").unwrap(), "\"right\"");
+    // Closing one leaves the other running
+    left.close();
+    assert!(right.is_running());
+    assert_eq!(common::eval(&mut right, "who()").unwrap(), "\"right\"");
+    right.close();
+    window::Window::delete(win);
+}
+
+fn device_scale_sizes_the_page() {
+    common::with_webview(|wv| {
+        common::load_html(wv, "<p>Scale</p>");
+        // The embedding window is 400x300
+        wv.set_device_scale(0.5);
+        assert!(common::wait_js(
+            wv,
+            "window.innerWidth === 200 && window.innerHeight === 150"
+        ));
+    });
+}
+
+fn builder_background_colors_the_window() {
+    let (win, mut wv_win) = common::window();
+    let mut wv = WebviewBuilder::new()
+        .background(Color::Red)
+        .build(&mut wv_win);
+    assert_eq!(wv_win.color(), Color::Red);
+    // Turning transparency off restores the builder's color rather than white
+    if wv.set_transparent(true).is_ok() {
+        wv.set_transparent(false).unwrap();
+        assert_eq!(wv_win.color(), Color::Red);
+    }
+    wv.close();
+    window::Window::delete(win);
+}
+
+fn native_window_handle_matches_the_platform() {
+    common::with_webview(|wv| {
+        let handle = wv.native_window_handle();
+        let raw = match handle {
+            NativeHandle::Win32(h) => {
+                assert!(cfg!(target_os = "windows"));
+                h
+            }
+            NativeHandle::Cocoa(h) => {
+                assert!(cfg!(target_os = "macos"));
+                h
+            }
+            NativeHandle::Gtk(h) => {
+                assert!(!cfg!(any(target_os = "macos", target_os = "windows")));
+                h
+            }
+        };
+        assert!(!raw.is_null());
+        assert_eq!(raw, wv.get_window());
+    });
+}
+
+fn offscreen_webviews_render_pages() {
+    let mut wv = Webview::create_offscreen(false, 320, 240).unwrap();
+    common::load_html(&mut wv, "<p>Offscreen</p>");
+    assert!(common::wait_js(
+        &mut wv,
+        "window.innerWidth === 320 && window.innerHeight === 240"
+    ));
+    assert_eq!(
+        common::eval(
+            &mut wv,
+            "document.querySelector('p').getBoundingClientRect().height > 0"
+        )
+        .unwrap(),
+        "true"
+    );
+    wv.close();
+}
+
+// fltk doesn't read size ranges back, and they're enforced by window managers,
+// so only the size of the webview is checked
+fn size_limits_keep_the_current_size() {
+    common::with_webview(|wv| {
+        common::load_html(wv, "<p>Limits</p>");
+        let size = wv.size();
+        wv.set_min_size(200, 150);
+        wv.set_max_size(800, 600);
+        let _ = app::wait_for(0.2);
+        assert_eq!(wv.size(), size);
+        assert_eq!(wv.size_hint(), SizeHint::None);
+        assert!(common::wait_js(
+            wv,
+            "window.innerWidth === 400 && window.innerHeight === 300"
+        ));
+    });
+}
+
+// Which compositor draws the page isn't visible to it, so this checks that pages
+// still run and lay out without the GPU, as on virtual machines
+fn pages_render_without_hardware_acceleration() {
+    let (win, mut wv_win) = common::window();
+    let mut wv = WebviewBuilder::new()
+        .hardware_acceleration(HardwareAcceleration::Never)
+        .build(&mut wv_win);
+    common::load_html(
+        &mut wv,
+        "<div style='width: 100px; height: 50px; transform: rotate(45deg)'></div>",
+    );
+    assert_eq!(
+        common::eval(
+            &mut wv,
+            "document.querySelector('div').getBoundingClientRect().width > 100"
+        )
+        .unwrap(),
+        "true"
+    );
+    wv.close();
+    window::Window::delete(win);
+}
+
+fn webviews_embed_in_native_windows() {
+    common::with_webview(|host| {
+        // The native window of a webview is a window of the platform's toolkit,
+        // like the containers of other toolkits
+        let mut wv = WebviewBuilder::new()
+            .build_in_handle(host.native_window_handle(), 200, 150)
+            .unwrap();
+        common::load_html(&mut wv, "<p>Embedded</p>");
+        assert!(common::wait_js(
+            &mut wv,
+            "window.innerWidth === 200 && window.innerHeight === 150"
+        ));
+        wv.close();
+        for handle in [
+            NativeHandle::Gtk(std::ptr::null_mut()),
+            NativeHandle::Win32(std::ptr::null_mut()),
+            NativeHandle::Cocoa(std::ptr::null_mut()),
+        ] {
+            assert!(matches!(
+                WebviewBuilder::new().build_in_handle(handle, 200, 150),
+                Err(WebviewError::Unsupported)
+            ));
+        }
+    });
+}
+
+fn closing_keeps_the_window_for_another_webview() {
+    let (win, mut wv_win) = common::window();
+    let mut wv = Webview::create(false, &mut wv_win);
+    common::load_html(&mut wv, "<p>First</p>");
+    let clone = wv.clone();
+    wv.close();
+    assert!(!clone.is_running());
+    // The application and its event loop keep running
+    assert!(app::wait_for(0.1).is_ok());
+    assert!(wv_win.shown());
+    let mut wv = Webview::create(false, &mut wv_win);
+    common::load_html(&mut wv, "<p>Second</p>");
+    assert_eq!(
+        common::eval(&mut wv, "document.body.textContent").unwrap(),
+        "\"Second\""
+    );
+    wv.close();
+    window::Window::delete(win);
+}
+
+fn hidden_windows_are_reported() {
+    let mut win = window::Window::default().with_size(400, 300);
+    let mut wv_win = window::Window::default_fill();
+    win.end();
+    assert!(matches!(
+        WebviewBuilder::new().try_build(&mut wv_win),
+        Err(WebviewError::WindowNotShown)
+    ));
+    // Windows shown right before are waited for
+    win.show();
+    let wv = WebviewBuilder::new().try_build(&mut wv_win).unwrap();
+    wv.close();
+    window::Window::delete(win);
+}
+
+fn is_running_is_readable_from_other_threads() {
+    let (win, mut wv_win) = common::window();
+    let wv = Webview::create(false, &mut wv_win);
+    let running = {
+        let wv = wv.clone();
+        std::thread::spawn(move || wv.is_running())
+    };
+    assert!(running.join().unwrap());
+    let clone = wv.clone();
+    wv.close();
+    let running = std::thread::spawn(move || clone.is_running());
+    assert!(!running.join().unwrap());
+    window::Window::delete(win);
+}
+
+// How often the backend is resized isn't observable from the page,
+// so this checks that coalesced resizes settle on the last size
+fn rapid_resizes_settle_on_the_last_size() {
+    let (win, mut wv_win) = common::window();
+    let mut wv = Webview::create(false, &mut wv_win);
+    common::load_html(&mut wv, "<p>Resize</p>");
+    for i in 0..20 {
+        wv_win.resize(0, 0, 400 - i * 5, 300 - i * 5);
+        wv_win.redraw();
+        let _ = app::wait_for(0.0);
+    }
+    wv_win.resize(0, 0, 250, 180);
+    wv_win.redraw();
+    assert!(common::wait_js(
+        &mut wv,
+        "window.innerWidth === 250 && window.innerHeight === 180"
+    ));
+    wv.close();
+    window::Window::delete(win);
+}
+
+fn raw_handles_reach_the_sys_crate() {
+    common::with_webview(|wv| {
+        common::load_html(wv, "<p>Raw</p>");
+        let raw = wv.as_raw();
+        assert_eq!(
+            unsafe { fltk_webview_sys::webview_get_window(raw) },
+            wv.get_window()
+        );
+        let js = std::ffi::CString::new("window.fromSys = true").unwrap();
+        unsafe {
+            fltk_webview_sys::webview_eval(raw, js.as_ptr());
+        }
+        assert!(common::wait_js(wv, "window.fromSys === true"));
+    });
+}
+
+fn suspended_pages_are_hidden() {
+    common::with_webview(|wv| {
+        common::load_html(wv, "<p>Suspend</p>");
+        assert_eq!(common::eval(wv, "document.hidden").unwrap(), "false");
+        wv.suspend();
+        assert!(common::wait_js(wv, "document.hidden"));
+        wv.resume();
+        assert!(common::wait_js(wv, "!document.hidden"));
+    });
+}
+
+fn size_hints_resize_and_reset() {
+    common::with_webview(|wv| {
+        common::load_html(wv, "<p>Hints</p>");
+        wv.set_size_and_hint(300, 200, SizeHint::Fixed);
+        assert_eq!(wv.size_hint(), SizeHint::Fixed);
+        assert!(common::wait_js(
+            wv,
+            "window.innerWidth === 300 && window.innerHeight === 200"
+        ));
+        // Min only limits the size
+        wv.set_size_and_hint(100, 100, SizeHint::Min);
+        assert_eq!(wv.size_hint(), SizeHint::Min);
+        let _ = app::wait_for(0.2);
+        assert_eq!(common::eval(wv, "window.innerWidth").unwrap(), "300");
+        wv.set_size_and_hint(250, 150, SizeHint::None);
+        assert_eq!(wv.size_hint(), SizeHint::None);
+        assert!(common::wait_js(
+            wv,
+            "window.innerWidth === 250 && window.innerHeight === 150"
+        ));
+    });
+}
+
+fn positions_are_kept_relative_to_the_window() {
+    common::with_webview(|wv| {
+        assert_eq!(wv.position(), (0, 0));
+        wv.set_position(20, 10);
+        assert!(common::wait_until(|| wv.position() == (20, 10)));
+        // Kept when the webview is resized
+        wv.set_size_and_hint(200, 100, SizeHint::None);
+        let _ = app::wait_for(0.2);
+        assert_eq!(wv.position(), (20, 10));
+    });
+}
+
+fn webviews_follow_their_group() {
+    let mut win = window::Window::default().with_size(400, 300);
+    let tile = group::Tile::default_fill();
+    let left = group::Group::new(0, 0, 200, 300, None);
+    left.end();
+    let mut right = group::Group::new(200, 0, 200, 300, None);
+    right.end();
+    tile.end();
+    win.end();
+    win.show();
+    let mut wv = Webview::create_in_group(false, &mut right).unwrap();
+    common::load_html(&mut wv, "<p>Group</p>");
+    assert!(common::wait_js(
+        &mut wv,
+        "window.innerWidth === 200 && window.innerHeight === 300"
+    ));
+    // Moving the tile's divider resizes the group
+    right.resize(100, 0, 300, 300);
+    assert!(common::wait_js(&mut wv, "window.innerWidth === 300"));
+    wv.close();
+    window::Window::delete(win);
+}
+
+/// Drag `selector` by (30, 20) with synthetic pointer events, from the screen position (100, 100)
+fn drag_js(selector: &str) -> String {
+    format!(
+        "var el = document.querySelector('{}'); \
+         ['pointerdown', 'pointermove', 'pointerup'].forEach(function(type, i) {{ \
+         el.dispatchEvent(new PointerEvent(type, {{ bubbles: true, button: 0, pointerId: 1, \
+         pointerType: 'mouse', screenX: 100 + (i ? 30 : 0), screenY: 100 + (i ? 20 : 0) }})); }})",
+        selector
+    )
+}
+
+fn app_regions_drag_the_window() {
+    let (win, mut wv_win) = common::window();
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.enable_app_regions();
+    common::load_html(
+        &mut wv,
+        "<header data-app-region='drag'>Title <button data-app-region='no-drag'>x</button></header>",
+    );
+    let (x, y) = (win.x(), win.y());
+    // Elements excluded from the region keep their clicks
+    wv.eval(&drag_js("button"));
+    let _ = app::wait_for(0.3);
+    assert_eq!((win.x(), win.y()), (x, y));
+    wv.eval(&drag_js("header"));
+    assert!(common::wait_until(|| (win.x(), win.y()) == (x + 30, y + 20)));
+    wv.close();
+    window::Window::delete(win);
+}
+
+fn engine_info_describes_the_running_engine() {
+    let (win, mut wv_win) = common::window();
+    let mut wv = Webview::create(true, &mut wv_win);
+    common::load_html(&mut wv, "<p>Engine</p>");
+    let info = wv.engine_info();
+    println!("{:?}", info);
+    let name = if cfg!(target_os = "windows") {
+        "WebView2"
+    } else if cfg!(target_os = "macos") {
+        "WKWebView"
+    } else {
+        "WebKitGTK"
+    };
+    assert_eq!(info.name, name);
+    assert!(info.is_debug);
+    // A dotted version, e.g. 2.44.1
+    let parts: Vec<&str> = info.version.split('.').collect();
+    assert!(parts.len() >= 2, "{}", info.version);
+    assert!(parts
+        .iter()
+        .all(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit())));
+    wv.close();
+    window::Window::delete(win);
+}
+
+// Whether the window is kept above others is up to the window manager on X11,
+// and none of the platforms let it be read back through fltk, so the flag is checked
+fn always_on_top_is_toggled() {
+    let (win, mut wv_win) = common::window();
+    let mut wv = Webview::create(false, &mut wv_win);
+    assert!(!wv.is_always_on_top());
+    wv.set_always_on_top(true);
+    assert!(wv.is_always_on_top());
+    common::load_html(&mut wv, "<p>On top</p>");
+    assert!(win.shown());
+    wv.set_always_on_top(false);
+    assert!(!wv.is_always_on_top());
+    wv.close();
+    window::Window::delete(win);
+}
+
+fn reparented_webviews_keep_their_page() {
+    let (first, mut first_wv_win) = common::window();
+    let (second, mut second_wv_win) = common::window();
+    let mut wv = Webview::create(false, &mut first_wv_win);
+    let count = Rc::new(Cell::new(0));
+    wv.bind_result("increment", {
+        let count = count.clone();
+        move |_req| {
+            count.set(count.get() + 1);
+            Ok(count.get().to_string())
+        }
+    });
+    common::load_html(&mut wv, "<p>Moved</p>");
+    wv.eval("window.kept = 'yes'");
+    assert_eq!(common::eval(&mut wv, "increment()").unwrap(), "1");
+    let result = wv.reparent(&mut second_wv_win);
+    if cfg!(target_os = "windows") {
+        assert!(matches!(result, Err(WebviewError::Unsupported)));
+        wv.close();
+        window::Window::delete(first);
+        return window::Window::delete(second);
+    }
+    result.unwrap();
+    // The first window can go, and the new one sizes the webview
+    window::Window::delete(first);
+    assert_eq!(common::eval(&mut wv, "window.kept").unwrap(), "\"yes\"");
+    assert_eq!(common::eval(&mut wv, "increment()").unwrap(), "2");
+    second_wv_win.resize(0, 0, 300, 200);
+    assert!(common::wait_js(&mut wv, "window.innerWidth === 300"));
+    wv.close();
+    window::Window::delete(second);
+}
+
+fn fill_parent_keeps_the_margin() {
+    let mut win = window::Window::default().with_size(400, 300);
+    let mut wv_win = window::Window::new(50, 50, 100, 100, None);
+    wv_win.end();
+    win.end();
+    win.make_resizable(true);
+    win.show();
+    let mut wv = Webview::create(false, &mut wv_win);
+    wv.fill_parent(10);
+    assert_eq!(
+        (wv_win.x(), wv_win.y(), wv_win.w(), wv_win.h()),
+        (10, 10, 380, 280)
+    );
+    common::load_html(&mut wv, "<p>Filled</p>");
+    assert!(common::wait_js(
+        &mut wv,
+        "window.innerWidth === 380 && window.innerHeight === 280"
+    ));
+    // Followed as the parent is resized
+    win.resize(win.x(), win.y(), 500, 400);
+    assert_eq!(
+        (wv_win.x(), wv_win.y(), wv_win.w(), wv_win.h()),
+        (10, 10, 480, 380)
+    );
+    assert!(common::wait_js(
+        &mut wv,
+        "window.innerWidth === 480 && window.innerHeight === 380"
+    ));
+    wv.close();
+    window::Window::delete(win);
+}
+
+// The faded rendering is composited outside of fltk, so only the recorded opacity is checked
+fn opacity_is_clamped_and_read_back() {
+    common::with_webview(|wv| {
+        assert_eq!(wv.opacity(), 1.0);
+        wv.set_opacity(0.5);
+        assert_eq!(wv.opacity(), 0.5);
+        common::load_html(wv, "<p>Faded</p>");
+        assert_eq!(wv.opacity(), 0.5);
+        wv.set_opacity(2.0);
+        assert_eq!(wv.opacity(), 1.0);
+        wv.set_opacity(-1.0);
+        assert_eq!(wv.opacity(), 0.0);
+    });
+}
+
+fn size_and_hint_are_read_back() {
+    common::with_webview(|wv| {
+        common::load_html(wv, "<p>Size</p>");
+        assert_eq!(wv.size(), (400, 300));
+        wv.set_size_and_hint(320, 240, SizeHint::Fixed);
+        assert_eq!(wv.size_hint(), SizeHint::Fixed);
+        assert!(common::wait_until(|| wv.size() == (320, 240)));
+        // Hints passed to the backend directly aren't tracked
+        wv.set_size(280, 200, SizeHint::None);
+        assert!(common::wait_until(|| wv.size() == (280, 200)));
+        assert_eq!(wv.size_hint(), SizeHint::Fixed);
+    });
+}
+
+fn window_state_changes_are_reported() {
+    common::with_webview(|wv| {
+        let states = Rc::new(RefCell::new(vec![]));
+        wv.on_window_state_changed({
+            let states = states.clone();
+            move |state| states.borrow_mut().push(state)
+        });
+        common::load_html(wv, "<p>States</p>");
+        assert_eq!(wv.window_state(), WindowState::Normal);
+        wv.set_window_state(WindowState::Maximized);
+        if !common::wait_until(|| wv.window_state() == WindowState::Maximized) {
+            // X11 without a window manager
+            return assert!(cfg!(not(any(target_os = "macos", target_os = "windows"))));
+        }
+        assert!(common::wait_until(
+            || states.borrow().last() == Some(&WindowState::Maximized)
+        ));
+        wv.set_window_state(WindowState::Minimized);
+        assert!(common::wait_until(
+            || states.borrow().last() == Some(&WindowState::Minimized)
+        ));
+        wv.set_window_state(WindowState::Normal);
+        assert!(common::wait_until(
+            || states.borrow().last() == Some(&WindowState::Normal)
+        ));
+        assert_eq!(wv.window_state(), WindowState::Normal);
+        // The page still runs
+        assert_eq!(common::eval(wv, "1 + 1").unwrap(), "2");
+    });
+}
+
+fn webviews_are_moved_between_screens() {
+    let (win, mut wv_win) = common::window();
+    let mut wv = Webview::create(false, &mut wv_win);
+    common::load_html(&mut wv, "<p>Screens</p>");
+    let last = app::screen_count().max(1) as usize - 1;
+    // Clamped to the last screen
+    wv.move_to_screen(last + 1);
+    assert!(common::wait_until(|| wv.screen() == last));
+    if last == 0 {
+        println!("single screen, only clamping checked");
+    }
+    for screen in [0, last] {
+        wv.move_to_screen(screen);
+        assert!(common::wait_until(|| wv.screen() == screen));
+        let (x, y, w, h) = app::screen_work_area(screen as i32);
+        assert!(win.x() >= x && win.y() >= y);
+        assert!(win.x() + win.w() <= x + w && win.y() + win.h() <= y + h);
+    }
+    assert_eq!(common::eval(&mut wv, "1 + 1").unwrap(), "2");
+    wv.close();
+    window::Window::delete(win);
+}
+
+fn fades_reach_their_target_opacity() {
+    common::with_webview(|wv| {
+        common::load_html(wv, "<p>Fading</p>");
+        wv.fade_in(200);
+        assert!(wv.opacity() < 0.5);
+        assert!(common::wait_until(|| wv.opacity() == 1.0));
+        let done = Rc::new(Cell::new(false));
+        wv.fade_out(200, {
+            let done = done.clone();
+            move || done.set(true)
+        });
+        assert!(common::wait_until(|| done.get()));
+        assert_eq!(wv.opacity(), 0.0);
+        // A fade started meanwhile cancels the callback of the previous one
+        let cancelled = Rc::new(Cell::new(false));
+        wv.fade_out(200, {
+            let cancelled = cancelled.clone();
+            move || cancelled.set(true)
+        });
+        wv.fade_in(100);
+        assert!(common::wait_until(|| wv.opacity() == 1.0));
+        let _ = app::wait_for(0.3);
+        assert!(!cancelled.get());
+    });
+}
+
+fn main() {
+    common::run(&[
+        (
+            "transparent_pages_show_the_parent",
+            transparent_pages_show_the_parent,
+        ),
+        ("focus_moves_into_the_page", focus_moves_into_the_page),
+        (
+            "webviews_in_one_window_are_independent",
+            webviews_in_one_window_are_independent,
+        ),
+        ("device_scale_sizes_the_page", device_scale_sizes_the_page),
+        (
+            "builder_background_colors_the_window",
+            builder_background_colors_the_window,
+        ),
+        (
+            "native_window_handle_matches_the_platform",
+            native_window_handle_matches_the_platform,
+        ),
+        (
+            "offscreen_webviews_render_pages",
+            offscreen_webviews_render_pages,
+        ),
+        (
+            "size_limits_keep_the_current_size",
+            size_limits_keep_the_current_size,
+        ),
+        (
+            "pages_render_without_hardware_acceleration",
+            pages_render_without_hardware_acceleration,
+        ),
+        (
+            "webviews_embed_in_native_windows",
+            webviews_embed_in_native_windows,
+        ),
+        (
+            "closing_keeps_the_window_for_another_webview",
+            closing_keeps_the_window_for_another_webview,
+        ),
+        ("hidden_windows_are_reported", hidden_windows_are_reported),
+        (
+            "is_running_is_readable_from_other_threads",
+            is_running_is_readable_from_other_threads,
+        ),
+        (
+            "rapid_resizes_settle_on_the_last_size",
+            rapid_resizes_settle_on_the_last_size,
+        ),
+        (
+            "raw_handles_reach_the_sys_crate",
+            raw_handles_reach_the_sys_crate,
+        ),
+        ("suspended_pages_are_hidden", suspended_pages_are_hidden),
+        ("size_hints_resize_and_reset", size_hints_resize_and_reset),
+        (
+            "positions_are_kept_relative_to_the_window",
+            positions_are_kept_relative_to_the_window,
+        ),
+        ("webviews_follow_their_group", webviews_follow_their_group),
+        ("app_regions_drag_the_window", app_regions_drag_the_window),
+        (
+            "engine_info_describes_the_running_engine",
+            engine_info_describes_the_running_engine,
+        ),
+        ("always_on_top_is_toggled", always_on_top_is_toggled),
+        (
+            "reparented_webviews_keep_their_page",
+            reparented_webviews_keep_their_page,
+        ),
+        ("fill_parent_keeps_the_margin", fill_parent_keeps_the_margin),
+        (
+            "opacity_is_clamped_and_read_back",
+            opacity_is_clamped_and_read_back,
+        ),
+        ("size_and_hint_are_read_back", size_and_hint_are_read_back),
+        (
+            "window_state_changes_are_reported",
+            window_state_changes_are_reported,
+        ),
+        (
+            "webviews_are_moved_between_screens",
+            webviews_are_moved_between_screens,
+        ),
+        (
+            "fades_reach_their_target_opacity",
+            fades_reach_their_target_opacity,
+        ),
+    ]);
+}
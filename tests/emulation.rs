@@ -0,0 +1,93 @@
+use fltk_webview::*;
+use std::sync::{Arc, Mutex};
+
+mod common;
+
+const METRICS_JS: &str = "[innerWidth, innerHeight, devicePixelRatio, screen.width, \
+    navigator.maxTouchPoints > 0, 'ontouchstart' in window, navigator.userAgent]";
+
+fn emulated_devices_are_seen_by_pages() {
+    let agent = Arc::new(Mutex::new(String::new()));
+    let base = common::serve({
+        let agent = agent.clone();
+        move |req| {
+            *agent.lock().unwrap() = req.header("user-agent").unwrap_or_default().to_string();
+            common::Response::html("<p>Device</p>")
+        }
+    });
+    common::with_webview(|wv| {
+        wv.emulate_device(Some(
+            DeviceMetrics::new(375, 667)
+                .with_device_pixel_ratio(2.0)
+                .with_user_agent("TestDevice/1.0")
+                .with_touch(true),
+        ));
+        common::navigate(wv, &format!("{}/", base));
+        assert_eq!(
+            common::eval(wv, METRICS_JS).unwrap(),
+            r#"[375,667,2,375,true,true,"TestDevice/1.0"]"#
+        );
+        // WebView2's user agent isn't changed for requests
+        if !cfg!(target_os = "windows") {
+            assert_eq!(*agent.lock().unwrap(), "TestDevice/1.0");
+        }
+        // Stopping applies to the current page
+        wv.emulate_device(None);
+        assert!(common::wait_js(
+            wv,
+            "innerWidth === 400 && innerHeight === 300 && screen.width !== 375"
+        ));
+    });
+}
+
+fn timezone_and_locale_overrides_apply_to_scripts() {
+    common::with_webview(|wv| {
+        assert!(matches!(
+            wv.override_timezone(Some("not a zone")),
+            Err(WebviewError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            wv.override_locale(Some("de DE")),
+            Err(WebviewError::InvalidArgument(_))
+        ));
+        wv.override_timezone(Some("America/New_York")).unwrap();
+        wv.override_locale(Some("de-DE")).unwrap();
+        common::load_html(wv, "<p>Time</p>");
+        // New York is 5 hours behind UTC in January
+        assert_eq!(
+            common::eval(
+                wv,
+                "var o = Intl.DateTimeFormat().resolvedOptions(); \
+                 var d = new Date(Date.UTC(2024, 0, 15, 12)); \
+                 [o.timeZone, o.locale, d.getTimezoneOffset(), d.getHours(), (1234.5).toLocaleString()]"
+            )
+            .unwrap(),
+            r#"["America/New_York","de-DE",300,7,"1.234,5"]"#
+        );
+        // Stopping applies to the next pages
+        wv.override_timezone(None).unwrap();
+        wv.override_locale(None).unwrap();
+        common::load_html(wv, "<p>Time</p>");
+        assert_eq!(
+            common::eval(
+                wv,
+                "Intl.DateTimeFormat().resolvedOptions().locale === 'de-DE'"
+            )
+            .unwrap(),
+            "false"
+        );
+    });
+}
+
+fn main() {
+    common::run(&[
+        (
+            "emulated_devices_are_seen_by_pages",
+            emulated_devices_are_seen_by_pages,
+        ),
+        (
+            "timezone_and_locale_overrides_apply_to_scripts",
+            timezone_and_locale_overrides_apply_to_scripts,
+        ),
+    ]);
+}
@@ -0,0 +1,34 @@
+use fltk::window;
+use fltk_webview::*;
+
+mod common;
+
+// The data location is chosen by the first webview of the process on WebKitGTK,
+// so this runs in its own test binary
+fn ephemeral_data_is_deleted_on_close() {
+    // The folder of the first ephemeral webview, see `WebviewBuilder::ephemeral`
+    let dir = std::env::temp_dir().join(format!("fltk-webview-{}-0", std::process::id()));
+    let base = common::serve(|_req| common::Response::html("<p>Ephemeral</p>"));
+    let (win, mut wv_win) = common::window();
+    let mut wv = WebviewBuilder::new().ephemeral(true).build(&mut wv_win);
+    common::navigate(&mut wv, &format!("{}/", base));
+    wv.eval("localStorage.setItem('kept', 'yes')");
+    wv.flush_storage();
+    // WKWebView ignores the folder, and WebView2's browser process may still hold it when closed
+    let linux = cfg!(not(any(target_os = "macos", target_os = "windows")));
+    if linux {
+        assert!(dir.is_dir());
+    }
+    wv.close();
+    if linux {
+        assert!(!dir.exists());
+    }
+    window::Window::delete(win);
+}
+
+fn main() {
+    common::run(&[(
+        "ephemeral_data_is_deleted_on_close",
+        ephemeral_data_is_deleted_on_close,
+    )]);
+}
@@ -0,0 +1,77 @@
+use fltk_webview::*;
+use std::{cell::RefCell, rc::Rc};
+
+mod common;
+
+/// The results of `scripts`, as passed to `eval_batch_with_result`
+fn batch(wv: &mut Webview, scripts: &[&str]) -> Vec<Result<String, WebviewError>> {
+    let results = Rc::new(RefCell::new(None));
+    wv.eval_batch_with_result(scripts, {
+        let results = results.clone();
+        move |r| *results.borrow_mut() = Some(r)
+    });
+    assert!(common::wait_until(|| results.borrow().is_some()));
+    results.take().unwrap()
+}
+
+fn batches_run_in_one_evaluation() {
+    common::with_webview(|wv| {
+        common::load_html(wv, "<p>Batch</p>");
+        // A timer set by the first script only runs after the last one, in a later task
+        let mut scripts = vec![
+            "window.order = []; setTimeout(function() { order.push('timer'); }, 0); 'start'"
+                .to_string(),
+        ];
+        scripts
+            .extend((0..100).map(|i| format!("var local = {}; order.push(local); local * 2", i)));
+        let scripts: Vec<&str> = scripts.iter().map(String::as_str).collect();
+        let results = batch(wv, &scripts);
+        assert_eq!(results.len(), 101);
+        assert_eq!(results[0].as_ref().unwrap(), "\"start\"");
+        for (i, result) in results[1..].iter().enumerate() {
+            assert_eq!(result.as_ref().unwrap(), &(i * 2).to_string());
+        }
+        assert!(common::wait_js(wv, "order.length === 101"));
+        assert_eq!(
+            common::eval(wv, "[order[0], order[99], order[100], typeof window.local]").unwrap(),
+            r#"[0,99,"timer","undefined"]"#
+        );
+    });
+}
+
+fn failing_scripts_dont_stop_the_batch() {
+    common::with_webview(|wv| {
+        common::load_html(wv, "<p>Batch</p>");
+        let results = batch(
+            wv,
+            &[
+                "window.before = true",
+                "throw new Error('boom')",
+                "Promise.resolve(42)",
+                "Promise.reject('refused')",
+                "window.after = true",
+            ],
+        );
+        assert_eq!(results[0].as_ref().unwrap(), "true");
+        assert!(matches!(&results[1], Err(WebviewError::Js(e)) if e.contains("boom")));
+        assert_eq!(results[2].as_ref().unwrap(), "42");
+        assert!(matches!(&results[3], Err(WebviewError::Js(e)) if e == "refused"));
+        assert_eq!(results[4].as_ref().unwrap(), "true");
+        // Without results
+        wv.eval_batch(&["throw new Error('boom')", "window.unreported = 1"]);
+        assert!(common::wait_js(wv, "window.unreported === 1"));
+    });
+}
+
+fn main() {
+    common::run(&[
+        (
+            "batches_run_in_one_evaluation",
+            batches_run_in_one_evaluation,
+        ),
+        (
+            "failing_scripts_dont_stop_the_batch",
+            failing_scripts_dont_stop_the_batch,
+        ),
+    ]);
+}
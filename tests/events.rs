@@ -0,0 +1,206 @@
+use fltk::{app, enums::Event, group, prelude::*, window};
+use fltk_webview::*;
+use std::{
+    cell::{Cell, RefCell},
+    path::PathBuf,
+    rc::Rc,
+};
+
+mod common;
+
+fn title_changes_are_reported() {
+    common::with_webview(|wv| {
+        let titles = Rc::new(RefCell::new(vec![]));
+        wv.on_title_changed({
+            let titles = titles.clone();
+            move |title| titles.borrow_mut().push(title.to_string())
+        });
+        common::load_html(wv, "<title>First</title>");
+        assert!(common::wait_until(|| titles
+            .borrow()
+            .last()
+            .is_some_and(|t| t == "First")));
+        wv.eval("document.title = 'Second'");
+        assert!(common::wait_until(|| titles
+            .borrow()
+            .last()
+            .is_some_and(|t| t == "Second")));
+    });
+}
+
+fn progress_reaches_completion() {
+    let base = common::serve(|_req| common::Response::html("<p>Progress</p>"));
+    common::with_webview(|wv| {
+        let progress = Rc::new(RefCell::new(vec![]));
+        wv.on_progress({
+            let progress = progress.clone();
+            move |p| progress.borrow_mut().push(p)
+        });
+        common::navigate(wv, &format!("{}/", base));
+        assert!(common::wait_until(|| progress.borrow().last() == Some(&1.0)));
+        let progress = progress.borrow();
+        assert!(progress.iter().all(|p| (0.0..=1.0).contains(p)));
+    });
+}
+
+fn drops_on_the_window_reach_the_drop_handler() {
+    let (win, mut wv_win) = common::window();
+    let mut wv = Webview::create(false, &mut wv_win);
+    let dropped = Rc::new(RefCell::new(vec![]));
+    wv.set_drop_handler({
+        let dropped = dropped.clone();
+        move |paths| *dropped.borrow_mut() = paths
+    });
+    // fltk delivers drops as paste events of newline-separated paths or file URLs
+    app::copy("file:///tmp/a.txt\n/tmp/b.txt\n");
+    app::paste_text(&wv_win);
+    assert!(common::wait_until(|| !dropped.borrow().is_empty()));
+    assert_eq!(
+        *dropped.borrow(),
+        [PathBuf::from("/tmp/a.txt"), PathBuf::from("/tmp/b.txt")]
+    );
+    wv.close();
+    window::Window::delete(win);
+}
+
+fn events_are_sent_to_receivers() {
+    let base = common::serve(|_req| common::Response::html("<title>Events</title>"));
+    common::with_webview(|wv| {
+        let rx = wv.events();
+        let url = format!("{}/", base);
+        common::navigate(wv, &url);
+        let mut events = vec![];
+        assert!(common::wait_until(|| {
+            events.extend(rx.try_iter());
+            events.contains(&WebviewEvent::TitleChanged("Events".to_string()))
+                && events.contains(&WebviewEvent::Progress(1.0))
+        }));
+        assert!(events.contains(&WebviewEvent::Navigated(url.clone())));
+        // Dropping the receiver stops its events, while new receivers get them
+        drop(rx);
+        let rx = wv.events();
+        wv.eval("document.title = 'Again'");
+        assert!(common::wait_until(|| rx
+            .try_iter()
+            .any(|e| e == WebviewEvent::TitleChanged("Again".to_string()))));
+    });
+}
+
+fn closing_the_top_window_asks_nested_webviews() {
+    let mut top = window::Window::default().with_size(400, 300);
+    let outer = group::Group::default_fill();
+    let inner = group::Group::default_fill();
+    let mut wv_win = window::Window::default_fill();
+    wv_win.end();
+    inner.end();
+    outer.end();
+    top.end();
+    top.show();
+    let mut wv = Webview::create(false, &mut wv_win);
+    let asked = Rc::new(RefCell::new(0));
+    wv.on_close({
+        let asked = asked.clone();
+        move || {
+            *asked.borrow_mut() += 1;
+            // Keeps the window open the first time
+            *asked.borrow() > 1
+        }
+    });
+    app::handle(Event::Close, &top).unwrap();
+    assert_eq!(*asked.borrow(), 1);
+    assert!(top.shown());
+    app::handle(Event::Close, &top).unwrap();
+    assert_eq!(*asked.borrow(), 2);
+    assert!(!top.shown());
+    wv.close();
+    window::Window::delete(top);
+}
+
+// The Fullscreen API needs a user gesture. WebKit gives one to the scripts evaluated by the application,
+// but WebView2's ExecuteScript doesn't, and fltk can't click into its window, so this is skipped on Windows
+fn fullscreen_requests_reach_the_handler() {
+    if cfg!(target_os = "windows") {
+        return;
+    }
+    common::with_webview(|wv| {
+        let requests = Rc::new(RefCell::new(vec![]));
+        let allow = Rc::new(Cell::new(false));
+        wv.on_fullscreen_request({
+            let requests = requests.clone();
+            let allow = allow.clone();
+            move |entering| {
+                requests.borrow_mut().push(entering);
+                allow.get()
+            }
+        });
+        common::load_html(wv, "<video></video>");
+        const ENTER_JS: &str =
+            "document.querySelector('video').requestFullscreen().catch(function() {})";
+        wv.eval(ENTER_JS);
+        assert!(common::wait_until(
+            || requests.borrow().first() == Some(&true)
+        ));
+        assert!(common::wait_js(wv, "!document.fullscreenElement"));
+        allow.set(true);
+        wv.eval(ENTER_JS);
+        assert!(common::wait_js(wv, "!!document.fullscreenElement"));
+        assert_eq!(requests.borrow().last(), Some(&true));
+        wv.eval("document.exitFullscreen()");
+        assert!(common::wait_until(
+            || requests.borrow().last() == Some(&false)
+        ));
+    });
+}
+
+fn before_unload_handler_decides_closing() {
+    let (win, mut wv_win) = common::window();
+    let mut wv = Webview::create(false, &mut wv_win);
+    let asked = Rc::new(RefCell::new(vec![]));
+    let leave = Rc::new(Cell::new(false));
+    wv.set_before_unload_handler({
+        let asked = asked.clone();
+        let leave = leave.clone();
+        move |msg| {
+            asked.borrow_mut().push(msg.to_string());
+            leave.get()
+        }
+    });
+    common::load_html(
+        &mut wv,
+        "<script>window.onbeforeunload = function() { return 'Unsaved changes'; };</script>",
+    );
+    wv.clone().close();
+    assert_eq!(*asked.borrow(), ["Unsaved changes"]);
+    assert!(wv.is_running());
+    assert_eq!(common::eval(&mut wv, "1 + 1").unwrap(), "2");
+    // Closed once the handler lets the page go
+    leave.set(true);
+    wv.clone().close();
+    assert_eq!(asked.borrow().len(), 2);
+    assert!(!wv.is_running());
+    window::Window::delete(win);
+}
+
+fn main() {
+    common::run(&[
+        ("title_changes_are_reported", title_changes_are_reported),
+        ("progress_reaches_completion", progress_reaches_completion),
+        (
+            "drops_on_the_window_reach_the_drop_handler",
+            drops_on_the_window_reach_the_drop_handler,
+        ),
+        ("events_are_sent_to_receivers", events_are_sent_to_receivers),
+        (
+            "closing_the_top_window_asks_nested_webviews",
+            closing_the_top_window_asks_nested_webviews,
+        ),
+        (
+            "fullscreen_requests_reach_the_handler",
+            fullscreen_requests_reach_the_handler,
+        ),
+        (
+            "before_unload_handler_decides_closing",
+            before_unload_handler_decides_closing,
+        ),
+    ]);
+}
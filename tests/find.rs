@@ -0,0 +1,29 @@
+use fltk_webview::*;
+use std::{cell::Cell, rc::Rc};
+
+mod common;
+
+fn find_counts_matches() {
+    common::with_webview(|wv| {
+        let count = Rc::new(Cell::new(None));
+        wv.on_find_count({
+            let count = count.clone();
+            move |n| count.set(Some(n))
+        });
+        common::load_html(wv, "<p>apple Apple apple pear</p>");
+        wv.find("apple", FindOptions::default());
+        assert!(common::wait_until(|| count.get() == Some(3)));
+        count.set(None);
+        let options = FindOptions {
+            case_sensitive: true,
+            wrap_around: true,
+        };
+        wv.find("Apple", options);
+        assert!(common::wait_until(|| count.get() == Some(1)));
+        wv.find_clear();
+    });
+}
+
+fn main() {
+    common::run(&[("find_counts_matches", find_counts_matches)]);
+}
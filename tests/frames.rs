@@ -0,0 +1,33 @@
+use fltk_webview::*;
+
+mod common;
+
+fn eval_in_frame_targets_subframes() {
+    let base = common::serve(|_req| {
+        common::Response::html(
+            "<iframe name=first srcdoc='<p>First</p>'></iframe>\
+             <iframe name=second srcdoc='<p>Second</p>'></iframe>",
+        )
+    });
+    common::with_webview(|wv| {
+        common::navigate(wv, &format!("{}/", base));
+        assert!(common::wait_js(
+            wv,
+            "Array.from(frames).every(function(f) { return f.document.readyState === 'complete'; })"
+        ));
+        wv.eval_in_frame("second", "window.ran = document.body.textContent;");
+        wv.eval_in_frame("0", "window.ran = document.body.textContent;");
+        assert!(common::wait_js(wv, "frames[0].ran && frames[1].ran"));
+        assert_eq!(
+            common::eval(wv, "[frames[0].ran, frames[1].ran, typeof window.ran]").unwrap(),
+            r#"["First","Second","undefined"]"#
+        );
+    });
+}
+
+fn main() {
+    common::run(&[(
+        "eval_in_frame_targets_subframes",
+        eval_in_frame_targets_subframes,
+    )]);
+}
@@ -0,0 +1,91 @@
+use fltk::{app, window};
+use fltk_webview::*;
+use std::{
+    ffi::CStr,
+    os::raw::{c_char, c_int},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+mod common;
+
+extern "C" {
+    fn setlocale(category: c_int, locale: *const c_char) -> *const c_char;
+}
+
+/// `LC_ALL` of glibc
+const LC_ALL: c_int = 6;
+
+fn locale() -> String {
+    unsafe { CStr::from_ptr(setlocale(LC_ALL, std::ptr::null())) }
+        .to_string_lossy()
+        .into_owned()
+}
+
+// GTK is initialized by the first webview of the process, so this runs in its own test binary
+fn gtk_init_keeps_the_application_locale() {
+    if !cfg!(target_os = "linux") {
+        return;
+    }
+    // gtk_init would switch to the locale of the environment
+    std::env::set_var("LC_ALL", "C.UTF-8");
+    for _ in 0..2 {
+        let (win, mut wv_win) = common::window();
+        // fltk sets the locale of characters when it opens the display
+        let before = locale();
+        let mut wv = Webview::create(false, &mut wv_win);
+        common::load_html(&mut wv, "<p>GTK</p>");
+        wv.close();
+        window::Window::delete(win);
+        assert_eq!(locale(), before);
+    }
+}
+
+fn gtk_pump_stops_with_the_last_webview() {
+    if !cfg!(target_os = "linux") {
+        return;
+    }
+    // Whether the event loop stays asleep, which it doesn't while GTK is pumped
+    let sleeps = || {
+        let start = Instant::now();
+        let _ = app::wait_for(0.2);
+        start.elapsed() >= Duration::from_millis(150)
+    };
+    let (win, mut wv_win) = common::window();
+    let mut wv = Webview::create(false, &mut wv_win);
+    common::load_html(&mut wv, "<p>Pumped</p>");
+    assert!(!sleeps());
+    wv.clone().close();
+    assert!(common::wait_until(sleeps));
+    // Restarted for work scheduled on GTK's main context, and for new webviews
+    let ran = Arc::new(AtomicBool::new(false));
+    std::thread::spawn({
+        let wv = wv.clone();
+        let ran = ran.clone();
+        move || wv.run_on_main(move || ran.store(true, Ordering::SeqCst))
+    })
+    .join()
+    .unwrap();
+    assert!(common::wait_until(|| ran.load(Ordering::SeqCst)));
+    assert!(common::wait_until(sleeps));
+    let mut wv = Webview::create(false, &mut wv_win);
+    common::load_html(&mut wv, "<p>Pumped again</p>");
+    wv.close();
+    window::Window::delete(win);
+}
+
+fn main() {
+    common::run(&[
+        (
+            "gtk_init_keeps_the_application_locale",
+            gtk_init_keeps_the_application_locale,
+        ),
+        (
+            "gtk_pump_stops_with_the_last_webview",
+            gtk_pump_stops_with_the_last_webview,
+        ),
+    ]);
+}
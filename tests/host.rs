@@ -0,0 +1,117 @@
+use fltk::window;
+use fltk_webview::*;
+use std::{
+    fs,
+    io::{Read, Write},
+    net::TcpStream,
+    path::PathBuf,
+};
+
+mod common;
+
+/// A folder with an `index.html` loading `app.js`, removed by the caller
+fn site_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("fltk-webview-{}-{}", name, std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join("index.html"),
+        "<title>Index</title><script src=/app.js></script>",
+    )
+    .unwrap();
+    fs::write(dir.join("app.js"), "window.appLoaded = true;").unwrap();
+    dir
+}
+
+fn virtual_hosts_serve_folders() {
+    let dir = site_dir("host");
+    common::with_webview(|wv| {
+        let result = wv.map_virtual_host("app.test", &dir, HostAccess::Deny);
+        if cfg!(any(target_os = "macos", target_os = "windows")) {
+            return assert!(matches!(result, Err(WebviewError::Unsupported)));
+        }
+        result.unwrap();
+        common::navigate(wv, "https://app.test/");
+        assert_eq!(
+            common::eval(wv, "[document.title, window.appLoaded]").unwrap(),
+            r#"["Index",true]"#
+        );
+        // Routes of single page apps fall back to the index
+        common::navigate(wv, "https://app.test/settings/profile");
+        assert_eq!(
+            common::eval(wv, "[document.title, location.pathname]").unwrap(),
+            r#"["Index","/settings/profile"]"#
+        );
+    });
+    fs::remove_dir_all(dir).unwrap();
+}
+
+fn internal_pages_are_served() {
+    common::with_webview(|wv| {
+        assert!(matches!(
+            wv.register_internal_page("bad/name", "<p>Bad</p>"),
+            Err(WebviewError::InvalidArgument(_))
+        ));
+        let result = wv.register_internal_page("help", "<title>Old</title>");
+        if cfg!(any(target_os = "macos", target_os = "windows")) {
+            return assert!(matches!(result, Err(WebviewError::Unsupported)));
+        }
+        result.unwrap();
+        // Registering again replaces the page, and names are case insensitive
+        wv.register_internal_page("Help", "<title>Help</title><p>Contents</p>")
+            .unwrap();
+        common::navigate(wv, "internal://help");
+        assert_eq!(
+            common::eval(wv, "[document.title, document.body.innerText]").unwrap(),
+            r#"["Help","Contents"]"#
+        );
+    });
+}
+
+/// The status line answered to a GET request of `/` naming `host`
+fn status_line(addr: &str, host: &str) -> String {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(stream, "GET / HTTP/1.1\r\nHost: {}\r\n\r\n", host).unwrap();
+    let mut response = String::new();
+    let _ = stream.read_to_string(&mut response);
+    response.lines().next().unwrap_or_default().to_string()
+}
+
+fn served_folders_are_loaded_over_http() {
+    let dir = site_dir("serve");
+    let (win, mut wv_win) = common::window();
+    let mut wv = Webview::create(false, &mut wv_win);
+    assert!(matches!(
+        wv.serve_dir(&dir.join("index.html")),
+        Err(WebviewError::InvalidArgument(_))
+    ));
+    let url = wv.serve_dir(&dir).unwrap();
+    assert!(url.starts_with("http://127.0.0.1:") && url.ends_with('/'));
+    common::navigate(&mut wv, &url);
+    assert_eq!(
+        common::eval(&mut wv, "[document.title, window.appLoaded, location.href]").unwrap(),
+        format!(r#"["Index",true,"{}"]"#, url)
+    );
+    // Only requests naming the server are answered
+    let addr = url.trim_start_matches("http://").trim_end_matches('/');
+    assert_eq!(status_line(addr, addr), "HTTP/1.1 200 OK");
+    assert_eq!(
+        status_line(addr, "evil.test"),
+        "HTTP/1.1 421 Misdirected Request"
+    );
+    // The server stops with the webview
+    wv.close();
+    window::Window::delete(win);
+    assert!(common::wait_until(|| TcpStream::connect(addr).is_err()));
+    fs::remove_dir_all(dir).unwrap();
+}
+
+fn main() {
+    common::run(&[
+        ("virtual_hosts_serve_folders", virtual_hosts_serve_folders),
+        ("internal_pages_are_served", internal_pages_are_served),
+        (
+            "served_folders_are_loaded_over_http",
+            served_folders_are_loaded_over_http,
+        ),
+    ]);
+}
@@ -0,0 +1,54 @@
+use fltk_webview::*;
+use std::{cell::RefCell, rc::Rc};
+
+mod common;
+
+fn html_documents_escape_user_text() {
+    common::with_webview(|wv| {
+        const INJECTED: &str = "<img src=x onerror='window.injected = true'>";
+        let saved = Rc::new(RefCell::new(vec![]));
+        wv.bind_result("save", {
+            let saved = saved.clone();
+            move |req| {
+                saved.borrow_mut().push(req.to_string());
+                Ok("null".to_string())
+            }
+        });
+        common::mark_page(wv);
+        wv.show_html(
+            Html::new()
+                .title("<b>Title</b>")
+                .style("p { color: rgb(1, 2, 3); }")
+                .body("<p id='text'></p><input id='field' value='{name}'>")
+                .text(INJECTED)
+                .body("<button data-bind='save' data-value='{name}'>Save</button>")
+                .var("name", "\"quoted\" & <tagged>")
+                .script("</script><script>window.injected = true;</script>")
+                .with_bindings(&["save"]),
+        );
+        common::wait_loaded(wv);
+        assert_eq!(
+            common::eval(
+                wv,
+                "[document.title, document.body.textContent.indexOf('<img') >= 0, \
+                 document.querySelectorAll('img').length, field.value, !!window.injected]"
+            )
+            .unwrap(),
+            r#"["<b>Title</b>",true,0,"\"quoted\" & <tagged>",false]"#
+        );
+        assert_eq!(
+            common::eval(wv, "getComputedStyle(text).color").unwrap(),
+            "\"rgb(1, 2, 3)\""
+        );
+        wv.click("button");
+        assert!(common::wait_until(|| !saved.borrow().is_empty()));
+        assert_eq!(saved.borrow()[0], r#"["\"quoted\" & <tagged>"]"#);
+    });
+}
+
+fn main() {
+    common::run(&[(
+        "html_documents_escape_user_text",
+        html_documents_escape_user_text,
+    )]);
+}
@@ -0,0 +1,34 @@
+use fltk_webview::*;
+use std::{cell::Cell, rc::Rc};
+
+mod common;
+
+fn later_instances_activate_the_first() {
+    let id = format!("fltk-webview-test-{}", std::process::id());
+    assert!(matches!(
+        SingleInstance::acquire("not/a/name"),
+        Err(WebviewError::InvalidArgument(_))
+    ));
+    let mut first = SingleInstance::acquire(&id).unwrap().unwrap();
+    let activations = Rc::new(Cell::new(0));
+    first.on_activate({
+        let activations = activations.clone();
+        move || activations.set(activations.get() + 1)
+    });
+    // In the same process, since the claim and the activation go through the same channels
+    assert!(SingleInstance::acquire(&id).unwrap().is_none());
+    assert!(common::wait_until(|| activations.get() == 1));
+    assert!(SingleInstance::acquire(&id).unwrap().is_none());
+    assert!(common::wait_until(|| activations.get() == 2));
+    // Released when dropped
+    drop(first);
+    let again = SingleInstance::acquire(&id).unwrap();
+    assert!(again.is_some());
+}
+
+fn main() {
+    common::run(&[(
+        "later_instances_activate_the_first",
+        later_instances_activate_the_first,
+    )]);
+}
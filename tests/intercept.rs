@@ -0,0 +1,83 @@
+use fltk_webview::*;
+use std::{cell::RefCell, rc::Rc};
+
+mod common;
+
+fn intercepted_requests_get_synthetic_responses() {
+    let base = common::serve(|req| {
+        common::Response::html(format!("served {}", req.path()))
+            .with_header("Access-Control-Allow-Origin", "*")
+    });
+    common::with_webview(|wv| {
+        let requests = Rc::new(RefCell::new(vec![]));
+        wv.intercept("https://api.example/*", {
+            let requests = requests.clone();
+            move |req| {
+                requests.borrow_mut().push(req.clone());
+                match req.url.as_str() {
+                    "https://api.example/data" => Some(InterceptedResponse::new(
+                        200,
+                        "application/json",
+                        r#"{"name": "fltk"}"#,
+                    )),
+                    _ => Some(InterceptedResponse::new(404, "text/plain", "missing")),
+                }
+            }
+        });
+        // Unanswered requests proceed to the network
+        wv.intercept(&format!("{}/*", base), |_req| None);
+        common::load_html(
+            wv,
+            "<pre id='data'></pre><script>fetch('https://api.example/data')\
+             .then(function(r) { return r.json(); })\
+             .then(function(d) { data.textContent = d.name; });</script>",
+        );
+        assert!(common::wait_js(wv, "data.textContent === 'fltk'"));
+        assert_eq!(
+            common::eval(
+                wv,
+                "fetch('https://api.example/other', { method: 'POST', body: 'sent', \
+                 headers: { 'X-Test': '1' } }).then(function(r) { \
+                 return r.text().then(function(t) { return [r.status, t]; }); })"
+            )
+            .unwrap(),
+            r#"[404,"missing"]"#
+        );
+        let post = requests.borrow()[1].clone();
+        assert_eq!(post.method, "POST");
+        assert_eq!(post.body, b"sent");
+        assert!(post
+            .headers
+            .contains(&("x-test".to_string(), "1".to_string())));
+        // XMLHttpRequest is intercepted too
+        assert_eq!(
+            common::eval(
+                wv,
+                "new Promise(function(resolve) { var x = new XMLHttpRequest(); \
+                 x.open('GET', 'https://api.example/data'); \
+                 x.onload = function() { resolve([x.status, JSON.parse(x.responseText).name]); }; \
+                 x.send(); })"
+            )
+            .unwrap(),
+            r#"[200,"fltk"]"#
+        );
+        assert_eq!(
+            common::eval(
+                wv,
+                &format!(
+                    "fetch('{}/page').then(function(r) {{ return r.text(); }})",
+                    base
+                )
+            )
+            .unwrap(),
+            "\"served /page\""
+        );
+    });
+}
+
+fn main() {
+    common::run(&[(
+        "intercepted_requests_get_synthetic_responses",
+        intercepted_requests_get_synthetic_responses,
+    )]);
+}
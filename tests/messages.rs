@@ -0,0 +1,27 @@
+use fltk_webview::*;
+use std::{cell::RefCell, rc::Rc};
+
+mod common;
+
+fn messages_go_both_ways() {
+    common::with_webview(|wv| {
+        let received = Rc::new(RefCell::new(vec![]));
+        wv.on_message({
+            let received = received.clone();
+            move |msg| received.borrow_mut().push(msg.to_string())
+        });
+        common::load_html(
+            wv,
+            "<script>window.external.receiveMessage = function(msg) { \
+             window.external.sendMessage('echo: ' + msg); };</script>",
+        );
+        wv.eval("window.external.sendMessage(42)");
+        wv.post_message("quoted \"text\"\n");
+        assert!(common::wait_until(|| received.borrow().len() == 2));
+        assert_eq!(*received.borrow(), ["42", "echo: quoted \"text\"\n"]);
+    });
+}
+
+fn main() {
+    common::run(&[("messages_go_both_ways", messages_go_both_ways)]);
+}
@@ -0,0 +1,750 @@
+use fltk_webview::*;
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+mod common;
+
+const TLS_PORT: u16 = 18443;
+
+/// `user:secret`, base64 encoded
+const AUTHORIZATION: &str = "Basic dXNlcjpzZWNyZXQ=";
+
+/// Serve a page protected by Basic authentication
+fn serve_protected() -> String {
+    common::serve(|req| {
+        let (status, body) = if req.header("authorization") == Some(AUTHORIZATION) {
+            ("200 OK", "<title>Protected</title>")
+        } else {
+            ("401 Unauthorized", "<title>Unauthorized</title>")
+        };
+        common::Response::new(status, "text/html", body)
+            .with_header("WWW-Authenticate", "Basic realm=\"fltk-webview\"")
+    })
+}
+
+fn tls_error_handler_accepts_certificates() {
+    let server = match common::tls_server(TLS_PORT) {
+        Some(server) => server,
+        None => return println!("needs openssl, skipped"),
+    };
+    common::with_webview(|wv| {
+        let hosts = Rc::new(RefCell::new(vec![]));
+        let result = wv.set_tls_error_handler({
+            let hosts = hosts.clone();
+            move |host, _cert| {
+                hosts.borrow_mut().push(host.to_string());
+                host == "localhost"
+            }
+        });
+        if cfg!(target_os = "windows") {
+            return assert!(matches!(result, Err(WebviewError::Unsupported)));
+        }
+        result.unwrap();
+        common::navigate(wv, &server.url);
+        assert_eq!(
+            common::eval(wv, "location.href").unwrap(),
+            format!("{:?}", server.url)
+        );
+        assert!(hosts.borrow().contains(&"localhost".to_string()));
+    });
+}
+
+fn tls_error_handler_rejects_certificates() {
+    let server = match common::tls_server(TLS_PORT) {
+        Some(server) => server,
+        None => return println!("needs openssl, skipped"),
+    };
+    common::with_webview(|wv| {
+        if wv.set_tls_error_handler(|_host, _cert| false).is_err() {
+            return;
+        }
+        let events = wv.events();
+        wv.navigate(&server.url);
+        assert!(common::wait_until(|| events
+            .try_iter()
+            .any(|e| matches!(e, WebviewEvent::NavigationFailed(..)))));
+    });
+}
+
+fn auth_handler_supplies_credentials() {
+    let base = serve_protected();
+    common::with_webview(|wv| {
+        let asked = Rc::new(RefCell::new(vec![]));
+        let result = wv.set_auth_handler({
+            let asked = asked.clone();
+            move |host, kind| {
+                asked.borrow_mut().push((host.to_string(), kind));
+                Some(Credentials::new("user", "secret"))
+            }
+        });
+        if cfg!(target_os = "windows") {
+            return assert!(matches!(result, Err(WebviewError::Unsupported)));
+        }
+        result.unwrap();
+        common::navigate(wv, &format!("{}/", base));
+        assert_eq!(common::eval(wv, "document.title").unwrap(), "\"Protected\"");
+        assert_eq!(
+            asked.borrow().first(),
+            Some(&("127.0.0.1".to_string(), AuthKind::Basic))
+        );
+    });
+}
+
+fn auth_handler_cancels() {
+    let base = serve_protected();
+    common::with_webview(|wv| {
+        if wv.set_auth_handler(|_host, _kind| None).is_err() {
+            return;
+        }
+        let events = wv.events();
+        common::mark_page(wv);
+        wv.navigate(&format!("{}/", base));
+        if cfg!(target_os = "macos") {
+            // WKWebView fails the navigation
+            assert!(common::wait_until(|| events
+                .try_iter()
+                .any(|e| matches!(e, WebviewEvent::NavigationFailed(..)))));
+        } else {
+            // WebKitGTK shows the server's 401 page
+            common::wait_loaded(wv);
+            assert_eq!(
+                common::eval(wv, "document.title").unwrap(),
+                "\"Unauthorized\""
+            );
+        }
+    });
+}
+
+fn error_page_replaces_failed_pages() {
+    common::with_webview(|wv| {
+        let result = wv.set_error_page("<title>Failed</title><p id=\"url\">{url}</p>");
+        if cfg!(target_os = "windows") {
+            return assert!(matches!(result, Err(WebviewError::Unsupported)));
+        }
+        result.unwrap();
+        // Nothing listens on port 1
+        wv.navigate("http://127.0.0.1:1/?a=1&b=2");
+        assert!(common::wait_js(wv, "document.title === 'Failed'"));
+        assert_eq!(
+            common::eval(wv, "document.getElementById('url').textContent").unwrap(),
+            "\"http://127.0.0.1:1/?a=1&b=2\""
+        );
+    });
+}
+
+fn on_redirect_reports_server_redirects() {
+    let base = common::serve(|req| match req.path() {
+        "/login" => common::Response::redirect("/auth?state=xyz"),
+        "/auth?state=xyz" => common::Response::redirect("/home"),
+        _ => common::Response::html("<h1>Logged in</h1>"),
+    });
+    common::with_webview(|wv| {
+        let hops = Rc::new(RefCell::new(vec![]));
+        let result = wv.on_redirect({
+            let hops = hops.clone();
+            move |from, to| hops.borrow_mut().push((from.to_string(), to.to_string()))
+        });
+        if cfg!(target_os = "windows") {
+            return assert!(matches!(result, Err(WebviewError::Unsupported)));
+        }
+        result.unwrap();
+        let urls: Vec<String> = ["/login", "/auth?state=xyz", "/home"]
+            .iter()
+            .map(|p| format!("{}{}", base, p))
+            .collect();
+        common::navigate(wv, &urls[0]);
+        assert_eq!(wv.last_redirect_chain(), urls);
+        assert_eq!(
+            *hops.borrow(),
+            vec![
+                (urls[0].clone(), urls[1].clone()),
+                (urls[1].clone(), urls[2].clone())
+            ]
+        );
+    });
+}
+
+/// Serve pages titled with the method, the `X-Test` header and the body of their request
+fn serve_echo() -> String {
+    common::serve(|req| {
+        let title = format!(
+            "{} {} {}",
+            req.method(),
+            req.header("x-test").unwrap_or("-"),
+            String::from_utf8_lossy(&req.body)
+        );
+        common::Response::html(format!("<title>{}</title>", title))
+    })
+}
+
+fn navigate_request_sends_headers_and_bodies() {
+    let base = serve_echo();
+    common::with_webview(|wv| {
+        let get = NavigationRequest::new("GET", &format!("{}/", base)).with_header("X-Test", "1");
+        common::mark_page(wv);
+        let result = wv.navigate_request(get);
+        if cfg!(target_os = "windows") {
+            return assert!(matches!(result, Err(WebviewError::Unsupported)));
+        }
+        result.unwrap();
+        common::wait_loaded(wv);
+        assert_eq!(common::eval(wv, "document.title").unwrap(), "\"GET 1\"");
+
+        let post = NavigationRequest::new("POST", &format!("{}/", base))
+            .with_header("Content-Type", "application/x-www-form-urlencoded")
+            .with_body(b"a=1&b=2");
+        common::mark_page(wv);
+        wv.navigate_request(post).unwrap();
+        common::wait_loaded(wv);
+        assert_eq!(
+            common::eval(wv, "document.title").unwrap(),
+            "\"POST - a=1&b=2\""
+        );
+    });
+}
+
+fn reload_bypass_cache_requests_the_page_again() {
+    let count = Arc::new(AtomicUsize::new(0));
+    let base = common::serve({
+        let count = count.clone();
+        move |_req| {
+            let n = count.fetch_add(1, Ordering::SeqCst) + 1;
+            common::Response::html(format!("<title>{}</title>", n))
+                .with_header("Cache-Control", "max-age=3600")
+        }
+    });
+    common::with_webview(|wv| {
+        common::navigate(wv, &format!("{}/", base));
+        assert_eq!(common::eval(wv, "document.title").unwrap(), "\"1\"");
+        common::mark_page(wv);
+        wv.reload_bypass_cache();
+        common::wait_loaded(wv);
+        assert_eq!(common::eval(wv, "document.title").unwrap(), "\"2\"");
+    });
+}
+
+fn set_html_with_base_resolves_relative_urls() {
+    let base = common::serve(|req| match req.path() {
+        "/dir/style.css" => {
+            common::Response::new("200 OK", "text/css", "p { color: rgb(1, 2, 3); }")
+        }
+        _ => common::Response::new("404 Not Found", "text/plain", "not found"),
+    });
+    common::with_webview(|wv| {
+        let base_url = format!("{}/dir/", base);
+        common::mark_page(wv);
+        wv.set_html_with_base(
+            "<link rel=stylesheet href=style.css><p>Based</p>",
+            &base_url,
+        );
+        common::wait_loaded(wv);
+        assert!(common::wait_js(
+            wv,
+            "getComputedStyle(document.querySelector('p')).color === 'rgb(1, 2, 3)'"
+        ));
+        // WebView2 only gets a `<base>` element, and the page keeps an opaque origin
+        if !cfg!(target_os = "windows") {
+            assert_eq!(
+                common::eval(wv, "location.href").unwrap(),
+                format!("{:?}", base_url)
+            );
+        }
+    });
+}
+
+fn history_state_follows_navigations() {
+    let base =
+        common::serve(|req| common::Response::html(format!("<title>{}</title>", req.path())));
+    common::with_webview(|wv| {
+        common::navigate(wv, &format!("{}/first", base));
+        common::navigate(wv, &format!("{}/second", base));
+        assert!(common::wait_until(|| wv.can_go_back()));
+        assert!(!wv.can_go_forward());
+        common::mark_page(wv);
+        wv.eval("history.back()");
+        common::wait_loaded(wv);
+        assert_eq!(common::eval(wv, "document.title").unwrap(), "\"/first\"");
+        assert!(common::wait_until(|| wv.can_go_forward()));
+    });
+}
+
+fn new_window_handler_decides_popups() {
+    let base =
+        common::serve(|req| common::Response::html(format!("<title>{}</title>", req.path())));
+    common::with_webview(|wv| {
+        let requested = Rc::new(RefCell::new(vec![]));
+        wv.set_new_window_handler({
+            let requested = requested.clone();
+            move |url| {
+                requested.borrow_mut().push(url.to_string());
+                if url.ends_with("/allowed") {
+                    NewWindowDecision::OpenInSame
+                } else {
+                    NewWindowDecision::Deny
+                }
+            }
+        });
+        common::navigate(wv, &format!("{}/opener", base));
+        // Scripts run through eval count as user gestures, so popups aren't blocked
+        wv.eval(&format!("window.open('{}/denied')", base));
+        assert!(common::wait_until(|| requested.borrow().len() == 1));
+        assert_eq!(common::eval(wv, "document.title").unwrap(), "\"/opener\"");
+        common::mark_page(wv);
+        wv.eval(&format!(
+            "var a = document.createElement('a'); a.href = '{}/allowed'; a.target = '_blank'; \
+             document.body.appendChild(a); a.click();",
+            base
+        ));
+        common::wait_loaded(wv);
+        assert_eq!(common::eval(wv, "document.title").unwrap(), "\"/allowed\"");
+        assert_eq!(
+            *requested.borrow(),
+            [format!("{}/denied", base), format!("{}/allowed", base)]
+        );
+    });
+}
+
+fn wait_until_loaded_waits_for_the_page() {
+    let base = common::serve(|req| {
+        if req.path() == "/slow" {
+            std::thread::sleep(Duration::from_secs(2));
+        }
+        common::Response::html("<p>Loaded</p>")
+    });
+    common::with_webview(|wv| {
+        wv.navigate("data:text/html,<p>Data</p>");
+        wv.wait_until_loaded(common::TIMEOUT).unwrap();
+        assert_eq!(
+            common::eval(wv, "[document.readyState, document.body.textContent]").unwrap(),
+            r#"["complete","Data"]"#
+        );
+        // WebView2's load state is reported by the pages, so it only changes
+        // once the next page runs, and the previous state would be seen
+        if cfg!(target_os = "windows") {
+            return;
+        }
+        wv.navigate(&format!("{}/slow", base));
+        assert!(matches!(
+            wv.wait_until_loaded(Duration::from_millis(200)),
+            Err(WebviewError::Timeout)
+        ));
+        wv.wait_until_loaded(common::TIMEOUT).unwrap();
+    });
+}
+
+fn default_headers_are_sent_to_their_origins() {
+    let (allowed, other) = (serve_echo(), serve_echo());
+    common::with_webview(|wv| {
+        let headers = |name: &str| HashMap::from([(name.to_string(), "1".to_string())]);
+        assert!(matches!(
+            wv.set_default_headers(headers("X Test"), &[&allowed]),
+            Err(WebviewError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            wv.set_default_headers(headers("X-Test"), &["file:///"]),
+            Err(WebviewError::InvalidArgument(_))
+        ));
+        let result = wv.set_default_headers(headers("X-Test"), &[&allowed]);
+        if cfg!(target_os = "windows") {
+            return assert!(matches!(result, Err(WebviewError::Unsupported)));
+        }
+        result.unwrap();
+        common::navigate(wv, &format!("{}/", allowed));
+        assert_eq!(common::eval(wv, "document.title").unwrap(), "\"GET 1\"");
+        common::navigate(wv, &format!("{}/", other));
+        assert_eq!(common::eval(wv, "document.title").unwrap(), "\"GET -\"");
+    });
+}
+
+fn hanging_navigations_time_out() {
+    let base = common::serve(|_req| {
+        std::thread::sleep(Duration::from_secs(5));
+        common::Response::html("<p>Late</p>")
+    });
+    common::with_webview(|wv| {
+        let result = wv.set_navigation_timeout(Some(Duration::from_millis(500)));
+        if cfg!(target_os = "windows") {
+            return assert!(matches!(result, Err(WebviewError::Unsupported)));
+        }
+        result.unwrap();
+        let rx = wv.events();
+        let url = format!("{}/", base);
+        let start = Instant::now();
+        wv.navigate(&url);
+        assert!(common::wait_until(|| rx.try_iter().any(|e| e
+            == WebviewEvent::NavigationFailed(
+                url.clone(),
+                "timed out".to_string()
+            ))));
+        assert!(start.elapsed() < Duration::from_secs(3));
+    });
+}
+
+fn last_response_status_reports_http_statuses() {
+    let base = common::serve(|req| match req.path() {
+        "/missing" => common::Response::new("404 Not Found", "text/html", "<p>Missing</p>"),
+        _ => common::Response::html("<p>Found</p>"),
+    });
+    common::with_webview(|wv| {
+        assert_eq!(wv.last_response_status(), None);
+        common::navigate(wv, &format!("{}/missing", base));
+        assert!(common::wait_until(|| wv.last_response_status() == Some(404)));
+        common::navigate(wv, &format!("{}/", base));
+        assert!(common::wait_until(|| wv.last_response_status() == Some(200)));
+        // Pages not loaded over HTTP have none
+        common::load_html(wv, "<p>Local</p>");
+        assert!(common::wait_until(|| wv.last_response_status().is_none()));
+    });
+}
+
+fn minimum_tls_version_keeps_modern_servers_reachable() {
+    let server = match common::tls_server(TLS_PORT) {
+        Some(server) => server,
+        None => return println!("needs openssl, skipped"),
+    };
+    common::with_webview(|wv| {
+        let result = wv.set_minimum_tls_version(TlsVersion::Tls12);
+        if cfg!(target_os = "macos") {
+            return assert!(matches!(result, Err(WebviewError::Unsupported)));
+        }
+        result.unwrap();
+        assert_eq!(
+            wv.set_minimum_tls_version(TlsVersion::Tls13).is_ok(),
+            cfg!(not(target_os = "windows"))
+        );
+        wv.set_minimum_tls_version(TlsVersion::Tls12).unwrap();
+        if wv
+            .set_tls_error_handler(|host, _cert| host == "localhost")
+            .is_err()
+        {
+            return;
+        }
+        // The status page of openssl's server names the negotiated protocol
+        common::navigate(wv, &server.url);
+        assert_eq!(
+            common::eval(wv, "/TLSv1\\.[23]/.test(document.body.textContent)").unwrap(),
+            "true"
+        );
+    });
+}
+
+fn network_idle_waits_for_requests() {
+    let base = common::serve(|req| match req.path() {
+        "/slow" => {
+            std::thread::sleep(Duration::from_secs(1));
+            common::Response::new("200 OK", "text/plain", "late")
+        }
+        "/hang" => {
+            std::thread::sleep(Duration::from_secs(5));
+            common::Response::new("200 OK", "text/plain", "never")
+        }
+        _ => common::Response::html(
+            "<script>setTimeout(function() { \
+             var xhr = new XMLHttpRequest(); \
+             xhr.onload = function() { window.done = xhr.responseText; }; \
+             xhr.open('GET', location.hash === '#hang' ? '/hang' : '/slow'); xhr.send(); \
+             }, 200);</script>",
+        ),
+    });
+    common::with_webview(|wv| {
+        // Tracking starts with the first call
+        wv.wait_for_network_idle(0, common::TIMEOUT).unwrap();
+        common::navigate(wv, &format!("{}/", base));
+        wv.wait_for_network_idle(500, common::TIMEOUT).unwrap();
+        assert_eq!(common::eval(wv, "window.done").unwrap(), "\"late\"");
+        common::navigate(wv, &format!("{}/other#hang", base));
+        assert!(matches!(
+            wv.wait_for_network_idle(500, Duration::from_secs(2)),
+            Err(WebviewError::Timeout)
+        ));
+    });
+}
+
+fn navigate_with_options_replaces_and_revalidates() {
+    let cache_control = Arc::new(Mutex::new(None));
+    let base = common::serve({
+        let cache_control = cache_control.clone();
+        move |req| {
+            if req.path() == "/fresh" {
+                *cache_control.lock().unwrap() = req.header("cache-control").map(str::to_string);
+            }
+            common::Response::html(format!("<title>{}</title>", req.path()))
+        }
+    });
+    common::with_webview(|wv| {
+        common::navigate(wv, &format!("{}/first", base));
+        let length = common::eval(wv, "history.length").unwrap();
+        let could_go_back = wv.can_go_back();
+        common::mark_page(wv);
+        wv.navigate_with_options(
+            &format!("{}/second", base),
+            NavigateOptions {
+                replace_current_entry: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        common::wait_loaded(wv);
+        assert_eq!(common::eval(wv, "document.title").unwrap(), "\"/second\"");
+        assert_eq!(common::eval(wv, "history.length").unwrap(), length);
+        assert_eq!(wv.can_go_back(), could_go_back);
+        // Pages can't send headers
+        assert!(matches!(
+            wv.navigate_with_options(
+                &format!("{}/fresh", base),
+                NavigateOptions {
+                    replace_current_entry: true,
+                    bypass_cache: true,
+                    ..Default::default()
+                },
+            ),
+            Err(WebviewError::Unsupported)
+        ));
+        common::mark_page(wv);
+        let result = wv.navigate_with_options(
+            &format!("{}/fresh", base),
+            NavigateOptions {
+                bypass_cache: true,
+                ..Default::default()
+            },
+        );
+        if cfg!(target_os = "windows") {
+            return assert!(matches!(result, Err(WebviewError::Unsupported)));
+        }
+        result.unwrap();
+        common::wait_loaded(wv);
+        assert_eq!(cache_control.lock().unwrap().as_deref(), Some("no-cache"));
+    });
+}
+
+fn insecure_content_of_secure_pages_is_reported() {
+    let server = match common::tls_server(TLS_PORT) {
+        Some(server) => server,
+        None => return println!("needs openssl, skipped"),
+    };
+    let base =
+        common::serve(|_req| common::Response::new("200 OK", "image/png", common::PIXEL_PNG));
+    let image = format!("{}/pixel.png", base);
+    let add_image = format!(
+        "var img = document.createElement('img'); img.src = '{}'; document.body.appendChild(img)",
+        image
+    );
+    common::with_webview(|wv| {
+        if wv
+            .set_tls_error_handler(|host, _cert| host == "localhost")
+            .is_err()
+        {
+            // Windows, where the self-signed page can't be accepted
+            return;
+        }
+        let reported = Rc::new(RefCell::new(vec![]));
+        wv.on_insecure_content({
+            let reported = reported.clone();
+            move |url| reported.borrow_mut().push(url.to_string())
+        });
+        // Insecure pages don't report
+        common::load_html(wv, "<p>Insecure</p>");
+        wv.eval(&add_image);
+        assert!(common::wait_js(
+            wv,
+            "document.querySelector('img').complete"
+        ));
+        common::navigate(wv, &server.url);
+        assert!(reported.borrow().is_empty());
+        wv.eval(&add_image);
+        assert!(common::wait_until(|| reported.borrow().contains(&image)));
+        // Blocked insecure images are still reported
+        wv.set_mixed_content_policy(MixedContentPolicy::Block);
+        common::navigate(wv, &server.url);
+        wv.eval(&add_image);
+        assert!(common::wait_until(|| reported.borrow().len() == 2));
+        assert_eq!(reported.borrow()[1], image);
+        assert!(common::wait_js(
+            wv,
+            "var img = document.querySelector('img'); img.complete && img.naturalWidth === 0"
+        ));
+    });
+}
+
+fn referrers_are_sent_and_limited() {
+    let referrers = Arc::new(Mutex::new(HashMap::new()));
+    let base = common::serve({
+        let referrers = referrers.clone();
+        move |req| {
+            let referrer = req.header("referer").unwrap_or("-").to_string();
+            referrers
+                .lock()
+                .unwrap()
+                .insert(req.path().to_string(), referrer);
+            match req.path() {
+                "/pixel.png" => common::Response::new("200 OK", "image/png", common::PIXEL_PNG)
+                    .with_header("Cache-Control", "no-store"),
+                _ => common::Response::html("<img src='/pixel.png'>"),
+            }
+        }
+    });
+    let referrer_of = |path: &str| referrers.lock().unwrap().get(path).cloned();
+    common::with_webview(|wv| {
+        let page = format!("{}/page", base);
+        // The subresources of pages follow the policy
+        wv.set_referrer_policy(ReferrerPolicy::NoReferrer);
+        common::navigate(wv, &page);
+        assert!(common::wait_until(|| referrer_of("/pixel.png").is_some()));
+        assert_eq!(referrer_of("/pixel.png").unwrap(), "-");
+        referrers.lock().unwrap().clear();
+        wv.set_referrer_policy(ReferrerPolicy::Origin);
+        common::navigate(wv, &format!("{}?origin", page));
+        assert!(common::wait_until(|| referrer_of("/pixel.png").is_some()));
+        assert_eq!(referrer_of("/pixel.png").unwrap(), format!("{}/", base));
+        assert!(matches!(
+            wv.navigate_with_options(
+                &page,
+                NavigateOptions {
+                    referrer: Some("ftp://partner.example/".to_string()),
+                    ..Default::default()
+                },
+            ),
+            Err(WebviewError::InvalidArgument(_))
+        ));
+        common::mark_page(wv);
+        let result = wv.navigate_with_options(
+            &format!("{}/custom", base),
+            NavigateOptions {
+                referrer: Some("https://partner.example/page".to_string()),
+                ..Default::default()
+            },
+        );
+        if cfg!(target_os = "windows") {
+            return assert!(matches!(result, Err(WebviewError::Unsupported)));
+        }
+        result.unwrap();
+        common::wait_loaded(wv);
+        assert_eq!(
+            referrer_of("/custom").as_deref(),
+            Some("https://partner.example/page")
+        );
+    });
+}
+
+fn loading_state_spans_redirects() {
+    let base = common::serve(|req| {
+        // Slow hops, which a flickering state would show between
+        std::thread::sleep(Duration::from_millis(200));
+        match req.path() {
+            "/start" => common::Response::redirect("/middle"),
+            "/middle" => common::Response::redirect("/final"),
+            _ => common::Response::html("<title>Final</title>"),
+        }
+    });
+    common::with_webview(|wv| {
+        common::load_html(wv, "<p>Idle</p>");
+        assert!(!wv.is_loading());
+        let changes = Rc::new(RefCell::new(vec![]));
+        wv.on_loading_changed({
+            let changes = changes.clone();
+            move |loading| changes.borrow_mut().push(loading)
+        });
+        wv.navigate(&format!("{}/start", base));
+        assert!(common::wait_until(|| wv.is_loading()));
+        // Not done between the hops, but once the final document is complete
+        assert!(common::wait_until(|| !wv.is_loading()));
+        assert_eq!(
+            common::eval(wv, "[document.title, document.readyState]").unwrap(),
+            r#"["Final","complete"]"#
+        );
+        assert!(common::wait_until(|| changes.borrow().len() >= 2));
+        assert_eq!(*changes.borrow(), [true, false]);
+    });
+}
+
+fn main() {
+    common::run(&[
+        // WebKitGTK remembers accepted certificates for the process, so rejecting comes first
+        (
+            "tls_error_handler_rejects_certificates",
+            tls_error_handler_rejects_certificates,
+        ),
+        (
+            "tls_error_handler_accepts_certificates",
+            tls_error_handler_accepts_certificates,
+        ),
+        (
+            "auth_handler_supplies_credentials",
+            auth_handler_supplies_credentials,
+        ),
+        ("auth_handler_cancels", auth_handler_cancels),
+        (
+            "error_page_replaces_failed_pages",
+            error_page_replaces_failed_pages,
+        ),
+        (
+            "on_redirect_reports_server_redirects",
+            on_redirect_reports_server_redirects,
+        ),
+        (
+            "navigate_request_sends_headers_and_bodies",
+            navigate_request_sends_headers_and_bodies,
+        ),
+        (
+            "reload_bypass_cache_requests_the_page_again",
+            reload_bypass_cache_requests_the_page_again,
+        ),
+        (
+            "set_html_with_base_resolves_relative_urls",
+            set_html_with_base_resolves_relative_urls,
+        ),
+        (
+            "history_state_follows_navigations",
+            history_state_follows_navigations,
+        ),
+        (
+            "new_window_handler_decides_popups",
+            new_window_handler_decides_popups,
+        ),
+        (
+            "wait_until_loaded_waits_for_the_page",
+            wait_until_loaded_waits_for_the_page,
+        ),
+        (
+            "default_headers_are_sent_to_their_origins",
+            default_headers_are_sent_to_their_origins,
+        ),
+        ("hanging_navigations_time_out", hanging_navigations_time_out),
+        (
+            "last_response_status_reports_http_statuses",
+            last_response_status_reports_http_statuses,
+        ),
+        (
+            "minimum_tls_version_keeps_modern_servers_reachable",
+            minimum_tls_version_keeps_modern_servers_reachable,
+        ),
+        (
+            "network_idle_waits_for_requests",
+            network_idle_waits_for_requests,
+        ),
+        (
+            "navigate_with_options_replaces_and_revalidates",
+            navigate_with_options_replaces_and_revalidates,
+        ),
+        (
+            "insecure_content_of_secure_pages_is_reported",
+            insecure_content_of_secure_pages_is_reported,
+        ),
+        (
+            "referrers_are_sent_and_limited",
+            referrers_are_sent_and_limited,
+        ),
+        (
+            "loading_state_spans_redirects",
+            loading_state_spans_redirects,
+        ),
+    ]);
+}
@@ -0,0 +1,310 @@
+use fltk_webview::*;
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+mod common;
+
+fn get_html_reflects_script_changes() {
+    common::with_webview(|wv| {
+        common::load_html(wv, "<p id=p>Loaded</p>");
+        wv.eval("document.getElementById('p').textContent = 'Changed \"quoted\"'");
+        let html = Rc::new(RefCell::new(None));
+        wv.get_html({
+            let html = html.clone();
+            move |h| *html.borrow_mut() = Some(h)
+        });
+        assert!(common::wait_until(|| html.borrow().is_some()));
+        let html = html.take().unwrap();
+        assert!(html.starts_with("<html>"), "{}", html);
+        assert!(
+            html.contains(r#"<p id="p">Changed "quoted"</p>"#),
+            "{}",
+            html
+        );
+    });
+}
+
+/// The favicon of the current page, once `favicon` passes one
+fn wait_favicon(wv: &mut Webview) -> Option<Vec<u8>> {
+    let mut icon = None;
+    common::wait_until(|| {
+        let result = Rc::new(RefCell::new(None));
+        wv.favicon({
+            let result = result.clone();
+            move |icon| *result.borrow_mut() = Some(icon)
+        });
+        // Engines record favicons after the page loaded, so they're asked again until then
+        common::wait_until(|| result.borrow().is_some());
+        icon = result.take().flatten();
+        icon.is_some()
+    });
+    icon
+}
+
+fn favicon_is_passed_as_png() {
+    let base = common::serve(|req| match req.path() {
+        "/icon.png" => common::Response::new("200 OK", "image/png", common::PIXEL_PNG),
+        _ => common::Response::html("<link rel=icon href=/icon.png><p>Icon</p>"),
+    });
+    common::with_webview(|wv| {
+        common::load_html(wv, "<p>No icon</p>");
+        let none = Rc::new(RefCell::new(None));
+        wv.favicon({
+            let none = none.clone();
+            move |icon| *none.borrow_mut() = Some(icon)
+        });
+        assert!(common::wait_until(|| none.borrow().is_some()));
+        assert_eq!(none.take(), Some(None));
+        common::navigate(wv, &format!("{}/", base));
+        let icon = wait_favicon(wv).expect("no favicon");
+        assert!(icon.starts_with(b"\x89PNG\r\n\x1a\n"));
+    });
+}
+
+fn wait_for_selector_waits_for_elements() {
+    common::with_webview(|wv| {
+        common::load_html(
+            wv,
+            "<script>setTimeout(function() { \
+             var p = document.createElement('p'); p.id = 'late'; document.body.appendChild(p); \
+             }, 500);</script>",
+        );
+        assert!(wv.wait_for_selector("#late", common::TIMEOUT).unwrap());
+        assert!(!wv
+            .wait_for_selector("#never", Duration::from_millis(200))
+            .unwrap());
+        // Selectors are passed as strings, not evaluated
+        assert!(matches!(
+            wv.wait_for_selector("')); window.injected = true; (('", common::TIMEOUT),
+            Err(WebviewError::Js(_))
+        ));
+        assert_eq!(
+            common::eval(wv, "typeof window.injected").unwrap(),
+            "\"undefined\""
+        );
+    });
+}
+
+const ARTICLE_HTML: &str = "<title>Article</title>\
+    <nav class='menu'><a href='#'>Home</a> <a href='#'>About</a></nav>\
+    <article><p>The first paragraph of the article, long enough to count, with commas, and more.</p>\
+    <p>The second paragraph of the article, also long enough to count as content.</p></article>\
+    <div class='sidebar'><p>Related links, which shouldn't be part of the content at all.</p></div>";
+
+fn reader_mode_shows_the_main_content() {
+    common::with_webview(|wv| {
+        common::load_html(wv, ARTICLE_HTML);
+        wv.set_reader_mode(true);
+        assert!(common::wait_js(
+            wv,
+            "!!document.querySelector('.reader-content')"
+        ));
+        assert_eq!(
+            common::eval(
+                wv,
+                "var c = document.querySelector('.reader-content'); \
+                 [c.querySelector('h1').textContent, c.querySelectorAll('p').length, \
+                 getComputedStyle(document.querySelector('nav')).display]"
+            )
+            .unwrap(),
+            r#"["Article",2,"none"]"#
+        );
+        // Applied to the next pages, until disabled
+        common::load_html(wv, ARTICLE_HTML);
+        assert!(common::wait_js(
+            wv,
+            "!!document.querySelector('.reader-content')"
+        ));
+        wv.set_reader_mode(false);
+        assert!(common::wait_js(
+            wv,
+            "!document.querySelector('.reader-content') \
+             && getComputedStyle(document.querySelector('nav')).display !== 'none'"
+        ));
+    });
+}
+
+fn text_zoom_scales_only_text() {
+    common::with_webview(|wv| {
+        const SIZES_JS: &str = "[document.querySelector('span').getBoundingClientRect().height, \
+                                document.querySelector('canvas').getBoundingClientRect().width]";
+        common::load_html(
+            wv,
+            "<p style='font-size: 16px'><span>Text</span></p><canvas width='50' height='50'></canvas>",
+        );
+        let sizes = common::eval(wv, SIZES_JS).unwrap();
+        wv.set_text_zoom(2.0);
+        // Measured as laid out, since native text zoom leaves the computed styles as they are
+        assert!(common::wait_js(
+            wv,
+            &format!(
+                "var s = {}, before = {}; s[0] >= before[0] * 1.5 && s[1] === before[1]",
+                SIZES_JS, sizes
+            )
+        ));
+        wv.set_text_zoom(1.0);
+        assert!(common::wait_js(
+            wv,
+            &format!("JSON.stringify({}) === '{}'", SIZES_JS, sizes)
+        ));
+    });
+}
+
+fn disabled_selection_clears_selections() {
+    common::with_webview(|wv| {
+        const SELECT_JS: &str = "getSelection().selectAllChildren(document.querySelector('p'))";
+        wv.set_selection_enabled(false);
+        common::load_html(wv, "<p>Protected text</p><input value='Editable'>");
+        wv.eval(SELECT_JS);
+        assert!(common::wait_js(wv, "getSelection().toString() === ''"));
+        assert_eq!(
+            common::eval(
+                wv,
+                "var e = new Event('copy', { bubbles: true, cancelable: true }); \
+                 document.querySelector('p').dispatchEvent(e); e.defaultPrevented"
+            )
+            .unwrap(),
+            "true"
+        );
+        // Form fields stay selectable
+        assert_eq!(
+            common::eval(
+                wv,
+                "var i = document.querySelector('input'); i.select(); i.selectionEnd - i.selectionStart"
+            )
+            .unwrap(),
+            "8"
+        );
+        wv.set_selection_enabled(true);
+        assert!(common::wait_js(
+            wv,
+            &format!(
+                "{}; getSelection().toString() === 'Protected text'",
+                SELECT_JS
+            )
+        ));
+    });
+}
+
+/// The selected text, as passed to `selected_text`
+fn selected_text(wv: &mut Webview) -> String {
+    let text = Rc::new(RefCell::new(None));
+    wv.selected_text({
+        let text = text.clone();
+        move |t| *text.borrow_mut() = Some(t)
+    });
+    assert!(common::wait_until(|| text.borrow().is_some()));
+    text.take().unwrap()
+}
+
+fn selected_text_reads_the_selection() {
+    common::with_webview(|wv| {
+        common::load_html(
+            wv,
+            "<p>Some <b>selected</b> text</p><input value='Field value'>\
+             <iframe srcdoc='<p>Framed text</p>'></iframe>",
+        );
+        assert_eq!(selected_text(wv), "");
+        wv.eval("getSelection().selectAllChildren(document.querySelector('p'))");
+        assert!(common::wait_js(wv, "getSelection().toString() !== ''"));
+        assert_eq!(selected_text(wv), "Some selected text");
+        // The selection of a focused field
+        wv.eval("var i = document.querySelector('input'); i.focus(); i.setSelectionRange(6, 11)");
+        assert!(common::wait_js(
+            wv,
+            "document.activeElement.selectionStart === 6"
+        ));
+        assert_eq!(selected_text(wv), "value");
+        // and of a focused frame of the same origin
+        assert!(common::wait_js(
+            wv,
+            "var d = frames[0].document; !!d.querySelector('p') && \
+             (frames[0].focus(), d.getSelection().selectAllChildren(d.querySelector('p')), \
+             document.activeElement.tagName === 'IFRAME')"
+        ));
+        assert_eq!(selected_text(wv), "Framed text");
+    });
+}
+
+fn saved_pages_inline_their_assets() {
+    let base = common::serve(|req| match req.path() {
+        "/pixel.png" => common::Response::new("200 OK", "image/png", common::PIXEL_PNG),
+        "/style.css" => common::Response::new("200 OK", "text/css", "p { color: rgb(1, 2, 3); }"),
+        _ => common::Response::html(
+            "<link rel='stylesheet' href='/style.css'><p>Saved</p><img src='/pixel.png'>\
+             <input><script>window.ran = true;</script>",
+        ),
+    });
+    let path = std::env::temp_dir().join(format!("fltk-webview-save-{}.html", std::process::id()));
+    common::with_webview(|wv| {
+        common::navigate(wv, &format!("{}/", base));
+        assert!(common::wait_js(
+            wv,
+            "document.querySelector('img').complete"
+        ));
+        wv.eval("document.querySelector('input').value = 'typed'");
+        let saved = Rc::new(RefCell::new(None));
+        wv.save_complete(&path, {
+            let saved = saved.clone();
+            move |res| *saved.borrow_mut() = Some(res)
+        });
+        assert!(common::wait_until(|| saved.borrow().is_some()));
+        saved.take().unwrap().unwrap();
+    });
+    let html = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(html.contains("data:image/png;base64,iVBORw0KGgo"));
+    assert!(html.contains("rgb(1, 2, 3)"));
+    assert!(!html.contains("/style.css") && !html.contains("/pixel.png"));
+    assert!(html.contains("typed"));
+    assert!(!html.contains("window.ran"));
+}
+
+fn set_cursor_overrides_page_cursors() {
+    common::with_webview(|wv| {
+        const CURSOR_JS: &str = "getComputedStyle(document.querySelector('a')).cursor";
+        common::load_html(wv, "<a href='#' style='cursor: pointer'>Link</a>");
+        wv.set_cursor(fltk::enums::Cursor::Wait);
+        assert!(common::wait_js(wv, &format!("{} === 'wait'", CURSOR_JS)));
+        // Replaced, and kept on the next pages
+        wv.set_cursor(fltk::enums::Cursor::Help);
+        common::load_html(wv, "<a href='#' style='cursor: pointer'>Link</a>");
+        assert_eq!(common::eval(wv, CURSOR_JS).unwrap(), "\"help\"");
+        wv.reset_cursor();
+        assert!(common::wait_js(wv, &format!("{} === 'pointer'", CURSOR_JS)));
+    });
+}
+
+fn main() {
+    common::run(&[
+        (
+            "get_html_reflects_script_changes",
+            get_html_reflects_script_changes,
+        ),
+        ("favicon_is_passed_as_png", favicon_is_passed_as_png),
+        (
+            "wait_for_selector_waits_for_elements",
+            wait_for_selector_waits_for_elements,
+        ),
+        (
+            "reader_mode_shows_the_main_content",
+            reader_mode_shows_the_main_content,
+        ),
+        ("text_zoom_scales_only_text", text_zoom_scales_only_text),
+        (
+            "disabled_selection_clears_selections",
+            disabled_selection_clears_selections,
+        ),
+        (
+            "selected_text_reads_the_selection",
+            selected_text_reads_the_selection,
+        ),
+        (
+            "saved_pages_inline_their_assets",
+            saved_pages_inline_their_assets,
+        ),
+        (
+            "set_cursor_overrides_page_cursors",
+            set_cursor_overrides_page_cursors,
+        ),
+    ]);
+}
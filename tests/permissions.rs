@@ -0,0 +1,172 @@
+use fltk_webview::*;
+use std::{cell::RefCell, rc::Rc};
+
+mod common;
+
+fn permission_handler_decides_requests() {
+    common::with_webview(|wv| {
+        let asked = Rc::new(RefCell::new(vec![]));
+        let result = wv.set_permission_handler({
+            let asked = asked.clone();
+            move |kind, _origin| {
+                asked.borrow_mut().push(kind);
+                false
+            }
+        });
+        if cfg!(target_os = "windows") {
+            return assert!(matches!(result, Err(WebviewError::Unsupported)));
+        }
+        result.unwrap();
+        // WKWebView only asks about the camera and the microphone,
+        // which would need the usage descriptions of an application bundle
+        if cfg!(target_os = "macos") {
+            return;
+        }
+        load_secure_page(wv);
+        assert_eq!(
+            common::eval(wv, "Notification.requestPermission()").unwrap(),
+            "\"denied\""
+        );
+        assert_eq!(*asked.borrow(), vec![PermissionKind::Notifications]);
+    });
+}
+
+/// Load a page in a secure context, which permissions need
+fn load_secure_page(wv: &mut Webview) {
+    common::mark_page(wv);
+    wv.set_html_with_base("<p>Permissions</p>", "https://localhost/");
+    common::wait_loaded(wv);
+}
+
+fn muting_silences_page_media() {
+    common::with_webview(|wv| {
+        const MUTED_JS: &str = "document.querySelector('audio').muted";
+        common::load_html(wv, "<audio src=\"data:audio/wav;base64,\"></audio>");
+        assert!(!wv.is_muted());
+        wv.set_muted(true);
+        assert!(wv.is_muted());
+        // WebView2 mutes the media elements of the page, and of the next ones when they play
+        if cfg!(target_os = "windows") {
+            assert!(common::wait_js(wv, MUTED_JS));
+            common::load_html(wv, "<audio src=\"data:audio/wav;base64,\"></audio>");
+            wv.eval("document.querySelector('audio').dispatchEvent(new Event('play'))");
+            assert!(common::wait_js(wv, MUTED_JS));
+        }
+        wv.set_muted(false);
+        assert!(!wv.is_muted());
+        if cfg!(target_os = "windows") {
+            assert!(common::wait_js(wv, &format!("!{}", MUTED_JS)));
+        }
+    });
+}
+
+/// Two media elements, the first one playing, whose playback is simulated,
+/// since the engines may lack the codecs and the audio output to play anything
+const MEDIA_HTML: &str = "<audio id='playing'></audio><video id='stopped'></video><script>\
+    Array.prototype.forEach.call(document.querySelectorAll('audio, video'), function(m) {\
+        var paused = m.id !== 'playing';\
+        Object.defineProperty(m, 'paused', { get: function() { return paused; } });\
+        m.pause = function() { paused = true; };\
+        m.play = function() { paused = false; return Promise.resolve(); };\
+    });</script>";
+
+const PAUSED_JS: &str = "[playing.paused, stopped.paused]";
+
+fn media_is_paused_and_resumed() {
+    common::with_webview(|wv| {
+        common::load_html(wv, MEDIA_HTML);
+        wv.pause_media();
+        assert!(common::wait_js(wv, "playing.paused"));
+        // Only the paused media is resumed
+        wv.resume_media();
+        assert!(common::wait_js(wv, "!playing.paused"));
+        assert_eq!(common::eval(wv, PAUSED_JS).unwrap(), "[false,true]");
+    });
+}
+
+/// Settles the promise of a clipboard operation, to `"ok"` or the name of its error
+fn clipboard_js(call: &str) -> String {
+    format!(
+        "navigator.clipboard.{}.then(function() {{ return 'ok'; }}, function(e) {{ return e.name; }})",
+        call
+    )
+}
+
+fn clipboard_access_gates_page_operations() {
+    common::with_webview(|wv| {
+        wv.set_clipboard_access(ClipboardAccess::AllowWrite);
+        load_secure_page(wv);
+        assert_eq!(
+            common::eval(wv, &clipboard_js("readText()")).unwrap(),
+            "\"NotAllowedError\""
+        );
+        // The other engines still require a user gesture for allowed operations
+        if cfg!(not(any(target_os = "macos", target_os = "windows"))) {
+            assert_eq!(
+                common::eval(wv, &clipboard_js("writeText('copied')")).unwrap(),
+                "\"ok\""
+            );
+        }
+        // Applied to the current page too
+        wv.set_clipboard_access(ClipboardAccess::Denied);
+        assert!(common::wait_js(
+            wv,
+            &format!(
+                "{}.then(function(r) {{ return r === 'NotAllowedError'; }})",
+                clipboard_js("writeText('copied')")
+            )
+        ));
+        assert_eq!(
+            common::eval(wv, "document.execCommand('copy')").unwrap(),
+            "false"
+        );
+    });
+}
+
+// Media keys pressed in the page are simulated, those reaching fltk need real key events
+fn media_keys_reach_the_page() {
+    common::with_webview(|wv| {
+        common::load_html(wv, MEDIA_HTML);
+        wv.dispatch_key("MediaPlayPause");
+        assert_eq!(common::eval(wv, PAUSED_JS).unwrap(), "[false,true]");
+        wv.set_media_keys_enabled(true);
+        wv.dispatch_key("MediaPlayPause");
+        assert!(common::wait_js(wv, "playing.paused"));
+        // Media Session handlers take precedence
+        common::load_html(
+            wv,
+            &format!(
+                "{}<script>window.actions = []; if (navigator.mediaSession) \
+                 navigator.mediaSession.setActionHandler('nexttrack', \
+                 function(d) {{ actions.push(d.action); }});</script>",
+                MEDIA_HTML
+            ),
+        );
+        if common::eval(wv, "!!navigator.mediaSession").unwrap() == "true" {
+            wv.dispatch_key("MediaTrackNext");
+            assert!(common::wait_js(wv, "actions.join() === 'nexttrack'"));
+        }
+        let actions = common::eval(wv, "actions.length").unwrap();
+        wv.set_media_keys_enabled(false);
+        wv.dispatch_key("MediaPlayPause");
+        wv.dispatch_key("MediaTrackNext");
+        assert_eq!(common::eval(wv, PAUSED_JS).unwrap(), "[false,true]");
+        assert_eq!(common::eval(wv, "actions.length").unwrap(), actions);
+    });
+}
+
+fn main() {
+    common::run(&[
+        (
+            "permission_handler_decides_requests",
+            permission_handler_decides_requests,
+        ),
+        ("muting_silences_page_media", muting_silences_page_media),
+        ("media_is_paused_and_resumed", media_is_paused_and_resumed),
+        (
+            "clipboard_access_gates_page_operations",
+            clipboard_access_gates_page_operations,
+        ),
+        ("media_keys_reach_the_page", media_keys_reach_the_page),
+    ]);
+}
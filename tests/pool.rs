@@ -0,0 +1,37 @@
+use fltk::window;
+use fltk_webview::*;
+
+mod common;
+
+fn released_webviews_are_reset_and_reused() {
+    let (win, mut wv_win) = common::window();
+    let mut pool = WebviewPool::default();
+    let mut wv = pool.acquire(&mut wv_win);
+    assert!(!wv.is_reused());
+    let handle = wv.get_window();
+    wv.bind_result("answer", |_req| Ok("42".to_string()));
+    common::load_html(&mut wv, "<p>First</p>");
+    drop(wv);
+    assert_eq!(pool.idle_count(), 1);
+    let mut wv = pool.acquire(&mut wv_win);
+    assert!(wv.is_reused());
+    assert_eq!(wv.get_window(), handle);
+    assert_eq!(pool.idle_count(), 0);
+    // Reset on release
+    assert!(wv.bindings().is_empty());
+    assert!(common::wait_js(
+        &mut wv,
+        "location.href === 'about:blank' && typeof window.answer === 'undefined'"
+    ));
+    drop(wv);
+    pool.clear();
+    assert_eq!(pool.idle_count(), 0);
+    window::Window::delete(win);
+}
+
+fn main() {
+    common::run(&[(
+        "released_webviews_are_reset_and_reused",
+        released_webviews_are_reset_and_reused,
+    )]);
+}
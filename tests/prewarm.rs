@@ -0,0 +1,39 @@
+use fltk::{app, window};
+use fltk_webview::*;
+use std::time::Instant;
+
+mod common;
+
+/// The number of shown fltk windows
+fn windows() -> usize {
+    app::windows().map_or(0, |w| w.len())
+}
+
+// Prewarming only helps before the first webview of the process, so this runs in its own test binary.
+// A cold start can't be measured afterwards in the same process, so the time is printed, not compared
+fn prewarmed_engines_are_reused() {
+    let before = windows();
+    prewarm().unwrap();
+    // The hidden webview's window
+    assert_eq!(windows(), before + 1);
+    prewarm().unwrap();
+    assert_eq!(windows(), before + 1);
+    let (win, mut wv_win) = common::window();
+    let shown = windows();
+    let start = Instant::now();
+    let mut wv = Webview::create(false, &mut wv_win);
+    common::load_html(&mut wv, "<p>Warm</p>");
+    println!("created and loaded in {:?} after prewarm", start.elapsed());
+    // Released once the webview is created
+    assert_eq!(windows(), shown - 1);
+    assert_eq!(
+        common::eval(&mut wv, "document.body.textContent").unwrap(),
+        "\"Warm\""
+    );
+    wv.close();
+    window::Window::delete(win);
+}
+
+fn main() {
+    common::run(&[("prewarmed_engines_are_reused", prewarmed_engines_are_reused)]);
+}
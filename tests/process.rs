@@ -0,0 +1,66 @@
+use fltk::window;
+use fltk_webview::*;
+use std::{cell::Cell, rc::Rc};
+
+mod common;
+
+// The model is only taken into account before any page is loaded in the process,
+// so this runs in its own test binary. Recent WebKitGTK versions ignore it, and give
+// each webview its own process, so only the webviews working under it is checked
+fn shared_process_model_runs_pages() {
+    let (win, mut wv_win) = common::window();
+    let mut first = WebviewBuilder::new()
+        .process_model(ProcessModel::SharedSecondaryProcess)
+        .build(&mut wv_win);
+    let mut second = WebviewBuilder::new()
+        .process_model(ProcessModel::SharedSecondaryProcess)
+        .build_offscreen(200, 100)
+        .unwrap();
+    for (wv, text) in [(&mut first, "First"), (&mut second, "Second")] {
+        common::load_html(wv, &format!("<p>{}</p>", text));
+        assert_eq!(
+            common::eval(wv, "document.body.textContent").unwrap(),
+            format!("\"{}\"", text)
+        );
+    }
+    second.close();
+    first.close();
+    window::Window::delete(win);
+}
+
+fn resource_usage_measures_the_engine() {
+    common::with_webview(|wv| {
+        common::load_html(wv, "<p>Usage</p>");
+        // Keeps the web process busy during the measurement
+        wv.eval(
+            "setTimeout(function() { var end = Date.now() + 1000; while (Date.now() < end); }, 0)",
+        );
+        let usage = Rc::new(Cell::new(None));
+        wv.resource_usage({
+            let usage = usage.clone();
+            move |u| usage.set(Some(u))
+        });
+        assert!(common::wait_until(|| usage.get().is_some()));
+        let usage = usage.get().unwrap();
+        if cfg!(target_os = "macos") && usage.processes == 0 {
+            // Without the private property finding the web process
+            return assert_eq!(usage, ResourceUsage::default());
+        }
+        assert!(usage.processes > 0);
+        assert!(usage.memory > 0);
+        assert!(usage.cpu > 0.0);
+    });
+}
+
+fn main() {
+    common::run(&[
+        (
+            "shared_process_model_runs_pages",
+            shared_process_model_runs_pages,
+        ),
+        (
+            "resource_usage_measures_the_engine",
+            resource_usage_measures_the_engine,
+        ),
+    ]);
+}
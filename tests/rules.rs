@@ -0,0 +1,55 @@
+use fltk_webview::*;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+mod common;
+
+const BLOCK_ADS: &str = r#"[{"trigger": {"url-filter": ".*ads.*"}, "action": {"type": "block"}}]"#;
+
+fn content_rules_block_matching_requests() {
+    let ad_requests = Arc::new(AtomicUsize::new(0));
+    let base = common::serve({
+        let ad_requests = ad_requests.clone();
+        move |req| match req.path() {
+            "/ads.js" => {
+                ad_requests.fetch_add(1, Ordering::SeqCst);
+                common::Response::new("200 OK", "text/javascript", "window.ads = true;")
+            }
+            "/app.js" => common::Response::new("200 OK", "text/javascript", "window.app = true;"),
+            _ => {
+                common::Response::html("<script src=/ads.js></script><script src=/app.js></script>")
+            }
+        }
+    });
+    common::with_webview(|wv| {
+        assert!(matches!(
+            wv.add_content_rules(r#"[{"trigger": {}}]"#),
+            Err(WebviewError::InvalidArgument(_))
+        ));
+        let rules = match wv.add_content_rules(BLOCK_ADS) {
+            Err(WebviewError::Unsupported) if cfg!(target_os = "windows") => return,
+            res => res.unwrap(),
+        };
+        common::navigate(wv, &format!("{}/", base));
+        assert_eq!(
+            common::eval(wv, "[typeof window.ads, window.app]").unwrap(),
+            r#"["undefined",true]"#
+        );
+        assert_eq!(ad_requests.load(Ordering::SeqCst), 0);
+        wv.remove_content_rules(rules);
+        common::navigate(wv, &format!("{}/", base));
+        assert_eq!(
+            common::eval(wv, "[window.ads, window.app]").unwrap(),
+            "[true,true]"
+        );
+    });
+}
+
+fn main() {
+    common::run(&[(
+        "content_rules_block_matching_requests",
+        content_rules_block_matching_requests,
+    )]);
+}
@@ -0,0 +1,32 @@
+use fltk::window;
+use fltk_webview::*;
+
+mod common;
+
+// WebView2 takes the setting for its whole browser process, which the webviews created before
+// with other options would hold, so this runs in its own test binary.
+// Whether sites are checked can't be seen by the page, so pages are checked to still load
+fn disabled_safe_browsing_loads_pages() {
+    let base = common::serve(|_req| common::Response::html("<title>Intranet</title>"));
+    let (win, mut wv_win) = common::window();
+    // The second webview joins the browser process of the first
+    for _ in 0..2 {
+        let mut wv = WebviewBuilder::new()
+            .safe_browsing(false)
+            .build(&mut wv_win);
+        common::navigate(&mut wv, &format!("{}/", base));
+        assert_eq!(
+            common::eval(&mut wv, "document.title").unwrap(),
+            "\"Intranet\""
+        );
+        wv.close();
+    }
+    window::Window::delete(win);
+}
+
+fn main() {
+    common::run(&[(
+        "disabled_safe_browsing_loads_pages",
+        disabled_safe_browsing_loads_pages,
+    )]);
+}
@@ -0,0 +1,27 @@
+use fltk::window;
+use fltk_webview::*;
+
+mod common;
+
+// The sandbox can only be enabled before any page is loaded in the process,
+// so this runs in its own test binary
+fn sandboxed_pages_run() {
+    let has_bwrap = std::process::Command::new("bwrap")
+        .arg("--version")
+        .output()
+        .is_ok();
+    if cfg!(not(any(target_os = "macos", target_os = "windows"))) && !has_bwrap {
+        return println!("needs bwrap, skipped");
+    }
+    let (win, mut wv_win) = common::window();
+    let mut wv = WebviewBuilder::new().sandbox(true).build(&mut wv_win);
+    wv.bind_result("answer", |_req| Ok("42".to_string()));
+    common::load_html(&mut wv, "<p>Sandboxed</p>");
+    assert_eq!(common::eval(&mut wv, "answer()").unwrap(), "42");
+    wv.close();
+    window::Window::delete(win);
+}
+
+fn main() {
+    common::run(&[("sandboxed_pages_run", sandboxed_pages_run)]);
+}
@@ -0,0 +1,145 @@
+use fltk::window;
+use fltk_webview::*;
+
+mod common;
+
+fn document_end_sees_body() {
+    common::with_webview(|wv| {
+        wv.init_with_timing(
+            "var bodyAtStart = !!document.body;",
+            InjectionTiming::DocumentStart,
+        );
+        // A top-level declaration, which stays global on every backend
+        wv.init_with_timing(
+            "var bodyAtEnd = !!document.body;",
+            InjectionTiming::DocumentEnd,
+        );
+        common::load_html(wv, "<p>Hello</p>");
+        assert_eq!(
+            common::eval(wv, "[window.bodyAtStart, window.bodyAtEnd]").unwrap(),
+            "[false,true]"
+        );
+    });
+}
+
+fn cleared_init_scripts_stop_running() {
+    common::with_webview(|wv| {
+        wv.init_with_timing("window.cleared = true;", InjectionTiming::DocumentStart);
+        wv.init("window.kept = true;");
+        let result = wv.clear_init_scripts();
+        if cfg!(target_os = "windows") {
+            return assert!(matches!(result, Err(WebviewError::Unsupported)));
+        }
+        result.unwrap();
+        common::load_html(wv, "<p>Hello</p>");
+        assert_eq!(
+            common::eval(wv, "[typeof window.cleared, window.kept]").unwrap(),
+            r#"["undefined",true]"#
+        );
+    });
+}
+
+fn user_styles_apply_until_removed() {
+    common::with_webview(|wv| {
+        const COLOR_JS: &str = "getComputedStyle(document.querySelector('p')).color";
+        common::load_html(wv, "<p>Styled</p>");
+        let red = wv.add_user_style("p { color: rgb(255, 0, 0); }");
+        let _blue = wv.add_user_style("p { background-color: rgb(0, 0, 255); }");
+        // Applied to the current page, and the next ones
+        assert!(common::wait_js(
+            wv,
+            &format!("{} === 'rgb(255, 0, 0)'", COLOR_JS)
+        ));
+        common::load_html(wv, "<p>Styled</p>");
+        assert_eq!(common::eval(wv, COLOR_JS).unwrap(), "\"rgb(255, 0, 0)\"");
+        wv.remove_user_style(red);
+        assert!(common::wait_js(
+            wv,
+            &format!("{} === 'rgb(0, 0, 0)'", COLOR_JS)
+        ));
+        common::load_html(wv, "<p>Styled</p>");
+        assert_eq!(common::eval(wv, COLOR_JS).unwrap(), "\"rgb(0, 0, 0)\"");
+        // The other style sheet stays
+        assert_eq!(
+            common::eval(
+                wv,
+                "getComputedStyle(document.querySelector('p')).backgroundColor"
+            )
+            .unwrap(),
+            "\"rgb(0, 0, 255)\""
+        );
+    });
+}
+
+fn builder_content_runs_builder_scripts() {
+    let base = common::serve(|_req| common::Response::html("<title>Initial</title>"));
+    let (win, mut wv_win) = common::window();
+    let mut wv = WebviewBuilder::new()
+        .init("window.bodyAtInit = !!document.body;")
+        .url(&format!("{}/", base))
+        .build(&mut wv_win);
+    assert!(common::wait_js(
+        &mut wv,
+        "document.readyState === 'complete' && document.title === 'Initial'"
+    ));
+    assert_eq!(common::eval(&mut wv, "window.bodyAtInit").unwrap(), "false");
+    wv.close();
+    // The last of `url` and `html` is loaded
+    let mut wv = WebviewBuilder::new()
+        .url(&format!("{}/", base))
+        .html("<title>Replaced</title>")
+        .build(&mut wv_win);
+    assert!(common::wait_js(
+        &mut wv,
+        "document.readyState === 'complete' && document.title === 'Replaced'"
+    ));
+    wv.close();
+    window::Window::delete(win);
+}
+
+fn isolated_scripts_share_only_the_dom() {
+    common::with_webview(|wv| {
+        let result = wv.init_in_world(
+            "var secret = 'isolated'; document.documentElement.dataset.world = typeof pageValue;",
+            InjectionTiming::DocumentEnd,
+            ContentWorld::Isolated,
+        );
+        if let Err(WebviewError::Unsupported) = result {
+            // Windows, and macOS before 11
+            return assert!(!cfg!(not(any(target_os = "macos", target_os = "windows"))));
+        }
+        result.unwrap();
+        common::load_html(wv, "<script>var pageValue = 1;</script><p>Worlds</p>");
+        // The script saw the DOM, but neither world sees the globals of the other
+        assert_eq!(
+            common::eval(
+                wv,
+                "[document.documentElement.dataset.world, typeof window.secret]"
+            )
+            .unwrap(),
+            r#"["undefined","undefined"]"#
+        );
+    });
+}
+
+fn main() {
+    common::run(&[
+        ("document_end_sees_body", document_end_sees_body),
+        (
+            "cleared_init_scripts_stop_running",
+            cleared_init_scripts_stop_running,
+        ),
+        (
+            "user_styles_apply_until_removed",
+            user_styles_apply_until_removed,
+        ),
+        (
+            "builder_content_runs_builder_scripts",
+            builder_content_runs_builder_scripts,
+        ),
+        (
+            "isolated_scripts_share_only_the_dom",
+            isolated_scripts_share_only_the_dom,
+        ),
+    ]);
+}
@@ -0,0 +1,121 @@
+use fltk_webview::*;
+use std::{cell::Cell, rc::Rc};
+
+mod common;
+
+const TALL_HTML: &str = "<div style='width: 3000px; height: 3000px'></div>";
+
+fn scroll_position_follows_scroll_to() {
+    common::with_webview(|wv| {
+        common::load_html(wv, TALL_HTML);
+        wv.scroll_to(100.0, 250.0);
+        assert!(common::wait_js(
+            wv,
+            "window.scrollX === 100 && window.scrollY === 250"
+        ));
+        let pos = Rc::new(Cell::new(None));
+        wv.scroll_position({
+            let pos = pos.clone();
+            move |x, y| pos.set(Some((x, y)))
+        });
+        assert!(common::wait_until(|| pos.get().is_some()));
+        assert_eq!(pos.get(), Some((100.0, 250.0)));
+    });
+}
+
+fn hidden_scrollbars_take_no_space() {
+    common::with_webview(|wv| {
+        wv.set_scrollbars_visible(false);
+        common::load_html(wv, TALL_HTML);
+        assert_eq!(
+            common::eval(
+                wv,
+                "[document.documentElement.clientWidth === window.innerWidth, \
+                 document.documentElement.clientHeight === window.innerHeight]"
+            )
+            .unwrap(),
+            "[true,true]"
+        );
+        // The page still scrolls
+        wv.scroll_to(0.0, 100.0);
+        assert!(common::wait_js(wv, "window.scrollY === 100"));
+    });
+}
+
+fn smooth_scrolling_animates_scripted_scrolls() {
+    common::with_webview(|wv| {
+        // An instant scroll has moved by the time `scrollTo` returns, a smooth one hasn't
+        const JUMPED_JS: &str =
+            "window.scrollTo(0, 0); window.scrollTo(0, 1000); window.scrollY === 1000";
+        wv.set_smooth_scrolling(true);
+        common::load_html(wv, TALL_HTML);
+        assert_eq!(
+            common::eval(
+                wv,
+                "getComputedStyle(document.documentElement).scrollBehavior"
+            )
+            .unwrap(),
+            "\"smooth\""
+        );
+        assert_eq!(common::eval(wv, JUMPED_JS).unwrap(), "false");
+        // Applied to the current page too
+        wv.set_smooth_scrolling(false);
+        assert!(common::wait_js(
+            wv,
+            "getComputedStyle(document.documentElement).scrollBehavior === 'auto'"
+        ));
+        assert_eq!(common::eval(wv, JUMPED_JS).unwrap(), "true");
+    });
+}
+
+fn viewport_metrics_describe_the_layout() {
+    common::with_webview(|wv| {
+        common::load_html(
+            wv,
+            "<body style='margin: 0'><div style='width: 1500px; height: 2500px'></div></body>",
+        );
+        let metrics = Rc::new(Cell::new(None));
+        wv.viewport_metrics({
+            let metrics = metrics.clone();
+            move |m| metrics.set(Some(m))
+        });
+        assert!(common::wait_until(|| metrics.get().is_some()));
+        let m = metrics.get().unwrap();
+        assert_eq!((m.scroll_width, m.scroll_height), (1500.0, 2500.0));
+        assert_eq!(
+            common::eval(
+                wv,
+                "[window.innerWidth, window.innerHeight, window.devicePixelRatio]"
+            )
+            .unwrap(),
+            format!(
+                "[{},{},{}]",
+                m.inner_width, m.inner_height, m.device_pixel_ratio
+            )
+        );
+        // The viewport is the webview's, smaller than the document
+        assert!(m.inner_width > 0.0 && m.inner_width < 1500.0);
+        assert!(m.inner_height > 0.0 && m.inner_height < 2500.0);
+    });
+}
+
+fn main() {
+    common::run(&[
+        (
+            "scroll_position_follows_scroll_to",
+            scroll_position_follows_scroll_to,
+        ),
+        (
+            "hidden_scrollbars_take_no_space",
+            hidden_scrollbars_take_no_space,
+        ),
+        (
+            "smooth_scrolling_animates_scripted_scrolls",
+            smooth_scrolling_animates_scripted_scrolls,
+        ),
+        (
+            "viewport_metrics_describe_the_layout",
+            viewport_metrics_describe_the_layout,
+        ),
+    ]);
+}
@@ -0,0 +1,59 @@
+use fltk::window;
+use fltk_webview::*;
+use std::{cell::RefCell, rc::Rc};
+
+mod common;
+
+fn session_state_is_restored_in_new_webviews() {
+    let base = common::serve(|req| {
+        common::Response::html(format!("<title>{}</title><input id='field'>", req.path()))
+    });
+    let second = format!("{}/second", base);
+    let (win, mut wv_win) = common::window();
+    let mut wv = Webview::create(false, &mut wv_win);
+    common::navigate(&mut wv, &format!("{}/first", base));
+    common::navigate(&mut wv, &second);
+    wv.eval("field.value = 'typed'");
+    let blob = Rc::new(RefCell::new(None));
+    wv.save_session_state({
+        let blob = blob.clone();
+        move |b| *blob.borrow_mut() = Some(b)
+    });
+    assert!(common::wait_until(|| blob.borrow().is_some()));
+    let blob = blob.take().unwrap().unwrap();
+    wv.close();
+    window::Window::delete(win);
+
+    let (win, mut wv_win) = common::window();
+    let mut wv = Webview::create(false, &mut wv_win);
+    assert!(matches!(
+        wv.restore_session_state(b"not a session"),
+        Err(WebviewError::InvalidArgument(_))
+    ));
+    common::mark_page(&mut wv);
+    wv.restore_session_state(&blob).unwrap();
+    common::wait_loaded(&mut wv);
+    assert_eq!(
+        common::eval(&mut wv, "[location.href, document.title]").unwrap(),
+        format!(r#"["{}","/second"]"#, second)
+    );
+    // The backends' own state has the history, the state of the page its fields
+    let history = wv.can_go_back();
+    let fields = common::wait_js(&mut wv, "field.value === 'typed'");
+    if cfg!(target_os = "windows") {
+        assert!(fields);
+    } else if cfg!(not(target_os = "macos")) {
+        assert!(history);
+    } else {
+        assert!(history || fields);
+    }
+    wv.close();
+    window::Window::delete(win);
+}
+
+fn main() {
+    common::run(&[(
+        "session_state_is_restored_in_new_webviews",
+        session_state_is_restored_in_new_webviews,
+    )]);
+}
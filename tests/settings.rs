@@ -0,0 +1,544 @@
+use fltk::{app, window};
+use fltk_webview::*;
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
+
+mod common;
+
+const HTML: &str = r#"
+<style>
+    @media (prefers-color-scheme: dark) { p { color: rgb(1, 1, 1); } }
+    @media (prefers-color-scheme: light) { p { color: rgb(2, 2, 2); } }
+</style>
+<p>Scheme</p>"#;
+
+/// The scheme seen by the style sheets and by `matchMedia`, as `["dark","dark"]`
+const SCHEME_JS: &str = "[getComputedStyle(document.querySelector('p')).color === 'rgb(1, 1, 1)' \
+    ? 'dark' : 'light', matchMedia('(prefers-color-scheme: dark)').matches ? 'dark' : 'light']";
+
+fn color_scheme_is_per_webview() {
+    common::with_webview(|wv| {
+        let (other_win, mut other_wv_win) = common::window();
+        let mut other = Webview::create(false, &mut other_wv_win);
+        common::load_html(&mut other, HTML);
+        let system = common::eval(&mut other, SCHEME_JS).unwrap();
+
+        wv.set_color_scheme(ColorScheme::Dark);
+        common::load_html(wv, HTML);
+        assert_eq!(common::eval(wv, SCHEME_JS).unwrap(), r#"["dark","dark"]"#);
+        // Applies to the loaded page too
+        wv.set_color_scheme(ColorScheme::Light);
+        assert_eq!(common::eval(wv, SCHEME_JS).unwrap(), r#"["light","light"]"#);
+        assert_eq!(common::eval(&mut other, SCHEME_JS).unwrap(), system);
+
+        wv.set_color_scheme(ColorScheme::Auto);
+        assert_eq!(common::eval(wv, SCHEME_JS).unwrap(), system);
+        other.close();
+        window::Window::delete(other_win);
+    });
+}
+
+fn color_scheme_applies_to_added_style_sheets() {
+    common::with_webview(|wv| {
+        wv.set_color_scheme(ColorScheme::Dark);
+        common::load_html(wv, "<p>Scheme</p>");
+        common::eval(
+            wv,
+            "var style = document.createElement('style'); \
+             style.textContent = '@media (prefers-color-scheme: dark) { p { color: rgb(1, 1, 1); } }'; \
+             document.head.appendChild(style); true",
+        )
+        .unwrap();
+        assert!(common::wait_js(
+            wv,
+            "getComputedStyle(document.querySelector('p')).color === 'rgb(1, 1, 1)'"
+        ));
+    });
+}
+
+fn disabled_javascript_stops_page_scripts() {
+    common::with_webview(|wv| {
+        let titles = Rc::new(RefCell::new(vec![]));
+        wv.on_title_changed({
+            let titles = titles.clone();
+            move |title| titles.borrow_mut().push(title.to_string())
+        });
+        let result = wv.set_javascript_enabled(false);
+        if cfg!(target_os = "windows") {
+            return assert!(matches!(result, Err(WebviewError::Unsupported)));
+        }
+        result.unwrap();
+        // eval needs JavaScript too, so the page is only observed through its title
+        let html = "<title>Static</title><script>document.title = 'Scripted';</script>";
+        wv.set_html(html);
+        assert!(common::wait_until(|| titles
+            .borrow()
+            .last()
+            .is_some_and(|t| t == "Static")));
+        let _ = app::wait_for(0.5);
+        assert!(!titles.borrow().iter().any(|t| t == "Scripted"));
+        wv.set_javascript_enabled(true).unwrap();
+        common::load_html(wv, html);
+        assert_eq!(common::eval(wv, "document.title").unwrap(), "\"Scripted\"");
+    });
+}
+
+// Whether words get marked as misspelled isn't visible to pages,
+// so this checks the validation, and the attribute pages get on Windows
+fn spellcheck_languages_are_validated() {
+    common::with_webview(|wv| {
+        for tag in ["en US", "e", "en-", "english-language-tag", "fr;"] {
+            assert!(
+                matches!(
+                    wv.set_spellcheck(true, &["en-US", tag]),
+                    Err(WebviewError::InvalidArgument(t)) if t == tag
+                ),
+                "{}",
+                tag
+            );
+        }
+        wv.set_spellcheck(true, &["en-US", "fr"]).unwrap();
+        common::load_html(wv, "<textarea>Helo wrold</textarea>");
+        if cfg!(target_os = "windows") {
+            assert_eq!(
+                common::eval(wv, "document.documentElement.spellcheck").unwrap(),
+                "true"
+            );
+        }
+        wv.set_spellcheck(false, &[]).unwrap();
+    });
+}
+
+fn default_charset_decodes_undeclared_pages() {
+    // "café" in ISO-8859-1, served without a charset
+    let base = common::serve(|_req| common::Response::html(&b"<p>caf\xe9</p>"[..]));
+    common::with_webview(|wv| {
+        assert!(matches!(
+            wv.set_default_charset("utf 8"),
+            Err(WebviewError::InvalidArgument(_))
+        ));
+        let result = wv.set_default_charset("ISO-8859-1");
+        if cfg!(any(target_os = "macos", target_os = "windows")) {
+            return assert!(matches!(result, Err(WebviewError::Unsupported)));
+        }
+        result.unwrap();
+        common::navigate(wv, &format!("{}/", base));
+        assert_eq!(
+            common::eval(wv, "[document.characterSet, document.body.textContent]").unwrap(),
+            r#"["windows-1252","café"]"#
+        );
+    });
+}
+
+fn with_settings_applies_engine_settings() {
+    common::with_webview(|wv| {
+        wv.with_settings(|_s| ()).unwrap();
+        let result = wv.with_settings(|s| s.webgl = Some(false));
+        if cfg!(any(target_os = "macos", target_os = "windows")) {
+            return assert!(matches!(result, Err(WebviewError::Unsupported)));
+        }
+        result.unwrap();
+        common::load_html(wv, "<canvas></canvas>");
+        assert_eq!(
+            common::eval(
+                wv,
+                "document.querySelector('canvas').getContext('webgl') === null"
+            )
+            .unwrap(),
+            "true"
+        );
+    });
+}
+
+// The inspector is only reachable through the context menu, which pages can't see,
+// so this checks that it's toggled independently of `debug`
+fn developer_extras_are_independent_of_debug() {
+    common::with_webview(|wv| {
+        let result = wv.set_developer_extras(true);
+        if cfg!(target_os = "windows") {
+            return assert!(matches!(result, Err(WebviewError::Unsupported)));
+        }
+        result.unwrap();
+        assert!(!wv.engine_info().is_debug);
+        common::load_html(wv, "<p>Extras</p>");
+        wv.set_developer_extras(false).unwrap();
+        assert!(!wv.engine_info().is_debug);
+    });
+}
+
+/// Counts the runs of a 20ms interval for a second, in a page which doesn't have focus
+const COUNT_INTERVALS_JS: &str = "new Promise(function(resolve) { \
+    document.hasFocus = function() { return false; }; \
+    var count = 0, id = setInterval(function() { count++; }, 20); \
+    setTimeout(function() { clearInterval(id); resolve(count); }, 1000); })";
+
+fn background_throttling_slows_unfocused_intervals() {
+    common::with_webview(|wv| {
+        wv.set_background_throttling(true);
+        common::load_html(wv, "<p>Throttled</p>");
+        let throttled: u32 = common::eval(wv, COUNT_INTERVALS_JS)
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!(throttled <= 2, "{}", throttled);
+        wv.set_background_throttling(false);
+        let free: u32 = common::eval(wv, COUNT_INTERVALS_JS)
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!(free >= 10, "{}", free);
+    });
+}
+
+fn disabled_back_forward_cache_reruns_init_scripts() {
+    let base = common::serve(|_req| common::Response::html("<p>Page</p>"));
+    common::with_webview(|wv| {
+        wv.set_back_forward_cache(false);
+        wv.init("sessionStorage.runs = (+sessionStorage.runs || 0) + 1;");
+        common::navigate(wv, &format!("{}/a", base));
+        common::navigate(wv, &format!("{}/b", base));
+        common::mark_page(wv);
+        wv.eval("history.back()");
+        common::wait_loaded(wv);
+        assert_eq!(
+            common::eval(wv, "[location.pathname, sessionStorage.runs]").unwrap(),
+            r#"["/a","3"]"#
+        );
+    });
+}
+
+fn languages_are_reported_to_pages() {
+    let accepted = Arc::new(Mutex::new(None));
+    let base = common::serve({
+        let accepted = accepted.clone();
+        move |req| {
+            *accepted.lock().unwrap() = req.header("accept-language").map(str::to_string);
+            common::Response::html("<p>Languages</p>")
+        }
+    });
+    common::with_webview(|wv| {
+        assert!(matches!(
+            wv.set_languages(&[]),
+            Err(WebviewError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            wv.set_languages(&["fr FR"]),
+            Err(WebviewError::InvalidArgument(_))
+        ));
+        wv.set_languages(&["fr-FR", "en"]).unwrap();
+        common::navigate(wv, &format!("{}/", base));
+        assert_eq!(
+            common::eval(
+                wv,
+                "navigator.language.startsWith('fr') && navigator.languages.length === 2"
+            )
+            .unwrap(),
+            "true"
+        );
+        // Only WebKitGTK changes the header
+        if cfg!(not(any(target_os = "macos", target_os = "windows"))) {
+            let accepted = accepted.lock().unwrap().clone().unwrap_or_default();
+            assert!(accepted.starts_with("fr"), "{}", accepted);
+        }
+    });
+}
+
+/// Have the page report the text of `url` through `report`, or `error` if it can't read it
+const READ_JS: &str = "function read(url) { \
+    var xhr = new XMLHttpRequest(); \
+    xhr.onload = function() { report(xhr.responseText); }; \
+    xhr.onerror = function() { report('error'); }; \
+    try { xhr.open('GET', url); xhr.send(); } catch (e) { report('error'); } \
+    }";
+
+fn file_urls_read_files_when_allowed() {
+    let dir = std::env::temp_dir().join("fltk-webview-file-access");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("data.txt"), "local data").unwrap();
+    std::fs::write(
+        dir.join("page.html"),
+        format!("<script>{}</script>", READ_JS),
+    )
+    .unwrap();
+    let path = dir
+        .join("page.html")
+        .display()
+        .to_string()
+        .replace('\\', "/");
+    let page = format!("file:///{}", path.trim_start_matches('/'));
+    let base = common::serve(|_req| common::Response::new("200 OK", "text/plain", "remote data"));
+    common::with_webview(|wv| {
+        let reported = Rc::new(RefCell::new(vec![]));
+        wv.bind_result("report", {
+            let reported = reported.clone();
+            move |req| {
+                reported.borrow_mut().push(req.to_string());
+                Ok("null".to_string())
+            }
+        });
+        let read = |wv: &mut Webview, url: &str| {
+            common::navigate(wv, &page);
+            reported.borrow_mut().clear();
+            wv.eval(&format!("read({:?})", url));
+            assert!(common::wait_until(|| !reported.borrow().is_empty()));
+            reported.borrow_mut().pop().unwrap()
+        };
+        assert_eq!(read(wv, "data.txt"), r#"["error"]"#);
+        let result = wv.set_allow_file_access_from_file_urls(true);
+        if cfg!(target_os = "windows") {
+            return assert!(matches!(result, Err(WebviewError::Unsupported)));
+        }
+        result.unwrap();
+        assert_eq!(read(wv, "data.txt"), r#"["local data"]"#);
+        // Other origins still need CORS, unless universal access is allowed
+        let remote = format!("{}/data", base);
+        assert_eq!(read(wv, &remote), r#"["error"]"#);
+        let result = wv.set_allow_universal_access_from_file_urls(true);
+        if cfg!(target_os = "macos") {
+            return assert!(matches!(result, Err(WebviewError::Unsupported)));
+        }
+        result.unwrap();
+        assert_eq!(read(wv, &remote), r#"["remote data"]"#);
+    });
+}
+
+fn autofill_settings_mark_form_fields() {
+    common::with_webview(|wv| {
+        const AUTOCOMPLETE_JS: &str =
+            "Array.prototype.map.call(document.querySelectorAll('input'), \
+            function(i) { return i.getAttribute('autocomplete'); })";
+        wv.set_password_autosave(false).unwrap();
+        assert!(matches!(
+            wv.set_password_autosave(true),
+            Err(WebviewError::Unsupported)
+        ));
+        wv.set_general_autofill(false).unwrap();
+        common::load_html(
+            wv,
+            "<form><input name='a'><input name='b' autocomplete='email'></form>",
+        );
+        assert!(common::wait_js(
+            wv,
+            &format!(
+                "JSON.stringify({}) === '[\"off\",\"off\"]'",
+                AUTOCOMPLETE_JS
+            )
+        ));
+        // Added fields too
+        wv.eval("document.forms[0].appendChild(document.createElement('input'))");
+        assert!(common::wait_js(
+            wv,
+            "document.querySelectorAll('input')[2].getAttribute('autocomplete') === 'off'"
+        ));
+        // Enabling restores the page's own attributes
+        wv.set_general_autofill(true).unwrap();
+        assert!(common::wait_js(
+            wv,
+            &format!(
+                "JSON.stringify({}) === '[null,\"email\",null]'",
+                AUTOCOMPLETE_JS
+            )
+        ));
+    });
+}
+
+fn disabled_webgl_gives_no_contexts() {
+    common::with_webview(|wv| {
+        wv.set_webgl_enabled(false);
+        common::load_html(wv, "<canvas></canvas>");
+        assert_eq!(
+            common::eval(
+                wv,
+                "var c = document.querySelector('canvas'); \
+                 [c.getContext('webgl'), c.getContext('experimental-webgl'), c.getContext('webgl2')]"
+            )
+            .unwrap(),
+            "[null,null,null]"
+        );
+        // Other contexts are kept
+        assert_eq!(
+            common::eval(wv, "!!document.createElement('canvas').getContext('2d')").unwrap(),
+            "true"
+        );
+    });
+}
+
+fn disabled_images_are_not_loaded() {
+    let base = common::serve(|req| match req.path() {
+        "/pixel.png" => common::Response::new("200 OK", "image/png", common::PIXEL_PNG),
+        _ => common::Response::html("<img src='/pixel.png'>"),
+    });
+    common::with_webview(|wv| {
+        const WIDTH_JS: &str = "new Promise(function(resolve) { \
+            var img = document.querySelector('img'); \
+            if (img.complete) return resolve(img.naturalWidth); \
+            img.onload = img.onerror = function() { resolve(img.naturalWidth); }; \
+            setTimeout(function() { resolve(img.naturalWidth); }, 1000); })";
+        let url = format!("{}/", base);
+        let result = wv.set_auto_load_images(false);
+        if cfg!(target_os = "windows") {
+            return assert!(matches!(result, Err(WebviewError::Unsupported)));
+        }
+        result.unwrap();
+        common::navigate(wv, &url);
+        assert_eq!(common::eval(wv, WIDTH_JS).unwrap(), "0");
+        wv.set_auto_load_images(true).unwrap();
+        common::navigate(wv, &format!("{}?again", url));
+        assert_eq!(common::eval(wv, WIDTH_JS).unwrap(), "1");
+    });
+}
+
+fn csp_blocks_inline_scripts() {
+    common::with_webview(|wv| {
+        const HTML: &str = "<p>CSP</p><script>window.inline = true;</script>";
+        const INJECT_JS: &str = "var s = document.createElement('script'); \
+             s.textContent = 'window.injected = true;'; document.body.appendChild(s); \
+             !!window.injected";
+        common::load_html(wv, HTML);
+        // Enforced on the current page
+        wv.set_csp("script-src 'self'");
+        assert!(common::wait_js(
+            wv,
+            "!!document.querySelector('meta[http-equiv=\"Content-Security-Policy\"]')"
+        ));
+        assert_eq!(common::eval(wv, INJECT_JS).unwrap(), "false");
+        // and on the next ones, before their scripts run
+        common::load_html(wv, HTML);
+        assert_eq!(common::eval(wv, "!!window.inline").unwrap(), "false");
+        wv.set_csp("");
+        common::load_html(wv, HTML);
+        assert_eq!(common::eval(wv, "!!window.inline").unwrap(), "true");
+    });
+}
+
+// The engines only move their own caret for real key presses, so keys are simulated
+// where the init script moves it, on Windows, and the setting is otherwise only toggled
+fn caret_browsing_moves_the_caret() {
+    common::with_webview(|wv| {
+        const OFFSET_JS: &str = "getSelection().focusOffset";
+        wv.set_caret_browsing(true);
+        common::load_html(wv, "<p>Caret text</p>");
+        wv.eval("getSelection().collapse(document.querySelector('p').firstChild, 0)");
+        wv.dispatch_key("ArrowRight");
+        wv.dispatch_key("ArrowRight");
+        if cfg!(target_os = "windows") {
+            assert!(common::wait_js(wv, &format!("{} === 2", OFFSET_JS)));
+        }
+        wv.set_caret_browsing(false);
+        let offset = common::eval(wv, OFFSET_JS).unwrap();
+        wv.dispatch_key("ArrowRight");
+        assert_eq!(common::eval(wv, OFFSET_JS).unwrap(), offset);
+    });
+}
+
+// Which font renders a generic family can't be read by the page,
+// so where the engine maps it, only the sizes are checked
+fn font_defaults_apply_to_pages() {
+    common::with_webview(|wv| {
+        assert!(matches!(
+            wv.set_font_defaults(FontConfig {
+                default_size: Some(200),
+                ..Default::default()
+            }),
+            Err(WebviewError::InvalidArgument(_))
+        ));
+        wv.set_font_defaults(FontConfig {
+            monospace: Some("DejaVu Sans Mono".to_string()),
+            default_size: Some(20),
+            monospace_size: Some(15),
+            ..Default::default()
+        })
+        .unwrap();
+        let fonts = Rc::new(RefCell::new(vec![]));
+        wv.bind_result("report", {
+            let fonts = fonts.clone();
+            move |req| {
+                fonts.borrow_mut().push(req.to_string());
+                Ok("null".to_string())
+            }
+        });
+        common::load_html(
+            wv,
+            "<p>Text</p><pre>Code</pre><script>\
+             var font = function(s) { var c = getComputedStyle(document.querySelector(s)); \
+             return [c.fontSize, c.fontFamily]; }; \
+             report(font('p'), font('pre'));</script>",
+        );
+        assert!(common::wait_until(|| !fonts.borrow().is_empty()));
+        let fonts = fonts.borrow()[0].clone();
+        assert!(fonts.starts_with(r#"[["20px","#), "{}", fonts);
+        assert!(fonts.contains(r#"["15px","#), "{}", fonts);
+        assert!(
+            fonts.contains("DejaVu Sans Mono") || fonts.ends_with(r#""monospace"]]"#),
+            "{}",
+            fonts
+        );
+    });
+}
+
+fn main() {
+    common::run(&[
+        ("color_scheme_is_per_webview", color_scheme_is_per_webview),
+        (
+            "color_scheme_applies_to_added_style_sheets",
+            color_scheme_applies_to_added_style_sheets,
+        ),
+        (
+            "disabled_javascript_stops_page_scripts",
+            disabled_javascript_stops_page_scripts,
+        ),
+        (
+            "spellcheck_languages_are_validated",
+            spellcheck_languages_are_validated,
+        ),
+        (
+            "default_charset_decodes_undeclared_pages",
+            default_charset_decodes_undeclared_pages,
+        ),
+        (
+            "with_settings_applies_engine_settings",
+            with_settings_applies_engine_settings,
+        ),
+        (
+            "developer_extras_are_independent_of_debug",
+            developer_extras_are_independent_of_debug,
+        ),
+        (
+            "background_throttling_slows_unfocused_intervals",
+            background_throttling_slows_unfocused_intervals,
+        ),
+        (
+            "disabled_back_forward_cache_reruns_init_scripts",
+            disabled_back_forward_cache_reruns_init_scripts,
+        ),
+        (
+            "languages_are_reported_to_pages",
+            languages_are_reported_to_pages,
+        ),
+        (
+            "file_urls_read_files_when_allowed",
+            file_urls_read_files_when_allowed,
+        ),
+        (
+            "autofill_settings_mark_form_fields",
+            autofill_settings_mark_form_fields,
+        ),
+        (
+            "disabled_webgl_gives_no_contexts",
+            disabled_webgl_gives_no_contexts,
+        ),
+        (
+            "disabled_images_are_not_loaded",
+            disabled_images_are_not_loaded,
+        ),
+        ("csp_blocks_inline_scripts", csp_blocks_inline_scripts),
+        (
+            "caret_browsing_moves_the_caret",
+            caret_browsing_moves_the_caret,
+        ),
+        ("font_defaults_apply_to_pages", font_defaults_apply_to_pages),
+    ]);
+}
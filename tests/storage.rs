@@ -0,0 +1,180 @@
+use fltk::window;
+use fltk_webview::*;
+use std::{
+    cell::RefCell,
+    fs,
+    path::Path,
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
+
+mod common;
+
+/// Whether `dir` holds a file, in any of its subfolders
+fn has_files(dir: &Path) -> bool {
+    fs::read_dir(dir).into_iter().flatten().flatten().any(|e| {
+        let path = e.path();
+        path.is_file() || has_files(&path)
+    })
+}
+
+// The storage folder only applies to the first webview of the process on WebKitGTK,
+// so this runs first
+fn persistent_storage_keeps_local_storage() {
+    let dir = std::env::temp_dir().join(format!("fltk-webview-storage-{}", std::process::id()));
+    let base = common::serve(|_req| common::Response::html("<p>Storage</p>"));
+    for run in 0..2 {
+        let (win, mut wv_win) = common::window();
+        let mut wv = WebviewBuilder::new()
+            .persistent_storage(dir.clone())
+            .build(&mut wv_win);
+        common::navigate(&mut wv, &format!("{}/", base));
+        if run == 0 {
+            wv.eval("localStorage.setItem('kept', 'yes')");
+            wv.flush_storage();
+        } else {
+            assert_eq!(
+                common::eval(&mut wv, "localStorage.getItem('kept')").unwrap(),
+                "\"yes\""
+            );
+        }
+        wv.close();
+        window::Window::delete(win);
+    }
+    // WKWebView stores under ~/Library/WebKit instead
+    if !cfg!(target_os = "macos") {
+        assert!(has_files(&dir));
+    }
+    let _ = fs::remove_dir_all(dir);
+}
+
+/// Pass the exported cookies named `session` or `theme`, sorted by name
+fn exported(wv: &mut Webview) -> Result<Vec<Cookie>, WebviewError> {
+    let result = Rc::new(RefCell::new(None));
+    wv.export_cookies({
+        let result = result.clone();
+        move |cookies| *result.borrow_mut() = Some(cookies)
+    });
+    assert!(common::wait_until(|| result.borrow().is_some()));
+    result.take().unwrap().map(|mut cookies| {
+        cookies.retain(|c| c.name == "session" || c.name == "theme");
+        cookies.sort_by(|a, b| a.name.cmp(&b.name));
+        cookies
+    })
+}
+
+fn exported_cookies_are_imported_back() {
+    let received = Arc::new(Mutex::new(String::new()));
+    let base = common::serve({
+        let received = received.clone();
+        move |req| {
+            *received.lock().unwrap() = req.header("cookie").unwrap_or_default().to_string();
+            let res = common::Response::html("<p>Cookies</p>");
+            match req.path() {
+                "/login" => res
+                    .with_header("Set-Cookie", "session=secret; Path=/; HttpOnly")
+                    .with_header("Set-Cookie", "theme=dark; Path=/; Max-Age=3600"),
+                "/logout" => res
+                    .with_header("Set-Cookie", "session=; Path=/; Max-Age=0")
+                    .with_header("Set-Cookie", "theme=; Path=/; Max-Age=0"),
+                _ => res,
+            }
+        }
+    });
+    common::with_webview(|wv| {
+        common::navigate(wv, &format!("{}/login", base));
+        let cookies = exported(wv);
+        if cfg!(target_os = "windows") {
+            assert!(matches!(cookies, Err(WebviewError::Unsupported)));
+            return assert!(matches!(
+                wv.import_cookies(&[]),
+                Err(WebviewError::Unsupported)
+            ));
+        }
+        let cookies = cookies.unwrap();
+        assert_eq!(
+            cookies
+                .iter()
+                .map(|c| (
+                    c.name.as_str(),
+                    c.value.as_str(),
+                    c.http_only,
+                    c.expires.is_some()
+                ))
+                .collect::<Vec<_>>(),
+            [
+                ("session", "secret", true, false),
+                ("theme", "dark", false, true)
+            ]
+        );
+        common::navigate(wv, &format!("{}/logout", base));
+        assert!(exported(wv).unwrap().is_empty());
+        wv.import_cookies(&cookies).unwrap();
+        assert_eq!(exported(wv).unwrap(), cookies);
+        common::navigate(wv, &format!("{}/", base));
+        let mut sent: Vec<String> = received
+            .lock()
+            .unwrap()
+            .split("; ")
+            .map(str::to_string)
+            .collect();
+        sent.sort();
+        assert_eq!(sent, ["session=secret", "theme=dark"]);
+    });
+}
+
+/// The value stored under `key` in the localStorage of the current page, as passed to `get_local_storage`
+fn stored(wv: &mut Webview, key: &str) -> Result<Option<String>, WebviewError> {
+    let result = Rc::new(RefCell::new(None));
+    wv.get_local_storage(key, {
+        let result = result.clone();
+        move |value| *result.borrow_mut() = Some(value)
+    });
+    assert!(common::wait_until(|| result.borrow().is_some()));
+    result.take().unwrap()
+}
+
+fn local_storage_is_seeded_before_page_scripts() {
+    const VALUE: &str = "{\"theme\": \"dark\", \"quote\": \"it's\"}\n</script>";
+    let base = common::serve(|_req| {
+        common::Response::html(
+            "<script>window.seen = localStorage.getItem('app \"config\"');</script>",
+        )
+    });
+    common::with_webview(|wv| {
+        wv.set_local_storage("app \"config\"", VALUE);
+        common::navigate(wv, &format!("{}/", base));
+        assert_eq!(
+            common::eval(wv, "window.seen === localStorage.getItem('app \"config\"')").unwrap(),
+            "true"
+        );
+        assert_eq!(
+            stored(wv, "app \"config\"").unwrap().as_deref(),
+            Some(VALUE)
+        );
+        assert_eq!(stored(wv, "missing").unwrap(), None);
+        // Setting a key again replaces its value, on the current page too
+        wv.set_local_storage("app \"config\"", "light");
+        assert_eq!(
+            stored(wv, "app \"config\"").unwrap().as_deref(),
+            Some("light")
+        );
+    });
+}
+
+fn main() {
+    common::run(&[
+        (
+            "persistent_storage_keeps_local_storage",
+            persistent_storage_keeps_local_storage,
+        ),
+        (
+            "exported_cookies_are_imported_back",
+            exported_cookies_are_imported_back,
+        ),
+        (
+            "local_storage_is_seeded_before_page_scripts",
+            local_storage_is_seeded_before_page_scripts,
+        ),
+    ]);
+}
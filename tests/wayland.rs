@@ -0,0 +1,38 @@
+#[cfg(target_os = "linux")]
+use fltk::{app, window};
+#[cfg(target_os = "linux")]
+use fltk_webview::*;
+
+mod common;
+
+#[cfg(target_os = "linux")]
+extern "C" {
+    fn Fl_using_wayland() -> std::os::raw::c_int;
+}
+
+// fltk picks its backend when it opens the display, so this runs in its own test binary
+#[cfg(target_os = "linux")]
+fn wayland_windows_are_refused() {
+    let (win, mut wv_win) = common::window();
+    let result = WebviewBuilder::new().try_build(&mut wv_win);
+    if unsafe { Fl_using_wayland() } != 0 {
+        assert!(matches!(result, Err(WebviewError::UnsupportedDisplay)));
+    } else {
+        // fltk runs on XWayland, which webviews are embedded through
+        let mut wv = result.unwrap();
+        common::load_html(&mut wv, "<p>XWayland</p>");
+        wv.close();
+    }
+    window::Window::delete(win);
+}
+
+fn main() {
+    // Not through `common::run`, which needs X11, which pure Wayland sessions lack
+    #[cfg(target_os = "linux")]
+    if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        let _app = app::App::default();
+        wayland_windows_are_refused();
+        return println!("test wayland_windows_are_refused ... ok");
+    }
+    println!("not a Wayland session, skipping");
+}